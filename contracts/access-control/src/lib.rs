@@ -25,19 +25,36 @@
 //! emergency_access, deactivate, role_grant, role_revoke. Audit systems can replay events for
 //! forensic analysis and compliance reporting.
 //!
+//! **Break-the-glass Review:** Every `emergency_access` opens an `EmergencyReview` case that an
+//! `Auditor` other than the responder must mark justified or unjustified within the configured
+//! review window. Patients are notified through `brk_open` / `brk_rvw` / `brk_esc` events. Overdue
+//! and unjustified cases escalate, and a responder who reaches the unjustified-override threshold
+//! loses `Role::EmergencyResponder` automatically.
+//!
 //! **Data Retention Policy:** Explicit consent records (ConsentRecord) with configurable expiration;
 //! temporary storage for emergency access grants (1-hour TTL); persistent storage for role assignments
 //! and access permissions. Deregister_patient removes all patient-specific state including Entity,
 //! AccessList, DID, and all Consent records.
 //!
 //! **Encryption/Integrity:** DID (W3C Decentralized Identifier) registration per address provides
-//! cryptographic identity anchoring. Commit-reveal anti-front-running mechanism via SHA256 hashing.
+//! cryptographic identity anchoring. A registered DID can carry a resolvable `DidDocument` (Ed25519
+//! verification methods, service endpoints, controllers). Rotated keys are kept in a key history,
+//! deactivation is permanent, and `verify_did_signature` checks payloads with `ed25519_verify`. Commit-reveal anti-front-running mechanism via SHA256 hashing.
 //! Rate limiting (10 ops/block) prevents consent operation abuse. All cryptographic operations use
 //! Soroban's verified crypto module.
-
+//!
+//! **Accounting of Disclosures:** `check_access` and `check_consent` are read-only. Callers that
+//! actually release data use `access_resource` / `access_with_consent`, which perform the same
+//! check and append a `shared::disclosure` entry to the data subject's ledger naming the
+//! accessor, resource, authorizing op_id and purpose. Patients read their ledger with
+//! `get_disclosures` and `get_disclosure_summary`.
+
+pub use shared::credentials::CredentialRecord;
+use shared::disclosure::{self, DisclosurePage, DisclosureSummary};
+use shared::pagination::{self, PageResult};
 use soroban_sdk::{
     contract, contracterror, contractimpl, contracttype, symbol_short, xdr::ToXdr, Address, Bytes,
-    BytesN, Env, String, Vec,
+    BytesN, Env, String, Symbol, TryFromVal, Vec,
 };
 
 mod test;
@@ -79,8 +96,71 @@ pub enum ContractError {
     BatchTooLarge = 26,
     /// #625: grantor or grantee entity has been deactivated via `deactivate_entity`.
     EntityInactive = 27,
+    // Delegated access chains
+    DelegationNotFound = 28,
+    /// The requested scope or expiry is wider than the delegator's own access.
+    DelegationExceedsGrant = 29,
+    /// The chain would exceed `MAX_DELEGATION_DEPTH` hops.
+    DelegationTooDeep = 30,
+    /// Delegating to oneself or to an address already upstream in the chain.
+    DelegationCycle = 31,
+    // Organization staff registry
+    /// The entity is not an organization (`EntityType::Hospital`).
+    NotAnOrganization = 32,
+    StaffAlreadyMember = 33,
+    StaffNotFound = 34,
+    /// Only the organization itself or the contract admin may manage its staff.
+    NotOrganizationManager = 35,
+    /// Organizations cannot be staff of other organizations.
+    OrganizationCannotBeStaff = 36,
+    // Break-the-glass review
+    EmergencyReviewNotFound = 37,
+    /// The case has already been marked justified or unjustified.
+    EmergencyReviewClosed = 38,
+    /// The case is still inside its review window.
+    EmergencyReviewNotOverdue = 39,
+    /// A responder may not review their own emergency access.
+    SelfReviewNotAllowed = 40,
+    /// Review window or suspension threshold of zero.
+    InvalidReviewConfig = 41,
+    // DID documents
+    /// `create_did_document` needs a DID registered with `register_did` first.
+    DidNotRegistered = 42,
+    DidDocumentExists = 43,
+    DidDocumentNotFound = 44,
+    DidDeactivated = 45,
+    /// Caller is neither the DID subject nor one of its controllers.
+    NotDidController = 46,
+    /// A verification method, service or controller with that id already exists.
+    DidEntryExists = 47,
+    DidEntryNotFound = 48,
+    /// `MAX_DID_METHODS`, `MAX_DID_SERVICES` or `MAX_DID_CONTROLLERS` reached.
+    DidLimitExceeded = 49,
+    /// A live document must keep at least one verification method.
+    LastVerificationMethod = 50,
+}
+
+/// Errors of the verifiable credential registry. Kept apart from
+/// `ContractError`, which is at the 50-variant limit of a contract spec;
+/// codes continue after it so both enums stay unambiguous.
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum CredentialError {
+    /// The issuer has no DID document.
+    IssuerDidNotFound = 51,
+    IssuerDidDeactivated = 52,
+    CredentialExists = 53,
+    CredentialNotFound = 54,
+    CredentialAlreadyRevoked = 55,
 }
 
+/// Default time an `Auditor` has to review an emergency access (72 hours).
+pub const DEFAULT_REVIEW_WINDOW_SECS: u64 = 72 * 3600;
+/// Default number of unjustified overrides after which a responder loses
+/// `Role::EmergencyResponder`.
+pub const DEFAULT_SUSPENSION_THRESHOLD: u32 = 3;
+
 /// Maximum number of access permissions a single grantee may accumulate.
 pub const MAX_ACCESS_LIST_LEN: u32 = 200;
 /// Maximum number of addresses authorized for a single resource.
@@ -89,6 +169,31 @@ pub const MAX_RESOURCE_AUTHORIZED: u32 = 200;
 pub const BATCH_SIZE_LIMIT: u32 = 20;
 /// Commit-reveal window: commits older than this many seconds are rejected.
 pub const COMMIT_EXPIRY_SECS: u64 = 3600;
+/// Maximum number of delegation hops below a direct `grant_access` grant.
+pub const MAX_DELEGATION_DEPTH: u32 = 4;
+
+/// Access scope bits shared by delegations and the consent engine.
+pub const SCOPE_READ: u32 = 0x01;
+pub const SCOPE_WRITE: u32 = 0x02;
+/// Permission to sub-delegate. A delegatee without it cannot extend the chain.
+pub const SCOPE_SHARE: u32 = 0x04;
+/// Scope of a direct `grant_access` permission.
+pub const SCOPE_DIRECT: u32 = SCOPE_READ | SCOPE_WRITE;
+/// Every scope bit.
+pub const SCOPE_ALL: u32 = SCOPE_READ | SCOPE_WRITE | SCOPE_SHARE;
+/// Maximum staff members per organization.
+pub const MAX_ORG_STAFF: u32 = 200;
+/// Maximum organizations a single address may belong to.
+pub const MAX_MEMBER_ORGS: u32 = 10;
+/// Maximum active verification methods in a DID document.
+pub const MAX_DID_METHODS: u32 = 10;
+/// Maximum service endpoints in a DID document.
+pub const MAX_DID_SERVICES: u32 = 10;
+/// Maximum controllers besides the subject.
+pub const MAX_DID_CONTROLLERS: u32 = 5;
+/// Size of one revocation status list. Each issuer fills its lists in order,
+/// `STATUS_LIST_BYTES * 8` credentials per list.
+pub const STATUS_LIST_BYTES: u32 = 2048;
 
 /// --------------------
 /// Role Types (RBAC)
@@ -162,6 +267,8 @@ pub struct AccessPermission {
     pub granted_at: u64,
     pub expires_at: u64, // 0 means no expiration
     pub op_id: u64,      // #222: immutable operation receipt / correlation ID
+    pub scope_mask: u32, // SCOPE_* bits; SCOPE_SHARE only when requested
+    pub subject: Address, // data subject whose ledger records disclosures
 }
 
 /// --------------------
@@ -241,6 +348,160 @@ pub struct EmergencyAccessRecord {
     pub op_id: u64,
 }
 
+/// --------------------
+/// DID documents
+/// --------------------
+/// An Ed25519 verification method. `id` is the fragment (e.g. `key-1`) that
+/// identifies the method within its document.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct VerificationMethod {
+    pub id: String,
+    pub public_key: BytesN<32>,
+    pub added_at: u64,
+    pub retired_at: u64, // 0 while active
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DidService {
+    pub id: String,
+    pub service_type: String,
+    pub endpoint: String,
+}
+
+/// Resolved DID document for `subject`. `id` is the DID registered with
+/// `register_did`; `verification_methods` holds active keys only, retired
+/// keys are kept in the key history.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DidDocument {
+    pub id: Bytes,
+    pub subject: Address,
+    /// Addresses besides `subject` that may update the document.
+    pub controllers: Vec<Address>,
+    pub verification_methods: Vec<VerificationMethod>,
+    pub services: Vec<DidService>,
+    pub created_at: u64,
+    pub updated_at: u64,
+    /// Incremented on every change.
+    pub version: u32,
+    pub deactivated: bool,
+}
+
+/// --------------------
+/// Break-the-glass review
+/// --------------------
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum ReviewStatus {
+    Pending,
+    Justified,
+    Unjustified,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Escalation {
+    None,
+    /// No review was recorded before `due_at`.
+    Overdue,
+    /// The reviewer found the override unjustified.
+    Unjustified,
+}
+
+/// Post-hoc review case opened by every `emergency_access` call. `id` is the
+/// emergency access op_id.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct EmergencyReview {
+    pub id: u64,
+    pub responder: Address,
+    pub patient: Address,
+    pub justification_hash: BytesN<32>,
+    pub opened_at: u64,
+    pub due_at: u64,
+    pub status: ReviewStatus,
+    pub reviewer: Option<Address>,
+    /// sha256 of the reviewer's written findings (stored off-chain).
+    pub findings_hash: Option<BytesN<32>>,
+    pub reviewed_at: u64, // 0 while pending
+    pub escalation: Escalation,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ReviewConfig {
+    /// Seconds after the override within which a review is due.
+    pub review_window_secs: u64,
+    /// Unjustified overrides that suspend a responder.
+    pub suspension_threshold: u32,
+}
+
+/// --------------------
+/// Delegated access
+/// --------------------
+/// One hop in a delegation chain. `scope_mask` and `expires_at` were checked
+/// against the delegator's own access when the hop was created, so every hop
+/// is at most as wide as the one above it.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Delegation {
+    /// op_id assigned when the delegation was created.
+    pub id: u64,
+    pub resource_id: String,
+    pub delegator: Address,
+    pub delegatee: Address,
+    pub scope_mask: u32,
+    pub granted_at: u64,
+    pub expires_at: u64, // 0 means no expiration
+    /// op_id of the upstream link: the delegator's `AccessPermission` when
+    /// `depth == 1`, otherwise the parent `Delegation`.
+    pub parent_id: u64,
+    /// Hops below the direct grant (1 = delegated by the direct grantee).
+    pub depth: u32,
+    pub revoked: bool,
+}
+
+/// The chain that authorized an entity, as reported by `resolve_access`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AccessPath {
+    /// Grantor of the direct `AccessPermission` at the root of the chain.
+    pub root_grantor: Address,
+    /// op_id of that root permission.
+    pub root_op_id: u64,
+    /// Data subject named on the root permission.
+    pub subject: Address,
+    /// Holders from the direct grantee down to the checked entity.
+    pub holders: Vec<Address>,
+    /// Delegation ids from the root downwards; empty for direct access.
+    pub delegation_ids: Vec<u64>,
+    /// Effective scope of the last hop.
+    pub scope_mask: u32,
+    /// Effective expiry of the last hop; 0 means no expiration.
+    pub expires_at: u64,
+    /// Organization whose access the entity inherited as staff, if any.
+    /// When set, it is the second-to-last entry of `holders`.
+    pub via_organization: Option<Address>,
+}
+
+/// --------------------
+/// Organization staff membership
+/// --------------------
+/// Grants and consents issued to an organization are inherited by its staff
+/// for as long as the membership exists and both parties stay active.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct StaffMembership {
+    pub organization: Address,
+    pub member: Address,
+    /// The member's role within the organization.
+    pub role: Role,
+    pub added_by: Address,
+    pub added_at: u64,
+}
+
 /// --------------------
 /// Storage Keys
 /// --------------------
@@ -270,6 +531,38 @@ pub enum DataKey {
     RateLimit(Address, u32),
     // #489: (responder, patient) -> EmergencyAccessRecord
     EmergencyAccess(Address, Address),
+    // Delegated access: id -> Delegation
+    Delegation(u64),
+    // delegatee -> Vec<u64> of delegation ids it received
+    DelegationsTo(Address),
+    // upstream op_id (root grant or delegation) -> Vec<u64> of child delegation ids
+    DelegationChildren(u64),
+    // (organization, member) -> StaffMembership
+    Staff(Address, Address),
+    // organization -> Vec<Address> of members
+    OrgStaff(Address),
+    // member -> Vec<Address> of organizations
+    MemberOrgs(Address),
+    // Break-the-glass review: ReviewConfig
+    ReviewConfig,
+    // emergency op_id -> EmergencyReview
+    EmergencyReview(u64),
+    // page -> Vec<u64> of opened review ids (shared::pagination, append-only)
+    OpenReviewsPage(u32),
+    // u32 index of the page currently being filled
+    OpenReviewsHead,
+    // responder -> u32 count of unjustified overrides
+    UnjustifiedOverrides(Address),
+    // subject -> DidDocument
+    DidDocument(Address),
+    // subject -> Vec<VerificationMethod> of retired keys, oldest first
+    DidKeyHistory(Address),
+    // (issuer, credential id) -> CredentialRecord
+    Credential(Address, BytesN<32>),
+    // issuer -> u64 number of credentials issued
+    IssuedCredentials(Address),
+    // (issuer, list id) -> Bytes revocation bitmap of STATUS_LIST_BYTES
+    StatusList(Address, u32),
 }
 
 #[contract]
@@ -588,6 +881,9 @@ impl AccessControl {
     /// * `resource_id`  - The identifier of the resource
     /// * `expires_at`   - Expiration timestamp (0 for no expiration)
     /// * `nonce`        - Optional nonce used in commit_grant
+    ///
+    /// The grant carries `SCOPE_DIRECT` (read and write). Use
+    /// `grant_access_with_scope` to let the grantee sub-delegate.
     pub fn grant_access(
         env: Env,
         grantor: Address,
@@ -595,8 +891,106 @@ impl AccessControl {
         resource_id: String,
         expires_at: u64,
         nonce: Option<BytesN<32>>,
+    ) -> Result<u64, ContractError> {
+        Self::grant_access_with_scope(
+            env,
+            grantor,
+            grantee,
+            resource_id,
+            SCOPE_DIRECT,
+            expires_at,
+            nonce,
+        )
+    }
+
+    /// `grant_access` with an explicit `scope_mask` of `SCOPE_READ` /
+    /// `SCOPE_WRITE` / `SCOPE_SHARE` bits. Only grants that include
+    /// `SCOPE_SHARE` may be sub-delegated with `delegate_access`.
+    ///
+    /// The grantor is recorded as the data subject of the resource.
+    pub fn grant_access_with_scope(
+        env: Env,
+        grantor: Address,
+        grantee: Address,
+        resource_id: String,
+        scope_mask: u32,
+        expires_at: u64,
+        nonce: Option<BytesN<32>>,
+    ) -> Result<u64, ContractError> {
+        grantor.require_auth();
+        let subject = grantor.clone();
+        Self::store_grant(
+            env,
+            grantor,
+            grantee,
+            subject,
+            resource_id,
+            scope_mask,
+            expires_at,
+            nonce,
+        )
+    }
+
+    /// `grant_access_with_scope` for a resource the grantor holds about
+    /// someone else, such as a provider sharing a patient's imaging study.
+    /// `subject` must be a registered entity; reads through the grant are
+    /// recorded in its disclosure ledger.
+    ///
+    /// The subject must authorize the grant unless the grantor already holds
+    /// `SCOPE_SHARE` on the resource through a chain rooted in a grant naming
+    /// that subject. Otherwise any entity could fill a patient's ledger and
+    /// summary with disclosures the patient never agreed to.
+    #[allow(clippy::too_many_arguments)]
+    pub fn grant_access_for_subject(
+        env: Env,
+        grantor: Address,
+        grantee: Address,
+        subject: Address,
+        resource_id: String,
+        scope_mask: u32,
+        expires_at: u64,
+        nonce: Option<BytesN<32>>,
     ) -> Result<u64, ContractError> {
         grantor.require_auth();
+        if !env
+            .storage()
+            .persistent()
+            .has(&DataKey::Entity(subject.clone()))
+        {
+            return Err(ContractError::EntityNotFound);
+        }
+        let shared_from_subject = Self::resolve_path(&env, &grantor, &resource_id, SCOPE_SHARE)
+            .is_some_and(|path| path.subject == subject);
+        if !shared_from_subject {
+            subject.require_auth();
+        }
+        Self::store_grant(
+            env,
+            grantor,
+            grantee,
+            subject,
+            resource_id,
+            scope_mask,
+            expires_at,
+            nonce,
+        )
+    }
+
+    /// Validates and stores a direct grant once the grantor has authorized it.
+    #[allow(clippy::too_many_arguments)]
+    fn store_grant(
+        env: Env,
+        grantor: Address,
+        grantee: Address,
+        subject: Address,
+        resource_id: String,
+        scope_mask: u32,
+        expires_at: u64,
+        nonce: Option<BytesN<32>>,
+    ) -> Result<u64, ContractError> {
+        if scope_mask == 0 || scope_mask & !SCOPE_ALL != 0 {
+            return Err(ContractError::InvalidScopeMask);
+        }
 
         // #228: verify commit if nonce provided
         if let Some(n) = nonce {
@@ -650,6 +1044,8 @@ impl AccessControl {
             granted_at: env.ledger().timestamp(),
             expires_at,
             op_id,
+            scope_mask,
+            subject,
         };
 
         // Add permission to grantee's access list
@@ -826,6 +1222,11 @@ impl AccessControl {
             + 1;
         env.storage().instance().set(&DataKey::OpCounter, &op_id);
 
+        // Delegation chains rooted in the revoked permission stop resolving
+        // because their root grant is gone. They are not rewritten here: a
+        // grantee could fan out enough delegations to push this call past
+        // transaction limits and make the grant irrevocable.
+
         // #222: include both the revocation op_id and the original grant op_id
         env.events().publish(
             (symbol_short!("revoke"), revokee, resource_id),
@@ -834,38 +1235,10 @@ impl AccessControl {
         Ok(op_id)
     }
 
-    /// Check if an entity has access to a specific resource
+    /// Check if an entity has access to a specific resource, either directly
+    /// or through a valid delegation chain (see `resolve_access`).
     pub fn check_access(env: Env, entity: Address, resource_id: String) -> bool {
-        if let Some(data) = env
-            .storage()
-            .persistent()
-            .get::<DataKey, EntityData>(&DataKey::Entity(entity.clone()))
-        {
-            if !data.active {
-                return false;
-            }
-        }
-
-        let access_key = DataKey::AccessList(entity);
-        let access_list: Vec<AccessPermission> = env
-            .storage()
-            .persistent()
-            .get(&access_key)
-            .unwrap_or(Vec::new(&env));
-
-        let current_time = env.ledger().timestamp();
-
-        for i in 0..access_list.len() {
-            if let Some(permission) = access_list.get(i) {
-                if permission.resource_id == resource_id {
-                    if permission.expires_at == 0 || permission.expires_at > current_time {
-                        return true;
-                    }
-                }
-            }
-        }
-
-        false
+        Self::resolve_path(&env, &entity, &resource_id, 0).is_some()
     }
 
     /// Get all entities with access to a specific resource
@@ -1062,6 +1435,19 @@ impl AccessControl {
         address.require_auth();
         Self::validate_did(&did)?;
 
+        let doc_key = DataKey::DidDocument(address.clone());
+        if let Some(mut doc) = env
+            .storage()
+            .persistent()
+            .get::<DataKey, DidDocument>(&doc_key)
+        {
+            if doc.deactivated {
+                return Err(ContractError::DidDeactivated);
+            }
+            doc.id = did.clone();
+            Self::store_did_document(&env, doc, symbol_short!("set_id"));
+        }
+
         let key = DataKey::Did(address.clone());
         let old_did: Option<Bytes> = env.storage().persistent().get(&key);
         let old_hash: Option<BytesN<32>> = old_did.map(|d| env.crypto().sha256(&d).into());
@@ -1078,6 +1464,258 @@ impl AccessControl {
         env.storage().persistent().get(&DataKey::Did(address))
     }
 
+    // -----------------------------------------------------------------------
+    // DID documents
+    // -----------------------------------------------------------------------
+
+    /// Create the DID document for `subject`'s registered DID with a first
+    /// Ed25519 verification method.
+    pub fn create_did_document(
+        env: Env,
+        subject: Address,
+        key_id: String,
+        public_key: BytesN<32>,
+    ) -> Result<(), ContractError> {
+        subject.require_auth();
+        let did: Bytes = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Did(subject.clone()))
+            .ok_or(ContractError::DidNotRegistered)?;
+        if env
+            .storage()
+            .persistent()
+            .has(&DataKey::DidDocument(subject.clone()))
+        {
+            return Err(ContractError::DidDocumentExists);
+        }
+
+        let now = env.ledger().timestamp();
+        let mut methods = Vec::new(&env);
+        methods.push_back(VerificationMethod {
+            id: key_id,
+            public_key,
+            added_at: now,
+            retired_at: 0,
+        });
+        let doc = DidDocument {
+            id: did,
+            subject,
+            controllers: Vec::new(&env),
+            verification_methods: methods,
+            services: Vec::new(&env),
+            created_at: now,
+            updated_at: now,
+            version: 0,
+            deactivated: false,
+        };
+        Self::store_did_document(&env, doc, symbol_short!("create"));
+        Ok(())
+    }
+
+    /// Returns the current DID document for `subject`, including deactivated
+    /// ones (check `deactivated`).
+    pub fn resolve_did(env: Env, subject: Address) -> Result<DidDocument, ContractError> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::DidDocument(subject))
+            .ok_or(ContractError::DidDocumentNotFound)
+    }
+
+    /// Retired verification methods for `subject`, oldest first.
+    pub fn get_did_key_history(env: Env, subject: Address) -> Vec<VerificationMethod> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::DidKeyHistory(subject))
+            .unwrap_or(Vec::new(&env))
+    }
+
+    pub fn add_verification_method(
+        env: Env,
+        controller: Address,
+        subject: Address,
+        key_id: String,
+        public_key: BytesN<32>,
+    ) -> Result<(), ContractError> {
+        let mut doc = Self::load_did_for_update(&env, &controller, &subject)?;
+        if Self::find_method(&doc, &key_id).is_some() {
+            return Err(ContractError::DidEntryExists);
+        }
+        if doc.verification_methods.len() >= MAX_DID_METHODS {
+            return Err(ContractError::DidLimitExceeded);
+        }
+        doc.verification_methods.push_back(VerificationMethod {
+            id: key_id,
+            public_key,
+            added_at: env.ledger().timestamp(),
+            retired_at: 0,
+        });
+        Self::store_did_document(&env, doc, symbol_short!("add_key"));
+        Ok(())
+    }
+
+    /// Replace the public key behind `key_id`. The previous key moves to the
+    /// key history and stops verifying signatures immediately.
+    pub fn rotate_did_key(
+        env: Env,
+        controller: Address,
+        subject: Address,
+        key_id: String,
+        new_public_key: BytesN<32>,
+    ) -> Result<(), ContractError> {
+        let mut doc = Self::load_did_for_update(&env, &controller, &subject)?;
+        let i = Self::find_method(&doc, &key_id).ok_or(ContractError::DidEntryNotFound)?;
+        let now = env.ledger().timestamp();
+        let old = doc.verification_methods.get(i).unwrap();
+        Self::retire_did_key(&env, &subject, old, now);
+        doc.verification_methods.set(
+            i,
+            VerificationMethod {
+                id: key_id,
+                public_key: new_public_key,
+                added_at: now,
+                retired_at: 0,
+            },
+        );
+        Self::store_did_document(&env, doc, symbol_short!("rotate"));
+        Ok(())
+    }
+
+    /// Retire `key_id` without a replacement.
+    pub fn remove_verification_method(
+        env: Env,
+        controller: Address,
+        subject: Address,
+        key_id: String,
+    ) -> Result<(), ContractError> {
+        let mut doc = Self::load_did_for_update(&env, &controller, &subject)?;
+        let i = Self::find_method(&doc, &key_id).ok_or(ContractError::DidEntryNotFound)?;
+        if doc.verification_methods.len() == 1 {
+            return Err(ContractError::LastVerificationMethod);
+        }
+        let old = doc.verification_methods.get(i).unwrap();
+        Self::retire_did_key(&env, &subject, old, env.ledger().timestamp());
+        doc.verification_methods.remove(i);
+        Self::store_did_document(&env, doc, symbol_short!("rm_key"));
+        Ok(())
+    }
+
+    pub fn add_did_service(
+        env: Env,
+        controller: Address,
+        subject: Address,
+        service: DidService,
+    ) -> Result<(), ContractError> {
+        let mut doc = Self::load_did_for_update(&env, &controller, &subject)?;
+        if doc.services.iter().any(|s| s.id == service.id) {
+            return Err(ContractError::DidEntryExists);
+        }
+        if doc.services.len() >= MAX_DID_SERVICES {
+            return Err(ContractError::DidLimitExceeded);
+        }
+        doc.services.push_back(service);
+        Self::store_did_document(&env, doc, symbol_short!("add_svc"));
+        Ok(())
+    }
+
+    pub fn remove_did_service(
+        env: Env,
+        controller: Address,
+        subject: Address,
+        service_id: String,
+    ) -> Result<(), ContractError> {
+        let mut doc = Self::load_did_for_update(&env, &controller, &subject)?;
+        let i = doc
+            .services
+            .iter()
+            .position(|s| s.id == service_id)
+            .ok_or(ContractError::DidEntryNotFound)?;
+        doc.services.remove(i as u32);
+        Self::store_did_document(&env, doc, symbol_short!("rm_svc"));
+        Ok(())
+    }
+
+    /// Let `new_controller` update `subject`'s document. Controllers have the
+    /// same powers as the subject, including adding further controllers.
+    pub fn add_did_controller(
+        env: Env,
+        controller: Address,
+        subject: Address,
+        new_controller: Address,
+    ) -> Result<(), ContractError> {
+        let mut doc = Self::load_did_for_update(&env, &controller, &subject)?;
+        if new_controller == subject || doc.controllers.contains(&new_controller) {
+            return Err(ContractError::DidEntryExists);
+        }
+        if doc.controllers.len() >= MAX_DID_CONTROLLERS {
+            return Err(ContractError::DidLimitExceeded);
+        }
+        doc.controllers.push_back(new_controller);
+        Self::store_did_document(&env, doc, symbol_short!("add_ctl"));
+        Ok(())
+    }
+
+    pub fn remove_did_controller(
+        env: Env,
+        controller: Address,
+        subject: Address,
+        old_controller: Address,
+    ) -> Result<(), ContractError> {
+        let mut doc = Self::load_did_for_update(&env, &controller, &subject)?;
+        let i = doc
+            .controllers
+            .first_index_of(&old_controller)
+            .ok_or(ContractError::DidEntryNotFound)?;
+        doc.controllers.remove(i);
+        Self::store_did_document(&env, doc, symbol_short!("rm_ctl"));
+        Ok(())
+    }
+
+    /// Permanently deactivate `subject`'s DID. Every active key is retired and
+    /// the document can no longer be updated or used to verify signatures.
+    pub fn deactivate_did(
+        env: Env,
+        controller: Address,
+        subject: Address,
+    ) -> Result<(), ContractError> {
+        let mut doc = Self::load_did_for_update(&env, &controller, &subject)?;
+        let now = env.ledger().timestamp();
+        for method in doc.verification_methods.iter() {
+            Self::retire_did_key(&env, &subject, method, now);
+        }
+        doc.verification_methods = Vec::new(&env);
+        doc.deactivated = true;
+        Self::store_did_document(&env, doc, symbol_short!("deact"));
+        Ok(())
+    }
+
+    /// Check an Ed25519 `signature` over `message` against the current key
+    /// `key_id` of `subject`'s DID.
+    ///
+    /// Returns an error when the document, key or DID is unusable. An invalid
+    /// signature aborts the invocation, as `ed25519_verify` traps on failure.
+    pub fn verify_did_signature(
+        env: Env,
+        subject: Address,
+        key_id: String,
+        message: Bytes,
+        signature: BytesN<64>,
+    ) -> Result<(), ContractError> {
+        let doc: DidDocument = env
+            .storage()
+            .persistent()
+            .get(&DataKey::DidDocument(subject))
+            .ok_or(ContractError::DidDocumentNotFound)?;
+        if doc.deactivated {
+            return Err(ContractError::DidDeactivated);
+        }
+        let i = Self::find_method(&doc, &key_id).ok_or(ContractError::DidEntryNotFound)?;
+        let method = doc.verification_methods.get(i).unwrap();
+        env.crypto()
+            .ed25519_verify(&method.public_key, &message, &signature);
+        Ok(())
+    }
+
     // -----------------------------------------------------------------------
     // #223: Unified Consent Engine
     // -----------------------------------------------------------------------
@@ -1211,49 +1849,8 @@ impl AccessControl {
         purpose_code: String,
         required_scope: u32,
     ) -> Result<(), ContractError> {
-        if let Some(data) = env
-            .storage()
-            .persistent()
-            .get::<DataKey, EntityData>(&DataKey::Entity(grantee.clone()))
-        {
-            if !data.active {
-                return Err(ContractError::ConsentDenied);
-            }
-        }
-        if let Some(data) = env
-            .storage()
-            .persistent()
-            .get::<DataKey, EntityData>(&DataKey::Entity(subject.clone()))
-        {
-            if !data.active {
-                return Err(ContractError::ConsentDenied);
-            }
-        }
-
-        let key = DataKey::Consent(subject, grantee, purpose_code);
-        let record: ConsentRecord = env
-            .storage()
-            .persistent()
-            .get(&key)
-            .ok_or(ContractError::ConsentNotFound)?;
-
-        match record.status {
-            ConsentStatus::Revoked => return Err(ContractError::ConsentRevoked),
-            ConsentStatus::Expired => return Err(ContractError::ConsentExpired),
-            ConsentStatus::Active => {}
-        }
-
-        let now = env.ledger().timestamp();
-        if record.expires_at != 0 && now > record.expires_at {
-            return Err(ContractError::ConsentExpired);
-        }
-
-        if required_scope != 0 && (record.scope_mask & required_scope) != required_scope {
-            return Err(ContractError::ConsentDenied);
-        }
-
-        Ok(())
-    }
+        Self::resolve_consent(&env, &subject, &grantee, &purpose_code, required_scope).map(|_| ())
+    }
 
     /// Return the full consent record for a (subject, grantee, purpose_code) triple.
     pub fn get_consent(
@@ -1320,10 +1917,12 @@ impl AccessControl {
 
         // Mandatory, unsuppressable audit event.
         env.events().publish(
-            (symbol_short!("emrg_acc"), responder, patient),
-            (justification_hash, expires_at, op_id),
+            (symbol_short!("emrg_acc"), responder.clone(), patient.clone()),
+            (justification_hash.clone(), expires_at, op_id),
         );
 
+        Self::open_review(&env, op_id, responder, patient, justification_hash, now);
+
         Ok(op_id)
     }
 
@@ -1338,6 +1937,766 @@ impl AccessControl {
         }
     }
 
+    // -----------------------------------------------------------------------
+    // Verifiable credentials
+    // -----------------------------------------------------------------------
+
+    /// Anchor a credential issued by `issuer` to `subject`.
+    ///
+    /// The issuer must hold a live DID document so verifiers can resolve the
+    /// key that signed the off-chain credential. Contracts such as
+    /// healthcare-credentialing and nft-badges issue as themselves.
+    ///
+    /// # Arguments
+    /// * `issuer`        - Address with an active DID document (must be authorized)
+    /// * `subject`       - Holder of the credential
+    /// * `credential_id` - Hash of the off-chain credential; must be unused by
+    ///   this issuer. Ids are scoped per issuer, so one issuer cannot claim
+    ///   ids another will use.
+    /// * `schema_hash`   - Hash of the credential schema
+    /// * `expires_at`    - Expiration timestamp (0 for no expiration)
+    pub fn issue_credential(
+        env: Env,
+        issuer: Address,
+        subject: Address,
+        credential_id: BytesN<32>,
+        schema_hash: BytesN<32>,
+        expires_at: u64,
+    ) -> Result<CredentialRecord, CredentialError> {
+        issuer.require_auth();
+        let issuer_doc: DidDocument = env
+            .storage()
+            .persistent()
+            .get(&DataKey::DidDocument(issuer.clone()))
+            .ok_or(CredentialError::IssuerDidNotFound)?;
+        if issuer_doc.deactivated {
+            return Err(CredentialError::IssuerDidDeactivated);
+        }
+        let key = DataKey::Credential(issuer.clone(), credential_id.clone());
+        if env.storage().persistent().has(&key) {
+            return Err(CredentialError::CredentialExists);
+        }
+
+        let count_key = DataKey::IssuedCredentials(issuer.clone());
+        let n: u64 = env.storage().persistent().get(&count_key).unwrap_or(0);
+        env.storage().persistent().set(&count_key, &(n + 1));
+        let bits_per_list = (STATUS_LIST_BYTES * 8) as u64;
+
+        let record = CredentialRecord {
+            id: credential_id.clone(),
+            issuer: issuer.clone(),
+            subject: subject.clone(),
+            schema_hash,
+            issued_at: env.ledger().timestamp(),
+            expires_at,
+            status_list: (n / bits_per_list) as u32,
+            status_index: (n % bits_per_list) as u32,
+        };
+        env.storage().persistent().set(&key, &record);
+
+        env.events().publish(
+            (symbol_short!("vc_issue"), issuer, subject),
+            (credential_id, record.status_list, record.status_index),
+        );
+        Ok(record)
+    }
+
+    /// Flip the credential's bit in its issuer's status list. Only the issuer
+    /// may revoke, and revocation is permanent.
+    pub fn revoke_credential(
+        env: Env,
+        issuer: Address,
+        credential_id: BytesN<32>,
+    ) -> Result<(), CredentialError> {
+        issuer.require_auth();
+        let record: CredentialRecord = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Credential(issuer.clone(), credential_id.clone()))
+            .ok_or(CredentialError::CredentialNotFound)?;
+
+        let list_key = DataKey::StatusList(issuer.clone(), record.status_list);
+        let mut list: Bytes = env
+            .storage()
+            .persistent()
+            .get(&list_key)
+            .unwrap_or(Bytes::from_array(&env, &[0u8; STATUS_LIST_BYTES as usize]));
+        let byte = record.status_index / 8;
+        let mask = 0x80u8 >> (record.status_index % 8);
+        let current = list.get(byte).unwrap_or(0);
+        if current & mask != 0 {
+            return Err(CredentialError::CredentialAlreadyRevoked);
+        }
+        list.set(byte, current | mask);
+        env.storage().persistent().set(&list_key, &list);
+
+        env.events().publish(
+            (symbol_short!("vc_revoke"), issuer, record.subject),
+            (credential_id, record.status_list, record.status_index),
+        );
+        Ok(())
+    }
+
+    pub fn get_credential(
+        env: Env,
+        issuer: Address,
+        credential_id: BytesN<32>,
+    ) -> Result<CredentialRecord, CredentialError> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Credential(issuer, credential_id))
+            .ok_or(CredentialError::CredentialNotFound)
+    }
+
+    /// Revocation bitmap `list_id` of `issuer`, always `STATUS_LIST_BYTES`
+    /// long. Lists nobody has revoked from yet read as all zeroes.
+    pub fn get_status_list(env: Env, issuer: Address, list_id: u32) -> Bytes {
+        env.storage()
+            .persistent()
+            .get(&DataKey::StatusList(issuer, list_id))
+            .unwrap_or(Bytes::from_array(&env, &[0u8; STATUS_LIST_BYTES as usize]))
+    }
+
+    /// `true` if the credential's status bit is set. Expiry is not
+    /// considered; compare `expires_at` separately.
+    pub fn is_credential_revoked(
+        env: Env,
+        issuer: Address,
+        credential_id: BytesN<32>,
+    ) -> Result<bool, CredentialError> {
+        let record = Self::get_credential(env.clone(), issuer, credential_id)?;
+        let list = Self::get_status_list(env, record.issuer, record.status_list);
+        let byte = list.get(record.status_index / 8).unwrap_or(0);
+        Ok(byte & (0x80u8 >> (record.status_index % 8)) != 0)
+    }
+
+    // -----------------------------------------------------------------------
+    // Break-the-glass review
+    // -----------------------------------------------------------------------
+
+    /// Set the review window and the number of unjustified overrides after
+    /// which a responder is suspended. Admin only.
+    pub fn set_review_config(
+        env: Env,
+        admin: Address,
+        review_window_secs: u64,
+        suspension_threshold: u32,
+    ) -> Result<(), ContractError> {
+        admin.require_auth();
+        Self::require_role(&env, &admin, &Role::Admin)?;
+        if review_window_secs == 0 || suspension_threshold == 0 {
+            return Err(ContractError::InvalidReviewConfig);
+        }
+        env.storage().persistent().set(
+            &DataKey::ReviewConfig,
+            &ReviewConfig {
+                review_window_secs,
+                suspension_threshold,
+            },
+        );
+        Ok(())
+    }
+
+    pub fn get_review_config(env: Env) -> ReviewConfig {
+        Self::review_config(&env)
+    }
+
+    /// Record an `Auditor`'s finding on an emergency access case.
+    ///
+    /// Late reviews are accepted; an overdue case keeps its `Overdue`
+    /// escalation. An unjustified finding escalates the case and counts
+    /// against the responder, who loses `Role::EmergencyResponder` once the
+    /// count reaches the configured threshold.
+    ///
+    /// # Arguments
+    /// * `reviewer`      - Must hold `Role::Auditor` granted by an admin, and
+    ///   be neither the responder nor an auditor the responder appointed
+    /// * `review_id`     - op_id returned by `emergency_access`
+    /// * `justified`     - The reviewer's finding
+    /// * `findings_hash` - sha256 of the written findings (stored off-chain)
+    pub fn review_emergency_access(
+        env: Env,
+        reviewer: Address,
+        review_id: u64,
+        justified: bool,
+        findings_hash: BytesN<32>,
+    ) -> Result<(), ContractError> {
+        reviewer.require_auth();
+        Self::require_role(&env, &reviewer, &Role::Auditor)?;
+
+        let key = DataKey::EmergencyReview(review_id);
+        let mut review: EmergencyReview = env
+            .storage()
+            .persistent()
+            .get(&key)
+            .ok_or(ContractError::EmergencyReviewNotFound)?;
+        if review.status != ReviewStatus::Pending {
+            return Err(ContractError::EmergencyReviewClosed);
+        }
+        if reviewer == review.responder {
+            return Err(ContractError::SelfReviewNotAllowed);
+        }
+        // A responder outranks `Auditor` and could otherwise appoint its own
+        // reviewer, so only auditors appointed by an admin are independent.
+        if let Some(assignment) = Self::load_active_role(&env, &reviewer, &Role::Auditor) {
+            if assignment.granted_by == review.responder {
+                return Err(ContractError::SelfReviewNotAllowed);
+            }
+            if Self::require_role(&env, &assignment.granted_by, &Role::Admin).is_err() {
+                return Err(ContractError::InsufficientRole);
+            }
+        }
+
+        review.status = if justified {
+            ReviewStatus::Justified
+        } else {
+            ReviewStatus::Unjustified
+        };
+        review.reviewer = Some(reviewer.clone());
+        review.findings_hash = Some(findings_hash);
+        review.reviewed_at = env.ledger().timestamp();
+        if !justified && review.escalation == Escalation::None {
+            review.escalation = Escalation::Unjustified;
+        }
+        env.storage().persistent().set(&key, &review);
+
+        env.events().publish(
+            (
+                symbol_short!("brk_rvw"),
+                review.patient.clone(),
+                review.responder.clone(),
+            ),
+            (review_id, justified, reviewer),
+        );
+
+        if !justified {
+            env.events().publish(
+                (
+                    symbol_short!("brk_esc"),
+                    review.patient.clone(),
+                    review.responder.clone(),
+                ),
+                (review_id, Escalation::Unjustified),
+            );
+            Self::record_unjustified_override(&env, &review.responder);
+        }
+        Ok(())
+    }
+
+    /// Escalate a case whose review window has passed without a finding.
+    /// Permissionless so that any keeper or compliance bot can trigger it.
+    pub fn escalate_overdue_review(env: Env, review_id: u64) -> Result<(), ContractError> {
+        let key = DataKey::EmergencyReview(review_id);
+        let mut review: EmergencyReview = env
+            .storage()
+            .persistent()
+            .get(&key)
+            .ok_or(ContractError::EmergencyReviewNotFound)?;
+        if review.status != ReviewStatus::Pending || review.escalation != Escalation::None {
+            return Err(ContractError::EmergencyReviewClosed);
+        }
+        if env.ledger().timestamp() <= review.due_at {
+            return Err(ContractError::EmergencyReviewNotOverdue);
+        }
+
+        review.escalation = Escalation::Overdue;
+        env.storage().persistent().set(&key, &review);
+
+        env.events().publish(
+            (symbol_short!("brk_esc"), review.patient, review.responder),
+            (review_id, Escalation::Overdue),
+        );
+        Ok(())
+    }
+
+    pub fn get_emergency_review(
+        env: Env,
+        review_id: u64,
+    ) -> Result<EmergencyReview, ContractError> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::EmergencyReview(review_id))
+            .ok_or(ContractError::EmergencyReviewNotFound)
+    }
+
+    /// Ids on `page` of the review index whose case is still pending, oldest
+    /// first. Each page covers a fixed run of opened cases, so pages shrink as
+    /// cases are reviewed; keep paging while `has_more` is set.
+    pub fn get_open_reviews(env: Env, page: u32) -> PageResult {
+        let mut result = pagination::get_paged(
+            &env,
+            DataKey::OpenReviewsPage,
+            || DataKey::OpenReviewsHead,
+            page,
+        );
+        let mut open = Vec::new(&env);
+        for id in result.ids.iter() {
+            let review_id = u64::try_from_val(&env, &id).unwrap_or_default();
+            let pending = env
+                .storage()
+                .persistent()
+                .get::<_, EmergencyReview>(&DataKey::EmergencyReview(review_id))
+                .is_some_and(|r| r.status == ReviewStatus::Pending);
+            if pending {
+                open.push_back(id);
+            }
+        }
+        result.ids = open;
+        result
+    }
+
+    pub fn get_unjustified_override_count(env: Env, responder: Address) -> u32 {
+        env.storage()
+            .persistent()
+            .get(&DataKey::UnjustifiedOverrides(responder))
+            .unwrap_or(0)
+    }
+
+    // -----------------------------------------------------------------------
+    // Delegated access
+    // -----------------------------------------------------------------------
+
+    /// Sub-delegate part of `delegator`'s access to `resource_id`.
+    ///
+    /// The delegator must currently hold the resource (directly or through a
+    /// chain) with `SCOPE_SHARE` plus every bit in `scope_mask`. `expires_at`
+    /// may not outlive the delegator's own access: when that access expires,
+    /// `expires_at` must be non-zero and no later.
+    ///
+    /// # Arguments
+    /// * `delegator`   - Current holder of the resource (must be authorized)
+    /// * `delegatee`   - Registered, active entity receiving the delegation
+    /// * `resource_id` - The identifier of the resource
+    /// * `scope_mask`  - Bitmask of `SCOPE_READ` / `SCOPE_WRITE` / `SCOPE_SHARE`
+    /// * `expires_at`  - Expiration timestamp (0 for no expiration)
+    pub fn delegate_access(
+        env: Env,
+        delegator: Address,
+        delegatee: Address,
+        resource_id: String,
+        scope_mask: u32,
+        expires_at: u64,
+    ) -> Result<u64, ContractError> {
+        delegator.require_auth();
+
+        if scope_mask == 0 || scope_mask & !SCOPE_ALL != 0 {
+            return Err(ContractError::InvalidScopeMask);
+        }
+        let delegatee_entity: EntityData = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Entity(delegatee.clone()))
+            .ok_or(ContractError::GranteeNotRegistered)?;
+        if !delegatee_entity.active {
+            return Err(ContractError::EntityInactive);
+        }
+
+        // Access inherited from an organization belongs to the organization
+        // and cannot be passed on by individual staff.
+        let parent = Self::resolve_path(&env, &delegator, &resource_id, scope_mask | SCOPE_SHARE)
+            .filter(|path| path.via_organization.is_none())
+            .ok_or(ContractError::DelegationExceedsGrant)?;
+        if parent.expires_at != 0 && (expires_at == 0 || expires_at > parent.expires_at) {
+            return Err(ContractError::DelegationExceedsGrant);
+        }
+        let depth = parent.delegation_ids.len() + 1;
+        if depth > MAX_DELEGATION_DEPTH {
+            return Err(ContractError::DelegationTooDeep);
+        }
+        if parent.holders.contains(&delegatee) {
+            return Err(ContractError::DelegationCycle);
+        }
+
+        let received_key = DataKey::DelegationsTo(delegatee.clone());
+        let mut received: Vec<u64> = env
+            .storage()
+            .persistent()
+            .get(&received_key)
+            .unwrap_or(Vec::new(&env));
+        if received.len() >= MAX_ACCESS_LIST_LEN {
+            return Err(ContractError::InputTooLarge);
+        }
+
+        let parent_id = parent.delegation_ids.last().unwrap_or(parent.root_op_id);
+        let children_key = DataKey::DelegationChildren(parent_id);
+        let mut children: Vec<u64> = env
+            .storage()
+            .persistent()
+            .get(&children_key)
+            .unwrap_or(Vec::new(&env));
+        if children.len() >= MAX_ACCESS_LIST_LEN {
+            return Err(ContractError::InputTooLarge);
+        }
+
+        let op_id: u64 = env
+            .storage()
+            .instance()
+            .get(&DataKey::OpCounter)
+            .unwrap_or(0u64)
+            + 1;
+        env.storage().instance().set(&DataKey::OpCounter, &op_id);
+
+        let delegation = Delegation {
+            id: op_id,
+            resource_id: resource_id.clone(),
+            delegator: delegator.clone(),
+            delegatee: delegatee.clone(),
+            scope_mask,
+            granted_at: env.ledger().timestamp(),
+            expires_at,
+            parent_id,
+            depth,
+            revoked: false,
+        };
+        env.storage()
+            .persistent()
+            .set(&DataKey::Delegation(op_id), &delegation);
+
+        received.push_back(op_id);
+        env.storage().persistent().set(&received_key, &received);
+        children.push_back(op_id);
+        env.storage().persistent().set(&children_key, &children);
+
+        env.events().publish(
+            (symbol_short!("delegate"), delegatee, resource_id),
+            (op_id, delegator, scope_mask),
+        );
+        Ok(op_id)
+    }
+
+    /// Revoke a delegation. Delegations below it stop resolving because an
+    /// upstream link is revoked; they are left as stored.
+    ///
+    /// Allowed for the delegator, any holder upstream of it in the chain, and
+    /// the contract admin.
+    pub fn revoke_delegation(
+        env: Env,
+        revoker: Address,
+        delegation_id: u64,
+    ) -> Result<(), ContractError> {
+        revoker.require_auth();
+
+        let delegation: Delegation = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Delegation(delegation_id))
+            .ok_or(ContractError::DelegationNotFound)?;
+
+        let admin: Address = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Admin)
+            .ok_or(ContractError::ContractNotInitialized)?;
+
+        let mut allowed = revoker == admin || revoker == delegation.delegator;
+        let mut cursor = delegation.clone();
+        while !allowed && cursor.depth > 1 {
+            match env
+                .storage()
+                .persistent()
+                .get::<DataKey, Delegation>(&DataKey::Delegation(cursor.parent_id))
+            {
+                Some(parent) => {
+                    allowed = parent.delegator == revoker;
+                    cursor = parent;
+                }
+                None => break,
+            }
+        }
+        if !allowed {
+            return Err(ContractError::NotAuthorizedToRevoke);
+        }
+
+        if !delegation.revoked {
+            Self::mark_delegation_revoked(&env, delegation);
+        }
+        Ok(())
+    }
+
+    /// Resolve how `entity` holds `resource_id` with at least `required_scope`
+    /// (0 = any scope). A direct grant covering the scope wins over
+    /// delegations; otherwise the first valid chain is returned. A chain is valid only while every hop is
+    /// unrevoked and unexpired, every holder is active, and the root
+    /// `AccessPermission` still exists.
+    pub fn resolve_access(
+        env: Env,
+        entity: Address,
+        resource_id: String,
+        required_scope: u32,
+    ) -> Result<AccessPath, ContractError> {
+        Self::resolve_path(&env, &entity, &resource_id, required_scope)
+            .ok_or(ContractError::AccessPermissionNotFound)
+    }
+
+    pub fn get_delegation(env: Env, delegation_id: u64) -> Result<Delegation, ContractError> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Delegation(delegation_id))
+            .ok_or(ContractError::DelegationNotFound)
+    }
+
+    /// Every delegation `delegatee` has received, including revoked ones.
+    pub fn get_received_delegations(env: Env, delegatee: Address) -> Vec<Delegation> {
+        let ids: Vec<u64> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::DelegationsTo(delegatee))
+            .unwrap_or(Vec::new(&env));
+        let mut out = Vec::new(&env);
+        for id in ids.iter() {
+            if let Some(d) = env
+                .storage()
+                .persistent()
+                .get::<DataKey, Delegation>(&DataKey::Delegation(id))
+            {
+                out.push_back(d);
+            }
+        }
+        out
+    }
+
+    // -----------------------------------------------------------------------
+    // Organization staff registry
+    // -----------------------------------------------------------------------
+
+    /// Add `member` to `organization`'s staff with `role`.
+    ///
+    /// Callable by the organization itself or the contract admin. Staff
+    /// inherit every grant and consent issued to the organization (see
+    /// `check_access` / `check_consent`) until removed, and lose it at once
+    /// if the organization is deactivated.
+    pub fn add_staff(
+        env: Env,
+        caller: Address,
+        organization: Address,
+        member: Address,
+        role: Role,
+    ) -> Result<(), ContractError> {
+        caller.require_auth();
+        Self::require_org_manager(&env, &caller, &organization)?;
+
+        let member_entity: EntityData = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Entity(member.clone()))
+            .ok_or(ContractError::EntityNotFound)?;
+        if !member_entity.active {
+            return Err(ContractError::EntityInactive);
+        }
+        if member_entity.entity_type == EntityType::Hospital {
+            return Err(ContractError::OrganizationCannotBeStaff);
+        }
+
+        let staff_key = DataKey::Staff(organization.clone(), member.clone());
+        if env.storage().persistent().has(&staff_key) {
+            return Err(ContractError::StaffAlreadyMember);
+        }
+
+        let org_key = DataKey::OrgStaff(organization.clone());
+        let mut staff: Vec<Address> = env
+            .storage()
+            .persistent()
+            .get(&org_key)
+            .unwrap_or(Vec::new(&env));
+        if staff.len() >= MAX_ORG_STAFF {
+            return Err(ContractError::InputTooLarge);
+        }
+        let member_key = DataKey::MemberOrgs(member.clone());
+        let mut orgs: Vec<Address> = env
+            .storage()
+            .persistent()
+            .get(&member_key)
+            .unwrap_or(Vec::new(&env));
+        if orgs.len() >= MAX_MEMBER_ORGS {
+            return Err(ContractError::InputTooLarge);
+        }
+
+        let membership = StaffMembership {
+            organization: organization.clone(),
+            member: member.clone(),
+            role: role.clone(),
+            added_by: caller,
+            added_at: env.ledger().timestamp(),
+        };
+        env.storage().persistent().set(&staff_key, &membership);
+        staff.push_back(member.clone());
+        env.storage().persistent().set(&org_key, &staff);
+        orgs.push_back(organization.clone());
+        env.storage().persistent().set(&member_key, &orgs);
+
+        env.events()
+            .publish((symbol_short!("staff_add"), organization, member), role);
+        Ok(())
+    }
+
+    /// Remove `member` from `organization`. Inherited access ends immediately.
+    /// Callable by the organization itself or the contract admin.
+    pub fn remove_staff(
+        env: Env,
+        caller: Address,
+        organization: Address,
+        member: Address,
+    ) -> Result<(), ContractError> {
+        caller.require_auth();
+        Self::require_org_manager(&env, &caller, &organization)?;
+
+        let staff_key = DataKey::Staff(organization.clone(), member.clone());
+        if !env.storage().persistent().has(&staff_key) {
+            return Err(ContractError::StaffNotFound);
+        }
+        env.storage().persistent().remove(&staff_key);
+
+        let org_key = DataKey::OrgStaff(organization.clone());
+        let staff: Vec<Address> = env
+            .storage()
+            .persistent()
+            .get(&org_key)
+            .unwrap_or(Vec::new(&env));
+        let mut remaining: Vec<Address> = Vec::new(&env);
+        for addr in staff.iter() {
+            if addr != member {
+                remaining.push_back(addr);
+            }
+        }
+        env.storage().persistent().set(&org_key, &remaining);
+
+        let member_key = DataKey::MemberOrgs(member.clone());
+        let orgs: Vec<Address> = env
+            .storage()
+            .persistent()
+            .get(&member_key)
+            .unwrap_or(Vec::new(&env));
+        let mut remaining_orgs: Vec<Address> = Vec::new(&env);
+        for org in orgs.iter() {
+            if org != organization {
+                remaining_orgs.push_back(org);
+            }
+        }
+        env.storage().persistent().set(&member_key, &remaining_orgs);
+
+        env.events().publish(
+            (symbol_short!("staff_rm"), organization, member),
+            symbol_short!("success"),
+        );
+        Ok(())
+    }
+
+    /// Every current staff membership of `organization`.
+    pub fn get_staff(env: Env, organization: Address) -> Vec<StaffMembership> {
+        let staff: Vec<Address> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::OrgStaff(organization.clone()))
+            .unwrap_or(Vec::new(&env));
+        let mut out = Vec::new(&env);
+        for member in staff.iter() {
+            if let Some(m) = env
+                .storage()
+                .persistent()
+                .get::<DataKey, StaffMembership>(&DataKey::Staff(organization.clone(), member))
+            {
+                out.push_back(m);
+            }
+        }
+        out
+    }
+
+    pub fn get_staff_membership(
+        env: Env,
+        organization: Address,
+        member: Address,
+    ) -> Result<StaffMembership, ContractError> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Staff(organization, member))
+            .ok_or(ContractError::StaffNotFound)
+    }
+
+    /// Organizations `member` belongs to, whether or not they are active.
+    pub fn get_member_organizations(env: Env, member: Address) -> Vec<Address> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::MemberOrgs(member))
+            .unwrap_or(Vec::new(&env))
+    }
+
+    // -----------------------------------------------------------------------
+    // Accounting of disclosures
+    // -----------------------------------------------------------------------
+
+    /// `check_access` for a read that actually releases the resource's data:
+    /// resolves the accessor's path and records a disclosure in the ledger of
+    /// the data subject named on its root grant, citing the op_id of the hop
+    /// that authorized it.
+    pub fn access_resource(
+        env: Env,
+        accessor: Address,
+        resource_id: String,
+        required_scope: u32,
+        purpose: String,
+    ) -> Result<AccessPath, ContractError> {
+        accessor.require_auth();
+        let path = Self::resolve_path(&env, &accessor, &resource_id, required_scope)
+            .ok_or(ContractError::AccessPermissionNotFound)?;
+        let basis_op_id = path.delegation_ids.last().unwrap_or(path.root_op_id);
+        disclosure::record_disclosure(
+            &env,
+            &path.subject,
+            &accessor,
+            resource_id,
+            basis_op_id,
+            purpose,
+        );
+        Ok(path)
+    }
+
+    /// `check_consent` for a read that actually releases `subject`'s data to
+    /// `grantee`. Consents are not tied to a resource, so the disclosure names
+    /// the consent's scope as `consent_scope:<scope_mask>` and cites its op_id.
+    /// Returns the disclosure id.
+    pub fn access_with_consent(
+        env: Env,
+        subject: Address,
+        grantee: Address,
+        purpose_code: String,
+        required_scope: u32,
+    ) -> Result<u64, ContractError> {
+        grantee.require_auth();
+        let record =
+            Self::resolve_consent(&env, &subject, &grantee, &purpose_code, required_scope)?;
+        Ok(disclosure::record_disclosure(
+            &env,
+            &subject,
+            &grantee,
+            disclosure::numbered_resource_id(
+                &env,
+                "consent_scope:",
+                record.scope_mask as u64,
+            ),
+            record.op_id,
+            purpose_code,
+        ))
+    }
+
+    /// Disclosures of `patient`'s data between `from` and `to` (inclusive).
+    /// See `shared::disclosure::get_disclosures` for paging. Patient only.
+    pub fn get_disclosures(
+        env: Env,
+        patient: Address,
+        from: u64,
+        to: u64,
+        page: u32,
+    ) -> DisclosurePage {
+        patient.require_auth();
+        disclosure::get_disclosures(&env, &patient, from, to, page)
+    }
+
+    /// Who has received `patient`'s data, how often and when. Patient only.
+    pub fn get_disclosure_summary(env: Env, patient: Address) -> DisclosureSummary {
+        patient.require_auth();
+        disclosure::get_summary(&env, &patient)
+    }
+
     // -----------------------------------------------------------------------
     // Patient deregistration hook
     // -----------------------------------------------------------------------
@@ -1353,7 +2712,7 @@ impl AccessControl {
     ///   `ResourceAccess`/`GrantIndex` entries
     /// - `SubjectConsents` index + every `Consent` record where the patient is
     ///   the subject
-    /// - `Did` registration
+    /// - `Did` registration, DID document and key history
     ///
     /// Only callable by the stored admin.
     pub fn deregister_patient(env: Env, patient: Address) -> Result<(), ContractError> {
@@ -1472,10 +2831,16 @@ impl AccessControl {
         }
         env.storage().persistent().remove(&granted_key);
 
-        // Remove DID
+        // Remove DID and its document
         env.storage()
             .persistent()
             .remove(&DataKey::Did(patient.clone()));
+        env.storage()
+            .persistent()
+            .remove(&DataKey::DidDocument(patient.clone()));
+        env.storage()
+            .persistent()
+            .remove(&DataKey::DidKeyHistory(patient.clone()));
 
         // Remove all consent records where patient is the subject
         let idx_key = DataKey::SubjectConsents(patient.clone());
@@ -1507,6 +2872,404 @@ impl AccessControl {
     // Internal helpers
     // -----------------------------------------------------------------------
 
+    /// The consent record authorizing `grantee` (directly or through an
+    /// organization it is staff of), after the entity-active checks.
+    fn resolve_consent(
+        env: &Env,
+        subject: &Address,
+        grantee: &Address,
+        purpose_code: &String,
+        required_scope: u32,
+    ) -> Result<ConsentRecord, ContractError> {
+        if Self::is_inactive(env, grantee) || Self::is_inactive(env, subject) {
+            return Err(ContractError::ConsentDenied);
+        }
+
+        let direct = Self::evaluate_consent(env, subject, grantee, purpose_code, required_scope);
+        if direct != Err(ContractError::ConsentNotFound) {
+            return direct;
+        }
+
+        // Fall back to consent the subject gave to an organization the
+        // grantee is staff of.
+        for org in Self::active_orgs_of(env, grantee).iter() {
+            if let Ok(record) =
+                Self::evaluate_consent(env, subject, &org, purpose_code, required_scope)
+            {
+                return Ok(record);
+            }
+        }
+        direct
+    }
+
+    /// Evaluate the stored consent for exactly (subject, grantee, purpose_code).
+    fn evaluate_consent(
+        env: &Env,
+        subject: &Address,
+        grantee: &Address,
+        purpose_code: &String,
+        required_scope: u32,
+    ) -> Result<ConsentRecord, ContractError> {
+        let key = DataKey::Consent(subject.clone(), grantee.clone(), purpose_code.clone());
+        let record: ConsentRecord = env
+            .storage()
+            .persistent()
+            .get(&key)
+            .ok_or(ContractError::ConsentNotFound)?;
+
+        match record.status {
+            ConsentStatus::Revoked => return Err(ContractError::ConsentRevoked),
+            ConsentStatus::Expired => return Err(ContractError::ConsentExpired),
+            ConsentStatus::Active => {}
+        }
+
+        let now = env.ledger().timestamp();
+        if record.expires_at != 0 && now > record.expires_at {
+            return Err(ContractError::ConsentExpired);
+        }
+
+        if required_scope != 0 && (record.scope_mask & required_scope) != required_scope {
+            return Err(ContractError::ConsentDenied);
+        }
+
+        Ok(record)
+    }
+
+    /// `caller` may manage `organization`'s staff: it is the organization
+    /// itself or the contract admin, and the organization is an active
+    /// `EntityType::Hospital`.
+    fn require_org_manager(
+        env: &Env,
+        caller: &Address,
+        organization: &Address,
+    ) -> Result<(), ContractError> {
+        let org: EntityData = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Entity(organization.clone()))
+            .ok_or(ContractError::EntityNotFound)?;
+        if org.entity_type != EntityType::Hospital {
+            return Err(ContractError::NotAnOrganization);
+        }
+        if !org.active {
+            return Err(ContractError::EntityInactive);
+        }
+        let admin: Address = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Admin)
+            .ok_or(ContractError::ContractNotInitialized)?;
+        if caller != organization && *caller != admin {
+            return Err(ContractError::NotOrganizationManager);
+        }
+        Ok(())
+    }
+
+    fn review_config(env: &Env) -> ReviewConfig {
+        env.storage()
+            .persistent()
+            .get(&DataKey::ReviewConfig)
+            .unwrap_or(ReviewConfig {
+                review_window_secs: DEFAULT_REVIEW_WINDOW_SECS,
+                suspension_threshold: DEFAULT_SUSPENSION_THRESHOLD,
+            })
+    }
+
+    /// Open the review case for emergency access `op_id` and notify the patient.
+    fn open_review(
+        env: &Env,
+        op_id: u64,
+        responder: Address,
+        patient: Address,
+        justification_hash: BytesN<32>,
+        now: u64,
+    ) {
+        let due_at = now.saturating_add(Self::review_config(env).review_window_secs);
+        let review = EmergencyReview {
+            id: op_id,
+            responder: responder.clone(),
+            patient: patient.clone(),
+            justification_hash,
+            opened_at: now,
+            due_at,
+            status: ReviewStatus::Pending,
+            reviewer: None,
+            findings_hash: None,
+            reviewed_at: 0,
+            escalation: Escalation::None,
+        };
+        env.storage()
+            .persistent()
+            .set(&DataKey::EmergencyReview(op_id), &review);
+
+        pagination::push_paged(
+            env,
+            DataKey::OpenReviewsPage,
+            || DataKey::OpenReviewsHead,
+            op_id,
+        );
+
+        env.events().publish(
+            (symbol_short!("brk_open"), patient, responder),
+            (op_id, due_at),
+        );
+    }
+
+    /// Count an unjustified override against `responder` and revoke their
+    /// `EmergencyResponder` role once the threshold is reached. The count
+    /// restarts after a suspension, so a re-granted role starts clean.
+    fn record_unjustified_override(env: &Env, responder: &Address) {
+        let count_key = DataKey::UnjustifiedOverrides(responder.clone());
+        let count: u32 = env.storage().persistent().get(&count_key).unwrap_or(0) + 1;
+        if count < Self::review_config(env).suspension_threshold {
+            env.storage().persistent().set(&count_key, &count);
+            return;
+        }
+
+        env.storage().persistent().remove(&count_key);
+        let role_key = DataKey::RoleAssignment(responder.clone(), Role::EmergencyResponder);
+        if env.storage().persistent().has(&role_key) {
+            env.storage().persistent().remove(&role_key);
+            env.events().publish(
+                (
+                    symbol_short!("role_rev"),
+                    responder.clone(),
+                    Role::EmergencyResponder,
+                ),
+                symbol_short!("success"),
+            );
+        }
+        env.events()
+            .publish((symbol_short!("er_susp"), responder.clone()), count);
+    }
+
+    /// Authorize `controller` against `subject`'s live DID document.
+    fn load_did_for_update(
+        env: &Env,
+        controller: &Address,
+        subject: &Address,
+    ) -> Result<DidDocument, ContractError> {
+        controller.require_auth();
+        let doc: DidDocument = env
+            .storage()
+            .persistent()
+            .get(&DataKey::DidDocument(subject.clone()))
+            .ok_or(ContractError::DidDocumentNotFound)?;
+        if doc.deactivated {
+            return Err(ContractError::DidDeactivated);
+        }
+        if controller != subject && !doc.controllers.contains(controller) {
+            return Err(ContractError::NotDidController);
+        }
+        Ok(doc)
+    }
+
+    fn find_method(doc: &DidDocument, key_id: &String) -> Option<u32> {
+        doc.verification_methods
+            .iter()
+            .position(|m| m.id == *key_id)
+            .map(|i| i as u32)
+    }
+
+    fn retire_did_key(env: &Env, subject: &Address, mut method: VerificationMethod, now: u64) {
+        let key = DataKey::DidKeyHistory(subject.clone());
+        let mut history: Vec<VerificationMethod> =
+            env.storage().persistent().get(&key).unwrap_or(Vec::new(env));
+        method.retired_at = now;
+        history.push_back(method);
+        env.storage().persistent().set(&key, &history);
+    }
+
+    fn store_did_document(env: &Env, mut doc: DidDocument, action: Symbol) {
+        doc.version += 1;
+        doc.updated_at = env.ledger().timestamp();
+        env.storage()
+            .persistent()
+            .set(&DataKey::DidDocument(doc.subject.clone()), &doc);
+        env.events().publish(
+            (symbol_short!("did_doc"), doc.subject, action),
+            doc.version,
+        );
+    }
+
+    /// `true` if `address` is a registered entity that has been deactivated.
+    /// Unregistered addresses are not considered inactive, matching the
+    /// historical `check_access` behaviour.
+    fn is_inactive(env: &Env, address: &Address) -> bool {
+        env.storage()
+            .persistent()
+            .get::<DataKey, EntityData>(&DataKey::Entity(address.clone()))
+            .is_some_and(|data| !data.active)
+    }
+
+    /// Returns the unexpired direct permission `holder` has on `resource_id`,
+    /// optionally pinned to a specific grant `op_id`.
+    fn live_permission(
+        env: &Env,
+        holder: &Address,
+        resource_id: &String,
+        op_id: Option<u64>,
+    ) -> Option<AccessPermission> {
+        let access_list: Vec<AccessPermission> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::AccessList(holder.clone()))
+            .unwrap_or(Vec::new(env));
+        let now = env.ledger().timestamp();
+        access_list.iter().find(|p| {
+            p.resource_id == *resource_id
+                && (p.expires_at == 0 || p.expires_at > now)
+                && op_id.is_none_or(|id| p.op_id == id)
+        })
+    }
+
+    fn resolve_path(
+        env: &Env,
+        entity: &Address,
+        resource_id: &String,
+        required_scope: u32,
+    ) -> Option<AccessPath> {
+        if Self::is_inactive(env, entity) {
+            return None;
+        }
+        if let Some(permission) = Self::live_permission(env, entity, resource_id, None)
+            .filter(|p| p.scope_mask & required_scope == required_scope)
+        {
+            return Some(AccessPath {
+                root_grantor: permission.granted_by,
+                root_op_id: permission.op_id,
+                subject: permission.subject,
+                holders: Vec::from_array(env, [entity.clone()]),
+                delegation_ids: Vec::new(env),
+                scope_mask: permission.scope_mask,
+                expires_at: permission.expires_at,
+                via_organization: None,
+            });
+        }
+
+        let received: Vec<u64> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::DelegationsTo(entity.clone()))
+            .unwrap_or(Vec::new(env));
+        for id in received.iter() {
+            let Some(leaf) = env
+                .storage()
+                .persistent()
+                .get::<DataKey, Delegation>(&DataKey::Delegation(id))
+            else {
+                continue;
+            };
+            if leaf.resource_id != *resource_id
+                || leaf.scope_mask & required_scope != required_scope
+            {
+                continue;
+            }
+            if let Some(path) = Self::resolve_chain(env, leaf) {
+                return Some(path);
+            }
+        }
+
+        // Inherited through organization membership. `add_staff` rejects
+        // organizations as members, so this recursion is one level deep.
+        for org in Self::active_orgs_of(env, entity).iter() {
+            if let Some(mut path) = Self::resolve_path(env, &org, resource_id, required_scope) {
+                if path.via_organization.is_none() {
+                    path.holders.push_back(entity.clone());
+                    path.via_organization = Some(org);
+                    return Some(path);
+                }
+            }
+        }
+        None
+    }
+
+    /// Organizations `member` currently inherits from: the membership exists
+    /// and the organization has not been deactivated.
+    fn active_orgs_of(env: &Env, member: &Address) -> Vec<Address> {
+        let orgs: Vec<Address> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::MemberOrgs(member.clone()))
+            .unwrap_or(Vec::new(env));
+        let mut active = Vec::new(env);
+        for org in orgs.iter() {
+            if !Self::is_inactive(env, &org)
+                && env
+                    .storage()
+                    .persistent()
+                    .has(&DataKey::Staff(org.clone(), member.clone()))
+            {
+                active.push_back(org);
+            }
+        }
+        active
+    }
+
+    /// Walk a delegation up to its root grant, returning the path if every
+    /// hop is still valid.
+    fn resolve_chain(env: &Env, leaf: Delegation) -> Option<AccessPath> {
+        let now = env.ledger().timestamp();
+        let mut holders = Vec::from_array(env, [leaf.delegatee.clone()]);
+        let mut delegation_ids = Vec::new(env);
+        let scope_mask = leaf.scope_mask;
+        let expires_at = leaf.expires_at;
+
+        let mut hop = leaf;
+        loop {
+            if hop.revoked
+                || (hop.expires_at != 0 && hop.expires_at <= now)
+                || Self::is_inactive(env, &hop.delegatee)
+                || Self::is_inactive(env, &hop.delegator)
+            {
+                return None;
+            }
+            delegation_ids.push_front(hop.id);
+            holders.push_front(hop.delegator.clone());
+            if hop.depth <= 1 {
+                let root = Self::live_permission(
+                    env,
+                    &hop.delegator,
+                    &hop.resource_id,
+                    Some(hop.parent_id),
+                )?;
+                return Some(AccessPath {
+                    root_grantor: root.granted_by,
+                    root_op_id: root.op_id,
+                    subject: root.subject,
+                    holders,
+                    delegation_ids,
+                    scope_mask,
+                    expires_at,
+                    via_organization: None,
+                });
+            }
+            let parent: Delegation = env
+                .storage()
+                .persistent()
+                .get(&DataKey::Delegation(hop.parent_id))?;
+            if parent.delegatee != hop.delegator {
+                return None;
+            }
+            hop = parent;
+        }
+    }
+
+    fn mark_delegation_revoked(env: &Env, mut delegation: Delegation) {
+        delegation.revoked = true;
+        env.storage()
+            .persistent()
+            .set(&DataKey::Delegation(delegation.id), &delegation);
+        env.events().publish(
+            (
+                symbol_short!("del_rev"),
+                delegation.delegatee,
+                delegation.resource_id,
+            ),
+            delegation.id,
+        );
+    }
+
     /// Numeric role level for hierarchy enforcement (#488, #626).
     /// Higher value = more privileged. Every role has an explicit level — no
     /// catch-all — so a newly added `Role` variant must be assigned one here
@@ -3,9 +3,9 @@
 use super::*;
 use proptest::prelude::*;
 use soroban_sdk::{
-    testutils::{Address as _, MockAuth, MockAuthInvoke},
+    testutils::{Address as _, Events, Ledger, MockAuth, MockAuthInvoke},
     xdr::ToXdr,
-    Address, Bytes, BytesN, Env, IntoVal, String,
+    vec, Address, Bytes, BytesN, Env, IntoVal, String,
 };
 
 fn setup() -> (Env, AccessControlClient<'static>) {
@@ -915,9 +915,8 @@ fn test_revoke_access_by_unprivileged_non_grantor_rejected() {
 proptest! {
     #[test]
     fn test_soundness_provider_without_grant_never_has_access(resource_id in "resource-[0-9]{1,3}") {
-        let env = Env::default();
-        env.mock_all_auths();
-        let (admin, client) = setup(&env);
+        let (env, client) = setup();
+        let admin = Address::generate(&env);
         let (grantor, grantee) = register_two(&env, &client, &admin);
         let other = Address::generate(&env);
 
@@ -929,9 +928,8 @@ proptest! {
 
     #[test]
     fn test_completeness_after_grant_access_succeeds(resource_id in "resource-[0-9]{1,3}", expires_at in 1u64..1_000u64) {
-        let env = Env::default();
-        env.mock_all_auths();
-        let (admin, client) = setup(&env);
+        let (env, client) = setup();
+        let admin = Address::generate(&env);
         let (grantor, grantee) = register_two(&env, &client, &admin);
 
         let resource = String::from_str(&env, &resource_id);
@@ -942,9 +940,8 @@ proptest! {
 
     #[test]
     fn test_revocation_after_revoke_access_denied(resource_id in "resource-[0-9]{1,3}") {
-        let env = Env::default();
-        env.mock_all_auths();
-        let (admin, client) = setup(&env);
+        let (env, client) = setup();
+        let admin = Address::generate(&env);
         let (grantor, grantee) = register_two(&env, &client, &admin);
 
         let resource = String::from_str(&env, &resource_id);
@@ -956,9 +953,8 @@ proptest! {
 
     #[test]
     fn test_expiry_after_expiry_timestamp_denies_access(resource_id in "resource-[0-9]{1,3}", expires_at in 1u64..1_000u64) {
-        let env = Env::default();
-        env.mock_all_auths();
-        let (admin, client) = setup(&env);
+        let (env, client) = setup();
+        let admin = Address::generate(&env);
         let (grantor, grantee) = register_two(&env, &client, &admin);
 
         let resource = String::from_str(&env, &resource_id);
@@ -974,9 +970,8 @@ proptest! {
         resource_b in "resource-[0-9]{1,3}"
     ) {
         prop_assume!(resource_a != resource_b);
-        let env = Env::default();
-        env.mock_all_auths();
-        let (admin, client) = setup(&env);
+        let (env, client) = setup();
+        let admin = Address::generate(&env);
         let (grantor, grantee) = register_two(&env, &client, &admin);
 
         let resource_a = String::from_str(&env, &resource_a);
@@ -1075,3 +1070,1331 @@ fn test_emergency_access_emits_event() {
     let events = env.events().all();
     assert!(events.len() >= 1, "at least one event must have been emitted");
 }
+
+// ── Break-the-glass review ───────────────────────────────────────────────────
+
+/// `setup_emergency` plus an auditor, and one emergency access opened at t=1000.
+fn setup_review(env: &Env) -> (Address, Address, Address, AccessControlClient<'static>, u64) {
+    env.mock_all_auths();
+    env.ledger().set_timestamp(1_000);
+    let (admin, responder, patient, client) = setup_emergency(env);
+    let auditor = Address::generate(env);
+    client.grant_role(&admin, &auditor, &Role::Auditor, &0);
+    let review_id = client.emergency_access(&responder, &patient, &make_justification(env));
+    (admin, responder, auditor, client, review_id)
+}
+
+fn findings(env: &Env) -> BytesN<32> {
+    BytesN::from_array(env, &[7u8; 32])
+}
+
+#[test]
+fn test_emergency_access_opens_review_and_notifies_patient() {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.ledger().set_timestamp(1_000);
+    let (_, responder, patient, client) = setup_emergency(&env);
+
+    let review_id = client.emergency_access(&responder, &patient, &make_justification(&env));
+    let expected: soroban_sdk::Vec<soroban_sdk::Val> =
+        (symbol_short!("brk_open"), patient.clone(), responder.clone()).into_val(&env);
+    assert!(env
+        .events()
+        .all()
+        .iter()
+        .any(|(_, topics, _)| topics == expected));
+
+    let review = client.get_emergency_review(&review_id);
+    assert_eq!(review.responder, responder);
+    assert_eq!(review.patient, patient);
+    assert_eq!(review.status, ReviewStatus::Pending);
+    assert_eq!(review.due_at, 1_000 + DEFAULT_REVIEW_WINDOW_SECS);
+    assert_eq!(review.escalation, Escalation::None);
+    let open = client.get_open_reviews(&0);
+    assert_eq!(open.ids, vec![&env, review_id.into_val(&env)]);
+    assert!(!open.has_more);
+}
+
+#[test]
+fn test_open_review_index_is_paged() {
+    let env = Env::default();
+    let (_, responder, auditor, client, first) = setup_review(&env);
+    let patient = Address::generate(&env);
+    for _ in 0..24 {
+        client.emergency_access(&responder, &patient, &make_justification(&env));
+    }
+
+    let page0 = client.get_open_reviews(&0);
+    assert_eq!(page0.ids.len(), 20);
+    assert!(page0.has_more);
+    let page1 = client.get_open_reviews(&1);
+    assert_eq!(page1.ids.len(), 5);
+    assert!(!page1.has_more);
+
+    client.review_emergency_access(&auditor, &first, &true, &findings(&env));
+    let page0 = client.get_open_reviews(&0);
+    assert_eq!(page0.ids.len(), 19);
+    let first: soroban_sdk::Val = first.into_val(&env);
+    assert!(!page0.ids.contains(first));
+    assert!(page0.has_more);
+}
+
+#[test]
+fn test_auditor_marks_review_justified() {
+    let env = Env::default();
+    let (_, _, auditor, client, review_id) = setup_review(&env);
+
+    env.ledger().set_timestamp(2_000);
+    client.review_emergency_access(&auditor, &review_id, &true, &findings(&env));
+
+    let review = client.get_emergency_review(&review_id);
+    assert_eq!(review.status, ReviewStatus::Justified);
+    assert_eq!(review.reviewer, Some(auditor.clone()));
+    assert_eq!(review.reviewed_at, 2_000);
+    assert_eq!(review.escalation, Escalation::None);
+    assert!(client.get_open_reviews(&0).ids.is_empty());
+    assert_eq!(
+        client.try_review_emergency_access(&auditor, &review_id, &false, &findings(&env)),
+        Err(Ok(ContractError::EmergencyReviewClosed))
+    );
+}
+
+#[test]
+fn test_review_requires_independent_auditor() {
+    let env = Env::default();
+    let (admin, responder, _, client, review_id) = setup_review(&env);
+
+    let nurse = Address::generate(&env);
+    client.grant_role(&admin, &nurse, &Role::Nurse, &0);
+    assert_eq!(
+        client.try_review_emergency_access(&nurse, &review_id, &true, &findings(&env)),
+        Err(Ok(ContractError::InsufficientRole))
+    );
+
+    client.grant_role(&admin, &responder, &Role::Auditor, &0);
+    assert_eq!(
+        client.try_review_emergency_access(&responder, &review_id, &true, &findings(&env)),
+        Err(Ok(ContractError::SelfReviewNotAllowed))
+    );
+}
+
+#[test]
+fn test_review_rejects_auditor_appointed_by_responder() {
+    let env = Env::default();
+    let (_, responder, _, client, review_id) = setup_review(&env);
+
+    let puppet = Address::generate(&env);
+    client.grant_role(&responder, &puppet, &Role::Auditor, &0);
+    assert_eq!(
+        client.try_review_emergency_access(&puppet, &review_id, &true, &findings(&env)),
+        Err(Ok(ContractError::SelfReviewNotAllowed))
+    );
+
+    // The responder can also appoint a Doctor, who outranks `Auditor` too.
+    let doctor = Address::generate(&env);
+    client.grant_role(&responder, &doctor, &Role::Doctor, &0);
+    let doctors_auditor = Address::generate(&env);
+    client.grant_role(&doctor, &doctors_auditor, &Role::Auditor, &0);
+    assert_eq!(
+        client.try_review_emergency_access(&doctors_auditor, &review_id, &true, &findings(&env)),
+        Err(Ok(ContractError::InsufficientRole))
+    );
+    assert_eq!(
+        client.get_emergency_review(&review_id).status,
+        ReviewStatus::Pending
+    );
+}
+
+#[test]
+fn test_overdue_review_escalates() {
+    let env = Env::default();
+    let (_, _, auditor, client, review_id) = setup_review(&env);
+
+    assert_eq!(
+        client.try_escalate_overdue_review(&review_id),
+        Err(Ok(ContractError::EmergencyReviewNotOverdue))
+    );
+
+    env.ledger()
+        .set_timestamp(1_000 + DEFAULT_REVIEW_WINDOW_SECS + 1);
+    client.escalate_overdue_review(&review_id);
+    assert_eq!(
+        client.get_emergency_review(&review_id).escalation,
+        Escalation::Overdue
+    );
+    assert_eq!(
+        client.try_escalate_overdue_review(&review_id),
+        Err(Ok(ContractError::EmergencyReviewClosed))
+    );
+
+    // A late review still closes the case but keeps the escalation.
+    client.review_emergency_access(&auditor, &review_id, &true, &findings(&env));
+    let review = client.get_emergency_review(&review_id);
+    assert_eq!(review.status, ReviewStatus::Justified);
+    assert_eq!(review.escalation, Escalation::Overdue);
+}
+
+#[test]
+fn test_repeated_unjustified_overrides_suspend_responder() {
+    let env = Env::default();
+    let (_, responder, auditor, client, first) = setup_review(&env);
+    let patient = Address::generate(&env);
+
+    client.review_emergency_access(&auditor, &first, &false, &findings(&env));
+    assert_eq!(
+        client.get_emergency_review(&first).escalation,
+        Escalation::Unjustified
+    );
+    for expected in 2..=DEFAULT_SUSPENSION_THRESHOLD {
+        assert_eq!(
+            client.get_unjustified_override_count(&responder),
+            expected - 1
+        );
+        let id = client.emergency_access(&responder, &patient, &make_justification(&env));
+        client.review_emergency_access(&auditor, &id, &false, &findings(&env));
+    }
+
+    assert!(!client.has_role(&responder, &Role::EmergencyResponder));
+    assert_eq!(client.get_unjustified_override_count(&responder), 0);
+    assert_eq!(
+        client.try_emergency_access(&responder, &patient, &make_justification(&env)),
+        Err(Ok(ContractError::InsufficientRole))
+    );
+}
+
+#[test]
+fn test_set_review_config() {
+    let env = Env::default();
+    let (admin, responder, auditor, client, review_id) = setup_review(&env);
+
+    assert_eq!(
+        client.try_set_review_config(&admin, &0, &1),
+        Err(Ok(ContractError::InvalidReviewConfig))
+    );
+    assert_eq!(
+        client.try_set_review_config(&auditor, &3600, &1),
+        Err(Ok(ContractError::InsufficientRole))
+    );
+    client.set_review_config(&admin, &3600, &1);
+    assert_eq!(client.get_review_config().review_window_secs, 3600);
+
+    // Cases opened earlier keep their original due date.
+    assert_eq!(
+        client.get_emergency_review(&review_id).due_at,
+        1_000 + DEFAULT_REVIEW_WINDOW_SECS
+    );
+    let id = client.emergency_access(
+        &responder,
+        &Address::generate(&env),
+        &make_justification(&env),
+    );
+    assert_eq!(client.get_emergency_review(&id).due_at, 1_000 + 3600);
+
+    // A threshold of one suspends on the first unjustified finding.
+    client.review_emergency_access(&auditor, &id, &false, &findings(&env));
+    assert!(!client.has_role(&responder, &Role::EmergencyResponder));
+}
+
+// ── Delegated access chains ──────────────────────────────────────────────────
+
+/// Hospital grants `resource` directly, with the share bit, to an attending
+/// doctor, who will delegate to a resident and from there to a student.
+fn setup_delegation(
+    env: &Env,
+    client: &AccessControlClient,
+) -> (Address, Address, Address, Address, Address, String) {
+    let admin = Address::generate(env);
+    let (hospital, attending) = register_two(env, client, &admin);
+    let resident = Address::generate(env);
+    let student = Address::generate(env);
+    for (who, name) in [(&resident, "Dr. Resident"), (&student, "Student")] {
+        client.register_entity(
+            who,
+            &EntityType::Doctor,
+            &String::from_str(env, name),
+            &String::from_str(env, "metadata"),
+        );
+    }
+    let resource = String::from_str(env, "patient-records");
+    client.grant_access_with_scope(
+        &hospital,
+        &attending,
+        &resource,
+        &SCOPE_ALL,
+        &10_000,
+        &None,
+    );
+    (admin, hospital, attending, resident, student, resource)
+}
+
+#[test]
+fn test_delegated_access_reports_path() {
+    let (env, client) = setup();
+    let (_, hospital, attending, resident, _, resource) = setup_delegation(&env, &client);
+
+    let id = client.delegate_access(&attending, &resident, &resource, &SCOPE_READ, &5_000);
+    assert!(client.check_access(&resident, &resource));
+
+    let path = client.resolve_access(&resident, &resource, &SCOPE_READ);
+    assert_eq!(path.root_grantor, hospital);
+    assert_eq!(
+        path.holders,
+        vec![&env, attending.clone(), resident.clone()]
+    );
+    assert_eq!(path.delegation_ids, vec![&env, id]);
+    assert_eq!(path.scope_mask, SCOPE_READ);
+    assert_eq!(path.expires_at, 5_000);
+
+    // Read-only delegation does not satisfy a write check.
+    assert_eq!(
+        client.try_resolve_access(&resident, &resource, &SCOPE_WRITE),
+        Err(Ok(ContractError::AccessPermissionNotFound))
+    );
+
+    // Direct holders report a single-hop path.
+    let direct = client.resolve_access(&attending, &resource, &SCOPE_WRITE);
+    assert_eq!(direct.holders, vec![&env, attending]);
+    assert!(direct.delegation_ids.is_empty());
+    assert_eq!(direct.scope_mask, SCOPE_ALL);
+}
+
+#[test]
+fn test_plain_grant_cannot_be_delegated() {
+    let (env, client) = setup();
+    let (_, hospital, _, resident, student, resource) = setup_delegation(&env, &client);
+
+    // `grant_access` carries read and write but not the share bit.
+    client.grant_access(&hospital, &resident, &resource, &0, &None);
+    let path = client.resolve_access(&resident, &resource, &SCOPE_WRITE);
+    assert_eq!(path.scope_mask, SCOPE_DIRECT);
+    assert_eq!(
+        client.try_resolve_access(&resident, &resource, &SCOPE_SHARE),
+        Err(Ok(ContractError::AccessPermissionNotFound))
+    );
+    assert_eq!(
+        client.try_delegate_access(&resident, &student, &resource, &SCOPE_READ, &0),
+        Err(Ok(ContractError::DelegationExceedsGrant))
+    );
+    assert_eq!(
+        client.try_grant_access_with_scope(&hospital, &student, &resource, &0x08, &0, &None),
+        Err(Ok(ContractError::InvalidScopeMask))
+    );
+}
+
+#[test]
+fn test_delegation_is_attenuated_by_delegator() {
+    let (env, client) = setup();
+    let (_, _, attending, resident, student, resource) = setup_delegation(&env, &client);
+
+    // Cannot outlive the delegator's own grant (expires at 10_000).
+    assert_eq!(
+        client.try_delegate_access(&attending, &resident, &resource, &SCOPE_READ, &0),
+        Err(Ok(ContractError::DelegationExceedsGrant))
+    );
+    assert_eq!(
+        client.try_delegate_access(&attending, &resident, &resource, &SCOPE_READ, &20_000),
+        Err(Ok(ContractError::DelegationExceedsGrant))
+    );
+
+    // A read-only delegate has no share bit and cannot extend the chain.
+    client.delegate_access(&attending, &resident, &resource, &SCOPE_READ, &5_000);
+    assert_eq!(
+        client.try_delegate_access(&resident, &student, &resource, &SCOPE_READ, &4_000),
+        Err(Ok(ContractError::DelegationExceedsGrant))
+    );
+
+    // Someone without any access cannot delegate at all.
+    assert_eq!(
+        client.try_delegate_access(&student, &resident, &resource, &SCOPE_READ, &1_000),
+        Err(Ok(ContractError::DelegationExceedsGrant))
+    );
+    assert_eq!(
+        client.try_delegate_access(&attending, &resident, &resource, &0x08, &1_000),
+        Err(Ok(ContractError::InvalidScopeMask))
+    );
+}
+
+#[test]
+fn test_sub_delegation_cannot_widen_scope_or_cycle() {
+    let (env, client) = setup();
+    let (_, _, attending, resident, student, resource) = setup_delegation(&env, &client);
+
+    client.delegate_access(
+        &attending,
+        &resident,
+        &resource,
+        &(SCOPE_READ | SCOPE_SHARE),
+        &5_000,
+    );
+    assert_eq!(
+        client.try_delegate_access(&resident, &student, &resource, &SCOPE_WRITE, &4_000),
+        Err(Ok(ContractError::DelegationExceedsGrant))
+    );
+    assert_eq!(
+        client.try_delegate_access(&resident, &attending, &resource, &SCOPE_READ, &4_000),
+        Err(Ok(ContractError::DelegationCycle))
+    );
+
+    client.delegate_access(&resident, &student, &resource, &SCOPE_READ, &4_000);
+    let path = client.resolve_access(&student, &resource, &SCOPE_READ);
+    assert_eq!(path.holders, vec![&env, attending, resident, student]);
+    assert_eq!(path.delegation_ids.len(), 2);
+}
+
+#[test]
+fn test_revoking_root_grant_collapses_chain() {
+    let (env, client) = setup();
+    let (_, hospital, attending, resident, student, resource) = setup_delegation(&env, &client);
+
+    let first = client.delegate_access(
+        &attending,
+        &resident,
+        &resource,
+        &(SCOPE_READ | SCOPE_SHARE),
+        &5_000,
+    );
+    let second = client.delegate_access(&resident, &student, &resource, &SCOPE_READ, &4_000);
+
+    // The chain stops resolving without its delegations being rewritten.
+    client.revoke_access(&hospital, &attending, &resource);
+    assert!(!client.check_access(&resident, &resource));
+    assert!(!client.check_access(&student, &resource));
+    assert!(!client.get_delegation(&first).revoked);
+    assert!(!client.get_delegation(&second).revoked);
+
+    // Re-granting the root does not revive the old chain.
+    client.grant_access_with_scope(&hospital, &attending, &resource, &SCOPE_ALL, &0, &None);
+    assert!(!client.check_access(&student, &resource));
+}
+
+#[test]
+fn test_revoke_delegation_cuts_chain_and_upstream_may_revoke() {
+    let (env, client) = setup();
+    let (_, _, attending, resident, student, resource) = setup_delegation(&env, &client);
+
+    let first = client.delegate_access(
+        &attending,
+        &resident,
+        &resource,
+        &(SCOPE_READ | SCOPE_SHARE),
+        &5_000,
+    );
+    let second = client.delegate_access(&resident, &student, &resource, &SCOPE_READ, &4_000);
+
+    // The student cannot revoke its own upstream link.
+    assert_eq!(
+        client.try_revoke_delegation(&student, &first),
+        Err(Ok(ContractError::NotAuthorizedToRevoke))
+    );
+
+    // The attending sits upstream of the resident's delegation to the student.
+    client.revoke_delegation(&attending, &second);
+    assert!(!client.check_access(&student, &resource));
+    assert!(client.check_access(&resident, &resource));
+
+    client.revoke_delegation(&attending, &first);
+    assert!(!client.check_access(&resident, &resource));
+    assert_eq!(client.get_received_delegations(&resident).len(), 1);
+}
+
+#[test]
+fn test_revoke_access_is_independent_of_delegation_fan_out() {
+    let (env, client) = setup();
+    let (_, hospital, attending, _, _, resource) = setup_delegation(&env, &client);
+
+    // The grantee fans out shareable delegations, each with its own children.
+    let mut leaves = Vec::new(&env);
+    for _ in 0..5 {
+        let mid = Address::generate(&env);
+        client.register_entity(
+            &mid,
+            &EntityType::Doctor,
+            &String::from_str(&env, "Delegate"),
+            &String::from_str(&env, "metadata"),
+        );
+        client.delegate_access(&attending, &mid, &resource, &SCOPE_ALL, &5_000);
+        for _ in 0..5 {
+            let leaf = Address::generate(&env);
+            client.register_entity(
+                &leaf,
+                &EntityType::Doctor,
+                &String::from_str(&env, "Delegate"),
+                &String::from_str(&env, "metadata"),
+            );
+            client.delegate_access(&mid, &leaf, &resource, &SCOPE_READ, &4_000);
+            leaves.push_back(leaf);
+        }
+    }
+    assert!(client.check_access(&leaves.get(24).unwrap(), &resource));
+
+    // Revocation touches only the grant itself; the chains resolve lazily.
+    client.revoke_access(&hospital, &attending, &resource);
+    for leaf in leaves.iter() {
+        assert!(!client.check_access(&leaf, &resource));
+    }
+}
+
+#[test]
+fn test_delegation_expiry_and_deactivation_break_chain() {
+    let (env, client) = setup();
+    let (admin, _, attending, resident, student, resource) = setup_delegation(&env, &client);
+
+    client.delegate_access(
+        &attending,
+        &resident,
+        &resource,
+        &(SCOPE_READ | SCOPE_SHARE),
+        &5_000,
+    );
+    client.delegate_access(&resident, &student, &resource, &SCOPE_READ, &4_000);
+
+    env.ledger().set_timestamp(4_000);
+    assert!(!client.check_access(&student, &resource));
+    assert!(client.check_access(&resident, &resource));
+
+    // Deactivating an intermediate holder cuts off everyone below it.
+    env.ledger().set_timestamp(100);
+    assert!(client.check_access(&student, &resource));
+    client.deactivate_entity(&admin, &resident);
+    assert!(!client.check_access(&student, &resource));
+}
+// ── Organization staff inheritance ───────────────────────────────────────────
+
+/// Admin, an organization (`register_two`'s hospital) and one clinician on its
+/// staff. The patient is registered but holds no grants yet.
+fn setup_org(env: &Env, client: &AccessControlClient) -> (Address, Address, Address, Address) {
+    let admin = Address::generate(env);
+    let (org, clinician) = register_two(env, client, &admin);
+    let patient = Address::generate(env);
+    client.register_entity(
+        &patient,
+        &EntityType::Patient,
+        &String::from_str(env, "Patient"),
+        &String::from_str(env, "metadata"),
+    );
+    client.add_staff(&org, &org, &clinician, &Role::Doctor);
+    (admin, org, clinician, patient)
+}
+
+#[test]
+fn test_staff_inherit_org_grants() {
+    let (env, client) = setup();
+    let (_, org, clinician, patient) = setup_org(&env, &client);
+    let resource = String::from_str(&env, "patient-records");
+
+    assert!(!client.check_access(&clinician, &resource));
+    client.grant_access(&patient, &org, &resource, &0, &None);
+    assert!(client.check_access(&clinician, &resource));
+
+    let path = client.resolve_access(&clinician, &resource, &SCOPE_READ);
+    assert_eq!(path.root_grantor, patient);
+    assert_eq!(path.holders, vec![&env, org.clone(), clinician.clone()]);
+    assert_eq!(path.via_organization, Some(org.clone()));
+
+    let membership = client.get_staff_membership(&org, &clinician);
+    assert_eq!(membership.role, Role::Doctor);
+    assert_eq!(client.get_member_organizations(&clinician), vec![&env, org]);
+}
+
+#[test]
+fn test_staff_inherit_org_consent() {
+    let (env, client) = setup();
+    let (_, org, clinician, patient) = setup_org(&env, &client);
+    let purpose = String::from_str(&env, "treatment");
+
+    client.grant_consent(
+        &patient,
+        &org,
+        &0x01,
+        &purpose,
+        &String::from_str(&env, "explicit_consent"),
+        &0,
+    );
+    assert_eq!(
+        client.try_check_consent(&patient, &clinician, &purpose, &0x01),
+        Ok(Ok(()))
+    );
+    assert_eq!(
+        client.try_check_consent(&patient, &clinician, &purpose, &0x02),
+        Err(Ok(ContractError::ConsentNotFound))
+    );
+
+    client.revoke_consent(&patient, &org, &purpose);
+    assert!(client
+        .try_check_consent(&patient, &clinician, &purpose, &0x01)
+        .is_err());
+}
+
+#[test]
+fn test_removed_staff_lose_inherited_access() {
+    let (env, client) = setup();
+    let (admin, org, clinician, patient) = setup_org(&env, &client);
+    let resource = String::from_str(&env, "patient-records");
+    client.grant_access(&patient, &org, &resource, &0, &None);
+
+    // Only the organization or the admin may manage staff.
+    assert_eq!(
+        client.try_remove_staff(&clinician, &org, &clinician),
+        Err(Ok(ContractError::NotOrganizationManager))
+    );
+
+    client.remove_staff(&admin, &org, &clinician);
+    assert!(!client.check_access(&clinician, &resource));
+    assert!(client.get_staff(&org).is_empty());
+    assert_eq!(
+        client.try_remove_staff(&org, &org, &clinician),
+        Err(Ok(ContractError::StaffNotFound))
+    );
+}
+
+#[test]
+fn test_org_deactivation_revokes_inherited_access() {
+    let (env, client) = setup();
+    let (admin, org, clinician, patient) = setup_org(&env, &client);
+    let resource = String::from_str(&env, "patient-records");
+    let purpose = String::from_str(&env, "treatment");
+    client.grant_access(&patient, &org, &resource, &0, &None);
+    client.grant_consent(
+        &patient,
+        &org,
+        &0x01,
+        &purpose,
+        &String::from_str(&env, "explicit_consent"),
+        &0,
+    );
+
+    client.deactivate_entity(&admin, &org);
+    assert!(!client.check_access(&clinician, &resource));
+    assert!(client
+        .try_check_consent(&patient, &clinician, &purpose, &0x01)
+        .is_err());
+    // The membership record survives, but the inactive org can no longer be managed.
+    assert_eq!(
+        client.try_add_staff(&admin, &org, &patient, &Role::Nurse),
+        Err(Ok(ContractError::EntityInactive))
+    );
+}
+
+#[test]
+fn test_staff_registry_rejects_invalid_memberships() {
+    let (env, client) = setup();
+    let (_, org, clinician, patient) = setup_org(&env, &client);
+
+    assert_eq!(
+        client.try_add_staff(&org, &org, &clinician, &Role::Nurse),
+        Err(Ok(ContractError::StaffAlreadyMember))
+    );
+    // A non-hospital entity cannot have staff.
+    assert_eq!(
+        client.try_add_staff(&clinician, &clinician, &patient, &Role::Nurse),
+        Err(Ok(ContractError::NotAnOrganization))
+    );
+
+    let other_org = Address::generate(&env);
+    client.register_entity(
+        &other_org,
+        &EntityType::Hospital,
+        &String::from_str(&env, "St. Mary's"),
+        &String::from_str(&env, "metadata"),
+    );
+    assert_eq!(
+        client.try_add_staff(&org, &org, &other_org, &Role::Provider),
+        Err(Ok(ContractError::OrganizationCannotBeStaff))
+    );
+}
+
+#[test]
+fn test_staff_cannot_delegate_inherited_access() {
+    let (env, client) = setup();
+    let (_, org, clinician, patient) = setup_org(&env, &client);
+    let resource = String::from_str(&env, "patient-records");
+    client.grant_access(&patient, &org, &resource, &0, &None);
+
+    assert_eq!(
+        client.try_delegate_access(&clinician, &patient, &resource, &SCOPE_READ, &0),
+        Err(Ok(ContractError::DelegationExceedsGrant))
+    );
+}
+// ── Accounting of disclosures ────────────────────────────────────────────────
+
+#[test]
+fn test_access_resource_records_disclosure() {
+    let (env, client) = setup();
+    let (_, patient, clinician, _) = setup_parties(&env, &client);
+    let resource = String::from_str(&env, "patient-records");
+    let purpose = String::from_str(&env, "treatment");
+    let op_id = client.grant_access(&patient, &clinician, &resource, &0, &None);
+
+    env.ledger().set_timestamp(500);
+    let path = client.access_resource(&clinician, &resource, &SCOPE_READ, &purpose);
+    assert_eq!(path.root_op_id, op_id);
+
+    let page = client.get_disclosures(&patient, &0, &u64::MAX, &0);
+    assert_eq!(page.records.len(), 1);
+    let record = page.records.get(0).unwrap();
+    assert_eq!(record.accessor, clinician);
+    assert_eq!(record.resource_id, resource);
+    assert_eq!(record.basis_op_id, op_id);
+    assert_eq!(record.purpose, purpose);
+    assert_eq!(record.disclosed_at, 500);
+    assert!(!page.has_more);
+
+    // check_access stays read-only.
+    client.check_access(&clinician, &resource);
+    assert_eq!(client.get_disclosure_summary(&patient).total, 1);
+}
+
+#[test]
+fn test_access_resource_records_against_data_subject() {
+    let (env, client) = setup();
+    let (_, patient, clinician, provider) = setup_parties(&env, &client);
+    let resource = String::from_str(&env, "imaging-study-3");
+    client.grant_access_for_subject(
+        &provider,
+        &clinician,
+        &patient,
+        &resource,
+        &SCOPE_DIRECT,
+        &0,
+        &None,
+    );
+
+    let path = client.access_resource(
+        &clinician,
+        &resource,
+        &SCOPE_READ,
+        &String::from_str(&env, "treatment"),
+    );
+    assert_eq!(path.root_grantor, provider);
+    assert_eq!(path.subject, patient);
+    assert_eq!(client.get_disclosure_summary(&patient).total, 1);
+    assert_eq!(client.get_disclosure_summary(&provider).total, 0);
+
+    assert_eq!(
+        client.try_grant_access_for_subject(
+            &provider,
+            &clinician,
+            &Address::generate(&env),
+            &String::from_str(&env, "imaging-study-4"),
+            &SCOPE_DIRECT,
+            &0,
+            &None,
+        ),
+        Err(Ok(ContractError::EntityNotFound))
+    );
+}
+
+#[test]
+fn test_grant_access_for_subject_requires_subject_authorization() {
+    let (env, client) = setup();
+    let (_, patient, clinician, provider) = setup_parties(&env, &client);
+    let contract_id = client.address.clone();
+    let resource = String::from_str(&env, "imaging-study-3");
+    let grant_args = (
+        &provider,
+        &clinician,
+        &patient,
+        &resource,
+        SCOPE_DIRECT,
+        0u64,
+        None::<BytesN<32>>,
+    );
+    let provider_only = MockAuth {
+        address: &provider,
+        invoke: &MockAuthInvoke {
+            contract: &contract_id,
+            fn_name: "grant_access_for_subject",
+            args: grant_args.into_val(&env),
+            sub_invokes: &[],
+        },
+    };
+
+    // The provider cannot name the patient as subject on its own.
+    assert!(client
+        .mock_auths(&[provider_only])
+        .try_grant_access_for_subject(
+            &provider,
+            &clinician,
+            &patient,
+            &resource,
+            &SCOPE_DIRECT,
+            &0,
+            &None,
+        )
+        .is_err());
+    assert!(!client.check_access(&clinician, &resource));
+
+    // Once the patient shares the resource with the provider, the provider
+    // may pass it on under the patient's name without asking again.
+    env.mock_all_auths();
+    client.grant_access_with_scope(&patient, &provider, &resource, &SCOPE_ALL, &0, &None);
+    let provider_only = MockAuth {
+        address: &provider,
+        invoke: &MockAuthInvoke {
+            contract: &contract_id,
+            fn_name: "grant_access_for_subject",
+            args: grant_args.into_val(&env),
+            sub_invokes: &[],
+        },
+    };
+    client
+        .mock_auths(&[provider_only])
+        .grant_access_for_subject(
+            &provider,
+            &clinician,
+            &patient,
+            &resource,
+            &SCOPE_DIRECT,
+            &0,
+            &None,
+        );
+    let path = client.access_resource(
+        &clinician,
+        &resource,
+        &SCOPE_READ,
+        &String::from_str(&env, "treatment"),
+    );
+    assert_eq!(path.subject, patient);
+}
+
+#[test]
+fn test_access_resource_subject_follows_root_grant() {
+    let (env, client) = setup();
+    let (_, patient, clinician, other) = setup_parties(&env, &client);
+    let resource = String::from_str(&env, "patient-records");
+    client.grant_access_with_scope(&patient, &clinician, &resource, &SCOPE_ALL, &0, &None);
+    client.delegate_access(&clinician, &other, &resource, &SCOPE_READ, &0);
+
+    // The delegatee reads under the patient's root grant, not its delegator's.
+    let purpose = String::from_str(&env, "treatment");
+    let path = client.access_resource(&other, &resource, &SCOPE_READ, &purpose);
+    assert_eq!(path.subject, patient);
+    assert_eq!(client.get_disclosure_summary(&patient).total, 1);
+    assert_eq!(client.get_disclosure_summary(&clinician).total, 0);
+}
+
+#[test]
+fn test_access_resource_denied_records_nothing() {
+    let (env, client) = setup();
+    let (_, patient, clinician, _) = setup_parties(&env, &client);
+    let resource = String::from_str(&env, "patient-records");
+
+    assert_eq!(
+        client.try_access_resource(
+            &clinician,
+            &resource,
+            &0,
+            &String::from_str(&env, "x")
+        ),
+        Err(Ok(ContractError::AccessPermissionNotFound))
+    );
+    assert_eq!(client.get_disclosure_summary(&patient).total, 0);
+}
+
+#[test]
+fn test_access_with_consent_cites_consent_op_id() {
+    let (env, client) = setup();
+    let (_, patient, clinician, other) = setup_parties(&env, &client);
+    let purpose = String::from_str(&env, "treatment");
+    let consent_op = client.grant_consent(
+        &patient,
+        &clinician,
+        &0x01,
+        &purpose,
+        &String::from_str(&env, "explicit_consent"),
+        &0,
+    );
+
+    client.access_with_consent(&patient, &clinician, &purpose, &0x01);
+    assert_eq!(
+        client.try_access_with_consent(&patient, &other, &purpose, &0x01),
+        Err(Ok(ContractError::ConsentNotFound))
+    );
+
+    let record = client
+        .get_disclosures(&patient, &0, &u64::MAX, &0)
+        .records
+        .get(0)
+        .unwrap();
+    assert_eq!(record.basis_op_id, consent_op);
+    assert_eq!(record.resource_id, String::from_str(&env, "consent_scope:1"));
+}
+
+#[test]
+fn test_disclosures_filter_by_window_and_summarize() {
+    let (env, client) = setup();
+    let (_, patient, clinician, other) = setup_parties(&env, &client);
+    let resource = String::from_str(&env, "patient-records");
+    let purpose = String::from_str(&env, "treatment");
+    client.grant_access(&patient, &clinician, &resource, &0, &None);
+    client.grant_access(&patient, &other, &resource, &0, &None);
+
+    for (t, who) in [(100, &clinician), (200, &other), (300, &clinician)] {
+        env.ledger().set_timestamp(t);
+        client.access_resource(who, &resource, &0, &purpose);
+    }
+
+    let window = client.get_disclosures(&patient, &150, &250, &0);
+    assert_eq!(window.records.len(), 1);
+    assert_eq!(window.records.get(0).unwrap().accessor, other);
+
+    let summary = client.get_disclosure_summary(&patient);
+    assert_eq!(summary.total, 3);
+    assert_eq!((summary.first_at, summary.last_at), (100, 300));
+    let first = summary.accessors.get(0).unwrap();
+    assert_eq!((first.accessor, first.count), (clinician, 2));
+    assert_eq!(summary.accessors.get(1).unwrap().count, 1);
+}
+
+#[test]
+fn test_disclosures_page_across_storage_pages() {
+    let (env, client) = setup();
+    let (_, patient, clinician, _) = setup_parties(&env, &client);
+    let resource = String::from_str(&env, "patient-records");
+    client.grant_access(&patient, &clinician, &resource, &0, &None);
+    for _ in 0..25 {
+        let purpose = String::from_str(&env, "t");
+        client.access_resource(&clinician, &resource, &0, &purpose);
+    }
+
+    let first = client.get_disclosures(&patient, &0, &u64::MAX, &0);
+    assert_eq!(first.records.len(), 20);
+    assert!(first.has_more);
+    let second = client.get_disclosures(&patient, &0, &u64::MAX, &1);
+    assert_eq!(second.records.len(), 5);
+    assert!(!second.has_more);
+}
+
+/// Admin, a registered patient and two registered clinicians with no grants.
+fn setup_parties(env: &Env, client: &AccessControlClient) -> (Address, Address, Address, Address) {
+    let admin = Address::generate(env);
+    let (_, clinician) = register_two(env, client, &admin);
+    let patient = Address::generate(env);
+    let other = Address::generate(env);
+    for (who, kind) in [
+        (&patient, EntityType::Patient),
+        (&other, EntityType::Doctor),
+    ] {
+        client.register_entity(
+            who,
+            &kind,
+            &String::from_str(env, "name"),
+            &String::from_str(env, "metadata"),
+        );
+    }
+    (admin, patient, clinician, other)
+}
+// ── DID documents ────────────────────────────────────────────────────────────
+
+fn signing_key(seed: u8) -> ed25519_dalek::SigningKey {
+    ed25519_dalek::SigningKey::from_bytes(&[seed; 32])
+}
+
+fn public_key(env: &Env, key: &ed25519_dalek::SigningKey) -> BytesN<32> {
+    BytesN::from_array(env, &key.verifying_key().to_bytes())
+}
+
+fn sign(env: &Env, key: &ed25519_dalek::SigningKey, message: &[u8]) -> BytesN<64> {
+    use ed25519_dalek::Signer;
+    BytesN::from_array(env, &key.sign(message).to_bytes())
+}
+
+/// Subject with a registered DID and a document holding `key-1` (seed 1).
+fn setup_did(env: &Env, client: &AccessControlClient) -> Address {
+    let subject = Address::generate(env);
+    client.register_did(
+        &subject,
+        &Bytes::from_slice(env, b"did:stellar:patient:doc1"),
+    );
+    client.create_did_document(
+        &subject,
+        &String::from_str(env, "key-1"),
+        &public_key(env, &signing_key(1)),
+    );
+    subject
+}
+
+#[test]
+fn test_create_and_resolve_did_document() {
+    let (env, client) = setup();
+    env.ledger().set_timestamp(100);
+    let subject = setup_did(&env, &client);
+
+    let doc = client.resolve_did(&subject);
+    assert_eq!(doc.id, Bytes::from_slice(&env, b"did:stellar:patient:doc1"));
+    assert_eq!(doc.subject, subject);
+    assert_eq!(doc.verification_methods.len(), 1);
+    assert_eq!(doc.version, 1);
+    assert_eq!(doc.created_at, 100);
+    assert!(!doc.deactivated);
+
+    assert_eq!(
+        client.try_create_did_document(
+            &subject,
+            &String::from_str(&env, "key-2"),
+            &public_key(&env, &signing_key(2)),
+        ),
+        Err(Ok(ContractError::DidDocumentExists))
+    );
+    let unregistered = Address::generate(&env);
+    assert_eq!(
+        client.try_create_did_document(
+            &unregistered,
+            &String::from_str(&env, "key-1"),
+            &public_key(&env, &signing_key(1)),
+        ),
+        Err(Ok(ContractError::DidNotRegistered))
+    );
+}
+
+#[test]
+fn test_register_did_updates_document_id() {
+    let (env, client) = setup();
+    let subject = setup_did(&env, &client);
+
+    let new_did = Bytes::from_slice(&env, b"did:stellar:patient:doc2");
+    client.register_did(&subject, &new_did);
+    let doc = client.resolve_did(&subject);
+    assert_eq!(doc.id, new_did);
+    assert_eq!(doc.version, 2);
+}
+
+#[test]
+fn test_verify_did_signature_with_current_key() {
+    let (env, client) = setup();
+    let subject = setup_did(&env, &client);
+    let message = b"consent:record-42";
+
+    client.verify_did_signature(
+        &subject,
+        &String::from_str(&env, "key-1"),
+        &Bytes::from_slice(&env, message),
+        &sign(&env, &signing_key(1), message),
+    );
+    assert_eq!(
+        client.try_verify_did_signature(
+            &subject,
+            &String::from_str(&env, "key-9"),
+            &Bytes::from_slice(&env, message),
+            &sign(&env, &signing_key(1), message),
+        ),
+        Err(Ok(ContractError::DidEntryNotFound))
+    );
+}
+
+#[test]
+#[should_panic]
+fn test_verify_did_signature_rejects_wrong_signer() {
+    let (env, client) = setup();
+    let subject = setup_did(&env, &client);
+    let message = b"consent:record-42";
+
+    client.verify_did_signature(
+        &subject,
+        &String::from_str(&env, "key-1"),
+        &Bytes::from_slice(&env, message),
+        &sign(&env, &signing_key(2), message),
+    );
+}
+
+#[test]
+fn test_rotate_did_key_keeps_history() {
+    let (env, client) = setup();
+    env.ledger().set_timestamp(100);
+    let subject = setup_did(&env, &client);
+    let key_id = String::from_str(&env, "key-1");
+
+    env.ledger().set_timestamp(200);
+    client.rotate_did_key(
+        &subject,
+        &subject,
+        &key_id,
+        &public_key(&env, &signing_key(2)),
+    );
+
+    let doc = client.resolve_did(&subject);
+    let current = doc.verification_methods.get(0).unwrap();
+    assert_eq!(current.public_key, public_key(&env, &signing_key(2)));
+    assert_eq!(current.added_at, 200);
+
+    let history = client.get_did_key_history(&subject);
+    assert_eq!(history.len(), 1);
+    let retired = history.get(0).unwrap();
+    assert_eq!(retired.public_key, public_key(&env, &signing_key(1)));
+    assert_eq!((retired.added_at, retired.retired_at), (100, 200));
+
+    let message = b"payload";
+    client.verify_did_signature(
+        &subject,
+        &key_id,
+        &Bytes::from_slice(&env, message),
+        &sign(&env, &signing_key(2), message),
+    );
+}
+
+#[test]
+fn test_did_controllers_manage_document() {
+    let (env, client) = setup();
+    let subject = setup_did(&env, &client);
+    let guardian = Address::generate(&env);
+    let stranger = Address::generate(&env);
+    let key_2 = String::from_str(&env, "key-2");
+
+    assert_eq!(
+        client.try_add_verification_method(
+            &stranger,
+            &subject,
+            &key_2,
+            &public_key(&env, &signing_key(2)),
+        ),
+        Err(Ok(ContractError::NotDidController))
+    );
+
+    client.add_did_controller(&subject, &subject, &guardian);
+    client.add_verification_method(
+        &guardian,
+        &subject,
+        &key_2,
+        &public_key(&env, &signing_key(2)),
+    );
+    let service = DidService {
+        id: String::from_str(&env, "records"),
+        service_type: String::from_str(&env, "LinkedDomains"),
+        endpoint: String::from_str(&env, "https://records.example.org"),
+    };
+    client.add_did_service(&guardian, &subject, &service);
+    assert_eq!(
+        client.try_add_did_service(&guardian, &subject, &service),
+        Err(Ok(ContractError::DidEntryExists))
+    );
+
+    let doc = client.resolve_did(&subject);
+    assert_eq!(doc.controllers, vec![&env, guardian.clone()]);
+    assert_eq!(doc.verification_methods.len(), 2);
+    assert_eq!(doc.services, vec![&env, service]);
+
+    client.remove_did_service(&guardian, &subject, &String::from_str(&env, "records"));
+    client.remove_verification_method(&guardian, &subject, &key_2);
+    assert_eq!(
+        client.try_remove_verification_method(
+            &guardian,
+            &subject,
+            &String::from_str(&env, "key-1")
+        ),
+        Err(Ok(ContractError::LastVerificationMethod))
+    );
+    client.remove_did_controller(&subject, &subject, &guardian);
+    assert_eq!(
+        client.try_remove_did_service(&guardian, &subject, &String::from_str(&env, "records")),
+        Err(Ok(ContractError::NotDidController))
+    );
+    assert_eq!(client.get_did_key_history(&subject).len(), 1);
+}
+
+#[test]
+fn test_deactivate_did() {
+    let (env, client) = setup();
+    let subject = setup_did(&env, &client);
+    let key_id = String::from_str(&env, "key-1");
+
+    client.deactivate_did(&subject, &subject);
+    let doc = client.resolve_did(&subject);
+    assert!(doc.deactivated);
+    assert!(doc.verification_methods.is_empty());
+    assert_eq!(client.get_did_key_history(&subject).len(), 1);
+
+    let message = b"payload";
+    assert_eq!(
+        client.try_verify_did_signature(
+            &subject,
+            &key_id,
+            &Bytes::from_slice(&env, message),
+            &sign(&env, &signing_key(1), message),
+        ),
+        Err(Ok(ContractError::DidDeactivated))
+    );
+    assert_eq!(
+        client.try_rotate_did_key(
+            &subject,
+            &subject,
+            &key_id,
+            &public_key(&env, &signing_key(2))
+        ),
+        Err(Ok(ContractError::DidDeactivated))
+    );
+    assert_eq!(
+        client.try_register_did(
+            &subject,
+            &Bytes::from_slice(&env, b"did:stellar:patient:new")
+        ),
+        Err(Ok(ContractError::DidDeactivated))
+    );
+}
+// ── Verifiable credentials ───────────────────────────────────────────────────
+
+fn credential_id(env: &Env, seed: u8) -> BytesN<32> {
+    BytesN::from_array(env, &[seed; 32])
+}
+
+fn schema(env: &Env) -> BytesN<32> {
+    BytesN::from_array(env, &[0xAB; 32])
+}
+
+#[test]
+fn test_issue_credential_requires_live_issuer_did() {
+    let (env, client) = setup();
+    let subject = Address::generate(&env);
+    let no_did = Address::generate(&env);
+    assert_eq!(
+        client.try_issue_credential(
+            &no_did,
+            &subject,
+            &credential_id(&env, 1),
+            &schema(&env),
+            &0
+        ),
+        Err(Ok(CredentialError::IssuerDidNotFound))
+    );
+
+    let issuer = setup_did(&env, &client);
+    client.deactivate_did(&issuer, &issuer);
+    assert_eq!(
+        client.try_issue_credential(
+            &issuer,
+            &subject,
+            &credential_id(&env, 1),
+            &schema(&env),
+            &0
+        ),
+        Err(Ok(CredentialError::IssuerDidDeactivated))
+    );
+}
+
+#[test]
+fn test_issue_credential_allocates_status_bits() {
+    let (env, client) = setup();
+    env.ledger().set_timestamp(50);
+    let issuer = setup_did(&env, &client);
+    let subject = Address::generate(&env);
+
+    let first = client.issue_credential(
+        &issuer,
+        &subject,
+        &credential_id(&env, 1),
+        &schema(&env),
+        &900,
+    );
+    let second = client.issue_credential(
+        &issuer,
+        &subject,
+        &credential_id(&env, 2),
+        &schema(&env),
+        &0,
+    );
+    assert_eq!((first.status_list, first.status_index), (0, 0));
+    assert_eq!((second.status_list, second.status_index), (0, 1));
+    assert_eq!(first.issued_at, 50);
+    assert_eq!(first.expires_at, 900);
+    assert_eq!(client.get_credential(&issuer, &credential_id(&env, 1)), first);
+    assert_eq!(
+        client.try_issue_credential(
+            &issuer,
+            &subject,
+            &credential_id(&env, 1),
+            &schema(&env),
+            &0
+        ),
+        Err(Ok(CredentialError::CredentialExists))
+    );
+}
+
+#[test]
+fn test_revoke_credential_sets_status_bit() {
+    let (env, client) = setup();
+    let issuer = setup_did(&env, &client);
+    let subject = Address::generate(&env);
+    client.issue_credential(
+        &issuer,
+        &subject,
+        &credential_id(&env, 1),
+        &schema(&env),
+        &0,
+    );
+    client.issue_credential(
+        &issuer,
+        &subject,
+        &credential_id(&env, 2),
+        &schema(&env),
+        &0,
+    );
+
+    let list = client.get_status_list(&issuer, &0);
+    assert_eq!(list.len(), STATUS_LIST_BYTES);
+    assert_eq!(list.get(0), Some(0));
+
+    client.revoke_credential(&issuer, &credential_id(&env, 2));
+    assert_eq!(
+        client.get_status_list(&issuer, &0).get(0),
+        Some(0b0100_0000)
+    );
+    assert!(client.is_credential_revoked(&issuer, &credential_id(&env, 2)));
+    assert!(!client.is_credential_revoked(&issuer, &credential_id(&env, 1)));
+    assert_eq!(
+        client.try_revoke_credential(&issuer, &credential_id(&env, 2)),
+        Err(Ok(CredentialError::CredentialAlreadyRevoked))
+    );
+}
+
+#[test]
+fn test_only_issuer_revokes_credential() {
+    let (env, client) = setup();
+    let issuer = setup_did(&env, &client);
+    let subject = Address::generate(&env);
+    client.issue_credential(
+        &issuer,
+        &subject,
+        &credential_id(&env, 1),
+        &schema(&env),
+        &0,
+    );
+
+    // Ids are scoped per issuer, so another address finds nothing to revoke.
+    assert_eq!(
+        client.try_revoke_credential(&subject, &credential_id(&env, 1)),
+        Err(Ok(CredentialError::CredentialNotFound))
+    );
+    assert_eq!(
+        client.try_revoke_credential(&issuer, &credential_id(&env, 9)),
+        Err(Ok(CredentialError::CredentialNotFound))
+    );
+}
+
+#[test]
+fn test_credential_ids_are_scoped_per_issuer() {
+    let (env, client) = setup();
+    let issuer = setup_did(&env, &client);
+    let squatter = setup_did(&env, &client);
+    let subject = Address::generate(&env);
+
+    // Another issuer anchoring the id first does not block the real issuer.
+    client.issue_credential(
+        &squatter,
+        &subject,
+        &credential_id(&env, 1),
+        &schema(&env),
+        &0,
+    );
+    let record = client.issue_credential(
+        &issuer,
+        &subject,
+        &credential_id(&env, 1),
+        &schema(&env),
+        &0,
+    );
+    assert_eq!(record.issuer, issuer);
+    assert_eq!(client.get_credential(&issuer, &credential_id(&env, 1)), record);
+
+    client.revoke_credential(&squatter, &credential_id(&env, 1));
+    assert!(client.is_credential_revoked(&squatter, &credential_id(&env, 1)));
+    assert!(!client.is_credential_revoked(&issuer, &credential_id(&env, 1)));
+}
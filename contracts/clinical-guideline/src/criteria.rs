@@ -0,0 +1,224 @@
+//! Declarative guideline criteria.
+//!
+//! A guideline's criteria are a small decision table: an ordered list of
+//! [`CriteriaRule`]s, each a conjunction of [`Predicate`]s over typed
+//! [`PatientFacts`]. Evaluation is first-match: the first rule whose
+//! predicates all hold supplies the recommendation, strength and
+//! alternatives. A rule with no predicates always matches, so it may only
+//! appear last, as a default branch.
+//!
+//! ## Facts
+//! * Ages are whole years.
+//! * Condition codes are matched by prefix, so `E11` matches `E11.9`.
+//! * Lab values are fixed-point integers in thousandths of the lab's reporting
+//!   unit (HbA1c 7.2 % → `7200`). A missing lab fails every lab predicate.
+//!
+//! ## Cost bounds
+//! Registration rejects tables with more than [`MAX_RULES`] rules,
+//! [`MAX_RULE_PREDICATES`] predicates per rule or [`MAX_TOTAL_PREDICATES`]
+//! overall, and evaluation rejects facts with more than [`MAX_FACT_CODES`]
+//! conditions or labs. Evaluating a table therefore costs at most
+//! `MAX_TOTAL_PREDICATES × MAX_FACT_CODES` code comparisons.
+
+use soroban_sdk::{String, Symbol, Vec, contracttype};
+
+use crate::Error;
+
+/// Most rules in one guideline's decision table.
+pub const MAX_RULES: u32 = 16;
+/// Most predicates in one rule.
+pub const MAX_RULE_PREDICATES: u32 = 8;
+/// Most predicates across a whole decision table.
+pub const MAX_TOTAL_PREDICATES: u32 = 48;
+/// Most alternative options listed by one rule.
+pub const MAX_ALTERNATIVES: u32 = 5;
+/// Most condition codes, and separately lab values, in submitted facts.
+pub const MAX_FACT_CODES: u32 = 32;
+/// Longest condition or lab code, in bytes.
+pub const MAX_CODE_LEN: u32 = 32;
+/// Longest recommendation or alternative text, in bytes.
+pub const MAX_TEXT_LEN: u32 = 256;
+/// Highest age bound a predicate may use.
+pub const MAX_AGE_YEARS: u32 = 150;
+
+/// One lab result, in thousandths of the lab's reporting unit.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct LabValue {
+    pub code: String,
+    pub value: i64,
+}
+
+/// Patient facts a guideline is evaluated against.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PatientFacts {
+    pub age_years: u32,
+    pub sex: Symbol,
+    /// Coded active conditions (e.g. ICD-10).
+    pub conditions: Vec<String>,
+    pub labs: Vec<LabValue>,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Predicate {
+    AgeAtLeast(u32),
+    AgeBelow(u32),
+    SexIs(Symbol),
+    /// Some condition code starts with this prefix.
+    HasCondition(String),
+    /// No condition code starts with this prefix.
+    LacksCondition(String),
+    /// Lab `code` is present with a value `>=` the threshold.
+    LabAtLeast(String, i64),
+    /// Lab `code` is present with a value `<` the threshold.
+    LabBelow(String, i64),
+}
+
+/// One branch of a decision table.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CriteriaRule {
+    /// All must hold for the rule to match.
+    pub predicates: Vec<Predicate>,
+    pub recommendation: String,
+    pub strength: Symbol,
+    pub alternatives: Vec<String>,
+}
+
+/// Ordered decision table; see the module docs.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct GuidelineCriteria {
+    pub rules: Vec<CriteriaRule>,
+}
+
+/// Reject decision tables that are malformed (`InvalidCriteria`) or exceed
+/// the cost bounds (`CriteriaTooComplex`).
+pub fn validate(criteria: &GuidelineCriteria) -> Result<(), Error> {
+    let rule_count = criteria.rules.len();
+    if rule_count == 0 {
+        return Err(Error::InvalidCriteria);
+    }
+    if rule_count > MAX_RULES {
+        return Err(Error::CriteriaTooComplex);
+    }
+
+    let mut total = 0u32;
+    for (i, rule) in criteria.rules.iter().enumerate() {
+        let count = rule.predicates.len();
+        if count > MAX_RULE_PREDICATES {
+            return Err(Error::CriteriaTooComplex);
+        }
+        total += count;
+        if total > MAX_TOTAL_PREDICATES {
+            return Err(Error::CriteriaTooComplex);
+        }
+        // A default branch would shadow every rule after it.
+        if count == 0 && i as u32 + 1 != rule_count {
+            return Err(Error::InvalidCriteria);
+        }
+        if rule.alternatives.len() > MAX_ALTERNATIVES {
+            return Err(Error::CriteriaTooComplex);
+        }
+        if !valid_text(&rule.recommendation) {
+            return Err(Error::InvalidCriteria);
+        }
+        for alternative in rule.alternatives.iter() {
+            if !valid_text(&alternative) {
+                return Err(Error::InvalidCriteria);
+            }
+        }
+
+        // Track the rule's age window so an unsatisfiable rule is rejected.
+        let mut age_min = 0u32;
+        let mut age_max = MAX_AGE_YEARS + 1;
+        for predicate in rule.predicates.iter() {
+            match predicate {
+                Predicate::AgeAtLeast(years) => {
+                    if years > MAX_AGE_YEARS {
+                        return Err(Error::InvalidCriteria);
+                    }
+                    age_min = age_min.max(years);
+                }
+                Predicate::AgeBelow(years) => {
+                    if years > MAX_AGE_YEARS {
+                        return Err(Error::InvalidCriteria);
+                    }
+                    age_max = age_max.min(years);
+                }
+                Predicate::SexIs(_) => {}
+                Predicate::HasCondition(code)
+                | Predicate::LacksCondition(code)
+                | Predicate::LabAtLeast(code, _)
+                | Predicate::LabBelow(code, _) => {
+                    if !valid_code(&code) {
+                        return Err(Error::InvalidCriteria);
+                    }
+                }
+            }
+        }
+        if age_min >= age_max {
+            return Err(Error::InvalidCriteria);
+        }
+    }
+    Ok(())
+}
+
+/// Reject facts that exceed the evaluation bounds.
+pub fn validate_facts(facts: &PatientFacts) -> Result<(), Error> {
+    if facts.conditions.len() > MAX_FACT_CODES || facts.labs.len() > MAX_FACT_CODES {
+        return Err(Error::InvalidInput);
+    }
+    Ok(())
+}
+
+/// Index of the first rule whose predicates all hold for `facts`.
+pub fn evaluate(criteria: &GuidelineCriteria, facts: &PatientFacts) -> Option<u32> {
+    for (i, rule) in criteria.rules.iter().enumerate() {
+        if rule.predicates.iter().all(|p| holds(&p, facts)) {
+            return Some(i as u32);
+        }
+    }
+    None
+}
+
+fn holds(predicate: &Predicate, facts: &PatientFacts) -> bool {
+    match predicate {
+        Predicate::AgeAtLeast(years) => facts.age_years >= *years,
+        Predicate::AgeBelow(years) => facts.age_years < *years,
+        Predicate::SexIs(sex) => facts.sex == *sex,
+        Predicate::HasCondition(prefix) => has_condition(facts, prefix),
+        Predicate::LacksCondition(prefix) => !has_condition(facts, prefix),
+        Predicate::LabAtLeast(code, threshold) => {
+            lab_value(facts, code).is_some_and(|v| v >= *threshold)
+        }
+        Predicate::LabBelow(code, threshold) => {
+            lab_value(facts, code).is_some_and(|v| v < *threshold)
+        }
+    }
+}
+
+fn has_condition(facts: &PatientFacts, prefix: &String) -> bool {
+    let prefix = prefix.to_bytes();
+    facts.conditions.iter().any(|code| {
+        code.len() >= prefix.len() && code.to_bytes().slice(0..prefix.len()) == prefix
+    })
+}
+
+fn lab_value(facts: &PatientFacts, code: &String) -> Option<i64> {
+    facts
+        .labs
+        .iter()
+        .find(|lab| lab.code == *code)
+        .map(|lab| lab.value)
+}
+
+fn valid_code(code: &String) -> bool {
+    !code.is_empty() && code.len() <= MAX_CODE_LEN
+}
+
+fn valid_text(text: &String) -> bool {
+    !text.is_empty() && text.len() <= MAX_TEXT_LEN
+}
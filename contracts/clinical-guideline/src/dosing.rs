@@ -0,0 +1,291 @@
+//! Medication dosing rules.
+//!
+//! A [`DosingRule`] is registered per medication code (the NDC code that keys
+//! prescription-management's `Medication` entries). It holds:
+//! * age [`DoseBand`]s, for pediatric and adult regimens. Each band has a
+//!   weight-based, BSA-based or fixed [`DoseBasis`] and single and daily caps.
+//! * renal tiers keyed by eGFR, and hepatic tiers keyed by Child-Pugh class.
+//!   Each tier scales the dose by a percentage, and 0 % contraindicates it.
+//! * the monitoring the medication requires.
+//!
+//! ## Units
+//! Doses are micrograms. Weight is decigrams, height is centimetres and eGFR
+//! is mL/min/1.73 m². BSA uses the Mosteller formula in hundredths of a m²,
+//! `isqrt(height_cm × weight_dg / 3600)`.
+//!
+//! ## Calculation
+//! 1. Pick the band whose age window contains the patient.
+//! 2. Compute the base dose from its basis.
+//! 3. Apply the most severe renal tier below the patient's eGFR, then the
+//!    hepatic tier for their Child-Pugh class.
+//! 4. Cap the dose at `max_single_mcg`, then at `max_daily_mcg / doses_per_day`.
+
+use soroban_sdk::{Env, String, Symbol, Vec, contracttype};
+
+use crate::Error;
+
+/// Most age bands in one rule.
+pub const MAX_DOSE_BANDS: u32 = 8;
+/// Most renal tiers in one rule.
+pub const MAX_RENAL_TIERS: u32 = 4;
+/// Most monitoring entries in one rule.
+pub const MAX_MONITORING: u32 = 8;
+/// Most doses per day a band may prescribe.
+pub const MAX_DOSES_PER_DAY: u32 = 24;
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum DoseBasis {
+    /// Micrograms per kilogram of body weight.
+    PerKg(u64),
+    /// Micrograms per m² of body surface area.
+    PerBsa(u64),
+    /// Micrograms, regardless of body size.
+    Fixed(u64),
+}
+
+/// Regimen for patients with `min_age_years <= age < max_age_years`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DoseBand {
+    pub min_age_years: u32,
+    pub max_age_years: u32,
+    pub basis: DoseBasis,
+    pub doses_per_day: u32,
+    /// Display label for `doses_per_day`, e.g. `TID`.
+    pub frequency: String,
+    pub max_single_mcg: u64,
+    pub max_daily_mcg: u64,
+}
+
+/// Scales the dose to `percent` when eGFR is below `egfr_below`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RenalTier {
+    pub egfr_below: u32,
+    pub percent: u32,
+}
+
+/// Child-Pugh class; `None` means no known hepatic impairment.
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum HepaticImpairment {
+    None,
+    Mild,
+    Moderate,
+    Severe,
+}
+
+/// Scales the dose to `percent` for patients in Child-Pugh class `class`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct HepaticTier {
+    pub class: HepaticImpairment,
+    pub percent: u32,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DosingRule {
+    pub route: Symbol,
+    /// Course length in seconds, if the regimen is time-limited.
+    pub duration: Option<u64>,
+    /// Ascending, non-overlapping age windows.
+    pub bands: Vec<DoseBand>,
+    /// Strictly descending `egfr_below`.
+    pub renal_tiers: Vec<RenalTier>,
+    /// At most one tier per impaired class.
+    pub hepatic_tiers: Vec<HepaticTier>,
+    pub monitoring: Vec<String>,
+}
+
+/// Patient measurements a dose is calculated from.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DosingFacts {
+    pub age_years: u32,
+    pub weight_dg: u32,
+    /// Required for BSA-based bands; 0 when unknown.
+    pub height_cm: u32,
+    /// eGFR; `None` skips renal adjustment.
+    pub egfr: Option<u32>,
+    pub hepatic: HepaticImpairment,
+}
+
+/// Result of applying a rule to [`DosingFacts`].
+pub struct Dose {
+    pub band: DoseBand,
+    pub dose_mcg: u64,
+    pub renal_adjusted: bool,
+    pub hepatic_adjusted: bool,
+    pub capped: bool,
+}
+
+/// Reject rules with missing, overlapping or out-of-range entries.
+pub fn validate(rule: &DosingRule) -> Result<(), Error> {
+    if rule.bands.is_empty()
+        || rule.bands.len() > MAX_DOSE_BANDS
+        || rule.renal_tiers.len() > MAX_RENAL_TIERS
+        || rule.monitoring.len() > MAX_MONITORING
+    {
+        return Err(Error::InvalidDosingRule);
+    }
+
+    let mut previous_max = 0u32;
+    for band in rule.bands.iter() {
+        let amount = match band.basis {
+            DoseBasis::PerKg(a) | DoseBasis::PerBsa(a) | DoseBasis::Fixed(a) => a,
+        };
+        if band.min_age_years >= band.max_age_years
+            || band.min_age_years < previous_max
+            || amount == 0
+            || band.doses_per_day == 0
+            || band.doses_per_day > MAX_DOSES_PER_DAY
+            || band.frequency.is_empty()
+            || band.max_single_mcg == 0
+            || band.max_daily_mcg < band.max_single_mcg
+        {
+            return Err(Error::InvalidDosingRule);
+        }
+        previous_max = band.max_age_years;
+    }
+
+    let mut previous_egfr = u32::MAX;
+    for tier in rule.renal_tiers.iter() {
+        if tier.egfr_below >= previous_egfr || tier.egfr_below == 0 || tier.percent > 100 {
+            return Err(Error::InvalidDosingRule);
+        }
+        previous_egfr = tier.egfr_below;
+    }
+
+    for (i, tier) in rule.hepatic_tiers.iter().enumerate() {
+        let duplicate = rule
+            .hepatic_tiers
+            .iter()
+            .take(i)
+            .any(|other| other.class == tier.class);
+        if tier.class == HepaticImpairment::None || tier.percent > 100 || duplicate {
+            return Err(Error::InvalidDosingRule);
+        }
+    }
+
+    if rule.monitoring.iter().any(|m| m.is_empty()) {
+        return Err(Error::InvalidDosingRule);
+    }
+    Ok(())
+}
+
+/// Apply `rule` to `facts`; see the module docs for the steps.
+pub fn calculate(rule: &DosingRule, facts: &DosingFacts) -> Result<Dose, Error> {
+    let band = rule
+        .bands
+        .iter()
+        .find(|b| b.min_age_years <= facts.age_years && facts.age_years < b.max_age_years)
+        .ok_or(Error::NoDosingBand)?;
+
+    if facts.weight_dg == 0 {
+        return Err(Error::InvalidInput);
+    }
+    let weight_dg = facts.weight_dg as u64;
+    let mut dose_mcg = match band.basis {
+        DoseBasis::PerKg(mcg_per_kg) => mcg_per_kg * weight_dg / 10_000,
+        DoseBasis::PerBsa(mcg_per_m2) => {
+            if facts.height_cm == 0 {
+                return Err(Error::InvalidInput);
+            }
+            mcg_per_m2 * bsa_centi_m2(facts.height_cm, facts.weight_dg) / 100
+        }
+        DoseBasis::Fixed(mcg) => mcg,
+    };
+
+    // Tiers are sorted by descending threshold, so the last match is the most severe.
+    let renal = facts.egfr.and_then(|egfr| {
+        rule.renal_tiers
+            .iter()
+            .filter(|tier| egfr < tier.egfr_below)
+            .last()
+    });
+    let hepatic = rule
+        .hepatic_tiers
+        .iter()
+        .find(|tier| tier.class == facts.hepatic);
+    for percent in [
+        renal.as_ref().map(|t| t.percent),
+        hepatic.as_ref().map(|t| t.percent),
+    ]
+    .into_iter()
+    .flatten()
+    {
+        if percent == 0 {
+            return Err(Error::DoseContraindicated);
+        }
+        dose_mcg = dose_mcg * percent as u64 / 100;
+    }
+
+    let per_dose_daily_cap = band.max_daily_mcg / band.doses_per_day as u64;
+    let capped_mcg = dose_mcg.min(band.max_single_mcg).min(per_dose_daily_cap);
+    Ok(Dose {
+        capped: capped_mcg < dose_mcg,
+        dose_mcg: capped_mcg,
+        renal_adjusted: renal.is_some(),
+        hepatic_adjusted: hepatic.is_some(),
+        band,
+    })
+}
+
+/// Mosteller body surface area in hundredths of a m².
+pub fn bsa_centi_m2(height_cm: u32, weight_dg: u32) -> u64 {
+    isqrt(height_cm as u64 * weight_dg as u64 / 3600)
+}
+
+fn isqrt(n: u64) -> u64 {
+    if n < 2 {
+        return n;
+    }
+    let mut x = n;
+    let mut y = x.div_ceil(2);
+    while y < x {
+        x = y;
+        y = (x + n / x) / 2;
+    }
+    x
+}
+
+/// Render a microgram dose in mg with up to three decimals, e.g. `1.25mg`.
+pub fn format_dose(env: &Env, dose_mcg: u64) -> String {
+    let mut buf = [0u8; 32];
+    let mut len = 0usize;
+
+    let mut digits = [0u8; 20];
+    let mut count = 0usize;
+    let mut whole = dose_mcg / 1000;
+    loop {
+        digits[count] = b'0' + (whole % 10) as u8;
+        count += 1;
+        whole /= 10;
+        if whole == 0 {
+            break;
+        }
+    }
+    while count > 0 {
+        count -= 1;
+        buf[len] = digits[count];
+        len += 1;
+    }
+
+    let mut frac = dose_mcg % 1000;
+    if frac > 0 {
+        buf[len] = b'.';
+        len += 1;
+        let mut scale = 100;
+        while frac > 0 {
+            buf[len] = b'0' + (frac / scale) as u8;
+            len += 1;
+            frac %= scale;
+            scale /= 10;
+        }
+    }
+    buf[len] = b'm';
+    buf[len + 1] = b'g';
+    String::from_bytes(env, &buf[..len + 2])
+}
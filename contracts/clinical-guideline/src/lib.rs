@@ -21,9 +21,66 @@
 //! **Encryption/Integrity:** Guideline evidence levels classified (e.g., A, B, C) for strength
 //! determination. Dosage recommendations include validation against renal function and monitoring
 //! requirements. Clinical decision data stored in contract state for integrity.
+//!
+//! ## Guideline Criteria
+//!
+//! Each guideline carries a versioned decision table (see [`criteria`]) that
+//! `evaluate_guideline` runs against submitted [`PatientFacts`]. Updating a
+//! guideline stores its criteria under a new version and keeps earlier versions
+//! readable, so every recommendation can be traced to the exact criteria used.
+//!
+//! ## Dosing Rules
+//!
+//! `calculate_drug_dosage` applies the latest [`DosingRule`] registered for the
+//! medication (see [`dosing`]). Rules are versioned the same way as guideline
+//! criteria, and each recommendation reports the rule version it used.
+//!
+//! ## Risk Scores
+//!
+//! `assess_risk_score` runs one of the calculators in [`risk`] and stores the
+//! result with the calculator version and a hash of its inputs, so each score
+//! can be reproduced and audited later. Only the patient, or a provider the
+//! patient has authorized with `authorize_reminder_provider`, may record or
+//! read a patient's assessments.
+//!
+//! ## Reminders
+//!
+//! Reminders may recur (see [`reminders`]) and move through pending, snoozed,
+//! completed and dismissed states. Open reminders are indexed per patient and
+//! per provider for due-list queries. `create_preventive_reminders` turns
+//! `check_preventive_care` results into reminders. Only the patient, or a
+//! provider the patient has authorized with `authorize_reminder_provider`,
+//! may open reminders for them.
+//!
+//! ## Preventive Care
+//!
+//! `check_preventive_care` checks a patient's age, sex, risk factors and last
+//! completions against the admin's versioned [`PreventiveSchedule`] (see
+//! [`preventive`]) and returns the services that are due or overdue, with
+//! their due dates.
 
 use soroban_sdk::{
     Address, BytesN, Env, String, Symbol, Vec, contract, contracterror, contractimpl, contracttype,
+    xdr::ToXdr,
+};
+
+pub mod criteria;
+pub mod dosing;
+pub mod preventive;
+pub mod reminders;
+pub mod risk;
+
+pub use criteria::{CriteriaRule, GuidelineCriteria, LabValue, PatientFacts, Predicate};
+pub use dosing::{
+    DoseBand, DoseBasis, DosingFacts, DosingRule, HepaticImpairment, HepaticTier, RenalTier,
+};
+pub use preventive::{
+    PreventiveDue, PreventiveSchedule, PreventiveService, PreventiveStatus, ServiceCompletion,
+};
+pub use reminders::{Recurrence, ReminderStatus};
+pub use risk::{
+    BradenInput, Cha2ds2VascInput, Curb65Input, HasBledInput, MeldInput, RiskBand, RiskInput,
+    WellsDvtInput, WellsPeInput,
 };
 
 // --- Custom Error Types ---
@@ -34,14 +91,35 @@ pub enum Error {
     NotAuthorized = 1,
     GuidelineNotFound = 2,
     InvalidInput = 3,
+    InvalidCriteria = 4,
+    CriteriaTooComplex = 5,
+    InvalidDosingRule = 6,
+    DosingRuleNotFound = 7,
+    NoDosingBand = 8,
+    DoseContraindicated = 9,
+    AssessmentNotFound = 10,
+    ReminderNotFound = 11,
+    ReminderClosed = 12,
+    TooManyReminders = 13,
+    InvalidSchedule = 14,
+    ScheduleNotFound = 15,
 }
 
+/// Most open reminders per patient.
+pub const MAX_OPEN_REMINDERS: u32 = 100;
+/// Most open reminders per provider.
+pub const MAX_PROVIDER_REMINDERS: u32 = 200;
+
 // --- Data Structures ---
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct GuidelineRecommendation {
     pub guideline_id: String,
+    /// Criteria version the facts were evaluated against.
+    pub version: u32,
     pub applicable: bool,
+    /// Index of the matched rule; `None` when no rule matched.
+    pub matched_rule: Option<u32>,
     pub recommendation: String,
     pub strength: Symbol,
     pub evidence_level: Symbol,
@@ -52,20 +130,38 @@ pub struct GuidelineRecommendation {
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct DosageRecommendation {
     pub medication: String,
+    /// Dosing rule version the dose was calculated with.
+    pub rule_version: u32,
     pub recommended_dose: String,
+    /// Single dose in micrograms.
+    pub dose_mcg: u64,
+    pub doses_per_day: u32,
     pub frequency: String,
     pub route: Symbol,
     pub duration: Option<u64>,
     pub renal_adjustment: bool,
+    pub hepatic_adjustment: bool,
+    /// Whether the single or daily maximum lowered the dose.
+    pub capped: bool,
     pub monitoring_required: Vec<String>,
 }
 
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct RiskScore {
+    pub assessment_id: u64,
+    pub patient_id: Address,
+    pub provider_id: Address,
     pub calculator: Symbol,
+    pub calculator_version: u32,
+    /// sha256 of the XDR-encoded `RiskInput`.
+    pub input_hash: BytesN<32>,
+    /// In units of `1 / score_scale`.
     pub score: i32,
+    pub score_scale: u32,
+    pub band: RiskBand,
     pub interpretation: String,
+    pub assessed_at: u64,
 }
 
 #[contracttype]
@@ -80,14 +176,28 @@ pub struct CarePathway {
 pub struct Reminder {
     pub reminder_id: u64,
     pub patient_id: Address,
+    pub provider_id: Address,
+    pub reminder_type: Symbol,
+    pub priority: Symbol,
+    /// When the reminder is due, after any snoozes.
     pub due_date: u64,
+    /// The occurrence date the recurrence is anchored on.
+    pub scheduled_for: u64,
+    pub recurrence: Recurrence,
+    pub status: ReminderStatus,
     pub created_at: u64,
+    pub updated_at: u64,
+    /// Occurrence opened when this one was completed.
+    pub next_reminder_id: Option<u64>,
 }
 
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct GuidelineMetadata {
     pub condition: String,
+    /// Latest criteria version; the first registration is version 1.
+    pub version: u32,
+    /// sha256 of the XDR-encoded criteria for `version`.
     pub criteria_hash: BytesN<32>,
     pub recommendation_hash: BytesN<32>,
     pub evidence_level: Symbol,
@@ -98,8 +208,18 @@ pub struct GuidelineMetadata {
 pub enum DataKey {
     Admin,
     Guideline(String),
+    GuidelineCriteria(String, u32), // (guideline_id, version) -> GuidelineCriteria
+    DosingRuleVersion(String),      // medication -> u32 latest version
+    DosingRule(String, u32),        // (medication, version) -> DosingRule
+    RiskAssessmentCounter(Address), // patient_id -> u64 (next assessment_id)
+    RiskAssessment(Address, u64),   // (patient_id, assessment_id) -> RiskScore
     ReminderCounter(Address),       // patient_id -> u64 (next reminder_id)
     Reminder(Address, u64),         // (patient_id, reminder_id) -> Reminder
+    PatientReminders(Address),      // patient_id -> Vec<u64> open reminder ids
+    ProviderReminders(Address),     // provider_id -> Vec<(patient_id, reminder_id)> open reminders
+    ReminderProvider(Address, Address), // (patient_id, provider_id) -> bool may open reminders
+    PreventiveScheduleVersion,      // u32 latest schedule version
+    PreventiveSchedule(u32),        // version -> PreventiveSchedule
 }
 
 #[contract]
@@ -122,7 +242,7 @@ impl ClinicalGuidelineContract {
         admin: Address,
         guideline_id: String,
         condition: String,
-        criteria_hash: BytesN<32>,
+        criteria: GuidelineCriteria,
         recommendation_hash: BytesN<32>,
         evidence_level: Symbol,
     ) -> Result<(), Error> {
@@ -137,6 +257,7 @@ impl ClinicalGuidelineContract {
         if admin != stored_admin {
             return Err(Error::NotAuthorized);
         }
+        criteria::validate(&criteria)?;
 
         // Reject silent overwrite — existing guideline_id must not already exist
         let key = DataKey::Guideline(guideline_id.clone());
@@ -146,7 +267,8 @@ impl ClinicalGuidelineContract {
 
         let metadata = GuidelineMetadata {
             condition,
-            criteria_hash,
+            version: 1,
+            criteria_hash: Self::store_criteria(&env, &guideline_id, 1, &criteria),
             recommendation_hash,
             evidence_level,
         };
@@ -155,12 +277,14 @@ impl ClinicalGuidelineContract {
         Ok(())
     }
 
+    /// Replace a guideline's metadata and criteria. The criteria are stored
+    /// under the next version; earlier versions stay readable.
     pub fn update_clinical_guideline(
         env: Env,
         admin: Address,
         guideline_id: String,
         condition: String,
-        criteria_hash: BytesN<32>,
+        criteria: GuidelineCriteria,
         recommendation_hash: BytesN<32>,
         evidence_level: Symbol,
     ) -> Result<(), Error> {
@@ -175,14 +299,20 @@ impl ClinicalGuidelineContract {
             return Err(Error::NotAuthorized);
         }
 
+        criteria::validate(&criteria)?;
+
         let key = DataKey::Guideline(guideline_id.clone());
-        if !env.storage().persistent().has(&key) {
-            return Err(Error::GuidelineNotFound);
-        }
+        let previous: GuidelineMetadata = env
+            .storage()
+            .persistent()
+            .get(&key)
+            .ok_or(Error::GuidelineNotFound)?;
+        let version = previous.version + 1;
 
         let metadata = GuidelineMetadata {
             condition,
-            criteria_hash,
+            version,
+            criteria_hash: Self::store_criteria(&env, &guideline_id, version, &criteria),
             recommendation_hash,
             evidence_level,
         };
@@ -191,97 +321,213 @@ impl ClinicalGuidelineContract {
         Ok(())
     }
 
+    pub fn get_guideline(env: Env, guideline_id: String) -> Result<GuidelineMetadata, Error> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Guideline(guideline_id))
+            .ok_or(Error::GuidelineNotFound)
+    }
+
+    pub fn get_guideline_criteria(
+        env: Env,
+        guideline_id: String,
+        version: u32,
+    ) -> Result<GuidelineCriteria, Error> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::GuidelineCriteria(guideline_id, version))
+            .ok_or(Error::GuidelineNotFound)
+    }
+
+    /// Evaluate the guideline's latest criteria against `facts`. The first
+    /// matching rule supplies the recommendation, strength and alternatives;
+    /// when none matches the guideline is not applicable.
     pub fn evaluate_guideline(
         env: Env,
         _patient_id: Address,
         _provider_id: Address,
         guideline_id: String,
-        patient_data_hash: BytesN<32>,
+        facts: PatientFacts,
     ) -> Result<GuidelineRecommendation, Error> {
         let metadata: GuidelineMetadata = env
             .storage()
             .persistent()
             .get(&DataKey::Guideline(guideline_id.clone()))
             .ok_or(Error::GuidelineNotFound)?;
+        let table: GuidelineCriteria = env
+            .storage()
+            .persistent()
+            .get(&DataKey::GuidelineCriteria(
+                guideline_id.clone(),
+                metadata.version,
+            ))
+            .ok_or(Error::GuidelineNotFound)?;
+        criteria::validate_facts(&facts)?;
 
-        let is_applicable = metadata.criteria_hash == patient_data_hash;
+        let matched_rule = criteria::evaluate(&table, &facts);
+        let (recommendation, strength, alternative_options) =
+            match matched_rule.and_then(|i| table.rules.get(i)) {
+                Some(rule) => (rule.recommendation, rule.strength, rule.alternatives),
+                None => (
+                    String::from_str(&env, ""),
+                    Symbol::new(&env, "None"),
+                    Vec::new(&env),
+                ),
+            };
 
         Ok(GuidelineRecommendation {
             guideline_id,
-            applicable: is_applicable,
-            recommendation: String::from_str(&env, "Follow evidence-based recommendation"),
-            strength: Symbol::new(&env, "Strong"),
+            version: metadata.version,
+            applicable: matched_rule.is_some(),
+            matched_rule,
+            recommendation,
+            strength,
             evidence_level: metadata.evidence_level,
-            alternative_options: Vec::new(&env),
+            alternative_options,
         })
     }
 
-    pub fn calculate_drug_dosage(
+    /// Register the first dosing rule for `medication`, which should be the
+    /// NDC code of its prescription-management `Medication`. Returns version 1.
+    pub fn register_dosing_rule(
         env: Env,
-        _patient_id: Address,
+        admin: Address,
         medication: String,
-        weight_dg: u32, // Decigrams (0.1g) to avoid f32
-        renal_function: Option<u32>,
-    ) -> Result<DosageRecommendation, Error> {
-        let gfr = renal_function.unwrap_or(100);
-        let is_renal_impaired = gfr < 60;
-
-        // Example: 5mg per kg (1000g = 10000dg)
-        // dosage = (weight_dg / 10000) * 5
-        let mut dose_mg = (weight_dg as u64 * 5) / 10000;
+        rule: DosingRule,
+    ) -> Result<u32, Error> {
+        Self::require_admin(&env, &admin)?;
+        dosing::validate(&rule)?;
 
-        // Reduce dose by 25% for renal impairment (GFR < 60)
-        if is_renal_impaired {
-            dose_mg = (dose_mg * 75) / 100;
+        let key = DataKey::DosingRuleVersion(medication.clone());
+        if env.storage().persistent().has(&key) {
+            return Err(Error::InvalidInput);
         }
+        Self::store_dosing_rule(&env, &medication, 1, &rule);
+        Ok(1)
+    }
 
-        // Build dose string: "XXmg" (simple numeric representation)
-        let dose_str = match dose_mg {
-            0 => String::from_str(&env, "0mg"),
-            1..=9 => {
-                let s = match dose_mg {
-                    1 => "1mg",
-                    2 => "2mg",
-                    3 => "3mg",
-                    4 => "4mg",
-                    5 => "5mg",
-                    6 => "6mg",
-                    7 => "7mg",
-                    8 => "8mg",
-                    9 => "9mg",
-                    _ => "0mg",
-                };
-                String::from_str(&env, s)
-            }
-            _ => String::from_str(&env, "10+mg"),
-        };
+    /// Publish a new version of `medication`'s dosing rule; earlier versions
+    /// stay readable. Returns the new version.
+    pub fn update_dosing_rule(
+        env: Env,
+        admin: Address,
+        medication: String,
+        rule: DosingRule,
+    ) -> Result<u32, Error> {
+        Self::require_admin(&env, &admin)?;
+        dosing::validate(&rule)?;
+
+        let version = Self::dosing_rule_version(&env, &medication)? + 1;
+        Self::store_dosing_rule(&env, &medication, version, &rule);
+        Ok(version)
+    }
+
+    pub fn get_dosing_rule(
+        env: Env,
+        medication: String,
+        version: u32,
+    ) -> Result<DosingRule, Error> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::DosingRule(medication, version))
+            .ok_or(Error::DosingRuleNotFound)
+    }
+
+    pub fn get_dosing_rule_version(env: Env, medication: String) -> Result<u32, Error> {
+        Self::dosing_rule_version(&env, &medication)
+    }
+
+    /// Calculate a dose from the latest dosing rule for `medication`.
+    ///
+    /// Fails with `DosingRuleNotFound` for unregistered medications,
+    /// `NoDosingBand` when no age band covers the patient and
+    /// `DoseContraindicated` when a renal or hepatic tier rules the drug out.
+    pub fn calculate_drug_dosage(
+        env: Env,
+        _patient_id: Address,
+        medication: String,
+        facts: DosingFacts,
+    ) -> Result<DosageRecommendation, Error> {
+        let rule_version = Self::dosing_rule_version(&env, &medication)?;
+        let rule: DosingRule = env
+            .storage()
+            .persistent()
+            .get(&DataKey::DosingRule(medication.clone(), rule_version))
+            .ok_or(Error::DosingRuleNotFound)?;
+        let dose = dosing::calculate(&rule, &facts)?;
 
         Ok(DosageRecommendation {
             medication,
-            recommended_dose: dose_str,
-            frequency: String::from_str(&env, "TID"),
-            route: Symbol::new(&env, "Oral"),
-            duration: Some(604800), // 7 days in seconds
-            renal_adjustment: is_renal_impaired,
-            monitoring_required: Vec::new(&env),
+            rule_version,
+            recommended_dose: dosing::format_dose(&env, dose.dose_mcg),
+            dose_mcg: dose.dose_mcg,
+            doses_per_day: dose.band.doses_per_day,
+            frequency: dose.band.frequency,
+            route: rule.route,
+            duration: rule.duration,
+            renal_adjustment: dose.renal_adjusted,
+            hepatic_adjustment: dose.hepatic_adjusted,
+            capped: dose.capped,
+            monitoring_required: rule.monitoring,
         })
     }
 
+    /// Score `input` with its calculator and keep the result on record for
+    /// `patient_id`. `provider_id` must be the patient or a provider they have
+    /// authorized. Out-of-range inputs fail with `InvalidInput`.
     pub fn assess_risk_score(
         env: Env,
-        _patient_id: Address,
-        risk_calculator: Symbol,
-        input_parameters: Vec<i32>,
+        patient_id: Address,
+        provider_id: Address,
+        input: RiskInput,
     ) -> Result<RiskScore, Error> {
-        let mut total_score: i32 = 0;
-        for val in input_parameters.iter() {
-            total_score += val;
-        }
-        Ok(RiskScore {
-            calculator: risk_calculator,
-            score: total_score,
-            interpretation: String::from_str(&env, "Risk assessment complete"),
-        })
+        Self::require_reminder_provider(&env, &patient_id, &provider_id)?;
+        let assessment = risk::assess(&input)?;
+
+        let counter_key = DataKey::RiskAssessmentCounter(patient_id.clone());
+        let assessment_id: u64 = env
+            .storage()
+            .persistent()
+            .get(&counter_key)
+            .unwrap_or(1);
+
+        let score = RiskScore {
+            assessment_id,
+            patient_id: patient_id.clone(),
+            provider_id,
+            calculator: risk::calculator_name(&env, &input),
+            calculator_version: risk::calculator_version(&input),
+            input_hash: env.crypto().sha256(&input.to_xdr(&env)).into(),
+            score: assessment.score,
+            score_scale: assessment.score_scale,
+            band: assessment.band,
+            interpretation: String::from_str(&env, assessment.interpretation),
+            assessed_at: env.ledger().timestamp(),
+        };
+
+        env.storage()
+            .persistent()
+            .set(&DataKey::RiskAssessment(patient_id, assessment_id), &score);
+        env.storage()
+            .persistent()
+            .set(&counter_key, &(assessment_id + 1));
+
+        Ok(score)
+    }
+
+    /// Read a stored assessment. `caller` must be the patient or a provider
+    /// they have authorized.
+    pub fn get_risk_assessment(
+        env: Env,
+        patient_id: Address,
+        caller: Address,
+        assessment_id: u64,
+    ) -> Result<RiskScore, Error> {
+        Self::require_reminder_provider(&env, &patient_id, &caller)?;
+        env.storage()
+            .persistent()
+            .get(&DataKey::RiskAssessment(patient_id, assessment_id))
+            .ok_or(Error::AssessmentNotFound)
     }
 
     pub fn suggest_care_pathway(
@@ -298,15 +544,327 @@ impl ClinicalGuidelineContract {
         Ok(CarePathway { condition, steps })
     }
 
+    /// Let `provider_id` open reminders and record or read risk assessments
+    /// for `patient_id`.
+    pub fn authorize_reminder_provider(env: Env, patient_id: Address, provider_id: Address) {
+        patient_id.require_auth();
+        env.storage()
+            .persistent()
+            .set(&DataKey::ReminderProvider(patient_id, provider_id), &true);
+    }
+
+    /// Stop `provider_id` from opening new reminders for `patient_id` or
+    /// touching their risk assessments. Reminders already open are kept.
+    pub fn revoke_reminder_provider(env: Env, patient_id: Address, provider_id: Address) {
+        patient_id.require_auth();
+        env.storage()
+            .persistent()
+            .remove(&DataKey::ReminderProvider(patient_id, provider_id));
+    }
+
+    pub fn is_reminder_provider(env: Env, patient_id: Address, provider_id: Address) -> bool {
+        patient_id == provider_id
+            || env
+                .storage()
+                .persistent()
+                .has(&DataKey::ReminderProvider(patient_id, provider_id))
+    }
+
+    /// Open a reminder for `patient_id`, optionally recurring. Returns its id.
+    /// `provider_id` must be the patient or a provider they have authorized.
     pub fn create_reminder(
         env: Env,
         patient_id: Address,
         provider_id: Address,
-        _reminder_type: Symbol,
+        reminder_type: Symbol,
         due_date: u64,
-        _priority: Symbol,
+        priority: Symbol,
+        recurrence: Recurrence,
     ) -> Result<u64, Error> {
-        provider_id.require_auth();
+        Self::require_reminder_provider(&env, &patient_id, &provider_id)?;
+        if !reminders::valid_recurrence(&recurrence) {
+            return Err(Error::InvalidInput);
+        }
+        Self::open_reminder(
+            &env,
+            &patient_id,
+            &provider_id,
+            reminder_type,
+            priority,
+            due_date,
+            recurrence,
+        )
+    }
+
+    pub fn get_reminder(
+        env: Env,
+        patient_id: Address,
+        reminder_id: u64,
+    ) -> Result<Reminder, Error> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Reminder(patient_id, reminder_id))
+            .ok_or(Error::ReminderNotFound)
+    }
+
+    /// Complete an open reminder. For a recurring reminder this opens the
+    /// next occurrence and returns its id. If the patient has revoked the
+    /// reminder's provider, the next occurrence is assigned to the patient.
+    pub fn complete_reminder(
+        env: Env,
+        caller: Address,
+        patient_id: Address,
+        reminder_id: u64,
+    ) -> Result<Option<u64>, Error> {
+        let mut reminder = Self::load_open_reminder(&env, &caller, &patient_id, reminder_id)?;
+        Self::close_reminder(&env, &mut reminder, ReminderStatus::Completed);
+
+        let next_due = reminders::next_occurrence(reminder.scheduled_for, &reminder.recurrence);
+        if let Some(next_due) = next_due {
+            // Revocation keeps open reminders but stops new ones, so the
+            // series continues under the patient rather than the provider.
+            let provider_id = if Self::is_reminder_provider(
+                env.clone(),
+                patient_id.clone(),
+                reminder.provider_id.clone(),
+            ) {
+                reminder.provider_id.clone()
+            } else {
+                patient_id.clone()
+            };
+            let next_id = Self::open_reminder(
+                &env,
+                &patient_id,
+                &provider_id,
+                reminder.reminder_type.clone(),
+                reminder.priority.clone(),
+                next_due,
+                reminder.recurrence.clone(),
+            )?;
+            reminder.next_reminder_id = Some(next_id);
+        }
+        env.storage()
+            .persistent()
+            .set(&DataKey::Reminder(patient_id, reminder_id), &reminder);
+        Ok(reminder.next_reminder_id)
+    }
+
+    /// Push an open reminder's due date to `until`, which must be in the future.
+    pub fn snooze_reminder(
+        env: Env,
+        caller: Address,
+        patient_id: Address,
+        reminder_id: u64,
+        until: u64,
+    ) -> Result<(), Error> {
+        let mut reminder = Self::load_open_reminder(&env, &caller, &patient_id, reminder_id)?;
+        if until <= env.ledger().timestamp() {
+            return Err(Error::InvalidInput);
+        }
+        reminder.due_date = until;
+        reminder.status = ReminderStatus::Snoozed;
+        reminder.updated_at = env.ledger().timestamp();
+        env.storage()
+            .persistent()
+            .set(&DataKey::Reminder(patient_id, reminder_id), &reminder);
+        Ok(())
+    }
+
+    /// Dismiss an open reminder, ending its series.
+    pub fn dismiss_reminder(
+        env: Env,
+        caller: Address,
+        patient_id: Address,
+        reminder_id: u64,
+    ) -> Result<(), Error> {
+        let mut reminder = Self::load_open_reminder(&env, &caller, &patient_id, reminder_id)?;
+        Self::close_reminder(&env, &mut reminder, ReminderStatus::Dismissed);
+        env.storage()
+            .persistent()
+            .set(&DataKey::Reminder(patient_id, reminder_id), &reminder);
+        Ok(())
+    }
+
+    /// Open reminders for `patient_id` due in `[from, to]`, earliest first.
+    pub fn get_patient_due_reminders(
+        env: Env,
+        patient_id: Address,
+        from: u64,
+        to: u64,
+    ) -> Vec<Reminder> {
+        let ids: Vec<u64> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::PatientReminders(patient_id.clone()))
+            .unwrap_or(Vec::new(&env));
+        let mut refs = Vec::new(&env);
+        for id in ids.iter() {
+            refs.push_back((patient_id.clone(), id));
+        }
+        Self::due_reminders(&env, &refs, from, to)
+    }
+
+    /// Open reminders assigned to `provider_id` due in `[from, to]`, earliest first.
+    pub fn get_provider_due_reminders(
+        env: Env,
+        provider_id: Address,
+        from: u64,
+        to: u64,
+    ) -> Vec<Reminder> {
+        let refs: Vec<(Address, u64)> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::ProviderReminders(provider_id))
+            .unwrap_or(Vec::new(&env));
+        Self::due_reminders(&env, &refs, from, to)
+    }
+
+    /// Open a reminder for each service `check_preventive_care` reports that
+    /// has no open reminder of the same type. Each reminder is due on the
+    /// service's due date and recurs at its interval; overdue services get
+    /// `High` priority. Returns the new ids. `provider_id` must be allowed to
+    /// open reminders for the patient, as in `create_reminder`.
+    pub fn create_preventive_reminders(
+        env: Env,
+        patient_id: Address,
+        provider_id: Address,
+        age: u32,
+        gender: Symbol,
+        risk_factors: Vec<Symbol>,
+        last_completed: Vec<ServiceCompletion>,
+    ) -> Result<Vec<u64>, Error> {
+        Self::require_reminder_provider(&env, &patient_id, &provider_id)?;
+        let services = Self::check_preventive_care(
+            env.clone(),
+            patient_id.clone(),
+            age,
+            gender,
+            risk_factors,
+            last_completed,
+        )?;
+        let open_ids: Vec<u64> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::PatientReminders(patient_id.clone()))
+            .unwrap_or(Vec::new(&env));
+
+        let mut created = Vec::new(&env);
+        for due in services.iter() {
+            let already_open = open_ids.iter().any(|id| {
+                Self::get_reminder(env.clone(), patient_id.clone(), id)
+                    .is_ok_and(|r| r.reminder_type == due.service)
+            });
+            if already_open {
+                continue;
+            }
+            let priority = match due.status {
+                PreventiveStatus::Due => "Normal",
+                PreventiveStatus::Overdue => "High",
+            };
+            created.push_back(Self::open_reminder(
+                &env,
+                &patient_id,
+                &provider_id,
+                due.service,
+                Symbol::new(&env, priority),
+                due.due_date,
+                due.interval,
+            )?);
+        }
+        Ok(created)
+    }
+
+    /// Publish a new version of the preventive care schedule; earlier versions
+    /// stay readable. Returns the new version, starting at 1.
+    pub fn set_preventive_schedule(
+        env: Env,
+        admin: Address,
+        schedule: PreventiveSchedule,
+    ) -> Result<u32, Error> {
+        Self::require_admin(&env, &admin)?;
+        preventive::validate(&schedule)?;
+
+        let version = Self::preventive_schedule_version(&env).unwrap_or(0) + 1;
+        env.storage()
+            .persistent()
+            .set(&DataKey::PreventiveSchedule(version), &schedule);
+        env.storage()
+            .persistent()
+            .set(&DataKey::PreventiveScheduleVersion, &version);
+        Ok(version)
+    }
+
+    pub fn get_preventive_schedule(env: Env, version: u32) -> Result<PreventiveSchedule, Error> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::PreventiveSchedule(version))
+            .ok_or(Error::ScheduleNotFound)
+    }
+
+    pub fn get_preventive_schedule_version(env: Env) -> Result<u32, Error> {
+        Self::preventive_schedule_version(&env)
+    }
+
+    /// Services in the latest preventive schedule that the patient is due or
+    /// overdue for, given when they last completed each one.
+    ///
+    /// Fails with `ScheduleNotFound` before a schedule is published and
+    /// `InvalidInput` for completions dated in the future.
+    pub fn check_preventive_care(
+        env: Env,
+        _patient_id: Address,
+        age: u32,
+        gender: Symbol,
+        risk_factors: Vec<Symbol>,
+        last_completed: Vec<ServiceCompletion>,
+    ) -> Result<Vec<PreventiveDue>, Error> {
+        let version = Self::preventive_schedule_version(&env)?;
+        let schedule: PreventiveSchedule = env
+            .storage()
+            .persistent()
+            .get(&DataKey::PreventiveSchedule(version))
+            .ok_or(Error::ScheduleNotFound)?;
+        preventive::validate_history(&env, &last_completed)?;
+
+        Ok(preventive::due_services(
+            &env,
+            &schedule,
+            version,
+            age,
+            &gender,
+            &risk_factors,
+            &last_completed,
+        ))
+    }
+}
+
+impl ClinicalGuidelineContract {
+    fn open_reminder(
+        env: &Env,
+        patient_id: &Address,
+        provider_id: &Address,
+        reminder_type: Symbol,
+        priority: Symbol,
+        due_date: u64,
+        recurrence: Recurrence,
+    ) -> Result<u64, Error> {
+        let patient_key = DataKey::PatientReminders(patient_id.clone());
+        let mut patient_open: Vec<u64> = env
+            .storage()
+            .persistent()
+            .get(&patient_key)
+            .unwrap_or(Vec::new(env));
+        let provider_key = DataKey::ProviderReminders(provider_id.clone());
+        let mut provider_open: Vec<(Address, u64)> = env
+            .storage()
+            .persistent()
+            .get(&provider_key)
+            .unwrap_or(Vec::new(env));
+        if patient_open.len() >= MAX_OPEN_REMINDERS
+            || provider_open.len() >= MAX_PROVIDER_REMINDERS
+        {
+            return Err(Error::TooManyReminders);
+        }
 
         let counter_key = DataKey::ReminderCounter(patient_id.clone());
         let reminder_id: u64 = env
@@ -315,11 +873,20 @@ impl ClinicalGuidelineContract {
             .get(&counter_key)
             .unwrap_or(1);
 
+        let now = env.ledger().timestamp();
         let reminder = Reminder {
             reminder_id,
             patient_id: patient_id.clone(),
+            provider_id: provider_id.clone(),
+            reminder_type,
+            priority,
             due_date,
-            created_at: env.ledger().timestamp(),
+            scheduled_for: due_date,
+            recurrence,
+            status: ReminderStatus::Pending,
+            created_at: now,
+            updated_at: now,
+            next_reminder_id: None,
         };
 
         env.storage()
@@ -329,37 +896,152 @@ impl ClinicalGuidelineContract {
             .persistent()
             .set(&counter_key, &(reminder_id + 1));
 
+        patient_open.push_back(reminder_id);
+        env.storage().persistent().set(&patient_key, &patient_open);
+        provider_open.push_back((patient_id.clone(), reminder_id));
+        env.storage().persistent().set(&provider_key, &provider_open);
+
         Ok(reminder_id)
     }
 
-    pub fn get_reminder(
-        env: Env,
-        patient_id: Address,
+    fn require_reminder_provider(
+        env: &Env,
+        patient_id: &Address,
+        provider_id: &Address,
+    ) -> Result<(), Error> {
+        provider_id.require_auth();
+        if !Self::is_reminder_provider(env.clone(), patient_id.clone(), provider_id.clone()) {
+            return Err(Error::NotAuthorized);
+        }
+        Ok(())
+    }
+
+    /// Load a reminder the caller (its patient or provider) may act on.
+    fn load_open_reminder(
+        env: &Env,
+        caller: &Address,
+        patient_id: &Address,
         reminder_id: u64,
     ) -> Result<Reminder, Error> {
-        env.storage()
+        caller.require_auth();
+        let reminder: Reminder = env
+            .storage()
             .persistent()
-            .get(&DataKey::Reminder(patient_id, reminder_id))
-            .ok_or(Error::GuidelineNotFound)
+            .get(&DataKey::Reminder(patient_id.clone(), reminder_id))
+            .ok_or(Error::ReminderNotFound)?;
+        if *caller != reminder.patient_id && *caller != reminder.provider_id {
+            return Err(Error::NotAuthorized);
+        }
+        if !reminder.status.is_open() {
+            return Err(Error::ReminderClosed);
+        }
+        Ok(reminder)
     }
 
-    pub fn check_preventive_care(
-        env: Env,
-        _patient_id: Address,
-        age: u32,
-        _gender: Symbol,
-        _risk_factors: Vec<Symbol>,
-    ) -> Result<Vec<Symbol>, Error> {
-        let mut alerts = Vec::new(&env);
+    /// Set a terminal status and drop the reminder from the open indexes.
+    /// The caller persists the reminder.
+    fn close_reminder(env: &Env, reminder: &mut Reminder, status: ReminderStatus) {
+        reminder.status = status;
+        reminder.updated_at = env.ledger().timestamp();
 
-        if age > 50 {
-            alerts.push_back(Symbol::new(&env, "Screening_A"));
+        let patient_key = DataKey::PatientReminders(reminder.patient_id.clone());
+        let mut patient_open: Vec<u64> = env
+            .storage()
+            .persistent()
+            .get(&patient_key)
+            .unwrap_or(Vec::new(env));
+        if let Some(i) = patient_open.first_index_of(reminder.reminder_id) {
+            patient_open.remove(i);
         }
-        if age > 20 {
-            alerts.push_back(Symbol::new(&env, "Regular_Checkup"));
+        env.storage().persistent().set(&patient_key, &patient_open);
+
+        let provider_key = DataKey::ProviderReminders(reminder.provider_id.clone());
+        let mut provider_open: Vec<(Address, u64)> = env
+            .storage()
+            .persistent()
+            .get(&provider_key)
+            .unwrap_or(Vec::new(env));
+        if let Some(i) =
+            provider_open.first_index_of((reminder.patient_id.clone(), reminder.reminder_id))
+        {
+            provider_open.remove(i);
+        }
+        env.storage().persistent().set(&provider_key, &provider_open);
+    }
+
+    /// Load `refs` due in `[from, to]`, insertion-sorted by due date.
+    fn due_reminders(env: &Env, refs: &Vec<(Address, u64)>, from: u64, to: u64) -> Vec<Reminder> {
+        let mut due: Vec<Reminder> = Vec::new(env);
+        for (patient_id, reminder_id) in refs.iter() {
+            let Some(reminder) = env
+                .storage()
+                .persistent()
+                .get::<DataKey, Reminder>(&DataKey::Reminder(patient_id, reminder_id))
+            else {
+                continue;
+            };
+            if reminder.due_date < from || reminder.due_date > to {
+                continue;
+            }
+            let pos = due
+                .iter()
+                .position(|r| r.due_date > reminder.due_date)
+                .unwrap_or(due.len() as usize);
+            due.insert(pos as u32, reminder);
+        }
+        due
+    }
+
+    fn require_admin(env: &Env, admin: &Address) -> Result<(), Error> {
+        admin.require_auth();
+        let stored_admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(Error::NotAuthorized)?;
+        if *admin != stored_admin {
+            return Err(Error::NotAuthorized);
         }
+        Ok(())
+    }
+
+    fn dosing_rule_version(env: &Env, medication: &String) -> Result<u32, Error> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::DosingRuleVersion(medication.clone()))
+            .ok_or(Error::DosingRuleNotFound)
+    }
+
+    fn preventive_schedule_version(env: &Env) -> Result<u32, Error> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::PreventiveScheduleVersion)
+            .ok_or(Error::ScheduleNotFound)
+    }
+
+    fn store_dosing_rule(env: &Env, medication: &String, version: u32, rule: &DosingRule) {
+        env.storage()
+            .persistent()
+            .set(&DataKey::DosingRule(medication.clone(), version), rule);
+        env.storage()
+            .persistent()
+            .set(&DataKey::DosingRuleVersion(medication.clone()), &version);
+    }
 
-        Ok(alerts)
+    /// Store `criteria` as `version` of the guideline and return its hash.
+    fn store_criteria(
+        env: &Env,
+        guideline_id: &String,
+        version: u32,
+        criteria: &GuidelineCriteria,
+    ) -> BytesN<32> {
+        env.storage().persistent().set(
+            &DataKey::GuidelineCriteria(guideline_id.clone(), version),
+            criteria,
+        );
+        env.crypto()
+            .sha256(&criteria.clone().to_xdr(env))
+            .into()
     }
 }
 
@@ -0,0 +1,178 @@
+//! Preventive care schedule.
+//!
+//! The admin publishes a versioned [`PreventiveSchedule`], an ordered list of
+//! [`PreventiveService`] entries. Each entry has:
+//! * an age window,
+//! * an optional sex,
+//! * risk factors, of which the patient must have at least one (an empty list
+//!   means every patient is eligible),
+//! * a repeat interval and a grace period.
+//!
+//! A service may appear more than once, e.g. a shorter colonoscopy interval
+//! for patients with a family history ahead of the general-population entry.
+//! The first eligible entry for a service applies.
+//!
+//! ## Due dates
+//! A service is due one interval after its last completion, using the same
+//! calendar rules as recurring reminders, and is `Overdue` once `grace_days`
+//! have passed since then. A one-off service (`Recurrence::None`) is never due
+//! again once completed. A service never completed is due now but never
+//! overdue, since the contract cannot tell when the patient became eligible.
+//! Services due later are not reported.
+
+use soroban_sdk::{Env, Symbol, Vec, contracttype};
+
+use crate::Error;
+use crate::reminders::{self, Recurrence};
+
+/// Most entries in one schedule.
+pub const MAX_SERVICES: u32 = 32;
+/// Most risk factors one entry may list.
+pub const MAX_SERVICE_RISK_FACTORS: u32 = 8;
+/// Longest grace period an entry may use.
+pub const MAX_GRACE_DAYS: u32 = 3650;
+/// Highest age bound an entry may use.
+pub const MAX_AGE_YEARS: u32 = 150;
+
+const SECS_PER_DAY: u64 = 86_400;
+
+/// Service eligibility and interval for patients with
+/// `min_age_years <= age < max_age_years`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PreventiveService {
+    pub service: Symbol,
+    pub min_age_years: u32,
+    pub max_age_years: u32,
+    /// `None` applies to every sex.
+    pub sex: Option<Symbol>,
+    /// The patient must have at least one; empty applies to everyone.
+    pub risk_factors: Vec<Symbol>,
+    pub interval: Recurrence,
+    pub grace_days: u32,
+}
+
+/// Ordered service list; see the module docs.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PreventiveSchedule {
+    pub services: Vec<PreventiveService>,
+}
+
+/// When the patient last completed `service`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ServiceCompletion {
+    pub service: Symbol,
+    pub completed_at: u64,
+}
+
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum PreventiveStatus {
+    Due,
+    Overdue,
+}
+
+/// A service the patient is due for.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PreventiveDue {
+    pub service: Symbol,
+    /// Schedule version the service was checked against.
+    pub schedule_version: u32,
+    pub due_date: u64,
+    pub status: PreventiveStatus,
+    pub interval: Recurrence,
+    /// `None` when the patient has no recorded completion.
+    pub last_completed: Option<u64>,
+}
+
+/// Reject schedules with missing, malformed or out-of-range entries.
+pub fn validate(schedule: &PreventiveSchedule) -> Result<(), Error> {
+    if schedule.services.is_empty() || schedule.services.len() > MAX_SERVICES {
+        return Err(Error::InvalidSchedule);
+    }
+    for entry in schedule.services.iter() {
+        if entry.min_age_years >= entry.max_age_years
+            || entry.max_age_years > MAX_AGE_YEARS
+            || entry.risk_factors.len() > MAX_SERVICE_RISK_FACTORS
+            || entry.grace_days > MAX_GRACE_DAYS
+            || !reminders::valid_recurrence(&entry.interval)
+        {
+            return Err(Error::InvalidSchedule);
+        }
+    }
+    Ok(())
+}
+
+/// Reject completion histories that are too long or dated in the future.
+pub fn validate_history(env: &Env, history: &Vec<ServiceCompletion>) -> Result<(), Error> {
+    let now = env.ledger().timestamp();
+    if history.len() > MAX_SERVICES || history.iter().any(|c| c.completed_at > now) {
+        return Err(Error::InvalidInput);
+    }
+    Ok(())
+}
+
+/// Services in `schedule` the patient is due or overdue for now, in schedule
+/// order.
+pub fn due_services(
+    env: &Env,
+    schedule: &PreventiveSchedule,
+    schedule_version: u32,
+    age: u32,
+    sex: &Symbol,
+    risk_factors: &Vec<Symbol>,
+    history: &Vec<ServiceCompletion>,
+) -> Vec<PreventiveDue> {
+    let now = env.ledger().timestamp();
+    let mut due = Vec::new(env);
+    let mut seen: Vec<Symbol> = Vec::new(env);
+    for entry in schedule.services.iter() {
+        if seen.contains(&entry.service) || !eligible(&entry, age, sex, risk_factors) {
+            continue;
+        }
+        seen.push_back(entry.service.clone());
+
+        let last_completed = history
+            .iter()
+            .filter(|c| c.service == entry.service)
+            .map(|c| c.completed_at)
+            .max();
+        let due_date = match last_completed {
+            None => now,
+            Some(at) => match reminders::next_occurrence(at, &entry.interval) {
+                Some(next) => next,
+                None => continue,
+            },
+        };
+        if due_date > now {
+            continue;
+        }
+
+        let overdue_at = due_date + entry.grace_days as u64 * SECS_PER_DAY;
+        let status = if now >= overdue_at && last_completed.is_some() {
+            PreventiveStatus::Overdue
+        } else {
+            PreventiveStatus::Due
+        };
+        due.push_back(PreventiveDue {
+            service: entry.service,
+            schedule_version,
+            due_date,
+            status,
+            interval: entry.interval,
+            last_completed,
+        });
+    }
+    due
+}
+
+fn eligible(entry: &PreventiveService, age: u32, sex: &Symbol, risk_factors: &Vec<Symbol>) -> bool {
+    entry.min_age_years <= age
+        && age < entry.max_age_years
+        && entry.sex.as_ref().is_none_or(|s| s == sex)
+        && (entry.risk_factors.is_empty()
+            || entry.risk_factors.iter().any(|r| risk_factors.contains(&r)))
+}
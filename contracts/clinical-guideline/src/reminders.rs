@@ -0,0 +1,116 @@
+//! Reminder recurrence and lifecycle types.
+//!
+//! A reminder is `Pending` until it is completed or dismissed. Snoozing moves
+//! its `due_date` and marks it `Snoozed`; it stays open. Completing a recurring
+//! reminder opens the next occurrence. That occurrence is scheduled from the
+//! completed one's `scheduled_for`, not its snoozed `due_date`, so snoozes do
+//! not drift the series. Dismissing ends the series.
+//!
+//! Month and year steps use the UTC calendar and keep the time of day. A day
+//! past the end of the target month is clamped to its last day, so Jan 31 is
+//! followed by Feb 28, and later occurrences step from the clamped date.
+
+use soroban_sdk::contracttype;
+
+const SECS_PER_DAY: u64 = 86_400;
+
+/// Longest interval a recurrence may use, per unit.
+pub const MAX_RECURRENCE_DAYS: u32 = 3650;
+pub const MAX_RECURRENCE_MONTHS: u32 = 120;
+pub const MAX_RECURRENCE_YEARS: u32 = 10;
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Recurrence {
+    None,
+    Days(u32),
+    Months(u32),
+    Years(u32),
+}
+
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ReminderStatus {
+    Pending,
+    Snoozed,
+    Completed,
+    Dismissed,
+}
+
+impl ReminderStatus {
+    pub fn is_open(self) -> bool {
+        matches!(self, ReminderStatus::Pending | ReminderStatus::Snoozed)
+    }
+}
+
+/// Whether `recurrence` has an interval within the bounds above.
+pub fn valid_recurrence(recurrence: &Recurrence) -> bool {
+    match *recurrence {
+        Recurrence::None => true,
+        Recurrence::Days(n) => (1..=MAX_RECURRENCE_DAYS).contains(&n),
+        Recurrence::Months(n) => (1..=MAX_RECURRENCE_MONTHS).contains(&n),
+        Recurrence::Years(n) => (1..=MAX_RECURRENCE_YEARS).contains(&n),
+    }
+}
+
+/// The occurrence after `scheduled_for`, or `None` for one-off reminders.
+pub fn next_occurrence(scheduled_for: u64, recurrence: &Recurrence) -> Option<u64> {
+    match *recurrence {
+        Recurrence::None => None,
+        Recurrence::Days(n) => Some(scheduled_for + n as u64 * SECS_PER_DAY),
+        Recurrence::Months(n) => Some(add_months(scheduled_for, n)),
+        Recurrence::Years(n) => Some(add_months(scheduled_for, n * 12)),
+    }
+}
+
+/// Add `months` calendar months to a UTC timestamp, clamping the day.
+pub fn add_months(timestamp: u64, months: u32) -> u64 {
+    let days = timestamp / SECS_PER_DAY;
+    let time_of_day = timestamp % SECS_PER_DAY;
+    let (year, month, day) = civil_from_days(days);
+
+    let month_index = year as u64 * 12 + (month as u64 - 1) + months as u64;
+    let year = (month_index / 12) as u32;
+    let month = (month_index % 12) as u32 + 1;
+    let day = day.min(days_in_month(year, month));
+    days_from_civil(year, month, day) * SECS_PER_DAY + time_of_day
+}
+
+fn is_leap_year(year: u32) -> bool {
+    (year.is_multiple_of(4) && !year.is_multiple_of(100)) || year.is_multiple_of(400)
+}
+
+fn days_in_month(year: u32, month: u32) -> u32 {
+    match month {
+        2 if is_leap_year(year) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+/// (year, month, day) for days since 1970-01-01.
+fn civil_from_days(days: u64) -> (u32, u32, u32) {
+    // Shift the epoch to 0000-03-01 so leap days fall at the end of each era year.
+    let z = days + 719_468;
+    let era = z / 146_097;
+    let doe = z % 146_097;
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = (doy - (153 * mp + 2) / 5 + 1) as u32;
+    let month = (if mp < 10 { mp + 3 } else { mp - 9 }) as u32;
+    let year = (yoe + era * 400) as u32 + u32::from(month <= 2);
+    (year, month, day)
+}
+
+/// Days since 1970-01-01 for a (year, month, day) on or after that date.
+fn days_from_civil(year: u32, month: u32, day: u32) -> u64 {
+    let year = (year - u32::from(month <= 2)) as u64;
+    let era = year / 400;
+    let yoe = year % 400;
+    let mp = (month as u64 + 9) % 12;
+    let doy = (153 * mp + 2) / 5 + day as u64 - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    era * 146_097 + doe - 719_468
+}
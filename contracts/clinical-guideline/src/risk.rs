@@ -0,0 +1,458 @@
+//! Validated clinical risk calculators.
+//!
+//! Each calculator takes a typed input struct, wrapped in [`RiskInput`], and
+//! returns an integer score with a [`RiskBand`] from the published
+//! thresholds. All arithmetic is integer or fixed-point:
+//!
+//! | Calculator | Range | Bands |
+//! |------------|-------|-------|
+//! | CHA₂DS₂-VASc | 0–9 | by score excluding the sex point: 0 low, 1 moderate, ≥2 high |
+//! | HAS-BLED | 0–9 | 0–1 low, 2 moderate, ≥3 high |
+//! | Wells DVT | −2–9 | ≤0 low, 1–2 moderate, ≥3 high |
+//! | Wells PE | 0–125 (tenths) | <20 low, 20–60 moderate, >60 high |
+//! | CURB-65 | 0–5 | 0–1 low, 2 moderate, ≥3 high |
+//! | MELD | 6–40 | <10 low, 10–19 moderate, 20–29 high, ≥30 very high |
+//! | Braden | 6–23 | ≥19 minimal, 15–18 low, 13–14 moderate, 10–12 high, ≤9 very high |
+//!
+//! Bump a calculator's version in [`calculator_version`] whenever its
+//! weights, thresholds or interpretation change, so stored assessments stay
+//! attributable to the logic that produced them.
+
+use soroban_sdk::{Env, Symbol, contracttype};
+
+use crate::Error;
+
+/// Highest age a calculator input may carry.
+pub const MAX_AGE_YEARS: u32 = 150;
+
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum RiskBand {
+    Minimal,
+    Low,
+    Moderate,
+    High,
+    VeryHigh,
+}
+
+/// Stroke risk in atrial fibrillation.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Cha2ds2VascInput {
+    pub age_years: u32,
+    pub female: bool,
+    pub congestive_heart_failure: bool,
+    pub hypertension: bool,
+    pub diabetes: bool,
+    pub stroke_or_tia: bool,
+    pub vascular_disease: bool,
+}
+
+/// Major bleeding risk on anticoagulation.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct HasBledInput {
+    pub age_years: u32,
+    /// Uncontrolled, systolic > 160 mmHg.
+    pub hypertension: bool,
+    pub abnormal_renal_function: bool,
+    pub abnormal_liver_function: bool,
+    pub stroke: bool,
+    pub bleeding_history: bool,
+    pub labile_inr: bool,
+    /// Antiplatelets or NSAIDs.
+    pub drugs: bool,
+    pub alcohol: bool,
+}
+
+/// Pre-test probability of deep vein thrombosis.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct WellsDvtInput {
+    pub active_cancer: bool,
+    pub paralysis_or_recent_cast: bool,
+    pub bedridden_or_recent_surgery: bool,
+    pub deep_vein_tenderness: bool,
+    pub entire_leg_swollen: bool,
+    pub calf_swelling_over_3cm: bool,
+    pub pitting_edema: bool,
+    pub collateral_superficial_veins: bool,
+    pub previous_dvt: bool,
+    pub alternative_diagnosis_likely: bool,
+}
+
+/// Pre-test probability of pulmonary embolism.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct WellsPeInput {
+    pub clinical_signs_of_dvt: bool,
+    pub pe_most_likely_diagnosis: bool,
+    pub heart_rate_over_100: bool,
+    pub immobilized_or_recent_surgery: bool,
+    pub previous_dvt_or_pe: bool,
+    pub hemoptysis: bool,
+    pub malignancy: bool,
+}
+
+/// Community-acquired pneumonia severity.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Curb65Input {
+    pub confusion: bool,
+    /// Blood urea in tenths of a mmol/L.
+    pub urea_mmol_l_x10: u32,
+    pub respiratory_rate: u32,
+    pub systolic_bp: u32,
+    pub diastolic_bp: u32,
+    pub age_years: u32,
+}
+
+/// End-stage liver disease severity (UNOS MELD, pre-2016).
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct MeldInput {
+    /// Hundredths of a mg/dL.
+    pub bilirubin_mg_dl_x100: u32,
+    /// Hundredths.
+    pub inr_x100: u32,
+    /// Hundredths of a mg/dL.
+    pub creatinine_mg_dl_x100: u32,
+    /// Dialysis at least twice in the past week.
+    pub dialysis: bool,
+}
+
+/// Pressure injury risk; each subscale is scored 1–4 (friction 1–3).
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BradenInput {
+    pub sensory_perception: u32,
+    pub moisture: u32,
+    pub activity: u32,
+    pub mobility: u32,
+    pub nutrition: u32,
+    pub friction_shear: u32,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum RiskInput {
+    Cha2ds2Vasc(Cha2ds2VascInput),
+    HasBled(HasBledInput),
+    WellsDvt(WellsDvtInput),
+    WellsPe(WellsPeInput),
+    Curb65(Curb65Input),
+    Meld(MeldInput),
+    Braden(BradenInput),
+}
+
+/// A scored input.
+pub struct Assessment {
+    pub score: i32,
+    /// `score` is in units of `1 / score_scale`.
+    pub score_scale: u32,
+    pub band: RiskBand,
+    pub interpretation: &'static str,
+}
+
+/// Symbol naming the calculator `input` is for.
+pub fn calculator_name(env: &Env, input: &RiskInput) -> Symbol {
+    let name = match input {
+        RiskInput::Cha2ds2Vasc(_) => "CHA2DS2_VASc",
+        RiskInput::HasBled(_) => "HAS_BLED",
+        RiskInput::WellsDvt(_) => "WELLS_DVT",
+        RiskInput::WellsPe(_) => "WELLS_PE",
+        RiskInput::Curb65(_) => "CURB_65",
+        RiskInput::Meld(_) => "MELD",
+        RiskInput::Braden(_) => "BRADEN",
+    };
+    Symbol::new(env, name)
+}
+
+/// Version of the scoring logic for `input`'s calculator.
+pub fn calculator_version(input: &RiskInput) -> u32 {
+    match input {
+        RiskInput::Cha2ds2Vasc(_)
+        | RiskInput::HasBled(_)
+        | RiskInput::WellsDvt(_)
+        | RiskInput::WellsPe(_)
+        | RiskInput::Curb65(_)
+        | RiskInput::Meld(_)
+        | RiskInput::Braden(_) => 1,
+    }
+}
+
+/// Score `input`, rejecting out-of-range values with `InvalidInput`.
+pub fn assess(input: &RiskInput) -> Result<Assessment, Error> {
+    match input {
+        RiskInput::Cha2ds2Vasc(i) => cha2ds2_vasc(i),
+        RiskInput::HasBled(i) => has_bled(i),
+        RiskInput::WellsDvt(i) => Ok(wells_dvt(i)),
+        RiskInput::WellsPe(i) => Ok(wells_pe(i)),
+        RiskInput::Curb65(i) => curb_65(i),
+        RiskInput::Meld(i) => meld(i),
+        RiskInput::Braden(i) => braden(i),
+    }
+}
+
+fn points(flags: &[(bool, i32)]) -> i32 {
+    flags.iter().filter(|(set, _)| *set).map(|(_, p)| p).sum()
+}
+
+fn cha2ds2_vasc(i: &Cha2ds2VascInput) -> Result<Assessment, Error> {
+    if i.age_years > MAX_AGE_YEARS {
+        return Err(Error::InvalidInput);
+    }
+    let age_points = if i.age_years >= 75 {
+        2
+    } else if i.age_years >= 65 {
+        1
+    } else {
+        0
+    };
+    let clinical = age_points
+        + points(&[
+            (i.congestive_heart_failure, 1),
+            (i.hypertension, 1),
+            (i.diabetes, 1),
+            (i.stroke_or_tia, 2),
+            (i.vascular_disease, 1),
+        ]);
+    let (band, interpretation) = match clinical {
+        0 => (
+            RiskBand::Low,
+            "Low stroke risk; anticoagulation not recommended",
+        ),
+        1 => (RiskBand::Moderate, "Consider oral anticoagulation"),
+        _ => (RiskBand::High, "Oral anticoagulation recommended"),
+    };
+    Ok(Assessment {
+        score: clinical + points(&[(i.female, 1)]),
+        score_scale: 1,
+        band,
+        interpretation,
+    })
+}
+
+fn has_bled(i: &HasBledInput) -> Result<Assessment, Error> {
+    if i.age_years > MAX_AGE_YEARS {
+        return Err(Error::InvalidInput);
+    }
+    let score = points(&[
+        (i.hypertension, 1),
+        (i.abnormal_renal_function, 1),
+        (i.abnormal_liver_function, 1),
+        (i.stroke, 1),
+        (i.bleeding_history, 1),
+        (i.labile_inr, 1),
+        (i.age_years > 65, 1),
+        (i.drugs, 1),
+        (i.alcohol, 1),
+    ]);
+    let (band, interpretation) = match score {
+        0..=1 => (RiskBand::Low, "Low bleeding risk"),
+        2 => (
+            RiskBand::Moderate,
+            "Moderate bleeding risk; address modifiable factors",
+        ),
+        _ => (
+            RiskBand::High,
+            "High bleeding risk; address modifiable factors and review closely",
+        ),
+    };
+    Ok(Assessment {
+        score,
+        score_scale: 1,
+        band,
+        interpretation,
+    })
+}
+
+fn wells_dvt(i: &WellsDvtInput) -> Assessment {
+    let score = points(&[
+        (i.active_cancer, 1),
+        (i.paralysis_or_recent_cast, 1),
+        (i.bedridden_or_recent_surgery, 1),
+        (i.deep_vein_tenderness, 1),
+        (i.entire_leg_swollen, 1),
+        (i.calf_swelling_over_3cm, 1),
+        (i.pitting_edema, 1),
+        (i.collateral_superficial_veins, 1),
+        (i.previous_dvt, 1),
+        (i.alternative_diagnosis_likely, -2),
+    ]);
+    let (band, interpretation) = if score <= 0 {
+        (RiskBand::Low, "Low DVT probability; D-dimer testing")
+    } else if score <= 2 {
+        (
+            RiskBand::Moderate,
+            "Moderate DVT probability; D-dimer or compression ultrasound",
+        )
+    } else {
+        (
+            RiskBand::High,
+            "High DVT probability; compression ultrasound",
+        )
+    };
+    Assessment {
+        score,
+        score_scale: 1,
+        band,
+        interpretation,
+    }
+}
+
+fn wells_pe(i: &WellsPeInput) -> Assessment {
+    let score = points(&[
+        (i.clinical_signs_of_dvt, 30),
+        (i.pe_most_likely_diagnosis, 30),
+        (i.heart_rate_over_100, 15),
+        (i.immobilized_or_recent_surgery, 15),
+        (i.previous_dvt_or_pe, 15),
+        (i.hemoptysis, 10),
+        (i.malignancy, 10),
+    ]);
+    let (band, interpretation) = if score < 20 {
+        (RiskBand::Low, "Low PE probability; D-dimer testing")
+    } else if score <= 60 {
+        (
+            RiskBand::Moderate,
+            "Moderate PE probability; D-dimer or CT pulmonary angiography",
+        )
+    } else {
+        (
+            RiskBand::High,
+            "High PE probability; CT pulmonary angiography",
+        )
+    };
+    Assessment {
+        score,
+        score_scale: 10,
+        band,
+        interpretation,
+    }
+}
+
+fn curb_65(i: &Curb65Input) -> Result<Assessment, Error> {
+    if i.age_years > MAX_AGE_YEARS || i.systolic_bp == 0 || i.diastolic_bp == 0 {
+        return Err(Error::InvalidInput);
+    }
+    let score = points(&[
+        (i.confusion, 1),
+        (i.urea_mmol_l_x10 > 70, 1),
+        (i.respiratory_rate >= 30, 1),
+        (i.systolic_bp < 90 || i.diastolic_bp <= 60, 1),
+        (i.age_years >= 65, 1),
+    ]);
+    let (band, interpretation) = match score {
+        0..=1 => (RiskBand::Low, "Low severity; consider outpatient treatment"),
+        2 => (
+            RiskBand::Moderate,
+            "Moderate severity; consider short inpatient stay",
+        ),
+        _ => (
+            RiskBand::High,
+            "High severity; hospitalize and assess for ICU",
+        ),
+    };
+    Ok(Assessment {
+        score,
+        score_scale: 1,
+        band,
+        interpretation,
+    })
+}
+
+/// `MELD = round(10 × (0.957 ln Cr + 0.378 ln bili + 1.120 ln INR + 0.643))`,
+/// with each value floored at 1.0, creatinine capped at 4.0 (or set to 4.0 on
+/// dialysis) and the score capped at 40.
+fn meld(i: &MeldInput) -> Result<Assessment, Error> {
+    if i.bilirubin_mg_dl_x100 == 0 || i.inr_x100 == 0 || i.creatinine_mg_dl_x100 == 0 {
+        return Err(Error::InvalidInput);
+    }
+    let bilirubin = i.bilirubin_mg_dl_x100.max(100);
+    let inr = i.inr_x100.max(100);
+    let creatinine = if i.dialysis {
+        400
+    } else {
+        i.creatinine_mg_dl_x100.clamp(100, 400)
+    };
+
+    // Coefficients in thousandths and ln in millionths give billionths.
+    let total =
+        957 * ln_micro(creatinine) + 378 * ln_micro(bilirubin) + 1120 * ln_micro(inr) + 643_000_000;
+    let score = ((total + 50_000_000) / 100_000_000).min(40) as i32;
+
+    let (band, interpretation) = match score {
+        ..=9 => (RiskBand::Low, "3-month mortality about 1.9%"),
+        10..=19 => (RiskBand::Moderate, "3-month mortality about 6.0%"),
+        20..=29 => (RiskBand::High, "3-month mortality about 19.6%"),
+        _ => (RiskBand::VeryHigh, "3-month mortality 52.6% or higher"),
+    };
+    Ok(Assessment {
+        score,
+        score_scale: 1,
+        band,
+        interpretation,
+    })
+}
+
+fn braden(i: &BradenInput) -> Result<Assessment, Error> {
+    let four_point = [
+        i.sensory_perception,
+        i.moisture,
+        i.activity,
+        i.mobility,
+        i.nutrition,
+    ];
+    if four_point.iter().any(|s| !(1..=4).contains(s)) || !(1..=3).contains(&i.friction_shear) {
+        return Err(Error::InvalidInput);
+    }
+    let score = (four_point.iter().sum::<u32>() + i.friction_shear) as i32;
+    let (band, interpretation) = match score {
+        19.. => (RiskBand::Minimal, "No pressure injury risk"),
+        15..=18 => (RiskBand::Low, "Mild pressure injury risk"),
+        13..=14 => (RiskBand::Moderate, "Moderate pressure injury risk"),
+        10..=12 => (RiskBand::High, "High pressure injury risk"),
+        _ => (RiskBand::VeryHigh, "Severe pressure injury risk"),
+    };
+    Ok(Assessment {
+        score,
+        score_scale: 1,
+        band,
+        interpretation,
+    })
+}
+
+const FIXED_ONE: i128 = 1_000_000_000;
+const LN_2: i128 = 693_147_181;
+
+/// `ln(x_hundredths / 100)` in millionths, for `x_hundredths >= 1`.
+///
+/// Normalizes to `m · 2^k` with `m` in `[1, 2)`, then sums the series
+/// `ln(m) = 2 · Σ y^(2n+1) / (2n+1)` with `y = (m − 1) / (m + 1) <= 1/3`.
+pub fn ln_micro(x_hundredths: u32) -> i64 {
+    let mut m = x_hundredths as i128 * FIXED_ONE / 100;
+    let mut k: i128 = 0;
+    while m >= 2 * FIXED_ONE {
+        m /= 2;
+        k += 1;
+    }
+    while m < FIXED_ONE {
+        m *= 2;
+        k -= 1;
+    }
+
+    let y = (m - FIXED_ONE) * FIXED_ONE / (m + FIXED_ONE);
+    let y2 = y * y / FIXED_ONE;
+    let mut term = y;
+    let mut sum = 0i128;
+    let mut n = 1i128;
+    while term != 0 {
+        sum += term / n;
+        term = term * y2 / FIXED_ONE;
+        n += 2;
+    }
+    let ln = 2 * sum + k * LN_2;
+    // Round billionths to millionths.
+    ((ln + if ln >= 0 { 500 } else { -500 }) / 1000) as i64
+}
@@ -2,73 +2,625 @@
 #![allow(deprecated)]
 use super::*;
 use soroban_sdk::{
-    Address, BytesN, Env, String, Symbol, Vec, testutils::Address as _, testutils::Ledger,
+    Address, BytesN, Env, String, Symbol, Vec, testutils::Address as _, testutils::Ledger, vec,
+    xdr::ToXdr,
 };
 
+fn setup_guidelines(env: &Env) -> (ClinicalGuidelineContractClient<'_>, Address) {
+    let contract_id = env.register(ClinicalGuidelineContract, ());
+    let client = ClinicalGuidelineContractClient::new(env, &contract_id);
+    let admin = Address::generate(env);
+    env.mock_all_auths();
+    client.initialize(&admin);
+    (client, admin)
+}
+
+fn rule(env: &Env, predicates: Vec<Predicate>, recommendation: &str) -> CriteriaRule {
+    CriteriaRule {
+        predicates,
+        recommendation: String::from_str(env, recommendation),
+        strength: Symbol::new(env, "Strong"),
+        alternatives: Vec::new(env),
+    }
+}
+
+/// Type 2 diabetes: insulin when HbA1c >= 9 %, metformin for other adults.
+fn diabetes_criteria(env: &Env) -> GuidelineCriteria {
+    let mut insulin = rule(
+        env,
+        vec![
+            env,
+            Predicate::HasCondition(String::from_str(env, "E11")),
+            Predicate::LabAtLeast(String::from_str(env, "4548-4"), 9000),
+        ],
+        "Start basal insulin",
+    );
+    insulin
+        .alternatives
+        .push_back(String::from_str(env, "GLP-1 receptor agonist"));
+    let mut metformin = rule(
+        env,
+        vec![
+            env,
+            Predicate::HasCondition(String::from_str(env, "E11")),
+            Predicate::AgeAtLeast(18),
+        ],
+        "Start metformin",
+    );
+    metformin.strength = Symbol::new(env, "Moderate");
+    GuidelineCriteria {
+        rules: vec![env, insulin, metformin],
+    }
+}
+
+fn facts(env: &Env, age_years: u32, conditions: &[&str], hba1c: Option<i64>) -> PatientFacts {
+    let mut codes = Vec::new(env);
+    for code in conditions {
+        codes.push_back(String::from_str(env, code));
+    }
+    let mut labs = Vec::new(env);
+    if let Some(value) = hba1c {
+        labs.push_back(LabValue {
+            code: String::from_str(env, "4548-4"),
+            value,
+        });
+    }
+    PatientFacts {
+        age_years,
+        sex: Symbol::new(env, "F"),
+        conditions: codes,
+        labs,
+    }
+}
+
 #[test]
 fn test_register_and_evaluate_guideline() {
     let env = Env::default();
-    let contract_id = env.register(ClinicalGuidelineContract, ());
-    let client = ClinicalGuidelineContractClient::new(&env, &contract_id);
+    let (client, admin) = setup_guidelines(&env);
+    let guideline_id = String::from_str(&env, "G123");
+    let evidence_level = Symbol::new(&env, "Level_A");
 
-    let admin = Address::generate(&env);
+    client.register_clinical_guideline(
+        &admin,
+        &guideline_id,
+        &String::from_str(&env, "Type 2 diabetes"),
+        &diabetes_criteria(&env),
+        &BytesN::from_array(&env, &[1u8; 32]),
+        &evidence_level,
+    );
+
+    let patient = Address::generate(&env);
+    let provider = Address::generate(&env);
+
+    // First rule: condition code matched by prefix and HbA1c over threshold.
+    let result = client.evaluate_guideline(
+        &patient,
+        &provider,
+        &guideline_id,
+        &facts(&env, 60, &["I10", "E11.9"], Some(9500)),
+    );
+    assert!(result.applicable);
+    assert_eq!(result.version, 1);
+    assert_eq!(result.matched_rule, Some(0));
+    assert_eq!(
+        result.recommendation,
+        String::from_str(&env, "Start basal insulin")
+    );
+    assert_eq!(result.strength, Symbol::new(&env, "Strong"));
+    assert_eq!(result.alternative_options.len(), 1);
+    assert_eq!(result.evidence_level, evidence_level);
+
+    // Second rule: HbA1c below threshold, or missing entirely.
+    for hba1c in [Some(7200), None] {
+        let result = client.evaluate_guideline(
+            &patient,
+            &provider,
+            &guideline_id,
+            &facts(&env, 60, &["E11.9"], hba1c),
+        );
+        assert_eq!(result.matched_rule, Some(1));
+        assert_eq!(result.strength, Symbol::new(&env, "Moderate"));
+    }
+
+    // No rule matches.
+    let result_fail = client.evaluate_guideline(
+        &patient,
+        &provider,
+        &guideline_id,
+        &facts(&env, 15, &["E10"], Some(9500)),
+    );
+    assert!(!result_fail.applicable);
+    assert_eq!(result_fail.matched_rule, None);
+    assert!(result_fail.alternative_options.is_empty());
+}
+
+#[test]
+fn test_update_guideline_versions_criteria() {
+    let env = Env::default();
+    let (client, admin) = setup_guidelines(&env);
     let guideline_id = String::from_str(&env, "G123");
-    let condition = String::from_str(&env, "Flu");
-    let criteria_hash = BytesN::from_array(&env, &[0u8; 32]);
+    let condition = String::from_str(&env, "Type 2 diabetes");
     let recommendation_hash = BytesN::from_array(&env, &[1u8; 32]);
     let evidence_level = Symbol::new(&env, "Level_A");
 
-    // Register guideline (Mocking auth)
-    env.mock_all_auths();
     client.register_clinical_guideline(
         &admin,
         &guideline_id,
         &condition,
-        &criteria_hash,
+        &diabetes_criteria(&env),
         &recommendation_hash,
         &evidence_level,
     );
+    let v1 = client.get_guideline(&guideline_id);
+
+    // v2 adds a default branch for everyone else.
+    let mut updated = diabetes_criteria(&env);
+    updated
+        .rules
+        .push_back(rule(&env, Vec::new(&env), "Routine screening"));
+    client.update_clinical_guideline(
+        &admin,
+        &guideline_id,
+        &condition,
+        &updated,
+        &recommendation_hash,
+        &evidence_level,
+    );
+
+    let v2 = client.get_guideline(&guideline_id);
+    assert_eq!(v2.version, 2);
+    assert_ne!(v1.criteria_hash, v2.criteria_hash);
+    assert_eq!(
+        client.get_guideline_criteria(&guideline_id, &1),
+        diabetes_criteria(&env)
+    );
+    assert_eq!(client.get_guideline_criteria(&guideline_id, &2), updated);
 
-    // Evaluate: Match
     let result = client.evaluate_guideline(
         &Address::generate(&env),
         &Address::generate(&env),
         &guideline_id,
-        &criteria_hash,
+        &facts(&env, 15, &[], None),
     );
-    assert!(result.applicable);
-    assert_eq!(result.evidence_level, evidence_level);
+    assert_eq!(result.version, 2);
+    assert_eq!(result.matched_rule, Some(2));
+}
 
-    // Evaluate: No Match (different hash)
-    let wrong_hash = BytesN::from_array(&env, &[2u8; 32]);
-    let result_fail = client.evaluate_guideline(
-        &Address::generate(&env),
-        &Address::generate(&env),
+#[test]
+fn test_register_rejects_malformed_criteria() {
+    let env = Env::default();
+    let (client, admin) = setup_guidelines(&env);
+    let code = || String::from_str(&env, "E11");
+
+    let empty = GuidelineCriteria {
+        rules: Vec::new(&env),
+    };
+    let default_first = GuidelineCriteria {
+        rules: vec![
+            &env,
+            rule(&env, Vec::new(&env), "Default"),
+            rule(
+                &env,
+                vec![&env, Predicate::HasCondition(code())],
+                "Never reached",
+            ),
+        ],
+    };
+    let empty_age_window = GuidelineCriteria {
+        rules: vec![
+            &env,
+            rule(
+                &env,
+                vec![&env, Predicate::AgeAtLeast(65), Predicate::AgeBelow(40)],
+                "Unsatisfiable",
+            ),
+        ],
+    };
+    let empty_code = GuidelineCriteria {
+        rules: vec![
+            &env,
+            rule(
+                &env,
+                vec![&env, Predicate::HasCondition(String::from_str(&env, ""))],
+                "Any",
+            ),
+        ],
+    };
+    let empty_text = GuidelineCriteria {
+        rules: vec![
+            &env,
+            rule(&env, vec![&env, Predicate::HasCondition(code())], ""),
+        ],
+    };
+    for criteria in [
+        empty,
+        default_first,
+        empty_age_window,
+        empty_code,
+        empty_text,
+    ] {
+        assert_eq!(
+            client.try_register_clinical_guideline(
+                &admin,
+                &String::from_str(&env, "BAD"),
+                &String::from_str(&env, "NA"),
+                &criteria,
+                &BytesN::from_array(&env, &[0u8; 32]),
+                &Symbol::new(&env, "B"),
+            ),
+            Err(Ok(Error::InvalidCriteria))
+        );
+    }
+}
+
+#[test]
+fn test_register_rejects_expensive_criteria() {
+    let env = Env::default();
+    let (client, admin) = setup_guidelines(&env);
+
+    let mut wide = Vec::new(&env);
+    for _ in 0..=criteria::MAX_RULE_PREDICATES {
+        wide.push_back(Predicate::AgeAtLeast(18));
+    }
+    let too_wide = GuidelineCriteria {
+        rules: vec![&env, rule(&env, wide, "Wide")],
+    };
+
+    let mut many = Vec::new(&env);
+    for _ in 0..=criteria::MAX_RULES {
+        many.push_back(rule(&env, vec![&env, Predicate::AgeAtLeast(18)], "Many"));
+    }
+    let too_many = GuidelineCriteria { rules: many };
+
+    let mut deep = Vec::new(&env);
+    let per_rule = criteria::MAX_RULE_PREDICATES;
+    for _ in 0..criteria::MAX_TOTAL_PREDICATES / per_rule + 1 {
+        let mut predicates = Vec::new(&env);
+        for _ in 0..per_rule {
+            predicates.push_back(Predicate::AgeAtLeast(18));
+        }
+        deep.push_back(rule(&env, predicates, "Deep"));
+    }
+    let too_deep = GuidelineCriteria { rules: deep };
+
+    for criteria in [too_wide, too_many, too_deep] {
+        assert_eq!(
+            client.try_register_clinical_guideline(
+                &admin,
+                &String::from_str(&env, "BIG"),
+                &String::from_str(&env, "NA"),
+                &criteria,
+                &BytesN::from_array(&env, &[0u8; 32]),
+                &Symbol::new(&env, "B"),
+            ),
+            Err(Ok(Error::CriteriaTooComplex))
+        );
+    }
+}
+
+#[test]
+fn test_evaluate_rejects_oversized_facts() {
+    let env = Env::default();
+    let (client, admin) = setup_guidelines(&env);
+    let guideline_id = String::from_str(&env, "G123");
+    client.register_clinical_guideline(
+        &admin,
         &guideline_id,
-        &wrong_hash,
+        &String::from_str(&env, "Type 2 diabetes"),
+        &diabetes_criteria(&env),
+        &BytesN::from_array(&env, &[1u8; 32]),
+        &Symbol::new(&env, "Level_A"),
     );
-    assert!(!result_fail.applicable);
+
+    let mut oversized = facts(&env, 60, &[], None);
+    for _ in 0..=criteria::MAX_FACT_CODES {
+        oversized
+            .conditions
+            .push_back(String::from_str(&env, "I10"));
+    }
+    assert_eq!(
+        client.try_evaluate_guideline(
+            &Address::generate(&env),
+            &Address::generate(&env),
+            &guideline_id,
+            &oversized,
+        ),
+        Err(Ok(Error::InvalidInput))
+    );
+}
+
+/// Amoxicillin-like rule: 25 mg/kg for children, 500 mg for adults, halved
+/// below eGFR 30 or in severe hepatic impairment, contraindicated below 10.
+fn amoxicillin_rule(env: &Env) -> DosingRule {
+    DosingRule {
+        route: Symbol::new(env, "Oral"),
+        duration: Some(604800),
+        bands: vec![
+            env,
+            DoseBand {
+                min_age_years: 0,
+                max_age_years: 12,
+                basis: DoseBasis::PerKg(25_000),
+                doses_per_day: 3,
+                frequency: String::from_str(env, "TID"),
+                max_single_mcg: 500_000,
+                max_daily_mcg: 1_500_000,
+            },
+            DoseBand {
+                min_age_years: 12,
+                max_age_years: 120,
+                basis: DoseBasis::Fixed(500_000),
+                doses_per_day: 3,
+                frequency: String::from_str(env, "TID"),
+                max_single_mcg: 1_000_000,
+                max_daily_mcg: 3_000_000,
+            },
+        ],
+        renal_tiers: vec![
+            env,
+            RenalTier {
+                egfr_below: 30,
+                percent: 50,
+            },
+            RenalTier {
+                egfr_below: 10,
+                percent: 0,
+            },
+        ],
+        hepatic_tiers: vec![
+            env,
+            HepaticTier {
+                class: HepaticImpairment::Severe,
+                percent: 50,
+            },
+        ],
+        monitoring: vec![env, String::from_str(env, "Renal function")],
+    }
+}
+
+fn dosing_facts(age_years: u32, weight_dg: u32, egfr: Option<u32>) -> DosingFacts {
+    DosingFacts {
+        age_years,
+        weight_dg,
+        height_cm: 170,
+        egfr,
+        hepatic: HepaticImpairment::None,
+    }
 }
 
 #[test]
 fn test_drug_dosage_calculation() {
     let env = Env::default();
-    let contract_id = env.register(ClinicalGuidelineContract, ());
-    let client = ClinicalGuidelineContractClient::new(&env, &contract_id);
+    let (client, admin) = setup_guidelines(&env);
+    let medication = String::from_str(&env, "0093-3109");
+    assert_eq!(
+        client.register_dosing_rule(&admin, &medication, &amoxicillin_rule(&env)),
+        1
+    );
+    let patient = Address::generate(&env);
 
+    // Adult, eGFR above every renal tier.
     let weight_dg = 700000; // 70kg = 70000g = 700000dg
     let result = client.calculate_drug_dosage(
-        &Address::generate(&env),
-        &String::from_str(&env, "Amoxicillin"),
-        &weight_dg,
-        &30,
-        &Some(50), // Renal impairment < 60
+        &patient,
+        &medication,
+        &dosing_facts(30, weight_dg, Some(50)),
     );
+    assert_eq!(result.medication, medication);
+    assert_eq!(result.rule_version, 1);
+    assert_eq!(result.dose_mcg, 500_000);
+    assert_eq!(result.recommended_dose, String::from_str(&env, "500mg"));
+    assert_eq!(result.frequency, String::from_str(&env, "TID"));
+    assert_eq!(result.duration, Some(604800));
+    assert!(!result.renal_adjustment);
+    assert_eq!(result.monitoring_required.len(), 1);
 
+    // Renal impairment halves the dose.
+    let result = client.calculate_drug_dosage(
+        &patient,
+        &medication,
+        &dosing_facts(30, weight_dg, Some(20)),
+    );
     assert!(result.renal_adjustment);
-    assert_eq!(result.medication, String::from_str(&env, "Amoxicillin"));
-    // (700000 * 5) / 10000 = 350
-    assert_eq!(result.duration, Some(350));
+    assert_eq!(result.recommended_dose, String::from_str(&env, "250mg"));
+
+    // Severe hepatic impairment halves it too.
+    let mut hepatic = dosing_facts(30, weight_dg, None);
+    hepatic.hepatic = HepaticImpairment::Severe;
+    let result = client.calculate_drug_dosage(&patient, &medication, &hepatic);
+    assert!(result.hepatic_adjustment);
+    assert_eq!(result.dose_mcg, 250_000);
+
+    // 0 % tier contraindicates the drug.
+    assert_eq!(
+        client.try_calculate_drug_dosage(
+            &patient,
+            &medication,
+            &dosing_facts(30, weight_dg, Some(5)),
+        ),
+        Err(Ok(Error::DoseContraindicated))
+    );
+}
+
+#[test]
+fn test_pediatric_band_weight_based_and_capped() {
+    let env = Env::default();
+    let (client, admin) = setup_guidelines(&env);
+    let medication = String::from_str(&env, "0093-3109");
+    client.register_dosing_rule(&admin, &medication, &amoxicillin_rule(&env));
+    let patient = Address::generate(&env);
+
+    // 18.5 kg × 25 mg/kg = 462.5 mg
+    let result =
+        client.calculate_drug_dosage(&patient, &medication, &dosing_facts(6, 185000, None));
+    assert_eq!(result.dose_mcg, 462_500);
+    assert_eq!(result.recommended_dose, String::from_str(&env, "462.5mg"));
+    assert!(!result.capped);
+
+    // 30 kg × 25 mg/kg = 750 mg, capped at the 500 mg single maximum.
+    let result =
+        client.calculate_drug_dosage(&patient, &medication, &dosing_facts(8, 300000, None));
+    assert_eq!(result.dose_mcg, 500_000);
+    assert!(result.capped);
+
+    assert_eq!(
+        client.try_calculate_drug_dosage(&patient, &medication, &dosing_facts(130, 700000, None)),
+        Err(Ok(Error::NoDosingBand))
+    );
+}
+
+#[test]
+fn test_bsa_dosing_and_daily_cap() {
+    let env = Env::default();
+    let (client, admin) = setup_guidelines(&env);
+    let medication = String::from_str(&env, "0703-5748");
+    let rule = DosingRule {
+        route: Symbol::new(&env, "IV"),
+        duration: None,
+        bands: vec![
+            &env,
+            DoseBand {
+                min_age_years: 18,
+                max_age_years: 120,
+                basis: DoseBasis::PerBsa(10_000),
+                doses_per_day: 2,
+                frequency: String::from_str(&env, "BID"),
+                max_single_mcg: 20_000,
+                max_daily_mcg: 30_000,
+            },
+        ],
+        renal_tiers: Vec::new(&env),
+        hepatic_tiers: Vec::new(&env),
+        monitoring: vec![
+            &env,
+            String::from_str(&env, "CBC"),
+            String::from_str(&env, "LFTs"),
+        ],
+    };
+    client.register_dosing_rule(&admin, &medication, &rule);
+    let patient = Address::generate(&env);
+
+    // 170 cm, 70 kg → Mosteller BSA 1.81 m² → 18.1 mg, capped at 30 mg / 2 doses.
+    assert_eq!(dosing::bsa_centi_m2(170, 700000), 181);
+    let result =
+        client.calculate_drug_dosage(&patient, &medication, &dosing_facts(40, 700000, None));
+    assert_eq!(result.dose_mcg, 15_000);
+    assert_eq!(result.recommended_dose, String::from_str(&env, "15mg"));
+    assert!(result.capped);
+    assert_eq!(result.monitoring_required, rule.monitoring);
+
+    // 150 cm, 40 kg → BSA 1.29 m² → 12.9 mg, under both caps.
+    let mut small = dosing_facts(40, 400000, None);
+    small.height_cm = 150;
+    let result = client.calculate_drug_dosage(&patient, &medication, &small);
+    assert_eq!(result.recommended_dose, String::from_str(&env, "12.9mg"));
+    assert!(!result.capped);
+
+    // BSA dosing needs a height.
+    small.height_cm = 0;
+    assert_eq!(
+        client.try_calculate_drug_dosage(&patient, &medication, &small),
+        Err(Ok(Error::InvalidInput))
+    );
+}
+
+#[test]
+fn test_dosing_rule_versions_and_validation() {
+    let env = Env::default();
+    let (client, admin) = setup_guidelines(&env);
+    let medication = String::from_str(&env, "0093-3109");
+    let patient = Address::generate(&env);
+
+    assert_eq!(
+        client.try_calculate_drug_dosage(&patient, &medication, &dosing_facts(30, 700000, None)),
+        Err(Ok(Error::DosingRuleNotFound))
+    );
+    assert_eq!(
+        client.try_update_dosing_rule(&admin, &medication, &amoxicillin_rule(&env)),
+        Err(Ok(Error::DosingRuleNotFound))
+    );
+
+    client.register_dosing_rule(&admin, &medication, &amoxicillin_rule(&env));
+    let mut v2 = amoxicillin_rule(&env);
+    let mut adult = v2.bands.get(1).unwrap();
+    adult.basis = DoseBasis::Fixed(875_000);
+    v2.bands.set(1, adult);
+    assert_eq!(client.update_dosing_rule(&admin, &medication, &v2), 2);
+    assert_eq!(client.get_dosing_rule_version(&medication), 2);
+    assert_eq!(
+        client.get_dosing_rule(&medication, &1),
+        amoxicillin_rule(&env)
+    );
+
+    let result =
+        client.calculate_drug_dosage(&patient, &medication, &dosing_facts(30, 700000, None));
+    assert_eq!(result.rule_version, 2);
+    assert_eq!(result.recommended_dose, String::from_str(&env, "875mg"));
+
+    let mut overlapping = amoxicillin_rule(&env);
+    let mut adult = overlapping.bands.get(1).unwrap();
+    adult.min_age_years = 10;
+    overlapping.bands.set(1, adult);
+
+    let mut daily_below_single = amoxicillin_rule(&env);
+    let mut child = daily_below_single.bands.get(0).unwrap();
+    child.max_daily_mcg = child.max_single_mcg - 1;
+    daily_below_single.bands.set(0, child);
+
+    let mut renal_ascending = amoxicillin_rule(&env);
+    renal_ascending.renal_tiers = vec![
+        &env,
+        RenalTier {
+            egfr_below: 10,
+            percent: 0,
+        },
+        RenalTier {
+            egfr_below: 30,
+            percent: 50,
+        },
+    ];
+
+    let mut no_bands = amoxicillin_rule(&env);
+    no_bands.bands = Vec::new(&env);
+
+    for rule in [overlapping, daily_below_single, renal_ascending, no_bands] {
+        assert_eq!(
+            client.try_update_dosing_rule(&admin, &medication, &rule),
+            Err(Ok(Error::InvalidDosingRule))
+        );
+    }
+}
+
+fn cha2ds2_vasc(age_years: u32, female: bool, hypertension: bool) -> RiskInput {
+    RiskInput::Cha2ds2Vasc(Cha2ds2VascInput {
+        age_years,
+        female,
+        congestive_heart_failure: false,
+        hypertension,
+        diabetes: false,
+        stroke_or_tia: false,
+        vascular_disease: false,
+    })
+}
+
+fn meld(bilirubin: u32, inr: u32, creatinine: u32, dialysis: bool) -> RiskInput {
+    RiskInput::Meld(MeldInput {
+        bilirubin_mg_dl_x100: bilirubin,
+        inr_x100: inr,
+        creatinine_mg_dl_x100: creatinine,
+        dialysis,
+    })
+}
+
+fn braden(subscale: u32, friction_shear: u32) -> RiskInput {
+    RiskInput::Braden(BradenInput {
+        sensory_perception: subscale,
+        moisture: subscale,
+        activity: subscale,
+        mobility: subscale,
+        nutrition: subscale,
+        friction_shear,
+    })
 }
 
 #[test]
@@ -76,20 +628,205 @@ fn test_risk_score_assessment() {
     let env = Env::default();
     let contract_id = env.register(ClinicalGuidelineContract, ());
     let client = ClinicalGuidelineContractClient::new(&env, &contract_id);
+    env.mock_all_auths();
+    env.ledger().with_mut(|li| {
+        li.timestamp = 12345;
+    });
 
-    let mut params = Vec::new(&env);
-    params.push_back(10);
-    params.push_back(20);
-    params.push_back(5);
+    let patient = Address::generate(&env);
+    let provider = Address::generate(&env);
+    client.authorize_reminder_provider(&patient, &provider);
 
-    let result = client.assess_risk_score(
-        &Address::generate(&env),
-        &Symbol::new(&env, "SCORE_X"),
-        &params,
+    // Age >= 75 (2) + hypertension (1) + female (1).
+    let input = cha2ds2_vasc(78, true, true);
+    let result = client.assess_risk_score(&patient, &provider, &input);
+    assert_eq!(result.assessment_id, 1);
+    assert_eq!(result.score, 4);
+    assert_eq!(result.band, RiskBand::High);
+    assert_eq!(result.calculator, Symbol::new(&env, "CHA2DS2_VASc"));
+    assert_eq!(result.calculator_version, 1);
+    let input_hash: BytesN<32> = env.crypto().sha256(&input.clone().to_xdr(&env)).into();
+    assert_eq!(result.input_hash, input_hash);
+    assert_eq!(result.assessed_at, 12345);
+
+    // The sex point alone does not raise the band.
+    let result = client.assess_risk_score(&patient, &provider, &cha2ds2_vasc(60, true, false));
+    assert_eq!(result.assessment_id, 2);
+    assert_eq!((result.score, result.band), (1, RiskBand::Low));
+    let result = client.assess_risk_score(&patient, &provider, &cha2ds2_vasc(66, false, false));
+    assert_eq!((result.score, result.band), (1, RiskBand::Moderate));
+
+    // Every assessment stays on record for audit.
+    let stored = client.get_risk_assessment(&patient, &provider, &1);
+    assert_eq!(stored.score, 4);
+    assert_eq!(stored.provider_id, provider);
+    assert_eq!(client.get_risk_assessment(&patient, &patient, &1), stored);
+    assert_eq!(
+        client.try_get_risk_assessment(&patient, &patient, &4),
+        Err(Ok(Error::AssessmentNotFound))
+    );
+}
+
+#[test]
+fn test_risk_assessments_require_patient_link() {
+    let env = Env::default();
+    let contract_id = env.register(ClinicalGuidelineContract, ());
+    let client = ClinicalGuidelineContractClient::new(&env, &contract_id);
+    env.mock_all_auths();
+    let patient = Address::generate(&env);
+    let provider = Address::generate(&env);
+    let input = cha2ds2_vasc(78, true, true);
+
+    assert_eq!(
+        client.try_assess_risk_score(&patient, &provider, &input),
+        Err(Ok(Error::NotAuthorized))
+    );
+    // Patients may always score themselves.
+    client.assess_risk_score(&patient, &patient, &input);
+    assert_eq!(
+        client.try_get_risk_assessment(&patient, &provider, &1),
+        Err(Ok(Error::NotAuthorized))
     );
 
-    assert_eq!(result.score, 35);
-    assert_eq!(result.calculator, Symbol::new(&env, "SCORE_X"));
+    client.authorize_reminder_provider(&patient, &provider);
+    client.assess_risk_score(&patient, &provider, &input);
+    assert_eq!(client.get_risk_assessment(&patient, &provider, &1).provider_id, patient);
+
+    client.revoke_reminder_provider(&patient, &provider);
+    assert_eq!(
+        client.try_get_risk_assessment(&patient, &provider, &2),
+        Err(Ok(Error::NotAuthorized))
+    );
+    assert_eq!(
+        client.try_assess_risk_score(&patient, &provider, &input),
+        Err(Ok(Error::NotAuthorized))
+    );
+}
+
+#[test]
+fn test_point_score_calculators() {
+    let env = Env::default();
+    let contract_id = env.register(ClinicalGuidelineContract, ());
+    let client = ClinicalGuidelineContractClient::new(&env, &contract_id);
+    env.mock_all_auths();
+    let patient = Address::generate(&env);
+    let provider = Address::generate(&env);
+    client.authorize_reminder_provider(&patient, &provider);
+
+    // HAS-BLED: elderly + hypertension + drugs.
+    let has_bled = RiskInput::HasBled(HasBledInput {
+        age_years: 70,
+        hypertension: true,
+        abnormal_renal_function: false,
+        abnormal_liver_function: false,
+        stroke: false,
+        bleeding_history: false,
+        labile_inr: false,
+        drugs: true,
+        alcohol: false,
+    });
+    let result = client.assess_risk_score(&patient, &provider, &has_bled);
+    assert_eq!((result.score, result.band), (3, RiskBand::High));
+
+    // Wells DVT: an alternative diagnosis subtracts two points.
+    let mut dvt = WellsDvtInput {
+        active_cancer: false,
+        paralysis_or_recent_cast: false,
+        bedridden_or_recent_surgery: false,
+        deep_vein_tenderness: false,
+        entire_leg_swollen: false,
+        calf_swelling_over_3cm: false,
+        pitting_edema: false,
+        collateral_superficial_veins: false,
+        previous_dvt: false,
+        alternative_diagnosis_likely: true,
+    };
+    let result = client.assess_risk_score(&patient, &provider, &RiskInput::WellsDvt(dvt.clone()));
+    assert_eq!((result.score, result.band), (-2, RiskBand::Low));
+    dvt.alternative_diagnosis_likely = false;
+    dvt.active_cancer = true;
+    dvt.entire_leg_swollen = true;
+    dvt.previous_dvt = true;
+    let result = client.assess_risk_score(&patient, &provider, &RiskInput::WellsDvt(dvt));
+    assert_eq!((result.score, result.band), (3, RiskBand::High));
+
+    // Wells PE is scored in tenths: 1.5 + 1.0 = 2.5.
+    let mut pe = WellsPeInput {
+        clinical_signs_of_dvt: false,
+        pe_most_likely_diagnosis: false,
+        heart_rate_over_100: true,
+        immobilized_or_recent_surgery: false,
+        previous_dvt_or_pe: false,
+        hemoptysis: true,
+        malignancy: false,
+    };
+    let result = client.assess_risk_score(&patient, &provider, &RiskInput::WellsPe(pe.clone()));
+    assert_eq!(result.score_scale, 10);
+    assert_eq!((result.score, result.band), (25, RiskBand::Moderate));
+    pe.clinical_signs_of_dvt = true;
+    pe.pe_most_likely_diagnosis = true;
+    let result = client.assess_risk_score(&patient, &provider, &RiskInput::WellsPe(pe));
+    assert_eq!((result.score, result.band), (85, RiskBand::High));
+
+    // CURB-65: urea > 7 mmol/L, respiratory rate >= 30, age >= 65.
+    let mut curb = Curb65Input {
+        confusion: false,
+        urea_mmol_l_x10: 80,
+        respiratory_rate: 32,
+        systolic_bp: 120,
+        diastolic_bp: 80,
+        age_years: 70,
+    };
+    let result = client.assess_risk_score(&patient, &provider, &RiskInput::Curb65(curb.clone()));
+    assert_eq!((result.score, result.band), (3, RiskBand::High));
+    curb.systolic_bp = 0;
+    assert_eq!(
+        client.try_assess_risk_score(&patient, &provider, &RiskInput::Curb65(curb)),
+        Err(Ok(Error::InvalidInput))
+    );
+
+    // Braden: lower totals mean higher risk.
+    let result = client.assess_risk_score(&patient, &provider, &braden(4, 3));
+    assert_eq!((result.score, result.band), (23, RiskBand::Minimal));
+    let result = client.assess_risk_score(&patient, &provider, &braden(1, 1));
+    assert_eq!((result.score, result.band), (6, RiskBand::VeryHigh));
+    assert_eq!(
+        client.try_assess_risk_score(&patient, &provider, &braden(5, 3)),
+        Err(Ok(Error::InvalidInput))
+    );
+}
+
+#[test]
+fn test_meld_fixed_point() {
+    let env = Env::default();
+    let contract_id = env.register(ClinicalGuidelineContract, ());
+    let client = ClinicalGuidelineContractClient::new(&env, &contract_id);
+    env.mock_all_auths();
+    let patient = Address::generate(&env);
+    let provider = Address::generate(&env);
+    client.authorize_reminder_provider(&patient, &provider);
+
+    assert_eq!(risk::ln_micro(100), 0);
+    assert_eq!(risk::ln_micro(200), 693147);
+    assert_eq!(risk::ln_micro(50), -693147);
+    assert_eq!(risk::ln_micro(1000), 2302585);
+
+    // Values below 1.0 are floored, so the minimum score is 6.
+    let result = client.assess_risk_score(&patient, &provider, &meld(50, 90, 70, false));
+    assert_eq!((result.score, result.band), (6, RiskBand::Low));
+
+    // 10 × (0.957 ln 1.5 + 0.378 ln 2 + 1.120 ln 1.5 + 0.643) = 17.47
+    let result = client.assess_risk_score(&patient, &provider, &meld(200, 150, 150, false));
+    assert_eq!((result.score, result.band), (17, RiskBand::Moderate));
+
+    // Dialysis sets creatinine to 4.0; the score is capped at 40.
+    let result = client.assess_risk_score(&patient, &provider, &meld(1000, 300, 90, true));
+    assert_eq!((result.score, result.band), (40, RiskBand::VeryHigh));
+
+    assert_eq!(
+        client.try_assess_risk_score(&patient, &provider, &meld(0, 100, 100, false)),
+        Err(Ok(Error::InvalidInput))
+    );
 }
 
 #[test]
@@ -105,46 +842,663 @@ fn test_care_pathway_suggestion() {
     assert!(result.steps.len() >= 3);
 }
 
+fn preventive_entry(
+    env: &Env,
+    service: &str,
+    ages: (u32, u32),
+    sex: Option<&str>,
+    risk_factors: &[&str],
+    interval: Recurrence,
+    grace_days: u32,
+) -> PreventiveService {
+    let mut risks = Vec::new(env);
+    for risk in risk_factors {
+        risks.push_back(Symbol::new(env, risk));
+    }
+    PreventiveService {
+        service: Symbol::new(env, service),
+        min_age_years: ages.0,
+        max_age_years: ages.1,
+        sex: sex.map(|s| Symbol::new(env, s)),
+        risk_factors: risks,
+        interval,
+        grace_days,
+    }
+}
+
+/// Family-history colonoscopy comes first so it overrides the general entry.
+fn preventive_schedule(env: &Env) -> PreventiveSchedule {
+    PreventiveSchedule {
+        services: vec![
+            env,
+            preventive_entry(
+                env,
+                "Colonoscopy",
+                (40, 76),
+                None,
+                &["FamilyHistory_CRC"],
+                Recurrence::Years(5),
+                90,
+            ),
+            preventive_entry(
+                env,
+                "Colonoscopy",
+                (45, 76),
+                None,
+                &[],
+                Recurrence::Years(10),
+                180,
+            ),
+            preventive_entry(
+                env,
+                "Mammogram",
+                (40, 75),
+                Some("F"),
+                &[],
+                Recurrence::Years(2),
+                90,
+            ),
+            preventive_entry(
+                env,
+                "AAA_Screen",
+                (65, 76),
+                Some("M"),
+                &["Smoker"],
+                Recurrence::None,
+                365,
+            ),
+            preventive_entry(
+                env,
+                "Annual_Checkup",
+                (18, 150),
+                None,
+                &[],
+                Recurrence::Years(1),
+                30,
+            ),
+        ],
+    }
+}
+
+fn completion(env: &Env, service: &str, completed_at: u64) -> ServiceCompletion {
+    ServiceCompletion {
+        service: Symbol::new(env, service),
+        completed_at,
+    }
+}
+
+fn services(env: &Env, due: &Vec<PreventiveDue>) -> Vec<Symbol> {
+    let mut names = Vec::new(env);
+    for item in due.iter() {
+        names.push_back(item.service);
+    }
+    names
+}
+
+// 09:30 UTC on Jan 31 of each year.
+const JAN_31_2016: u64 = 1454232600;
+const JAN_31_2025: u64 = 1738315800;
+
 #[test]
 fn test_preventive_care_logic() {
     let env = Env::default();
-    let contract_id = env.register(ClinicalGuidelineContract, ());
-    let client = ClinicalGuidelineContractClient::new(&env, &contract_id);
+    let (client, admin) = setup_guidelines(&env);
+    let patient = Address::generate(&env);
+    let none: Vec<Symbol> = Vec::new(&env);
+    let no_history: Vec<ServiceCompletion> = Vec::new(&env);
+    assert_eq!(
+        client.try_check_preventive_care(
+            &patient,
+            &55,
+            &Symbol::new(&env, "M"),
+            &none,
+            &no_history
+        ),
+        Err(Ok(Error::ScheduleNotFound))
+    );
+    client.set_preventive_schedule(&admin, &preventive_schedule(&env));
 
-    // Test for older patient
-    let alerts = client.check_preventive_care(
-        &Address::generate(&env),
+    // Average-risk man: general colonoscopy interval and a checkup, both due now.
+    let due =
+        client.check_preventive_care(&patient, &55, &Symbol::new(&env, "M"), &none, &no_history);
+    assert_eq!(
+        services(&env, &due),
+        vec![
+            &env,
+            Symbol::new(&env, "Colonoscopy"),
+            Symbol::new(&env, "Annual_Checkup")
+        ]
+    );
+    let colonoscopy = due.get(0).unwrap();
+    assert_eq!(colonoscopy.interval, Recurrence::Years(10));
+    assert_eq!(colonoscopy.status, PreventiveStatus::Due);
+    assert_eq!(colonoscopy.schedule_version, 1);
+    assert_eq!(colonoscopy.last_completed, None);
+
+    // Family history lowers the colonoscopy age and shortens its interval.
+    let due = client.check_preventive_care(
+        &patient,
+        &42,
+        &Symbol::new(&env, "F"),
+        &vec![&env, Symbol::new(&env, "FamilyHistory_CRC")],
+        &no_history,
+    );
+    assert_eq!(
+        services(&env, &due),
+        vec![
+            &env,
+            Symbol::new(&env, "Colonoscopy"),
+            Symbol::new(&env, "Mammogram"),
+            Symbol::new(&env, "Annual_Checkup")
+        ]
+    );
+    assert_eq!(due.get(0).unwrap().interval, Recurrence::Years(5));
+
+    // Sex- and risk-restricted screenings.
+    let smoker = vec![&env, Symbol::new(&env, "Smoker")];
+    let due =
+        client.check_preventive_care(&patient, &66, &Symbol::new(&env, "M"), &smoker, &no_history);
+    assert!(services(&env, &due).contains(Symbol::new(&env, "AAA_Screen")));
+    let due =
+        client.check_preventive_care(&patient, &66, &Symbol::new(&env, "F"), &smoker, &no_history);
+    assert!(!services(&env, &due).contains(Symbol::new(&env, "AAA_Screen")));
+    assert!(services(&env, &due).contains(Symbol::new(&env, "Mammogram")));
+}
+
+#[test]
+fn test_preventive_due_dates_follow_history() {
+    let env = Env::default();
+    let (client, admin) = setup_guidelines(&env);
+    client.set_preventive_schedule(&admin, &preventive_schedule(&env));
+    env.ledger().with_mut(|li| li.timestamp = MAR_28_2026);
+    let patient = Address::generate(&env);
+
+    let history = vec![
+        &env,
+        completion(&env, "Colonoscopy", JAN_31_2016),
+        completion(&env, "Annual_Checkup", JAN_31_2025),
+        completion(&env, "AAA_Screen", JAN_31_2025),
+    ];
+    let due = client.check_preventive_care(
+        &patient,
+        &66,
+        &Symbol::new(&env, "M"),
+        &vec![&env, Symbol::new(&env, "Smoker")],
+        &history,
+    );
+    // The one-off AAA screen is done for good.
+    assert_eq!(due.len(), 2);
+
+    // Ten years on and within its 180-day grace period.
+    let colonoscopy = due.get(0).unwrap();
+    assert_eq!(colonoscopy.due_date, JAN_31_2026);
+    assert_eq!(colonoscopy.status, PreventiveStatus::Due);
+    assert_eq!(colonoscopy.last_completed, Some(JAN_31_2016));
+
+    // A year on and past its 30-day grace period.
+    let checkup = due.get(1).unwrap();
+    assert_eq!(checkup.due_date, JAN_31_2026);
+    assert_eq!(checkup.status, PreventiveStatus::Overdue);
+
+    // The latest completion counts, and services not yet due are left out.
+    let history = vec![
+        &env,
+        completion(&env, "Annual_Checkup", JAN_31_2025),
+        completion(&env, "Annual_Checkup", FEB_28_2026),
+        completion(&env, "Colonoscopy", FEB_05_2026),
+    ];
+    let due = client.check_preventive_care(
+        &patient,
         &55,
         &Symbol::new(&env, "M"),
         &Vec::new(&env),
+        &history,
     );
+    assert!(due.is_empty());
 
-    assert!(alerts.len() >= 2);
-    assert!(alerts.contains(Symbol::new(&env, "Screening_A")));
+    let future = vec![&env, completion(&env, "Colonoscopy", MAR_28_2026 + 1)];
+    assert_eq!(
+        client.try_check_preventive_care(
+            &patient,
+            &55,
+            &Symbol::new(&env, "M"),
+            &Vec::new(&env),
+            &future
+        ),
+        Err(Ok(Error::InvalidInput))
+    );
 }
 
 #[test]
-fn test_reminders() {
+fn test_preventive_schedule_versions_and_validation() {
     let env = Env::default();
-    let contract_id = env.register(ClinicalGuidelineContract, ());
-    let client = ClinicalGuidelineContractClient::new(&env, &contract_id);
+    let (client, admin) = setup_guidelines(&env);
+    let schedule = preventive_schedule(&env);
 
-    let patient = Address::generate(&env);
-    let due_date = 1000000;
+    assert_eq!(
+        client.try_set_preventive_schedule(&Address::generate(&env), &schedule),
+        Err(Ok(Error::NotAuthorized))
+    );
+    let empty = PreventiveSchedule {
+        services: Vec::new(&env),
+    };
+    assert_eq!(
+        client.try_set_preventive_schedule(&admin, &empty),
+        Err(Ok(Error::InvalidSchedule))
+    );
+    let mut bad = schedule.clone();
+    bad.services.set(
+        0,
+        preventive_entry(
+            &env,
+            "Mammogram",
+            (75, 40),
+            None,
+            &[],
+            Recurrence::Years(2),
+            0,
+        ),
+    );
+    assert_eq!(
+        client.try_set_preventive_schedule(&admin, &bad),
+        Err(Ok(Error::InvalidSchedule))
+    );
+    let mut bad = schedule.clone();
+    bad.services.set(
+        0,
+        preventive_entry(
+            &env,
+            "Mammogram",
+            (40, 75),
+            None,
+            &[],
+            Recurrence::Months(0),
+            0,
+        ),
+    );
+    assert_eq!(
+        client.try_set_preventive_schedule(&admin, &bad),
+        Err(Ok(Error::InvalidSchedule))
+    );
+
+    assert_eq!(client.set_preventive_schedule(&admin, &schedule), 1);
+    let mut updated = schedule.clone();
+    updated.services.pop_back();
+    assert_eq!(client.set_preventive_schedule(&admin, &updated), 2);
+    assert_eq!(client.get_preventive_schedule_version(), 2);
+    assert_eq!(client.get_preventive_schedule(&1), schedule);
+    assert_eq!(client.get_preventive_schedule(&2), updated);
+    assert_eq!(
+        client.try_get_preventive_schedule(&3),
+        Err(Ok(Error::ScheduleNotFound))
+    );
 
+    let due = client.check_preventive_care(
+        &Address::generate(&env),
+        &30,
+        &Symbol::new(&env, "M"),
+        &Vec::new(&env),
+        &Vec::new(&env),
+    );
+    assert!(due.is_empty());
+}
+
+// 09:30 UTC on each date.
+const JAN_31_2026: u64 = 1769851800;
+const FEB_05_2026: u64 = 1770283800;
+const FEB_28_2026: u64 = 1772271000;
+const MAR_28_2026: u64 = 1774690200;
+
+fn setup_reminders(env: &Env) -> (ClinicalGuidelineContractClient<'_>, Address, Address) {
+    let contract_id = env.register(ClinicalGuidelineContract, ());
+    let client = ClinicalGuidelineContractClient::new(env, &contract_id);
+    env.mock_all_auths();
     env.ledger().with_mut(|li| {
         li.timestamp = 12345;
     });
+    let (patient, provider) = (Address::generate(env), Address::generate(env));
+    client.authorize_reminder_provider(&patient, &provider);
+    (client, patient, provider)
+}
+
+#[test]
+fn test_reminders() {
+    let env = Env::default();
+    let (client, patient, provider) = setup_reminders(&env);
+    let due_date = 1000000;
 
     let reminder_id = client.create_reminder(
         &patient,
-        &Address::generate(&env),
+        &provider,
         &Symbol::new(&env, "MEDS"),
         &due_date,
         &Symbol::new(&env, "HIGH"),
+        &Recurrence::None,
+    );
+
+    assert_eq!(reminder_id, 1);
+    let reminder = client.get_reminder(&patient, &reminder_id);
+    assert_eq!(reminder.reminder_type, Symbol::new(&env, "MEDS"));
+    assert_eq!(reminder.priority, Symbol::new(&env, "HIGH"));
+    assert_eq!(reminder.status, ReminderStatus::Pending);
+    assert_eq!(reminder.created_at, 12345);
+
+    // One-off reminders do not recur.
+    assert_eq!(
+        client.complete_reminder(&provider, &patient, &reminder_id),
+        None
+    );
+    assert_eq!(
+        client.get_reminder(&patient, &reminder_id).status,
+        ReminderStatus::Completed
+    );
+    assert_eq!(
+        client.try_get_reminder(&patient, &2),
+        Err(Ok(Error::ReminderNotFound))
+    );
+}
+
+#[test]
+fn test_recurring_reminder_ignores_snooze_drift() {
+    let env = Env::default();
+    let (client, patient, provider) = setup_reminders(&env);
+
+    let first = client.create_reminder(
+        &patient,
+        &provider,
+        &Symbol::new(&env, "INR_CHECK"),
+        &JAN_31_2026,
+        &Symbol::new(&env, "Normal"),
+        &Recurrence::Months(1),
+    );
+    client.snooze_reminder(&patient, &patient, &first, &FEB_05_2026);
+    let snoozed = client.get_reminder(&patient, &first);
+    assert_eq!(snoozed.status, ReminderStatus::Snoozed);
+    assert_eq!(snoozed.due_date, FEB_05_2026);
+    assert_eq!(snoozed.scheduled_for, JAN_31_2026);
+
+    // Next occurrence steps from Jan 31, clamped to the end of February.
+    let second = client
+        .complete_reminder(&patient, &patient, &first)
+        .unwrap();
+    assert_eq!(
+        client.get_reminder(&patient, &first).next_reminder_id,
+        Some(second)
+    );
+    let reminder = client.get_reminder(&patient, &second);
+    assert_eq!(reminder.due_date, FEB_28_2026);
+    assert_eq!(reminder.recurrence, Recurrence::Months(1));
+
+    let third = client
+        .complete_reminder(&provider, &patient, &second)
+        .unwrap();
+    assert_eq!(client.get_reminder(&patient, &third).due_date, MAR_28_2026);
+
+    // Dismissing ends the series.
+    client.dismiss_reminder(&provider, &patient, &third);
+    let dismissed = client.get_reminder(&patient, &third);
+    assert_eq!(dismissed.status, ReminderStatus::Dismissed);
+    assert_eq!(dismissed.next_reminder_id, None);
+    assert!(
+        client
+            .get_patient_due_reminders(&patient, &0, &u64::MAX)
+            .is_empty()
     );
 
-    assert_eq!(reminder_id, 12345);
+    // Leap day + 1 year clamps to Feb 28.
+    assert_eq!(reminders::add_months(1835429400, 12), 1866965400);
+}
+
+#[test]
+fn test_reminder_transitions_are_guarded() {
+    let env = Env::default();
+    let (client, patient, provider) = setup_reminders(&env);
+    let id = client.create_reminder(
+        &patient,
+        &provider,
+        &Symbol::new(&env, "MEDS"),
+        &JAN_31_2026,
+        &Symbol::new(&env, "Normal"),
+        &Recurrence::Days(7),
+    );
+
+    let stranger = Address::generate(&env);
+    assert_eq!(
+        client.try_complete_reminder(&stranger, &patient, &id),
+        Err(Ok(Error::NotAuthorized))
+    );
+    assert_eq!(
+        client.try_snooze_reminder(&patient, &patient, &id, &12345),
+        Err(Ok(Error::InvalidInput))
+    );
+    assert_eq!(
+        client.try_create_reminder(
+            &patient,
+            &provider,
+            &Symbol::new(&env, "MEDS"),
+            &JAN_31_2026,
+            &Symbol::new(&env, "Normal"),
+            &Recurrence::Days(0),
+        ),
+        Err(Ok(Error::InvalidInput))
+    );
+
+    client.dismiss_reminder(&patient, &patient, &id);
+    assert_eq!(
+        client.try_complete_reminder(&patient, &patient, &id),
+        Err(Ok(Error::ReminderClosed))
+    );
+    assert_eq!(
+        client.try_snooze_reminder(&provider, &patient, &id, &FEB_05_2026),
+        Err(Ok(Error::ReminderClosed))
+    );
+}
+
+#[test]
+fn test_only_authorized_providers_open_reminders() {
+    let env = Env::default();
+    let (client, patient, provider) = setup_reminders(&env);
+    let stranger = Address::generate(&env);
+    let kind = Symbol::new(&env, "MEDS");
+    let priority = Symbol::new(&env, "Normal");
+
+    assert_eq!(
+        client.try_create_reminder(
+            &patient,
+            &stranger,
+            &kind,
+            &JAN_31_2026,
+            &priority,
+            &Recurrence::None,
+        ),
+        Err(Ok(Error::NotAuthorized))
+    );
+    assert_eq!(
+        client.try_create_preventive_reminders(
+            &patient,
+            &stranger,
+            &55,
+            &Symbol::new(&env, "M"),
+            &Vec::new(&env),
+            &Vec::new(&env),
+        ),
+        Err(Ok(Error::NotAuthorized))
+    );
+
+    // Patients may always open their own reminders.
+    client.create_reminder(&patient, &patient, &kind, &JAN_31_2026, &priority, &Recurrence::None);
+
+    let id = client.create_reminder(
+        &patient,
+        &provider,
+        &kind,
+        &JAN_31_2026,
+        &priority,
+        &Recurrence::None,
+    );
+    client.revoke_reminder_provider(&patient, &provider);
+    assert!(!client.is_reminder_provider(&patient, &provider));
+    assert_eq!(
+        client.try_create_reminder(
+            &patient,
+            &provider,
+            &kind,
+            &JAN_31_2026,
+            &priority,
+            &Recurrence::None,
+        ),
+        Err(Ok(Error::NotAuthorized))
+    );
+    // Open reminders survive revocation.
+    assert_eq!(client.get_reminder(&patient, &id).status, ReminderStatus::Pending);
+}
+
+#[test]
+fn test_recurrence_after_revocation_moves_to_patient() {
+    let env = Env::default();
+    let (client, patient, provider) = setup_reminders(&env);
+    let first = client.create_reminder(
+        &patient,
+        &provider,
+        &Symbol::new(&env, "INR_CHECK"),
+        &JAN_31_2026,
+        &Symbol::new(&env, "Normal"),
+        &Recurrence::Months(1),
+    );
+    client.revoke_reminder_provider(&patient, &provider);
+
+    // The revoked provider may still close its open reminder, but the
+    // next occurrence is not assigned to it.
+    let second = client
+        .complete_reminder(&provider, &patient, &first)
+        .unwrap();
+    let next = client.get_reminder(&patient, &second);
+    assert_eq!(next.provider_id, patient);
+    assert_eq!(next.due_date, FEB_28_2026);
+    assert!(
+        client
+            .get_provider_due_reminders(&provider, &0, &u64::MAX)
+            .is_empty()
+    );
+    assert_eq!(
+        client.try_complete_reminder(&provider, &patient, &second),
+        Err(Ok(Error::NotAuthorized))
+    );
+}
+
+#[test]
+fn test_due_reminder_lists() {
+    let env = Env::default();
+    let (client, patient, provider) = setup_reminders(&env);
+    let other_patient = Address::generate(&env);
+    let other_provider = Address::generate(&env);
+    client.authorize_reminder_provider(&other_patient, &provider);
+    client.authorize_reminder_provider(&patient, &other_provider);
+    let kind = Symbol::new(&env, "FOLLOW_UP");
+    let priority = Symbol::new(&env, "Normal");
+
+    let late = client.create_reminder(
+        &patient,
+        &provider,
+        &kind,
+        &MAR_28_2026,
+        &priority,
+        &Recurrence::None,
+    );
+    let early = client.create_reminder(
+        &patient,
+        &provider,
+        &kind,
+        &JAN_31_2026,
+        &priority,
+        &Recurrence::None,
+    );
+    let middle = client.create_reminder(
+        &other_patient,
+        &provider,
+        &kind,
+        &FEB_28_2026,
+        &priority,
+        &Recurrence::None,
+    );
+    client.create_reminder(
+        &patient,
+        &other_provider,
+        &kind,
+        &FEB_05_2026,
+        &priority,
+        &Recurrence::None,
+    );
+
+    // Patient list spans providers, earliest first, within the window.
+    let due = client.get_patient_due_reminders(&patient, &0, &FEB_28_2026);
+    assert_eq!(due.len(), 2);
+    assert_eq!(due.get(0).unwrap().reminder_id, early);
+    assert_eq!(due.get(1).unwrap().due_date, FEB_05_2026);
+
+    // Provider list spans patients.
+    let due = client.get_provider_due_reminders(&provider, &0, &u64::MAX);
+    assert_eq!(due.len(), 3);
+    assert_eq!(due.get(0).unwrap().reminder_id, early);
+    assert_eq!(due.get(1).unwrap().reminder_id, middle);
+    assert_eq!(due.get(1).unwrap().patient_id, other_patient);
+    assert_eq!(due.get(2).unwrap().reminder_id, late);
+
+    // Closed reminders leave both lists.
+    client.complete_reminder(&provider, &patient, &early);
+    assert_eq!(
+        client
+            .get_provider_due_reminders(&provider, &0, &u64::MAX)
+            .len(),
+        2
+    );
+    assert_eq!(
+        client
+            .get_patient_due_reminders(&patient, &0, &u64::MAX)
+            .len(),
+        2
+    );
+}
+
+#[test]
+fn test_preventive_reminders_created_once() {
+    let env = Env::default();
+    let (client, patient, provider) = setup_reminders(&env);
+    let admin = Address::generate(&env);
+    client.initialize(&admin);
+    client.set_preventive_schedule(&admin, &preventive_schedule(&env));
+    env.ledger().with_mut(|li| li.timestamp = MAR_28_2026);
+    let history = vec![&env, completion(&env, "Annual_Checkup", JAN_31_2025)];
+
+    let created = client.create_preventive_reminders(
+        &patient,
+        &provider,
+        &55,
+        &Symbol::new(&env, "M"),
+        &Vec::new(&env),
+        &history,
+    );
+    assert_eq!(created.len(), 2);
+    let colonoscopy = client.get_reminder(&patient, &created.get(0).unwrap());
+    assert_eq!(colonoscopy.reminder_type, Symbol::new(&env, "Colonoscopy"));
+    assert_eq!(colonoscopy.recurrence, Recurrence::Years(10));
+    assert_eq!(colonoscopy.due_date, MAR_28_2026);
+    assert_eq!(colonoscopy.priority, Symbol::new(&env, "Normal"));
+    let checkup = client.get_reminder(&patient, &created.get(1).unwrap());
+    assert_eq!(checkup.recurrence, Recurrence::Years(1));
+    assert_eq!(checkup.due_date, JAN_31_2026);
+    assert_eq!(checkup.priority, Symbol::new(&env, "High"));
+
+    // Services that already have an open reminder are skipped.
+    let again = client.create_preventive_reminders(
+        &patient,
+        &provider,
+        &55,
+        &Symbol::new(&env, "M"),
+        &Vec::new(&env),
+        &history,
+    );
+    assert!(again.is_empty());
 }
 
 #[test]
@@ -160,7 +1514,7 @@ fn test_unauthorized_registration() {
         &admin,
         &String::from_str(&env, "FAIL"),
         &String::from_str(&env, "NA"),
-        &BytesN::from_array(&env, &[0u8; 32]),
+        &diabetes_criteria(&env),
         &BytesN::from_array(&env, &[0u8; 32]),
         &Symbol::new(&env, "B"),
     );
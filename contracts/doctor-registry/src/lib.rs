@@ -21,9 +21,35 @@
 //! **Encryption/Integrity:** Doctor addresses validated via nonzero address checks. Institutional
 //! wallet references provide cryptographic identity anchoring. Profile metadata encrypted via
 //! Soroban's secure storage mechanisms.
+//!
+//! ## Verification workflow
+//!
+//! ```text
+//! Unverified ──submit_for_verification──▶ UnderReview ──sync_verification──▶ Verified
+//!                                              │                                │  ▲
+//!                                              ▼                                ▼  │
+//!                                          Rejected                            Lapsed
+//!
+//! any state ──suspend_doctor──▶ Suspended ──reinstate_doctor──▶ UnderReview
+//! any state ──revoke_doctor───▶ Revoked (terminal)
+//! ```
+//!
+//! `sync_verification` reads the linked case from the `healthcare-credentialing` contract and
+//! maps its status: `Approved` / `RecredentialingInProgress` → `Verified`, `Denied` →
+//! `Rejected`, `RecredentialingExpired` → `Lapsed`, anything else → `UnderReview`. It never
+//! lifts an admin suspension or revocation.
+//!
+//! `is_verified_doctor` is the lookup used by `shared::actor_verification::verify_actor` for
+//! `ActorType::Doctor`: the doctor must be `Verified` and hold at least one unexpired license.
 
 use shared::privacy::validate_nonzero_address;
-use soroban_sdk::{contract, contractimpl, contracterror, contracttype, symbol_short, Address, Env, String};
+use soroban_sdk::{
+    contract, contractclient, contracterror, contractimpl, contracttype, symbol_short, Address,
+    Env, String, Symbol, Vec,
+};
+
+/// Maximum licenses or board certifications held on one profile.
+pub const MAX_CREDENTIALS_PER_KIND: u32 = 20;
 
 /// Error codes for doctor registry operations
 #[contracterror]
@@ -35,11 +61,59 @@ pub enum Error {
     Unauthorized = 3,
     AlreadyInitialized = 4,
     InvalidAddress = 5,
+    /// Empty license number, or expiry not after issue date.
+    InvalidCredential = 6,
+    /// Verification requires at least one unexpired license.
+    NoValidLicense = 7,
+    InvalidStatusTransition = 8,
+    /// No healthcare-credentialing contract configured, or no case linked.
+    CredentialingNotConfigured = 9,
+    /// The credentialing case belongs to a different provider.
+    CaseMismatch = 10,
+    TooManyCredentials = 11,
+    /// The credentialing contract has no case with this id.
+    CaseNotFound = 12,
 }
 
 /// --------------------
 /// Doctor Structures
 /// --------------------
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum VerificationStatus {
+    Unverified,
+    UnderReview,
+    Verified,
+    /// The credentialing case failed.
+    Rejected,
+    /// Recredentialing deadline missed; restored when the case is approved again.
+    Lapsed,
+    /// Suspended by the registry admin.
+    Suspended,
+    /// Permanently removed from the directory.
+    Revoked,
+}
+
+/// A license to practise, identified by number within a jurisdiction.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct License {
+    pub license_number: String,
+    /// Issuing jurisdiction, e.g. `US_CA` or `UK_GMC`.
+    pub jurisdiction: Symbol,
+    pub issued_at: u64,
+    pub expires_at: u64,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BoardCertification {
+    pub board: String,
+    pub specialty: String,
+    pub certified_at: u64,
+    pub expires_at: u64,
+}
+
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct DoctorProfileData {
@@ -47,6 +121,50 @@ pub struct DoctorProfileData {
     pub specialization: String,
     pub institution_wallet: Address,
     pub metadata: String,
+    pub licenses: Vec<License>,
+    pub board_certifications: Vec<BoardCertification>,
+    pub verification_status: VerificationStatus,
+    pub status_updated_at: u64,
+    /// healthcare-credentialing case driving verification; 0 when none linked.
+    pub credentialing_case_id: u64,
+}
+
+// ── Cross-contract interface for healthcare-credentialing ────────────────────
+//
+// Mirrors the credentialing contract's `CredentialingStatus` and
+// `CredentialingCase` so the generated client decodes its return value.
+// Variant and field names must stay in sync with that contract.
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum CredentialingStatus {
+    Incomplete,
+    InReview,
+    CommitteeReview,
+    Approved,
+    Denied,
+    DeferredForMoreInfo,
+    RecredentialingInProgress,
+    RecredentialingExpired,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CredentialingCase {
+    pub case_id: u64,
+    pub provider_id: Address,
+    pub facility_id: Address,
+    pub case_type: Symbol,
+    pub status: CredentialingStatus,
+    pub initiated_date: u64,
+    pub target_completion_date: u64,
+    pub verifications_complete: u32,
+    pub verifications_required: u32,
+}
+
+#[contractclient(name = "CredentialingClient")]
+pub trait CredentialingInterface {
+    fn get_credentialing_case(env: Env, case_id: u64) -> CredentialingCase;
 }
 
 /// --------------------
@@ -56,6 +174,8 @@ pub struct DoctorProfileData {
 pub enum DataKey {
     Admin,
     Doctor(Address),
+    /// healthcare-credentialing contract address
+    Credentialing,
 }
 
 #[contract]
@@ -107,6 +227,11 @@ impl DoctorRegistry {
             specialization,
             institution_wallet,
             metadata: String::from_str(&env, ""),
+            licenses: Vec::new(&env),
+            board_certifications: Vec::new(&env),
+            verification_status: VerificationStatus::Unverified,
+            status_updated_at: env.ledger().timestamp(),
+            credentialing_case_id: 0,
         };
 
         env.storage().persistent().set(&key, &doctor_profile);
@@ -155,11 +280,274 @@ impl DoctorRegistry {
 
     pub fn get_doctor_profile(env: Env, wallet: Address) -> Result<DoctorProfileData, Error> {
         validate_nonzero_address(&wallet).map_err(|_| Error::InvalidAddress)?;
-        let key = DataKey::Doctor(wallet);
+        load_profile(&env, &wallet)
+    }
+
+    /// Add a license, replacing any existing one with the same number and
+    /// jurisdiction (renewal).
+    pub fn add_license(
+        env: Env,
+        registrar: Address,
+        wallet: Address,
+        license: License,
+    ) -> Result<(), Error> {
+        require_admin(&env, &registrar)?;
+        if license.license_number.is_empty() || license.expires_at <= license.issued_at {
+            return Err(Error::InvalidCredential);
+        }
+        let mut profile = load_profile(&env, &wallet)?;
+
+        let mut licenses = Vec::new(&env);
+        for existing in profile.licenses.iter() {
+            if existing.license_number != license.license_number
+                || existing.jurisdiction != license.jurisdiction
+            {
+                licenses.push_back(existing);
+            }
+        }
+        if licenses.len() >= MAX_CREDENTIALS_PER_KIND {
+            return Err(Error::TooManyCredentials);
+        }
+        licenses.push_back(license.clone());
+        profile.licenses = licenses;
+        save_profile(&env, &wallet, &profile);
+
+        env.events().publish(
+            (symbol_short!("lic_add"), wallet),
+            (license.jurisdiction, license.expires_at),
+        );
+        Ok(())
+    }
+
+    /// Add a board certification, replacing any existing one from the same
+    /// board for the same specialty.
+    pub fn add_board_certification(
+        env: Env,
+        registrar: Address,
+        wallet: Address,
+        certification: BoardCertification,
+    ) -> Result<(), Error> {
+        require_admin(&env, &registrar)?;
+        if certification.board.is_empty()
+            || certification.expires_at <= certification.certified_at
+        {
+            return Err(Error::InvalidCredential);
+        }
+        let mut profile = load_profile(&env, &wallet)?;
+
+        let mut certs = Vec::new(&env);
+        for existing in profile.board_certifications.iter() {
+            if existing.board != certification.board
+                || existing.specialty != certification.specialty
+            {
+                certs.push_back(existing);
+            }
+        }
+        if certs.len() >= MAX_CREDENTIALS_PER_KIND {
+            return Err(Error::TooManyCredentials);
+        }
+        certs.push_back(certification.clone());
+        profile.board_certifications = certs;
+        save_profile(&env, &wallet, &profile);
+
+        env.events().publish(
+            (symbol_short!("cert_add"), wallet),
+            (certification.specialty, certification.expires_at),
+        );
+        Ok(())
+    }
+
+    /// Point the registry at the healthcare-credentialing contract whose case
+    /// outcomes drive verification. Admin only.
+    pub fn set_credentialing_contract(
+        env: Env,
+        admin: Address,
+        credentialing: Address,
+    ) -> Result<(), Error> {
+        require_admin(&env, &admin)?;
         env.storage()
             .persistent()
-            .get(&key)
-            .ok_or(Error::ProfileNotFound)
+            .set(&DataKey::Credentialing, &credentialing);
+        Ok(())
+    }
+
+    /// Link a credentialing case and move an unverified (or rejected) doctor
+    /// to `UnderReview`. The doctor must hold an unexpired license and the
+    /// case must belong to them.
+    pub fn submit_for_verification(
+        env: Env,
+        registrar: Address,
+        wallet: Address,
+        case_id: u64,
+    ) -> Result<(), Error> {
+        require_admin(&env, &registrar)?;
+        let mut profile = load_profile(&env, &wallet)?;
+        match profile.verification_status {
+            VerificationStatus::Unverified | VerificationStatus::Rejected => {}
+            _ => return Err(Error::InvalidStatusTransition),
+        }
+        if !has_valid_license(&env, &profile) {
+            return Err(Error::NoValidLicense);
+        }
+        let case = fetch_case(&env, case_id)?;
+        if case.provider_id != wallet {
+            return Err(Error::CaseMismatch);
+        }
+
+        profile.credentialing_case_id = case_id;
+        set_status(&env, &wallet, &mut profile, VerificationStatus::UnderReview);
+        Ok(())
+    }
+
+    /// Pull the linked credentialing case and apply its outcome. Callable by
+    /// anyone; the result depends only on the credentialing contract's state.
+    pub fn sync_verification(env: Env, wallet: Address) -> Result<VerificationStatus, Error> {
+        let mut profile = load_profile(&env, &wallet)?;
+        match profile.verification_status {
+            VerificationStatus::Suspended | VerificationStatus::Revoked => {
+                return Ok(profile.verification_status)
+            }
+            VerificationStatus::Unverified => return Err(Error::InvalidStatusTransition),
+            _ => {}
+        }
+        if profile.credentialing_case_id == 0 {
+            return Err(Error::CredentialingNotConfigured);
+        }
+        let case = fetch_case(&env, profile.credentialing_case_id)?;
+        if case.provider_id != wallet {
+            return Err(Error::CaseMismatch);
+        }
+
+        let next = match case.status {
+            CredentialingStatus::Approved | CredentialingStatus::RecredentialingInProgress => {
+                VerificationStatus::Verified
+            }
+            CredentialingStatus::Denied => VerificationStatus::Rejected,
+            CredentialingStatus::RecredentialingExpired => VerificationStatus::Lapsed,
+            _ => VerificationStatus::UnderReview,
+        };
+        if next != profile.verification_status {
+            set_status(&env, &wallet, &mut profile, next);
+        }
+        Ok(next)
+    }
+
+    /// Suspend a doctor. Admin only; not undone by `sync_verification`.
+    pub fn suspend_doctor(
+        env: Env,
+        registrar: Address,
+        wallet: Address,
+        reason: String,
+    ) -> Result<(), Error> {
+        require_admin(&env, &registrar)?;
+        let mut profile = load_profile(&env, &wallet)?;
+        if profile.verification_status == VerificationStatus::Suspended
+            || profile.verification_status == VerificationStatus::Revoked
+        {
+            return Err(Error::InvalidStatusTransition);
+        }
+        set_status(&env, &wallet, &mut profile, VerificationStatus::Suspended);
+        env.events()
+            .publish((symbol_short!("doc_susp"), wallet), reason);
+        Ok(())
+    }
+
+    /// Lift a suspension. The doctor returns to `UnderReview` until the next
+    /// `sync_verification`.
+    pub fn reinstate_doctor(env: Env, registrar: Address, wallet: Address) -> Result<(), Error> {
+        require_admin(&env, &registrar)?;
+        let mut profile = load_profile(&env, &wallet)?;
+        if profile.verification_status != VerificationStatus::Suspended {
+            return Err(Error::InvalidStatusTransition);
+        }
+        let next = if profile.credentialing_case_id == 0 {
+            VerificationStatus::Unverified
+        } else {
+            VerificationStatus::UnderReview
+        };
+        set_status(&env, &wallet, &mut profile, next);
+        Ok(())
+    }
+
+    /// Permanently revoke a doctor. Admin only.
+    pub fn revoke_doctor(
+        env: Env,
+        registrar: Address,
+        wallet: Address,
+        reason: String,
+    ) -> Result<(), Error> {
+        require_admin(&env, &registrar)?;
+        let mut profile = load_profile(&env, &wallet)?;
+        if profile.verification_status == VerificationStatus::Revoked {
+            return Err(Error::InvalidStatusTransition);
+        }
+        set_status(&env, &wallet, &mut profile, VerificationStatus::Revoked);
+        env.events()
+            .publish((symbol_short!("doc_rev"), wallet), reason);
+        Ok(())
+    }
+
+    pub fn get_verification_status(env: Env, wallet: Address) -> Result<VerificationStatus, Error> {
+        Ok(load_profile(&env, &wallet)?.verification_status)
+    }
+
+    /// `true` when the doctor is `Verified` and holds an unexpired license.
+    /// Lookup target for `shared::actor_verification` (`ActorType::Doctor`).
+    pub fn is_verified_doctor(env: Env, wallet: Address) -> bool {
+        match env
+            .storage()
+            .persistent()
+            .get::<_, DoctorProfileData>(&DataKey::Doctor(wallet))
+        {
+            Some(profile) => {
+                profile.verification_status == VerificationStatus::Verified
+                    && has_valid_license(&env, &profile)
+            }
+            None => false,
+        }
+    }
+}
+
+fn load_profile(env: &Env, wallet: &Address) -> Result<DoctorProfileData, Error> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::Doctor(wallet.clone()))
+        .ok_or(Error::ProfileNotFound)
+}
+
+fn save_profile(env: &Env, wallet: &Address, profile: &DoctorProfileData) {
+    env.storage()
+        .persistent()
+        .set(&DataKey::Doctor(wallet.clone()), profile);
+}
+
+fn set_status(
+    env: &Env,
+    wallet: &Address,
+    profile: &mut DoctorProfileData,
+    status: VerificationStatus,
+) {
+    profile.verification_status = status;
+    profile.status_updated_at = env.ledger().timestamp();
+    save_profile(env, wallet, profile);
+    env.events()
+        .publish((symbol_short!("ver_upd"), wallet.clone()), status);
+}
+
+fn has_valid_license(env: &Env, profile: &DoctorProfileData) -> bool {
+    let now = env.ledger().timestamp();
+    profile.licenses.iter().any(|l| l.expires_at > now)
+}
+
+fn fetch_case(env: &Env, case_id: u64) -> Result<CredentialingCase, Error> {
+    let credentialing: Address = env
+        .storage()
+        .persistent()
+        .get(&DataKey::Credentialing)
+        .ok_or(Error::CredentialingNotConfigured)?;
+    match CredentialingClient::new(env, &credentialing).try_get_credentialing_case(&case_id) {
+        Ok(Ok(case)) => Ok(case),
+        _ => Err(Error::CaseNotFound),
     }
 }
 
@@ -253,3 +253,360 @@ fn test_non_admin_cannot_create_profile() {
 
     assert_eq!(result, Err(Ok(Error::Unauthorized)));
 }
+
+// ── licenses, certifications and verification workflow ──────────────────────
+
+use healthcare_credentialing::{
+    HealthcareCredentialingSystem, HealthcareCredentialingSystemClient,
+};
+use shared::actor_verification::{verify_actor, ActorType, VerificationKey};
+use soroban_sdk::{contract, testutils::Ledger, BytesN, Symbol, Vec};
+
+const YEAR: u64 = 365 * 24 * 60 * 60;
+
+struct Setup {
+    env: Env,
+    client: DoctorRegistryClient<'static>,
+    credentialing: HealthcareCredentialingSystemClient<'static>,
+    admin: Address,
+    doctor: Address,
+    facility: Address,
+}
+
+fn setup() -> Setup {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.ledger().set_timestamp(1_700_000_000);
+
+    let client = DoctorRegistryClient::new(&env, &env.register(DoctorRegistry, ()));
+    let credentialing = HealthcareCredentialingSystemClient::new(
+        &env,
+        &env.register(HealthcareCredentialingSystem, ()),
+    );
+    let admin = Address::generate(&env);
+    let doctor = Address::generate(&env);
+    let facility = Address::generate(&env);
+
+    client.initialize(&admin);
+    client.set_credentialing_contract(&admin, &credentialing.address);
+    credentialing.initialize(&admin);
+
+    client.create_doctor_profile(
+        &admin,
+        &doctor,
+        &String::from_str(&env, "Dr. Ada Byrne"),
+        &String::from_str(&env, "Cardiology"),
+        &Address::generate(&env),
+    );
+    Setup {
+        env,
+        client,
+        credentialing,
+        admin,
+        doctor,
+        facility,
+    }
+}
+
+fn license(env: &Env, number: &str, expires_at: u64) -> License {
+    License {
+        license_number: String::from_str(env, number),
+        jurisdiction: Symbol::new(env, "US_CA"),
+        issued_at: 1_600_000_000,
+        expires_at,
+    }
+}
+
+fn open_case(s: &Setup) -> u64 {
+    let mut requested = Vec::new(&s.env);
+    requested.push_back(Symbol::new(&s.env, "cardiology"));
+    s.credentialing
+        .initiate_credentialing(&s.doctor, &s.facility, &1_700_000_000, &requested)
+}
+
+/// Drive a credentialing case all the way to `Approved`.
+fn approve_case(s: &Setup, case_id: u64) {
+    let verifier = Address::generate(&s.env);
+    let committee = Address::generate(&s.env);
+    s.credentialing.add_authorized_verifier(&s.admin, &verifier);
+    s.credentialing.add_committee_member(&s.admin, &committee);
+    let license_doc = Symbol::new(&s.env, "medical_license");
+    s.credentialing.submit_credential_document(
+        &case_id,
+        &s.doctor,
+        &license_doc,
+        &BytesN::from_array(&s.env, &[1; 32]),
+        &String::from_str(&s.env, "Medical Board of California"),
+        &1_600_000_000,
+        &Some(1_900_000_000),
+    );
+    for _ in 0..5 {
+        s.credentialing.verify_credential(
+            &case_id,
+            &license_doc,
+            &verifier,
+            &Symbol::new(&s.env, "primary_source"),
+            &true,
+            &1_700_000_000,
+            &String::from_str(&s.env, "ok"),
+        );
+    }
+    let mut privileges = Vec::new(&s.env);
+    privileges.push_back(Symbol::new(&s.env, "cardiology"));
+    s.credentialing.grant_privileges(
+        &case_id,
+        &committee,
+        &privileges,
+        &None,
+        &1_700_000_000,
+        &(1_700_000_000 + 2 * YEAR),
+    );
+}
+
+#[test]
+fn test_add_license_and_certification() {
+    let s = setup();
+    s.client.add_license(
+        &s.admin,
+        &s.doctor,
+        &license(&s.env, "A12345", 1_800_000_000),
+    );
+    s.client.add_board_certification(
+        &s.admin,
+        &s.doctor,
+        &BoardCertification {
+            board: String::from_str(&s.env, "ABIM"),
+            specialty: String::from_str(&s.env, "Cardiovascular Disease"),
+            certified_at: 1_650_000_000,
+            expires_at: 1_950_000_000,
+        },
+    );
+
+    // Renewal replaces the license with the same number and jurisdiction.
+    s.client.add_license(
+        &s.admin,
+        &s.doctor,
+        &license(&s.env, "A12345", 1_900_000_000),
+    );
+    s.client
+        .add_license(&s.admin, &s.doctor, &license(&s.env, "B777", 1_800_000_000));
+
+    let profile = s.client.get_doctor_profile(&s.doctor);
+    assert_eq!(profile.licenses.len(), 2);
+    assert_eq!(
+        profile.licenses.get(0).unwrap().license_number,
+        String::from_str(&s.env, "A12345")
+    );
+    assert_eq!(profile.licenses.get(0).unwrap().expires_at, 1_900_000_000);
+    assert_eq!(profile.board_certifications.len(), 1);
+    assert_eq!(profile.verification_status, VerificationStatus::Unverified);
+}
+
+#[test]
+fn test_invalid_license_rejected() {
+    let s = setup();
+    assert_eq!(
+        s.client
+            .try_add_license(&s.admin, &s.doctor, &license(&s.env, "", 1_800_000_000)),
+        Err(Ok(Error::InvalidCredential))
+    );
+    assert_eq!(
+        s.client
+            .try_add_license(&s.admin, &s.doctor, &license(&s.env, "A1", 1_500_000_000)),
+        Err(Ok(Error::InvalidCredential))
+    );
+    let outsider = Address::generate(&s.env);
+    assert_eq!(
+        s.client
+            .try_add_license(&outsider, &s.doctor, &license(&s.env, "A1", 1_800_000_000)),
+        Err(Ok(Error::Unauthorized))
+    );
+}
+
+#[test]
+fn test_verification_requires_valid_license_and_own_case() {
+    let s = setup();
+    let case_id = open_case(&s);
+    assert_eq!(
+        s.client
+            .try_submit_for_verification(&s.admin, &s.doctor, &case_id),
+        Err(Ok(Error::NoValidLicense))
+    );
+
+    // An expired license does not count.
+    s.client
+        .add_license(&s.admin, &s.doctor, &license(&s.env, "OLD", 1_650_000_000));
+    assert_eq!(
+        s.client
+            .try_submit_for_verification(&s.admin, &s.doctor, &case_id),
+        Err(Ok(Error::NoValidLicense))
+    );
+
+    s.client
+        .add_license(&s.admin, &s.doctor, &license(&s.env, "A1", 1_800_000_000));
+    let other = Address::generate(&s.env);
+    let mut requested = Vec::new(&s.env);
+    requested.push_back(Symbol::new(&s.env, "surgery"));
+    let other_case =
+        s.credentialing
+            .initiate_credentialing(&other, &s.facility, &1_700_000_000, &requested);
+    assert_eq!(
+        s.client
+            .try_submit_for_verification(&s.admin, &s.doctor, &other_case),
+        Err(Ok(Error::CaseMismatch))
+    );
+    assert_eq!(
+        s.client
+            .try_submit_for_verification(&s.admin, &s.doctor, &999),
+        Err(Ok(Error::CaseNotFound))
+    );
+
+    s.client
+        .submit_for_verification(&s.admin, &s.doctor, &case_id);
+    assert_eq!(
+        s.client.get_verification_status(&s.doctor),
+        VerificationStatus::UnderReview
+    );
+}
+
+#[test]
+fn test_credentialing_approval_verifies_doctor() {
+    let s = setup();
+    s.client
+        .add_license(&s.admin, &s.doctor, &license(&s.env, "A1", 1_800_000_000));
+    let case_id = open_case(&s);
+    s.client
+        .submit_for_verification(&s.admin, &s.doctor, &case_id);
+
+    // Case still in progress.
+    assert_eq!(
+        s.client.sync_verification(&s.doctor),
+        VerificationStatus::UnderReview
+    );
+    assert!(!s.client.is_verified_doctor(&s.doctor));
+
+    approve_case(&s, case_id);
+    assert_eq!(
+        s.client.sync_verification(&s.doctor),
+        VerificationStatus::Verified
+    );
+    assert!(s.client.is_verified_doctor(&s.doctor));
+
+    // License expiry removes the doctor from verified lookups.
+    s.env.ledger().set_timestamp(1_800_000_001);
+    assert!(!s.client.is_verified_doctor(&s.doctor));
+}
+
+#[test]
+fn test_credentialing_denial_rejects_doctor() {
+    let s = setup();
+    s.client
+        .add_license(&s.admin, &s.doctor, &license(&s.env, "A1", 1_800_000_000));
+    let case_id = open_case(&s);
+    s.client
+        .submit_for_verification(&s.admin, &s.doctor, &case_id);
+
+    let checker = Address::generate(&s.env);
+    s.credentialing.add_authorized_verifier(&s.admin, &checker);
+    let mut dbs = Vec::new(&s.env);
+    dbs.push_back(Symbol::new(&s.env, "NPDB"));
+    s.credentialing
+        .check_sanctions(&case_id, &checker, &dbs, &true, &1_700_000_000);
+
+    assert_eq!(
+        s.client.sync_verification(&s.doctor),
+        VerificationStatus::Rejected
+    );
+
+    // A rejected doctor may be resubmitted with a new case.
+    let retry = open_case(&s);
+    s.client
+        .submit_for_verification(&s.admin, &s.doctor, &retry);
+    assert_eq!(
+        s.client.get_doctor_profile(&s.doctor).credentialing_case_id,
+        retry
+    );
+}
+
+#[test]
+fn test_suspension_is_not_lifted_by_sync() {
+    let s = setup();
+    s.client
+        .add_license(&s.admin, &s.doctor, &license(&s.env, "A1", 1_800_000_000));
+    let case_id = open_case(&s);
+    s.client
+        .submit_for_verification(&s.admin, &s.doctor, &case_id);
+    approve_case(&s, case_id);
+    s.client.sync_verification(&s.doctor);
+
+    s.client
+        .suspend_doctor(&s.admin, &s.doctor, &String::from_str(&s.env, "complaint"));
+    assert_eq!(
+        s.client.sync_verification(&s.doctor),
+        VerificationStatus::Suspended
+    );
+    assert!(!s.client.is_verified_doctor(&s.doctor));
+
+    s.client.reinstate_doctor(&s.admin, &s.doctor);
+    assert_eq!(
+        s.client.get_verification_status(&s.doctor),
+        VerificationStatus::UnderReview
+    );
+    assert_eq!(
+        s.client.sync_verification(&s.doctor),
+        VerificationStatus::Verified
+    );
+
+    s.client
+        .revoke_doctor(&s.admin, &s.doctor, &String::from_str(&s.env, "fraud"));
+    assert_eq!(
+        s.client.sync_verification(&s.doctor),
+        VerificationStatus::Revoked
+    );
+    assert_eq!(
+        s.client.try_reinstate_doctor(&s.admin, &s.doctor),
+        Err(Ok(Error::InvalidStatusTransition))
+    );
+    assert_eq!(
+        s.client
+            .try_submit_for_verification(&s.admin, &s.doctor, &case_id),
+        Err(Ok(Error::InvalidStatusTransition))
+    );
+}
+
+#[test]
+fn test_sync_requires_linked_case() {
+    let s = setup();
+    assert_eq!(
+        s.client.try_sync_verification(&s.doctor),
+        Err(Ok(Error::InvalidStatusTransition))
+    );
+    assert!(!s.client.is_verified_doctor(&Address::generate(&s.env)));
+}
+
+/// Stand-in for any contract that verifies actors through `shared`.
+#[contract]
+struct ActorCheckHost;
+
+#[test]
+fn test_verify_actor_doctor_uses_registry() {
+    let s = setup();
+    s.client
+        .add_license(&s.admin, &s.doctor, &license(&s.env, "A1", 1_800_000_000));
+    let case_id = open_case(&s);
+    s.client
+        .submit_for_verification(&s.admin, &s.doctor, &case_id);
+    approve_case(&s, case_id);
+    s.client.sync_verification(&s.doctor);
+
+    let host = s.env.register(ActorCheckHost, ());
+    let stranger = Address::generate(&s.env);
+    s.env.as_contract(&host, || {
+        s.env
+            .storage()
+            .instance()
+            .set(&VerificationKey::DoctorRegistry, &s.client.address);
+        assert!(verify_actor(&s.env, ActorType::Doctor, &s.doctor));
+        assert!(!verify_actor(&s.env, ActorType::Doctor, &stranger));
+    });
+}
@@ -25,9 +25,17 @@
 //! **Encryption/Integrity:** Credential data stored encrypted in persistent storage. Credential
 //! type enumeration prevents invalid credential categories. Expiration timestamp comparison
 //! prevents use of expired credentials. Provider address validation via registry.
+//!
+//! **Verifiable Credentials:** Once a credential registry is configured, every approval in
+//! `grant_privileges` or `complete_recredentialing` anchors a privileging credential in the access-control registry, issued
+//! under this contract's own DID so any verifier can check its status. `suspend_privileges` revokes
+//! the provider's live credential at that facility, and `reinstate_privileges` issues a fresh one,
+//! since a status-list bit cannot be cleared.
 
+use shared::credentials::{self, AnchoredCredential, CredentialRegistryClient};
 use soroban_sdk::{
-    contract, contracterror, contractimpl, contracttype, Address, BytesN, Env, String, Symbol, Vec,
+    contract, contracterror, contractimpl, contracttype, Address, Bytes, BytesN, Env, String,
+    Symbol, Vec,
 };
 
 const REQUIRED_CREDENTIALS: u32 = 5;
@@ -49,6 +57,7 @@ pub enum Error {
     CredentialExpired = 11,
     RecredentialingInProgress = 12,
     AlreadyInitialized = 13,
+    CredentialRegistryNotSet = 14,
 }
 
 #[contracttype]
@@ -225,6 +234,18 @@ pub enum DataKey {
     Admin,
     AuthorizedVerifier(Address),
     CommitteeMember(Address),
+    /// access-control contract that anchors issued credentials.
+    CredentialRegistry,
+    /// Schema hash of the privileging credential.
+    PrivilegeSchema,
+    /// case_id -> AnchoredCredential, the latest credential issued for the case.
+    CaseCredential(u64),
+    /// u64 local id of the last credential issued; see `derive_credential_id`.
+    CredentialCounter,
+    /// (provider, facility) -> u64 case whose credential is live.
+    ProviderFacilityCredential(Address, Address),
+    /// (provider, facility) -> u64 case whose credential was revoked on suspension.
+    SuspendedCredential(Address, Address),
 }
 
 #[contract]
@@ -253,6 +274,52 @@ impl HealthcareCredentialingSystem {
         Ok(())
     }
 
+    /// Point approvals at the access-control credential registry. `schema_hash`
+    /// identifies the privileging credential schema. Admin only.
+    pub fn set_credential_registry(
+        env: Env,
+        admin: Address,
+        registry: Address,
+        schema_hash: BytesN<32>,
+    ) -> Result<(), Error> {
+        admin.require_auth();
+        require_admin(&env, &admin)?;
+        env.storage()
+            .instance()
+            .set(&DataKey::CredentialRegistry, &registry);
+        env.storage()
+            .instance()
+            .set(&DataKey::PrivilegeSchema, &schema_hash);
+        Ok(())
+    }
+
+    /// Register this contract's DID and signing key with the credential
+    /// registry so it can issue credentials. Admin only, once.
+    pub fn register_issuer_did(
+        env: Env,
+        admin: Address,
+        did: Bytes,
+        key_id: String,
+        public_key: BytesN<32>,
+    ) -> Result<(), Error> {
+        admin.require_auth();
+        require_admin(&env, &admin)?;
+        let registry: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::CredentialRegistry)
+            .ok_or(Error::CredentialRegistryNotSet)?;
+        credentials::register_issuer(&env, &registry, &did, &key_id, &public_key);
+        Ok(())
+    }
+
+    /// Latest credential anchored for `case_id`, if any.
+    pub fn get_case_credential(env: Env, case_id: u64) -> Option<AnchoredCredential> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::CaseCredential(case_id))
+    }
+
     /// Revoke an address's authorized-verifier status. Admin only.
     pub fn remove_authorized_verifier(env: Env, admin: Address, verifier: Address) -> Result<(), Error> {
         admin.require_auth();
@@ -566,6 +633,7 @@ impl HealthcareCredentialingSystem {
         }
 
         case.status = CredentialingStatus::Approved;
+        issue_privilege_credential(&env, &case, expiration_date);
         env.storage()
             .instance()
             .set(&DataKey::PrivilegeCounter, &privilege_counter);
@@ -804,9 +872,10 @@ impl HealthcareCredentialingSystem {
             &privileges,
         );
         env.storage().persistent().set(
-            &DataKey::ProviderFacilitySuspensions(provider_id, facility_id),
+            &DataKey::ProviderFacilitySuspensions(provider_id.clone(), facility_id.clone()),
             &history,
         );
+        revoke_privilege_credential(&env, &provider_id, &facility_id);
         Ok(())
     }
 
@@ -864,9 +933,20 @@ impl HealthcareCredentialingSystem {
             &privileges,
         );
         env.storage().persistent().set(
-            &DataKey::ProviderFacilityReinstatements(provider_id, facility_id),
+            &DataKey::ProviderFacilityReinstatements(provider_id.clone(), facility_id.clone()),
             &history,
         );
+
+        let suspended_key = DataKey::SuspendedCredential(provider_id, facility_id);
+        if let Some(case_id) = env.storage().persistent().get::<DataKey, u64>(&suspended_key) {
+            env.storage().persistent().remove(&suspended_key);
+            let expires_at = privileges
+                .iter()
+                .map(|p| p.expiration_date)
+                .max()
+                .unwrap_or(0);
+            issue_privilege_credential(&env, &get_case(&env, case_id)?, expires_at);
+        }
         Ok(())
     }
 
@@ -1103,6 +1183,7 @@ impl HealthcareCredentialingSystem {
         }
 
         case.status = CredentialingStatus::Approved;
+        issue_privilege_credential(&env, &case, new_expiration_date);
         env.storage().persistent().set(
             &DataKey::ProviderFacilityPrivileges(
                 case.provider_id.clone(),
@@ -1125,6 +1206,71 @@ fn get_case(env: &Env, case_id: u64) -> Result<CredentialingCase, Error> {
         .ok_or(Error::CaseNotFound)
 }
 
+/// Anchor a privileging credential for an approved case when a registry is
+/// configured. The credential expires with the granted privileges and becomes
+/// the provider's live credential at the case's facility.
+fn issue_privilege_credential(env: &Env, case: &CredentialingCase, expires_at: u64) {
+    let Some(registry) = env
+        .storage()
+        .instance()
+        .get::<DataKey, Address>(&DataKey::CredentialRegistry)
+    else {
+        return;
+    };
+    let schema_hash: BytesN<32> = env
+        .storage()
+        .instance()
+        .get(&DataKey::PrivilegeSchema)
+        .unwrap();
+    let local_id: u64 = env
+        .storage()
+        .instance()
+        .get(&DataKey::CredentialCounter)
+        .unwrap_or(0)
+        + 1;
+    env.storage()
+        .instance()
+        .set(&DataKey::CredentialCounter, &local_id);
+    let credential_id = credentials::derive_credential_id(env, local_id);
+    CredentialRegistryClient::new(env, &registry).issue_credential(
+        &env.current_contract_address(),
+        &case.provider_id,
+        &credential_id,
+        &schema_hash,
+        &expires_at,
+    );
+    env.storage().persistent().set(
+        &DataKey::CaseCredential(case.case_id),
+        &AnchoredCredential { registry, credential_id },
+    );
+    env.storage().persistent().set(
+        &DataKey::ProviderFacilityCredential(case.provider_id.clone(), case.facility_id.clone()),
+        &case.case_id,
+    );
+}
+
+/// Revoke the provider's live credential at `facility_id`, if any, in the
+/// registry it was issued in, and remember its case so reinstatement can
+/// issue a replacement.
+fn revoke_privilege_credential(env: &Env, provider_id: &Address, facility_id: &Address) {
+    let live_key = DataKey::ProviderFacilityCredential(provider_id.clone(), facility_id.clone());
+    let Some(case_id) = env.storage().persistent().get::<DataKey, u64>(&live_key) else {
+        return;
+    };
+    let credential: AnchoredCredential = env
+        .storage()
+        .persistent()
+        .get(&DataKey::CaseCredential(case_id))
+        .unwrap();
+    CredentialRegistryClient::new(env, &credential.registry)
+        .revoke_credential(&env.current_contract_address(), &credential.credential_id);
+    env.storage().persistent().remove(&live_key);
+    env.storage().persistent().set(
+        &DataKey::SuspendedCredential(provider_id.clone(), facility_id.clone()),
+        &case_id,
+    );
+}
+
 fn require_admin(env: &Env, caller: &Address) -> Result<(), Error> {
     let admin: Address = env
         .storage()
@@ -1266,13 +1412,34 @@ mod test {
         client.initiate_credentialing(provider, facility, &1_700_000_000, &requested)
     }
 
+    /// Initialize with a fresh admin and register the given verifiers and
+    /// committee members.
+    fn setup_roles(
+        env: &Env,
+        client: &HealthcareCredentialingSystemClient,
+        verifiers: &[&Address],
+        committee: &[&Address],
+    ) -> Address {
+        let admin = Address::generate(env);
+        client.initialize(&admin);
+        for verifier in verifiers {
+            client.add_authorized_verifier(&admin, verifier);
+        }
+        for member in committee {
+            client.add_committee_member(&admin, member);
+        }
+        admin
+    }
+
     fn submit_required_docs(env: &Env, client: &HealthcareCredentialingSystemClient, case_id: u64) {
         let types = ["medical_license", "dea", "board_cert", "cv", "references"];
+        let provider = client.get_credentialing_case(&case_id).provider_id;
 
         let mut idx: usize = 0;
         while idx < types.len() {
             client.submit_credential_document(
                 &case_id,
+                &provider,
                 &Symbol::new(env, types[idx]),
                 &BytesN::from_array(env, &[idx as u8; 32]),
                 &String::from_str(env, "Issuer"),
@@ -1297,6 +1464,12 @@ mod test {
         let checker = Address::generate(&env);
         let ref_provider = Address::generate(&env);
         let committee = Address::generate(&env);
+        setup_roles(
+            &env,
+            &client,
+            &[&verifier, &checker, &ref_provider],
+            &[&committee],
+        );
 
         let case_id = create_case(&env, &client, &provider, &facility);
         submit_required_docs(&env, &client, case_id);
@@ -1356,6 +1529,8 @@ mod test {
 
         let privileges = client.get_provider_privileges(&provider, &facility);
         assert_eq!(privileges.len(), 2);
+        // No credential registry configured: nothing is anchored.
+        assert_eq!(client.get_case_credential(&case_id), None);
     }
 
     #[test]
@@ -1369,6 +1544,7 @@ mod test {
         let provider = Address::generate(&env);
         let facility = Address::generate(&env);
         let verifier = Address::generate(&env);
+        setup_roles(&env, &client, &[&verifier], &[]);
         let case_id = create_case(&env, &client, &provider, &facility);
 
         let res = client.try_verify_credential(
@@ -1394,6 +1570,7 @@ mod test {
         let provider = Address::generate(&env);
         let facility = Address::generate(&env);
         let ref_provider = Address::generate(&env);
+        setup_roles(&env, &client, &[&ref_provider], &[]);
         let case_id = create_case(&env, &client, &provider, &facility);
 
         let mut ratings = Vec::new(&env);
@@ -1423,6 +1600,7 @@ mod test {
         let provider = Address::generate(&env);
         let facility = Address::generate(&env);
         let checker = Address::generate(&env);
+        setup_roles(&env, &client, &[&checker], &[]);
         let case_id = create_case(&env, &client, &provider, &facility);
 
         let mut dbs = Vec::new(&env);
@@ -1496,6 +1674,12 @@ mod test {
         let ref_provider = Address::generate(&env);
         let committee = Address::generate(&env);
         let authority = Address::generate(&env);
+        setup_roles(
+            &env,
+            &client,
+            &[&verifier, &checker, &ref_provider],
+            &[&committee],
+        );
 
         let case_id = create_case(&env, &client, &provider, &facility);
         submit_required_docs(&env, &client, case_id);
@@ -1572,4 +1756,102 @@ mod test {
             &String::from_str(&env, "SUSPENDED")
         ));
     }
+
+    #[test]
+    fn approval_issues_verifiable_credential() {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let contract_id = env.register(HealthcareCredentialingSystem, ());
+        let client = HealthcareCredentialingSystemClient::new(&env, &contract_id);
+        let registry_id = env.register(access_control::AccessControl, ());
+        let registry = access_control::AccessControlClient::new(&env, &registry_id);
+
+        let provider = Address::generate(&env);
+        let facility = Address::generate(&env);
+        let verifier = Address::generate(&env);
+        let committee = Address::generate(&env);
+        let admin = setup_roles(&env, &client, &[&verifier], &[&committee]);
+
+        let schema = BytesN::from_array(&env, &[3; 32]);
+        assert_eq!(
+            client.try_register_issuer_did(
+                &admin,
+                &Bytes::from_slice(&env, b"did:stellar:credentialing:main"),
+                &String::from_str(&env, "key-1"),
+                &BytesN::from_array(&env, &[1; 32]),
+            ),
+            Err(Ok(Error::CredentialRegistryNotSet))
+        );
+        client.set_credential_registry(&admin, &registry_id, &schema);
+        client.register_issuer_did(
+            &admin,
+            &Bytes::from_slice(&env, b"did:stellar:credentialing:main"),
+            &String::from_str(&env, "key-1"),
+            &BytesN::from_array(&env, &[1; 32]),
+        );
+        assert!(!registry.resolve_did(&contract_id).deactivated);
+
+        let case_id = create_case(&env, &client, &provider, &facility);
+        submit_required_docs(&env, &client, case_id);
+        for doc_type in ["medical_license", "dea", "board_cert", "cv", "references"] {
+            client.verify_credential(
+                &case_id,
+                &Symbol::new(&env, doc_type),
+                &verifier,
+                &Symbol::new(&env, "primary_source"),
+                &true,
+                &1_700_010_000,
+                &String::from_str(&env, "Verified"),
+            );
+        }
+        let mut approved = Vec::new(&env);
+        approved.push_back(Symbol::new(&env, "surgery"));
+        client.grant_privileges(
+            &case_id,
+            &committee,
+            &approved,
+            &None,
+            &1_700_030_000,
+            &1_900_000_000,
+        );
+
+        let anchored = client.get_case_credential(&case_id).unwrap();
+        assert_eq!(anchored.registry, registry_id);
+        let credential_id = anchored.credential_id;
+        let credential = registry.get_credential(&contract_id, &credential_id);
+        assert_eq!(credential.issuer, contract_id);
+        assert_eq!(credential.subject, provider);
+        assert_eq!(credential.schema_hash, schema);
+        assert_eq!(credential.expires_at, 1_900_000_000);
+        assert!(!registry.is_credential_revoked(&contract_id, &credential_id));
+
+        // Suspension revokes the live credential; reinstatement issues a new one.
+        let authority = Address::generate(&env);
+        client.suspend_privileges(
+            &provider,
+            &facility,
+            &authority,
+            &String::from_str(&env, "Quality concern"),
+            &1_700_040_000,
+            &true,
+            &false,
+        );
+        assert!(registry.is_credential_revoked(&contract_id, &credential_id));
+
+        client.reinstate_privileges(
+            &provider,
+            &facility,
+            &authority,
+            &Vec::new(&env),
+            &Vec::new(&env),
+        );
+        let reissued = client.get_case_credential(&case_id).unwrap().credential_id;
+        assert_ne!(reissued, credential_id);
+        assert!(registry.is_credential_revoked(&contract_id, &credential_id));
+        assert!(!registry.is_credential_revoked(&contract_id, &reissued));
+        let credential = registry.get_credential(&contract_id, &reissued);
+        assert_eq!(credential.subject, provider);
+        assert_eq!(credential.expires_at, 1_900_000_000);
+    }
 }
@@ -23,11 +23,55 @@
 //! **Encryption/Integrity:** Lab values stored encrypted in persistent storage. Test result
 //! reference ranges validated. Pathologist digital signature via address authentication. Critical
 //! value thresholds immutable. Specimen type enumeration prevents invalid tests.
+//!
+//! ## Order lifecycle
+//!
+//! ```text
+//! Ordered ──assign_lab──▶ Assigned ──record_specimen_collection──▶ SpecimenCollected
+//!    │                       │                                            │
+//!    │                       └──────────────submit_results────────────────┤
+//!    │                                                                    ▼
+//!    └──────cancel_order (any state before Completed)──▶ Cancelled    Completed
+//! ```
+//!
+//! A completed order's results may be corrected with `amend_results`; the superseded
+//! `results_hash` is kept in an append-only amendment trail.
+//!
+//! ## Result access
+//!
+//! The ordering provider and the patient can always read an order's results. Anyone else,
+//! including the performing lab, needs active patient consent in the access-control contract
+//! for the supplied purpose code with the read scope bit (`0x01`). Such reads go through
+//! access-control's `access_with_consent`, which records each one in the patient's
+//! accounting-of-disclosures ledger under the consent it relied on.
 
+use shared::pagination::{self, PageResult, MAX_PAGE_SIZE};
 use soroban_sdk::{
-    Address, BytesN, Env, String, Symbol, Vec, contract, contracterror, contractimpl, contracttype, vec, IntoVal,
+    Address, BytesN, Env, String, Symbol, Vec, contract, contractclient, contracterror,
+    contractimpl, contracttype,
 };
 
+/// Scope bit required in the patient's consent record to read results.
+pub const RESULTS_READ_SCOPE: u32 = 0x01;
+
+// ── Cross-contract interface for consent verification ────────────────────────
+//
+// Defines only the one method we need; the generated `AccessControlClient`
+// calls the access-control contract by function name at runtime.
+#[contractclient(name = "AccessControlClient")]
+pub trait AccessControlInterface {
+    /// Records the read in `subject`'s disclosure ledger and returns the
+    /// disclosure id; traps when consent is absent, expired, revoked or lacks
+    /// the required scope.
+    fn access_with_consent(
+        env: Env,
+        subject: Address,
+        grantee: Address,
+        purpose_code: String,
+        required_scope: u32,
+    ) -> u64;
+}
+
 #[contracterror]
 #[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
 #[repr(u32)]
@@ -38,6 +82,12 @@ pub enum Error {
     /// The lab order counter has reached u64::MAX and cannot be incremented.
     OrderIdOverflow = 5,
     ProviderNotRegistered = 6,
+    /// The order is not in a state that allows the requested transition.
+    InvalidStatus = 7,
+    /// The requester is neither the ordering provider nor the patient and the
+    /// access-control contract did not confirm patient consent.
+    ConsentNotVerified = 8,
+    AlreadyInitialized = 9,
 }
 
 #[contracttype]
@@ -53,14 +103,57 @@ pub struct TestResult {
 }
 
 #[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum LabOrderStatus {
+    Ordered,
+    Assigned,
+    SpecimenCollected,
+    Completed,
+    Cancelled,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
 pub struct LabOrder {
+    pub order_id: u64,
     pub provider_id: Address,
     pub patient_id: Address,
     pub lab_id: Option<Address>,
     pub test_panel: Vec<String>,
-    pub status: Symbol,
+    pub priority: Symbol,
+    pub status: LabOrderStatus,
     pub results_hash: Option<BytesN<32>>,
     pub quality_control_passed: bool,
+    pub ordered_at: u64,
+    pub updated_at: u64,
+    /// Set by `cancel_order`.
+    pub cancellation_reason: Option<String>,
+}
+
+/// Specimen draw recorded against an order.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SpecimenCollection {
+    pub order_id: u64,
+    pub collected_by: Address,
+    pub specimen_type: Symbol,
+    /// Hash of the specimen barcode / accession label.
+    pub specimen_id_hash: BytesN<32>,
+    pub collected_at: u64,
+    pub recorded_at: u64,
+}
+
+/// One correction to a completed order's results. `previous_hash` is the
+/// `results_hash` the correction superseded.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ResultAmendment {
+    pub order_id: u64,
+    pub amended_by: Address,
+    pub previous_hash: BytesN<32>,
+    pub new_hash: BytesN<32>,
+    pub reason: String,
+    pub amended_at: u64,
 }
 
 #[contracttype]
@@ -80,12 +173,32 @@ pub struct OrderRequest {
 /// `LabCounter`.
 #[contracttype]
 pub enum DataKey {
+    /// Address that initialized the contract
+    Admin,
     /// Per-order persistent storage: DataKey::LabOrder(order_id) -> LabOrder
     LabOrder(u64),
     /// Monotonic counter in instance storage.
     LabCounter,
     /// Provider registry contract address
     ProviderRegistry,
+    /// Access-control contract queried for patient consent
+    AccessControlId,
+    /// order_id -> SpecimenCollection
+    Specimen(u64),
+    /// order_id -> Vec<ResultAmendment>, oldest first.
+    ResultAmendments(u64),
+    /// Paged order index per patient: (patient, page_num) → Vec<u64>
+    PatientOrdersPage(Address, u32),
+    /// Current (highest-written) page index for a patient's order list
+    PatientOrdersHead(Address),
+    /// Paged order index per ordering provider
+    ProviderOrdersPage(Address, u32),
+    /// Current page index for a provider's order list
+    ProviderOrdersHead(Address),
+    /// Paged order index per assigned lab
+    LabOrdersPage(Address, u32),
+    /// Current page index for a lab's order list
+    LabOrdersHead(Address),
 }
 
 #[contract]
@@ -93,10 +206,26 @@ pub struct LabManagementContract;
 
 #[contractimpl]
 impl LabManagementContract {
-    pub fn initialize(env: Env, provider_registry: Address) -> Result<(), Error> {
+    /// Wire up the provider registry used to vet ordering providers and the
+    /// access-control contract whose `access_with_consent` gates and logs
+    /// third-party reads of lab results. Callable once, by `admin`.
+    pub fn initialize(
+        env: Env,
+        admin: Address,
+        provider_registry: Address,
+        access_control_id: Address,
+    ) -> Result<(), Error> {
+        if env.storage().instance().has(&DataKey::Admin) {
+            return Err(Error::AlreadyInitialized);
+        }
+        admin.require_auth();
+        env.storage().instance().set(&DataKey::Admin, &admin);
         env.storage()
             .instance()
             .set(&DataKey::ProviderRegistry, &provider_registry);
+        env.storage()
+            .instance()
+            .set(&DataKey::AccessControlId, &access_control_id);
         Ok(())
     }
 
@@ -150,35 +279,140 @@ impl LabManagementContract {
             .instance()
             .set(&DataKey::LabCounter, &next_id);
 
+        let now = env.ledger().timestamp();
         let order = LabOrder {
-            provider_id,
-            patient_id,
+            order_id: id,
+            provider_id: provider_id.clone(),
+            patient_id: patient_id.clone(),
             lab_id: None,
             test_panel: req.test_panel,
-            status: Symbol::new(&env, "Ordered"),
+            priority: req.priority,
+            status: LabOrderStatus::Ordered,
             results_hash: None,
             quality_control_passed: false,
+            ordered_at: now,
+            updated_at: now,
+            cancellation_reason: None,
         };
 
         // Use the typed DataKey so the full u64 is embedded in the storage key.
         env.storage()
             .persistent()
             .set(&DataKey::LabOrder(id), &order);
+
+        pagination::push_paged(
+            &env,
+            |page| DataKey::PatientOrdersPage(patient_id.clone(), page),
+            || DataKey::PatientOrdersHead(patient_id.clone()),
+            id,
+        );
+        pagination::push_paged(
+            &env,
+            |page| DataKey::ProviderOrdersPage(provider_id.clone(), page),
+            || DataKey::ProviderOrdersHead(provider_id.clone()),
+            id,
+        );
         Ok(id)
     }
 
+    /// Assign (or reassign, before specimen collection) the performing lab.
+    /// The order is added to the new lab's order index.
     pub fn assign_lab(env: Env, order_id: u64, lab_id: Address, _eta: u64) -> Result<(), Error> {
-        let mut order: LabOrder = env
-            .storage()
-            .persistent()
-            .get(&DataKey::LabOrder(order_id))
-            .ok_or(Error::NotFound)?;
+        let mut order = Self::load_order(&env, order_id)?;
         order.provider_id.require_auth();
+        if order.status != LabOrderStatus::Ordered && order.status != LabOrderStatus::Assigned {
+            return Err(Error::InvalidStatus);
+        }
+        if order.lab_id.as_ref() != Some(&lab_id) {
+            pagination::push_paged(
+                &env,
+                |page| DataKey::LabOrdersPage(lab_id.clone(), page),
+                || DataKey::LabOrdersHead(lab_id.clone()),
+                order_id,
+            );
+        }
         order.lab_id = Some(lab_id);
-        order.status = Symbol::new(&env, "Assigned");
+        order.status = LabOrderStatus::Assigned;
+        order.updated_at = env.ledger().timestamp();
+        env.storage()
+            .persistent()
+            .set(&DataKey::LabOrder(order_id), &order);
+        Ok(())
+    }
+
+    /// Record the specimen draw for an assigned order. Either the assigned lab
+    /// or the ordering provider may record it.
+    pub fn record_specimen_collection(
+        env: Env,
+        order_id: u64,
+        collector: Address,
+        specimen_type: Symbol,
+        specimen_id_hash: BytesN<32>,
+        collected_at: u64,
+    ) -> Result<(), Error> {
+        collector.require_auth();
+        let mut order = Self::load_order(&env, order_id)?;
+        if collector != order.provider_id && order.lab_id.as_ref() != Some(&collector) {
+            return Err(Error::Unauthorized);
+        }
+        if order.status != LabOrderStatus::Assigned {
+            return Err(Error::InvalidStatus);
+        }
+
+        let now = env.ledger().timestamp();
+        let collection = SpecimenCollection {
+            order_id,
+            collected_by: collector.clone(),
+            specimen_type: specimen_type.clone(),
+            specimen_id_hash,
+            collected_at,
+            recorded_at: now,
+        };
+        env.storage()
+            .persistent()
+            .set(&DataKey::Specimen(order_id), &collection);
+
+        order.status = LabOrderStatus::SpecimenCollected;
+        order.updated_at = now;
         env.storage()
             .persistent()
             .set(&DataKey::LabOrder(order_id), &order);
+
+        env.events().publish(
+            (Symbol::new(&env, "LAB"), Symbol::new(&env, "SPECIMEN"), order_id),
+            (collector, specimen_type),
+        );
+        Ok(())
+    }
+
+    /// Cancel an order that has not completed. Only the ordering provider may
+    /// cancel.
+    pub fn cancel_order(
+        env: Env,
+        order_id: u64,
+        provider_id: Address,
+        reason: String,
+    ) -> Result<(), Error> {
+        provider_id.require_auth();
+        let mut order = Self::load_order(&env, order_id)?;
+        if order.provider_id != provider_id {
+            return Err(Error::Unauthorized);
+        }
+        if order.status == LabOrderStatus::Completed || order.status == LabOrderStatus::Cancelled {
+            return Err(Error::InvalidStatus);
+        }
+
+        order.status = LabOrderStatus::Cancelled;
+        order.cancellation_reason = Some(reason);
+        order.updated_at = env.ledger().timestamp();
+        env.storage()
+            .persistent()
+            .set(&DataKey::LabOrder(order_id), &order);
+
+        env.events().publish(
+            (Symbol::new(&env, "LAB"), Symbol::new(&env, "CANCEL"), order_id),
+            provider_id,
+        );
         Ok(())
     }
 
@@ -195,25 +429,29 @@ impl LabManagementContract {
         // VALIDATION PHASE: All validations must pass before any storage writes.
 
         // 1. Verify order exists.
-        let mut order: LabOrder = env
-            .storage()
-            .persistent()
-            .get(&DataKey::LabOrder(order_id))
-            .ok_or(Error::NotFound)?;
+        let mut order = Self::load_order(&env, order_id)?;
 
         // 2. Verify the caller is the assigned lab.
         if order.lab_id != Some(lab_id.clone()) {
             return Err(Error::Unauthorized);
         }
 
-        // 3. Perform QC validation (BEFORE any mutations).
+        // 3. Results are accepted once; corrections go through amend_results.
+        if order.status != LabOrderStatus::Assigned
+            && order.status != LabOrderStatus::SpecimenCollected
+        {
+            return Err(Error::InvalidStatus);
+        }
+
+        // 4. Perform QC validation (BEFORE any mutations).
         Self::validate_qc_results(qc_passed, &results_summary)?;
 
         // MUTATION PHASE: All state changes after validations have passed.
 
         order.results_hash = Some(results_hash);
         order.quality_control_passed = qc_passed;
-        order.status = Symbol::new(&env, "Completed");
+        order.status = LabOrderStatus::Completed;
+        order.updated_at = env.ledger().timestamp();
 
         env.events().publish(
             (
@@ -230,6 +468,64 @@ impl LabManagementContract {
         Ok(())
     }
 
+    /// Correct the results of a completed order. The superseded hash is
+    /// appended to the order's amendment trail; the order keeps its
+    /// `Completed` status.
+    pub fn amend_results(
+        env: Env,
+        order_id: u64,
+        lab_id: Address,
+        results_hash: BytesN<32>,
+        results_summary: Vec<TestResult>,
+        qc_passed: bool,
+        reason: String,
+    ) -> Result<(), Error> {
+        lab_id.require_auth();
+        let mut order = Self::load_order(&env, order_id)?;
+        if order.lab_id != Some(lab_id.clone()) {
+            return Err(Error::Unauthorized);
+        }
+        if order.status != LabOrderStatus::Completed {
+            return Err(Error::InvalidStatus);
+        }
+        let previous_hash = order.results_hash.clone().ok_or(Error::NotFound)?;
+        Self::validate_qc_results(qc_passed, &results_summary)?;
+
+        let now = env.ledger().timestamp();
+        let key = DataKey::ResultAmendments(order_id);
+        let mut trail: Vec<ResultAmendment> = env
+            .storage()
+            .persistent()
+            .get(&key)
+            .unwrap_or(Vec::new(&env));
+        trail.push_back(ResultAmendment {
+            order_id,
+            amended_by: lab_id,
+            previous_hash,
+            new_hash: results_hash.clone(),
+            reason,
+            amended_at: now,
+        });
+        env.storage().persistent().set(&key, &trail);
+
+        order.results_hash = Some(results_hash);
+        order.quality_control_passed = qc_passed;
+        order.updated_at = now;
+        env.storage()
+            .persistent()
+            .set(&DataKey::LabOrder(order_id), &order);
+
+        env.events().publish(
+            (
+                Symbol::new(&env, "LAB"),
+                Symbol::new(&env, "AMENDED"),
+                order.patient_id.clone(),
+            ),
+            (order_id, results_summary),
+        );
+        Ok(())
+    }
+
     pub fn flag_critical_value(
         env: Env,
         order_id: u64,
@@ -241,5 +537,173 @@ impl LabManagementContract {
         env.events()
             .publish((Symbol::new(&env, "CRITICAL"), order_id), (test_code, val));
     }
+
+    // ── queries ───────────────────────────────────────────────────────────────
+
+    /// Read an order including its `results_hash`. The ordering provider and
+    /// the patient always may; anyone else needs patient consent for
+    /// `purpose_code` (see module docs).
+    pub fn get_lab_order(
+        env: Env,
+        order_id: u64,
+        requester: Address,
+        purpose_code: String,
+    ) -> Result<LabOrder, Error> {
+        Self::load_results_for_read(&env, order_id, &requester, purpose_code)
+    }
+
+    /// Specimen collection recorded for an order, if any. Readable by the
+    /// ordering provider, the patient and the assigned lab.
+    pub fn get_specimen_collection(
+        env: Env,
+        order_id: u64,
+        requester: Address,
+    ) -> Result<Option<SpecimenCollection>, Error> {
+        Self::load_order_for_read(&env, order_id, &requester)?;
+        Ok(env.storage().persistent().get(&DataKey::Specimen(order_id)))
+    }
+
+    /// Amendment trail for an order's results, oldest first. Same access rule
+    /// as `get_lab_order`.
+    pub fn get_result_amendments(
+        env: Env,
+        order_id: u64,
+        requester: Address,
+        purpose_code: String,
+    ) -> Result<Vec<ResultAmendment>, Error> {
+        Self::load_results_for_read(&env, order_id, &requester, purpose_code)?;
+        Ok(env
+            .storage()
+            .persistent()
+            .get(&DataKey::ResultAmendments(order_id))
+            .unwrap_or(Vec::new(&env)))
+    }
+
+    /// Get a page of order IDs for a patient. Only the patient may list them.
+    ///
+    /// Each page contains at most `MAX_PAGE_SIZE` IDs; keep requesting the next
+    /// page while `has_more` is true.
+    pub fn get_patient_orders(
+        env: Env,
+        patient_id: Address,
+        requester: Address,
+        page: u32,
+    ) -> Result<PageResult, Error> {
+        requester.require_auth();
+        if requester != patient_id {
+            return Err(Error::Unauthorized);
+        }
+        Ok(pagination::get_paged(
+            &env,
+            |pg| DataKey::PatientOrdersPage(patient_id.clone(), pg),
+            || DataKey::PatientOrdersHead(patient_id.clone()),
+            page,
+        ))
+    }
+
+    /// Get a page of order IDs placed by a provider.
+    ///
+    /// See `get_patient_orders` for pagination semantics.
+    pub fn get_provider_orders(
+        env: Env,
+        provider_id: Address,
+        requester: Address,
+        page: u32,
+    ) -> Result<PageResult, Error> {
+        requester.require_auth();
+        if requester != provider_id {
+            return Err(Error::Unauthorized);
+        }
+        Ok(pagination::get_paged(
+            &env,
+            |pg| DataKey::ProviderOrdersPage(provider_id.clone(), pg),
+            || DataKey::ProviderOrdersHead(provider_id.clone()),
+            page,
+        ))
+    }
+
+    /// Get a page of order IDs ever assigned to a lab. An order reassigned to
+    /// another lab stays listed here but is no longer readable by this lab.
+    ///
+    /// See `get_patient_orders` for pagination semantics.
+    pub fn get_lab_orders(
+        env: Env,
+        lab_id: Address,
+        requester: Address,
+        page: u32,
+    ) -> Result<PageResult, Error> {
+        requester.require_auth();
+        if requester != lab_id {
+            return Err(Error::Unauthorized);
+        }
+        Ok(pagination::get_paged(
+            &env,
+            |pg| DataKey::LabOrdersPage(lab_id.clone(), pg),
+            || DataKey::LabOrdersHead(lab_id.clone()),
+            page,
+        ))
+    }
+
+    /// Maximum items per page (re-exported for callers).
+    pub fn max_page_size(_env: Env) -> u32 {
+        MAX_PAGE_SIZE
+    }
+
+    fn load_order(env: &Env, order_id: u64) -> Result<LabOrder, Error> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::LabOrder(order_id))
+            .ok_or(Error::NotFound)
+    }
+
+    fn load_order_for_read(
+        env: &Env,
+        order_id: u64,
+        requester: &Address,
+    ) -> Result<LabOrder, Error> {
+        requester.require_auth();
+        let order = Self::load_order(env, order_id)?;
+        if *requester != order.provider_id
+            && *requester != order.patient_id
+            && order.lab_id.as_ref() != Some(requester)
+        {
+            return Err(Error::Unauthorized);
+        }
+        Ok(order)
+    }
+
+    /// Load an order for a read that exposes results. Parties other than the
+    /// ordering provider and the patient are checked against the patient's
+    /// consent in the access-control contract, which logs the disclosure.
+    fn load_results_for_read(
+        env: &Env,
+        order_id: u64,
+        requester: &Address,
+        purpose_code: String,
+    ) -> Result<LabOrder, Error> {
+        requester.require_auth();
+        let order = Self::load_order(env, order_id)?;
+        if *requester == order.provider_id || *requester == order.patient_id {
+            return Ok(order);
+        }
+
+        // No access-control contract configured means consent cannot be
+        // verified; fail closed.
+        let access_control_id: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::AccessControlId)
+            .ok_or(Error::ConsentNotVerified)?;
+        AccessControlClient::new(env, &access_control_id)
+            .try_access_with_consent(
+                &order.patient_id,
+                requester,
+                &purpose_code,
+                &RESULTS_READ_SCOPE,
+            )
+            .map_err(|_| Error::ConsentNotVerified)?
+            .map_err(|_| Error::ConsentNotVerified)?;
+        Ok(order)
+    }
 }
 mod test;
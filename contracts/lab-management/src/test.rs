@@ -1,10 +1,94 @@
 #![cfg(test)]
 use super::*;
-use soroban_sdk::{Address, BytesN, Env, String, Symbol, testutils::Address as _, vec};
+use soroban_sdk::{
+    Address, BytesN, Env, String, Symbol, contract, contractimpl, symbol_short,
+    testutils::{Address as _, Ledger},
+    vec,
+};
 use provider_registry::{ProviderRegistry, ProviderRegistryClient};
 
 // ── helpers ──────────────────────────────────────────────────────────────────
 
+/// Provider registry stand-in that accepts every provider.
+#[contract]
+struct OpenProviderRegistry;
+
+#[contractimpl]
+impl OpenProviderRegistry {
+    pub fn is_provider(_env: Env, _provider: Address) -> bool {
+        true
+    }
+}
+
+/// Access-control stand-in holding (subject, grantee, purpose) → scope_mask
+/// and the list of disclosures it was asked to record.
+#[contract]
+struct MockAccessControl;
+
+#[contractimpl]
+impl MockAccessControl {
+    pub fn grant_consent(
+        env: Env,
+        subject: Address,
+        grantee: Address,
+        purpose_code: String,
+        scope_mask: u32,
+    ) {
+        env.storage()
+            .persistent()
+            .set(&(subject, grantee, purpose_code), &scope_mask);
+    }
+
+    pub fn access_with_consent(
+        env: Env,
+        subject: Address,
+        grantee: Address,
+        purpose_code: String,
+        required_scope: u32,
+    ) -> u64 {
+        let scope: u32 = env
+            .storage()
+            .persistent()
+            .get(&(subject.clone(), grantee.clone(), purpose_code.clone()))
+            .expect("consent not found");
+        assert_eq!(scope & required_scope, required_scope, "consent denied");
+        let mut log = Self::disclosures(env.clone());
+        log.push_back((subject, grantee, purpose_code));
+        env.storage().persistent().set(&symbol_short!("log"), &log);
+        log.len() as u64
+    }
+
+    pub fn disclosures(env: Env) -> Vec<(Address, Address, String)> {
+        env.storage()
+            .persistent()
+            .get(&symbol_short!("log"))
+            .unwrap_or(Vec::new(&env))
+    }
+}
+
+/// Register the lab contract wired to a registry that accepts every provider
+/// and to an empty mock access-control contract.
+fn register_lab(env: &Env) -> Address {
+    register_lab_with_access_control(env).0
+}
+
+/// Like `register_lab`, also returning the mock access-control client.
+fn register_lab_with_access_control(env: &Env) -> (Address, MockAccessControlClient<'static>) {
+    let registry_id = env.register(OpenProviderRegistry, ());
+    let ac_id = env.register(MockAccessControl, ());
+    let contract_id = env.register(LabManagementContract, ());
+    LabManagementContractClient::new(env, &contract_id).initialize(
+        &Address::generate(env),
+        &registry_id,
+        &ac_id,
+    );
+    (contract_id, MockAccessControlClient::new(env, &ac_id))
+}
+
+fn treatment(env: &Env) -> String {
+    String::from_str(env, "treatment")
+}
+
 fn make_req(env: &Env) -> OrderRequest {
     OrderRequest {
         test_panel: vec![env, String::from_str(env, "2345-7")],
@@ -34,7 +118,7 @@ fn test_happy_path_lifecycle() {
     let env = Env::default();
     env.mock_all_auths();
 
-    let contract_id = env.register(LabManagementContract, ());
+    let contract_id = register_lab(&env);
     let client = LabManagementContractClient::new(&env, &contract_id);
 
     let provider = Address::generate(&env);
@@ -60,7 +144,7 @@ fn test_happy_path_lifecycle() {
 fn test_fail_qc_check() {
     let env = Env::default();
     env.mock_all_auths();
-    let contract_id = env.register(LabManagementContract, ());
+    let contract_id = register_lab(&env);
     let client = LabManagementContractClient::new(&env, &contract_id);
 
     let provider = Address::generate(&env);
@@ -76,6 +160,7 @@ fn test_fail_qc_check() {
     };
 
     let id = client.order_lab_test(&provider, &patient, &req);
+    client.assign_lab(&id, &lab, &0);
     client.submit_results(
         &id,
         &lab,
@@ -89,7 +174,7 @@ fn test_fail_qc_check() {
 fn test_critical_value_alerting() {
     let env = Env::default();
     env.mock_all_auths();
-    let contract_id = env.register(LabManagementContract, ());
+    let contract_id = register_lab(&env);
     let client = LabManagementContractClient::new(&env, &contract_id);
 
     let lab = Address::generate(&env);
@@ -104,7 +189,7 @@ fn test_critical_value_alerting() {
 fn test_fail_assign_nonexistent_order() {
     let env = Env::default();
     env.mock_all_auths();
-    let contract_id = env.register(LabManagementContract, ());
+    let contract_id = register_lab(&env);
     let client = LabManagementContractClient::new(&env, &contract_id);
 
     let lab = Address::generate(&env);
@@ -118,7 +203,7 @@ fn test_fail_assign_nonexistent_order() {
 fn test_order_ids_are_sequential() {
     let env = Env::default();
     env.mock_all_auths();
-    let contract_id = env.register(LabManagementContract, ());
+    let contract_id = register_lab(&env);
     let client = LabManagementContractClient::new(&env, &contract_id);
 
     let provider = Address::generate(&env);
@@ -139,7 +224,7 @@ fn test_order_ids_are_sequential() {
 fn test_distinct_ids_store_independent_records() {
     let env = Env::default();
     env.mock_all_auths();
-    let contract_id = env.register(LabManagementContract, ());
+    let contract_id = register_lab(&env);
     let client = LabManagementContractClient::new(&env, &contract_id);
 
     let provider = Address::generate(&env);
@@ -182,7 +267,7 @@ fn test_distinct_ids_store_independent_records() {
 fn test_id_above_u32_max_stored_and_retrieved() {
     let env = Env::default();
     env.mock_all_auths();
-    let contract_id = env.register(LabManagementContract, ());
+    let contract_id = register_lab(&env);
 
     // Seed the counter to u32::MAX so the next order gets ID u32::MAX.
     // We write directly into instance storage to avoid ordering u32::MAX orders.
@@ -217,7 +302,7 @@ fn test_id_above_u32_max_stored_and_retrieved() {
 fn test_id_strictly_above_u32_max_no_collision() {
     let env = Env::default();
     env.mock_all_auths();
-    let contract_id = env.register(LabManagementContract, ());
+    let contract_id = register_lab(&env);
 
     // Seed counter to u32::MAX so the first call returns u32::MAX,
     // and the second call returns u32::MAX + 1.
@@ -261,7 +346,7 @@ fn test_id_strictly_above_u32_max_no_collision() {
 fn test_order_id_overflow_panics() {
     let env = Env::default();
     env.mock_all_auths();
-    let contract_id = env.register(LabManagementContract, ());
+    let contract_id = register_lab(&env);
 
     // Seed the counter to u64::MAX so the next increment overflows.
     env.as_contract(&contract_id, || {
@@ -292,7 +377,13 @@ fn test_provider_registration_verification() {
     // Register LabManagementContract and initialize it with ProviderRegistry
     let lab_contract_id = env.register(LabManagementContract, ());
     let client = LabManagementContractClient::new(&env, &lab_contract_id);
-    client.initialize(&provider_registry_id);
+    client.initialize(&admin, &provider_registry_id, &Address::generate(&env));
+
+    // A second initialize cannot repoint the registries.
+    assert_eq!(
+        client.try_initialize(&admin, &provider_registry_id, &Address::generate(&env)),
+        Err(Ok(Error::AlreadyInitialized))
+    );
 
     let unregistered_provider = Address::generate(&env);
     let registered_provider = Address::generate(&env);
@@ -319,7 +410,7 @@ fn test_provider_registration_verification() {
     // Order from registered provider should succeed
     let result = client.try_order_lab_test(&registered_provider, &patient, &make_req(&env));
     assert!(result.is_ok());
-    let order_id = result.unwrap();
+    let order_id = result.unwrap().unwrap();
     assert_eq!(order_id, 0);
 }
 
@@ -328,7 +419,7 @@ fn test_provider_registration_verification() {
 fn test_submit_results_by_unassigned_lab_returns_error() {
     let env = Env::default();
     env.mock_all_auths();
-    let contract_id = env.register(LabManagementContract, ());
+    let contract_id = register_lab(&env);
     let client = LabManagementContractClient::new(&env, &contract_id);
 
     let provider = Address::generate(&env);
@@ -350,11 +441,11 @@ fn test_submit_results_by_unassigned_lab_returns_error() {
 }
 
 #[test]
-#[should_panic(expected = "Error(Contract, #2)")]
+#[should_panic(expected = "Error(Auth, InvalidAction)")]
 fn test_assign_lab_without_auth_returns_error() {
     let env = Env::default();
-    // Don't mock all auths - we want to test auth failure
-    let contract_id = env.register(LabManagementContract, ());
+    env.mock_all_auths();
+    let contract_id = register_lab(&env);
     let client = LabManagementContractClient::new(&env, &contract_id);
 
     let provider = Address::generate(&env);
@@ -362,7 +453,460 @@ fn test_assign_lab_without_auth_returns_error() {
     let lab = Address::generate(&env);
 
     let order_id = client.order_lab_test(&provider, &patient, &make_req(&env));
-    
+
+    // Stop mocking auths - we want to test auth failure
+    env.set_auths(&[]);
+
     // This should fail because provider_id.require_auth() is not satisfied
     client.assign_lab(&order_id, &lab, &3600);
 }
+
+// ── lifecycle and query API ──────────────────────────────────────────────────
+
+struct Fixture {
+    env: Env,
+    client: LabManagementContractClient<'static>,
+    access_control: MockAccessControlClient<'static>,
+    provider: Address,
+    patient: Address,
+    lab: Address,
+}
+
+fn fixture() -> Fixture {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (contract_id, access_control) = register_lab_with_access_control(&env);
+    let client = LabManagementContractClient::new(&env, &contract_id);
+    let provider = Address::generate(&env);
+    let patient = Address::generate(&env);
+    let lab = Address::generate(&env);
+    Fixture {
+        env,
+        client,
+        access_control,
+        provider,
+        patient,
+        lab,
+    }
+}
+
+fn complete_order(f: &Fixture, hash: u8) -> u64 {
+    let id = f
+        .client
+        .order_lab_test(&f.provider, &f.patient, &make_req(&f.env));
+    f.client.assign_lab(&id, &f.lab, &0);
+    f.client.submit_results(
+        &id,
+        &f.lab,
+        &BytesN::from_array(&f.env, &[hash; 32]),
+        &vec![&f.env, make_result(&f.env)],
+        &true,
+    );
+    id
+}
+
+#[test]
+fn test_get_lab_order_tracks_status() {
+    let f = fixture();
+    f.env.ledger().set_timestamp(1_000);
+    let id = f
+        .client
+        .order_lab_test(&f.provider, &f.patient, &make_req(&f.env));
+
+    let order = f.client.get_lab_order(&id, &f.provider, &treatment(&f.env));
+    assert_eq!(order.order_id, id);
+    assert_eq!(order.status, LabOrderStatus::Ordered);
+    assert_eq!(order.priority, Symbol::new(&f.env, "STAT"));
+    assert_eq!(order.ordered_at, 1_000);
+    assert_eq!(order.lab_id, None);
+
+    f.client.assign_lab(&id, &f.lab, &0);
+    assert_eq!(
+        f.client
+            .get_lab_order(&id, &f.patient, &treatment(&f.env))
+            .status,
+        LabOrderStatus::Assigned
+    );
+
+    f.client.record_specimen_collection(
+        &id,
+        &f.lab,
+        &Symbol::new(&f.env, "BLOOD"),
+        &BytesN::from_array(&f.env, &[5u8; 32]),
+        &900,
+    );
+    assert_eq!(
+        f.client
+            .get_lab_order(&id, &f.provider, &treatment(&f.env))
+            .status,
+        LabOrderStatus::SpecimenCollected
+    );
+    let specimen = f.client.get_specimen_collection(&id, &f.patient).unwrap();
+    assert_eq!(specimen.collected_by, f.lab);
+    assert_eq!(specimen.collected_at, 900);
+
+    f.client.submit_results(
+        &id,
+        &f.lab,
+        &BytesN::from_array(&f.env, &[2u8; 32]),
+        &vec![&f.env, make_result(&f.env)],
+        &true,
+    );
+    let order = f.client.get_lab_order(&id, &f.patient, &treatment(&f.env));
+    assert_eq!(order.status, LabOrderStatus::Completed);
+    assert_eq!(
+        order.results_hash,
+        Some(BytesN::from_array(&f.env, &[2u8; 32]))
+    );
+    assert!(order.quality_control_passed);
+}
+
+#[test]
+fn test_get_lab_order_rejects_unrelated_reader() {
+    let f = fixture();
+    let id = f
+        .client
+        .order_lab_test(&f.provider, &f.patient, &make_req(&f.env));
+    let stranger = Address::generate(&f.env);
+    assert_eq!(
+        f.client
+            .try_get_lab_order(&id, &stranger, &treatment(&f.env)),
+        Err(Ok(Error::ConsentNotVerified))
+    );
+    assert_eq!(
+        f.client
+            .try_get_lab_order(&42, &f.provider, &treatment(&f.env)),
+        Err(Ok(Error::NotFound))
+    );
+}
+
+#[test]
+fn test_specimen_collection_requires_assigned_order() {
+    let f = fixture();
+    let id = f
+        .client
+        .order_lab_test(&f.provider, &f.patient, &make_req(&f.env));
+    let res = f.client.try_record_specimen_collection(
+        &id,
+        &f.provider,
+        &Symbol::new(&f.env, "BLOOD"),
+        &BytesN::from_array(&f.env, &[5u8; 32]),
+        &0,
+    );
+    assert_eq!(res, Err(Ok(Error::InvalidStatus)));
+
+    f.client.assign_lab(&id, &f.lab, &0);
+    let stranger = Address::generate(&f.env);
+    let res = f.client.try_record_specimen_collection(
+        &id,
+        &stranger,
+        &Symbol::new(&f.env, "BLOOD"),
+        &BytesN::from_array(&f.env, &[5u8; 32]),
+        &0,
+    );
+    assert_eq!(res, Err(Ok(Error::Unauthorized)));
+}
+
+#[test]
+fn test_cancel_order() {
+    let f = fixture();
+    let id = f
+        .client
+        .order_lab_test(&f.provider, &f.patient, &make_req(&f.env));
+    f.client.assign_lab(&id, &f.lab, &0);
+
+    let stranger = Address::generate(&f.env);
+    let reason = String::from_str(&f.env, "duplicate order");
+    assert_eq!(
+        f.client.try_cancel_order(&id, &stranger, &reason),
+        Err(Ok(Error::Unauthorized))
+    );
+
+    f.client.cancel_order(&id, &f.provider, &reason);
+    let order = f.client.get_lab_order(&id, &f.provider, &treatment(&f.env));
+    assert_eq!(order.status, LabOrderStatus::Cancelled);
+    assert_eq!(order.cancellation_reason, Some(reason.clone()));
+
+    // A cancelled order accepts no further transitions.
+    assert_eq!(
+        f.client.try_cancel_order(&id, &f.provider, &reason),
+        Err(Ok(Error::InvalidStatus))
+    );
+    assert_eq!(
+        f.client.try_submit_results(
+            &id,
+            &f.lab,
+            &BytesN::from_array(&f.env, &[2u8; 32]),
+            &vec![&f.env, make_result(&f.env)],
+            &true,
+        ),
+        Err(Ok(Error::InvalidStatus))
+    );
+    assert_eq!(
+        f.client.try_assign_lab(&id, &f.lab, &0),
+        Err(Ok(Error::InvalidStatus))
+    );
+}
+
+#[test]
+fn test_cannot_cancel_completed_order() {
+    let f = fixture();
+    let id = complete_order(&f, 2);
+    assert_eq!(
+        f.client
+            .try_cancel_order(&id, &f.provider, &String::from_str(&f.env, "late")),
+        Err(Ok(Error::InvalidStatus))
+    );
+}
+
+#[test]
+fn test_resubmitting_results_is_rejected() {
+    let f = fixture();
+    let id = complete_order(&f, 2);
+    assert_eq!(
+        f.client.try_submit_results(
+            &id,
+            &f.lab,
+            &BytesN::from_array(&f.env, &[3u8; 32]),
+            &vec![&f.env, make_result(&f.env)],
+            &true,
+        ),
+        Err(Ok(Error::InvalidStatus))
+    );
+}
+
+#[test]
+fn test_amend_results_keeps_trail() {
+    let f = fixture();
+    let id = complete_order(&f, 2);
+    f.env.ledger().set_timestamp(5_000);
+
+    let h3 = BytesN::from_array(&f.env, &[3u8; 32]);
+    let h4 = BytesN::from_array(&f.env, &[4u8; 32]);
+    f.client.amend_results(
+        &id,
+        &f.lab,
+        &h3,
+        &vec![&f.env, make_result(&f.env)],
+        &true,
+        &String::from_str(&f.env, "transcription error"),
+    );
+    f.client.amend_results(
+        &id,
+        &f.lab,
+        &h4,
+        &vec![&f.env, make_result(&f.env)],
+        &true,
+        &String::from_str(&f.env, "unit correction"),
+    );
+
+    let order = f.client.get_lab_order(&id, &f.patient, &treatment(&f.env));
+    assert_eq!(order.status, LabOrderStatus::Completed);
+    assert_eq!(order.results_hash, Some(h4.clone()));
+
+    let trail = f
+        .client
+        .get_result_amendments(&id, &f.provider, &treatment(&f.env));
+    assert_eq!(trail.len(), 2);
+    let first = trail.get(0).unwrap();
+    assert_eq!(first.previous_hash, BytesN::from_array(&f.env, &[2u8; 32]));
+    assert_eq!(first.new_hash, h3.clone());
+    assert_eq!(first.amended_at, 5_000);
+    let second = trail.get(1).unwrap();
+    assert_eq!(second.previous_hash, h3);
+    assert_eq!(second.new_hash, h4);
+}
+
+#[test]
+fn test_amend_results_requires_completed_order_and_assigned_lab() {
+    let f = fixture();
+    let id = f
+        .client
+        .order_lab_test(&f.provider, &f.patient, &make_req(&f.env));
+    f.client.assign_lab(&id, &f.lab, &0);
+    let reason = String::from_str(&f.env, "fix");
+    let hash = BytesN::from_array(&f.env, &[3u8; 32]);
+    let results = vec![&f.env, make_result(&f.env)];
+
+    assert_eq!(
+        f.client
+            .try_amend_results(&id, &f.lab, &hash, &results, &true, &reason),
+        Err(Ok(Error::InvalidStatus))
+    );
+
+    f.client.submit_results(&id, &f.lab, &hash, &results, &true);
+    let other_lab = Address::generate(&f.env);
+    assert_eq!(
+        f.client
+            .try_amend_results(&id, &other_lab, &hash, &results, &true, &reason),
+        Err(Ok(Error::Unauthorized))
+    );
+    assert_eq!(
+        f.client
+            .try_amend_results(&id, &f.lab, &hash, &results, &false, &reason),
+        Err(Ok(Error::QCFieldFailed))
+    );
+    assert!(
+        f.client
+            .get_result_amendments(&id, &f.patient, &treatment(&f.env))
+            .is_empty()
+    );
+}
+
+#[test]
+fn test_order_indices_paginate() {
+    let f = fixture();
+    let other_patient = Address::generate(&f.env);
+    let total = MAX_PAGE_SIZE + 3;
+    for i in 0..total {
+        let patient = if i % 2 == 0 {
+            &f.patient
+        } else {
+            &other_patient
+        };
+        let id = f
+            .client
+            .order_lab_test(&f.provider, patient, &make_req(&f.env));
+        if i < 5 {
+            f.client.assign_lab(&id, &f.lab, &0);
+        }
+    }
+
+    let first = f.client.get_provider_orders(&f.provider, &f.provider, &0);
+    assert_eq!(first.ids.len(), MAX_PAGE_SIZE);
+    assert!(first.has_more);
+    let second = f.client.get_provider_orders(&f.provider, &f.provider, &1);
+    assert_eq!(second.ids.len(), 3);
+    assert!(!second.has_more);
+
+    let patient_page = f.client.get_patient_orders(&f.patient, &f.patient, &0);
+    assert_eq!(patient_page.ids.len(), total.div_ceil(2));
+    let lab_page = f.client.get_lab_orders(&f.lab, &f.lab, &0);
+    assert_eq!(lab_page.ids.len(), 5);
+    assert!(!lab_page.has_more);
+
+    assert_eq!(
+        f.client.try_get_patient_orders(&f.patient, &f.provider, &0),
+        Err(Ok(Error::Unauthorized))
+    );
+}
+
+#[test]
+fn test_reassigned_lab_loses_read_access() {
+    let f = fixture();
+    let id = f
+        .client
+        .order_lab_test(&f.provider, &f.patient, &make_req(&f.env));
+    let lab_b = Address::generate(&f.env);
+    f.client.assign_lab(&id, &f.lab, &0);
+    f.client.assign_lab(&id, &lab_b, &0);
+
+    assert_eq!(f.client.get_specimen_collection(&id, &lab_b), None);
+    assert_eq!(
+        f.client.try_get_specimen_collection(&id, &f.lab),
+        Err(Ok(Error::Unauthorized))
+    );
+    assert_eq!(f.client.get_lab_orders(&lab_b, &lab_b, &0).ids.len(), 1);
+}
+
+// ── consent-gated result access ──────────────────────────────────────────────
+
+#[test]
+fn test_third_party_reads_results_with_consent() {
+    let f = fixture();
+    let id = complete_order(&f, 2);
+    let specialist = Address::generate(&f.env);
+
+    f.access_control.grant_consent(
+        &f.patient,
+        &specialist,
+        &treatment(&f.env),
+        &RESULTS_READ_SCOPE,
+    );
+
+    let order = f.client.get_lab_order(&id, &specialist, &treatment(&f.env));
+    assert_eq!(
+        order.results_hash,
+        Some(BytesN::from_array(&f.env, &[2u8; 32]))
+    );
+    assert!(
+        f.client
+            .get_result_amendments(&id, &specialist, &treatment(&f.env))
+            .is_empty()
+    );
+
+    // Both reads are logged against the patient.
+    let log = f.access_control.disclosures();
+    assert_eq!(log.len(), 2);
+    let (subject, accessor, purpose) = log.get(0).unwrap();
+    assert_eq!((subject, accessor), (f.patient.clone(), specialist.clone()));
+    assert_eq!(purpose, treatment(&f.env));
+
+    // The patient's own reads are not disclosures.
+    f.client.get_lab_order(&id, &f.patient, &treatment(&f.env));
+    assert_eq!(f.access_control.disclosures().len(), 2);
+}
+
+#[test]
+fn test_third_party_without_consent_is_rejected() {
+    let f = fixture();
+    let id = complete_order(&f, 2);
+    let specialist = Address::generate(&f.env);
+
+    assert_eq!(
+        f.client
+            .try_get_lab_order(&id, &specialist, &treatment(&f.env)),
+        Err(Ok(Error::ConsentNotVerified))
+    );
+    assert_eq!(
+        f.client
+            .try_get_result_amendments(&id, &specialist, &treatment(&f.env)),
+        Err(Ok(Error::ConsentNotVerified))
+    );
+}
+
+#[test]
+fn test_consent_must_match_purpose_and_scope() {
+    let f = fixture();
+    let id = complete_order(&f, 2);
+    let insurer = Address::generate(&f.env);
+    let billing = String::from_str(&f.env, "billing");
+
+    // Write-only consent does not cover reads.
+    f.access_control
+        .grant_consent(&f.patient, &insurer, &billing, &0x02);
+    assert_eq!(
+        f.client.try_get_lab_order(&id, &insurer, &billing),
+        Err(Ok(Error::ConsentNotVerified))
+    );
+
+    // Read consent for billing does not extend to another purpose.
+    f.access_control
+        .grant_consent(&f.patient, &insurer, &billing, &RESULTS_READ_SCOPE);
+    assert!(f.client.try_get_lab_order(&id, &insurer, &billing).is_ok());
+    assert_eq!(
+        f.client
+            .try_get_lab_order(&id, &insurer, &treatment(&f.env)),
+        Err(Ok(Error::ConsentNotVerified))
+    );
+}
+
+#[test]
+fn test_performing_lab_needs_consent_to_read_results() {
+    let f = fixture();
+    let id = complete_order(&f, 2);
+    assert_eq!(
+        f.client.try_get_lab_order(&id, &f.lab, &treatment(&f.env)),
+        Err(Ok(Error::ConsentNotVerified))
+    );
+    // Specimen data stays readable by the lab handling the order.
+    assert!(f.client.try_get_specimen_collection(&id, &f.lab).is_ok());
+
+    f.access_control
+        .grant_consent(&f.patient, &f.lab, &treatment(&f.env), &RESULTS_READ_SCOPE);
+    assert!(
+        f.client
+            .try_get_lab_order(&id, &f.lab, &treatment(&f.env))
+            .is_ok()
+    );
+}
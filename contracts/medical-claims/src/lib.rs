@@ -28,11 +28,13 @@ mod test;
 mod types;
 
 use shared::privacy::{validate_policy_metadata, PolicyMetadata};
-use soroban_sdk::{contract, contractclient, contractimpl, symbol_short, Address, BytesN, Env, String, Vec};
+use soroban_sdk::{
+    contract, contractclient, contractimpl, symbol_short, token, Address, BytesN, Env, String, Vec,
+};
 use types::{
     ClaimRecord, ClaimReconciledEvent, ClaimStatus, DataKey, DenialInfo, DisputeRecord,
     DisputeStatus, Error, InsurerPaymentRecord,
-    PatientPaymentRecord, ReconciliationStatus, ServiceLine,
+    PatientPaymentRecord, ReconciliationStatus, ServiceLine, SettlementReceipt,
 };
 
 // ── Cross-contract interface for consent verification (#300) ──────────────────
@@ -79,6 +81,11 @@ impl MedicalClaimsSystem {
     ///
     /// `insurer_registry_id` is the address of the deployed insurer-registry
     /// contract used to verify insurer credentials before claim submission (#527).
+    ///
+    /// `settlement_token` is the optional SAC token used to settle claims. When
+    /// set, `process_payment` and `apply_patient_payment` transfer the paid
+    /// amount to the provider and emit a `SettlementReceipt`; when `None`,
+    /// payments are bookkeeping only.
     pub fn initialize(
         env: Env,
         admin: Address,
@@ -86,6 +93,7 @@ impl MedicalClaimsSystem {
         financial_records_id: Address,
         reconciliation_threshold: u64,
         insurer_registry_id: Address,
+        settlement_token: Option<Address>,
     ) -> Result<(), Error> {
         if env.storage().instance().has(&DataKey::Admin) {
             return Err(Error::AlreadyInitialized);
@@ -104,9 +112,19 @@ impl MedicalClaimsSystem {
         env.storage()
             .instance()
             .set(&DataKey::InsurerRegistryId, &insurer_registry_id);
+        if let Some(token_id) = settlement_token {
+            env.storage()
+                .instance()
+                .set(&DataKey::SettlementToken, &token_id);
+        }
         Ok(())
     }
 
+    /// The settlement token configured at `initialize`, if any.
+    pub fn get_settlement_token(env: Env) -> Option<Address> {
+        env.storage().instance().get(&DataKey::SettlementToken)
+    }
+
     /// Admin-only: authorize an insurer address to adjudicate and pay claims.
     pub fn register_insurer(env: Env, admin: Address, insurer: Address) -> Result<(), Error> {
         admin.require_auth();
@@ -357,6 +375,9 @@ impl MedicalClaimsSystem {
     }
 
     /// Process payment. Caller must be the registered insurer bound to this claim.
+    ///
+    /// With a settlement token configured, `payment_amount` is transferred from
+    /// the insurer to the claim's provider in the same invocation.
     pub fn process_payment(
         env: Env,
         claim_id: u64,
@@ -402,10 +423,20 @@ impl MedicalClaimsSystem {
             .persistent()
             .get(&DataKey::ClaimPayment(claim_id))
             .unwrap_or(Vec::new(&env));
+        let settled_amount = Self::settle(
+            &env,
+            claim_id,
+            payments.len(),
+            true,
+            &insurer_id,
+            &claim.provider_id,
+            payment_amount,
+        )?;
         payments.push_back(InsurerPaymentRecord {
             payment_date,
             payment_amount,
             payment_reference_hash,
+            settled_amount,
             reconciled: false,
             financial_record_owner: None,
             financial_record_idx: None,
@@ -421,6 +452,10 @@ impl MedicalClaimsSystem {
         Ok(())
     }
 
+    /// Record a patient payment toward the claim's patient responsibility.
+    ///
+    /// With a settlement token configured, `payment_amount` is transferred from
+    /// the patient to the claim's provider in the same invocation.
     pub fn apply_patient_payment(
         env: Env,
         claim_id: u64,
@@ -456,9 +491,19 @@ impl MedicalClaimsSystem {
             .persistent()
             .get(&DataKey::PatientPayment(claim_id))
             .unwrap_or(Vec::new(&env));
+        let settled_amount = Self::settle(
+            &env,
+            claim_id,
+            payments.len(),
+            false,
+            &patient_id,
+            &claim.provider_id,
+            payment_amount,
+        )?;
         payments.push_back(PatientPaymentRecord {
             payment_date,
             payment_amount,
+            settled_amount,
             reconciled: false,
             financial_record_owner: None,
             financial_record_idx: None,
@@ -555,6 +600,50 @@ impl MedicalClaimsSystem {
         Ok((insurer_due, patient_due))
     }
 
+    /// Transfer `amount` of the settlement token from `payer` to `payee` and
+    /// emit a `SettlementReceipt`. Returns the amount the payee actually
+    /// received, or `0` when no settlement token is configured. Fails with
+    /// `SettlementMismatch` unless the payee received exactly `amount`.
+    fn settle(
+        env: &Env,
+        claim_id: u64,
+        payment_idx: u32,
+        is_insurer_payment: bool,
+        payer: &Address,
+        payee: &Address,
+        amount: i128,
+    ) -> Result<i128, Error> {
+        let token_id: Address = match env.storage().instance().get(&DataKey::SettlementToken) {
+            Some(id) => id,
+            None => return Ok(0),
+        };
+        let client = token::TokenClient::new(env, &token_id);
+        let before = client.balance(payee);
+        client.transfer(payer, payee, &amount);
+        let received = client
+            .balance(payee)
+            .checked_sub(before)
+            .ok_or(Error::AmountOverflow)?;
+        if received != amount {
+            return Err(Error::SettlementMismatch);
+        }
+
+        env.events().publish(
+            (symbol_short!("settled"), claim_id),
+            SettlementReceipt {
+                claim_id,
+                payment_idx,
+                is_insurer_payment,
+                payer: payer.clone(),
+                payee: payee.clone(),
+                token: token_id,
+                amount: received,
+                settled_at: env.ledger().timestamp(),
+            },
+        );
+        Ok(received)
+    }
+
     fn checked_add(lhs: i128, rhs: i128) -> Result<i128, Error> {
         lhs.checked_add(rhs).ok_or(Error::AmountOverflow)
     }
@@ -568,6 +657,11 @@ impl MedicalClaimsSystem {
     /// Reconcile a claim with a payment record from the financial-records contract.
     /// Links a payment to a claim and updates reconciliation status.
     /// This operation is transactional - both claim and payment records update or neither does.
+    ///
+    /// Settlement is exact: with a settlement token configured, `settle`
+    /// rejects any payment the provider did not receive in full, so every
+    /// recorded payment has `settled_amount == payment_amount` and needs no
+    /// further check here.
     pub fn reconcile_claim(
         env: Env,
         claim_id: u64,
@@ -4,7 +4,10 @@
 use super::*;
 use insurer_registry::{InsurerRegistry, InsurerRegistryClient};
 use shared::privacy::PolicyMetadata;
-use soroban_sdk::{contract, contractimpl, testutils::{Address as _, Ledger}, BytesN, Env, String, Symbol, Vec};
+use soroban_sdk::{
+    contract, contractimpl, testutils::{Address as _, Events, Ledger}, token, BytesN, Env, String,
+    Symbol, TryIntoVal, Vec,
+};
 
 // ── Mock access-control contract for tests (#300) ────────────────────────────
 //
@@ -93,7 +96,7 @@ fn setup(
     let patient = Address::generate(env);
     let insurer = Address::generate(env);
     let ir_id = register_active_insurer(env, &insurer);
-    client.initialize(&admin, &ac_id, &fr_id, &86400, &ir_id);
+    client.initialize(&admin, &ac_id, &fr_id, &86400, &ir_id, &None);
     client.register_insurer(&admin, &insurer);
     (client, admin, provider, patient, insurer)
 }
@@ -343,7 +346,7 @@ fn test_double_initialize_fails() {
     let dummy_ac = Address::generate(&env);
     let dummy_fr = Address::generate(&env);
     let dummy_ir = Address::generate(&env);
-    let result = client.try_initialize(&admin, &dummy_ac, &dummy_fr, &86400, &dummy_ir);
+    let result = client.try_initialize(&admin, &dummy_ac, &dummy_fr, &86400, &dummy_ir, &None);
     assert_eq!(result, Err(Ok(Error::AlreadyInitialized)));
 }
 
@@ -743,7 +746,7 @@ fn test_integration_expired_insurer_returns_insurer_not_active() {
         &dummy_hash(&env, 3),
     );
 
-    client.initialize(&admin, &ac_id, &fr_id, &86400, &ir_id);
+    client.initialize(&admin, &ac_id, &fr_id, &86400, &ir_id, &None);
     client.register_insurer(&admin, &insurer);
     env.ledger().with_mut(|li| li.timestamp = 200);
 
@@ -801,3 +804,155 @@ fn test_dispute_lifecycle() {
     let result = client.try_resolve_dispute(&other_dispute_id, &rogue, &resolution_hash);
     assert_eq!(result, Err(Ok(Error::NotAuthorizedReviewer)));
 }
+
+// -----------------------------------------------------------------------
+// On-chain settlement
+// -----------------------------------------------------------------------
+
+fn setup_with_settlement(
+    env: &Env,
+) -> (
+    MedicalClaimsSystemClient<'static>,
+    token::TokenClient<'static>,
+    Address,
+    Address,
+    Address,
+) {
+    let ac_id = env.register(MockAccessControl, ());
+    let fr_id = Address::generate(env);
+
+    let token_admin = Address::generate(env);
+    let token_id = env.register_stellar_asset_contract_v2(token_admin).address();
+    let token_admin_client = token::StellarAssetClient::new(env, &token_id);
+
+    let contract_id = env.register(MedicalClaimsSystem, ());
+    let client = MedicalClaimsSystemClient::new(env, &contract_id);
+    let admin = Address::generate(env);
+    let provider = Address::generate(env);
+    let patient = Address::generate(env);
+    let insurer = Address::generate(env);
+    let ir_id = register_active_insurer(env, &insurer);
+    client.initialize(&admin, &ac_id, &fr_id, &86400, &ir_id, &Some(token_id.clone()));
+    client.register_insurer(&admin, &insurer);
+
+    token_admin_client.mint(&insurer, &50_000);
+    token_admin_client.mint(&patient, &5_000);
+
+    (client, token::TokenClient::new(env, &token_id), provider, patient, insurer)
+}
+
+fn submit_and_adjudicate(
+    env: &Env,
+    client: &MedicalClaimsSystemClient,
+    provider: &Address,
+    patient: &Address,
+    insurer: &Address,
+) -> u64 {
+    let claim_id = client.submit_claim(
+        provider,
+        patient,
+        insurer,
+        &1,
+        &1000,
+        &make_services(env),
+        &Vec::new(env),
+        &BytesN::from_array(env, &[0; 32]),
+        &policy(env),
+        &15000,
+    );
+    client.adjudicate_claim(&claim_id, insurer, &Vec::new(env), &Vec::new(env), &10000, &2000);
+    claim_id
+}
+
+fn last_settlement_receipt(env: &Env) -> Option<SettlementReceipt> {
+    let mut receipt = None;
+    for (_, topics, data) in env.events().all().iter() {
+        let topic: Option<Symbol> = topics.get(0).and_then(|t| t.try_into_val(env).ok());
+        if topic == Some(symbol_short!("settled")) {
+            receipt = data.try_into_val(env).ok();
+        }
+    }
+    receipt
+}
+
+#[test]
+fn test_settlement_token_defaults_to_none() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, _, _, _, _) = setup(&env);
+    assert_eq!(client.get_settlement_token(), None);
+}
+
+#[test]
+fn test_insurer_payment_transfers_tokens_to_provider() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, token, provider, patient, insurer) = setup_with_settlement(&env);
+    let claim_id = submit_and_adjudicate(&env, &client, &provider, &patient, &insurer);
+
+    client.process_payment(&claim_id, &insurer, &6000, &1690100000, &reference_hash(&env, 8));
+
+    let receipt = last_settlement_receipt(&env).expect("settlement receipt emitted");
+    assert_eq!(receipt.claim_id, claim_id);
+    assert_eq!(receipt.payment_idx, 0);
+    assert!(receipt.is_insurer_payment);
+    assert_eq!(receipt.payer, insurer);
+    assert_eq!(receipt.payee, provider);
+    assert_eq!(receipt.amount, 6000);
+
+    assert_eq!(token.balance(&provider), 6000);
+    assert_eq!(token.balance(&insurer), 44_000);
+    let payments = client.get_insurer_payments(&claim_id);
+    assert_eq!(payments.get(0).unwrap().settled_amount, 6000);
+}
+
+#[test]
+fn test_patient_payment_transfers_tokens_to_provider() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, token, provider, patient, insurer) = setup_with_settlement(&env);
+    let claim_id = submit_and_adjudicate(&env, &client, &provider, &patient, &insurer);
+
+    client.process_payment(&claim_id, &insurer, &10000, &1690100000, &reference_hash(&env, 8));
+    client.apply_patient_payment(&claim_id, &patient, &2000, &1690200000);
+
+    let receipt = last_settlement_receipt(&env).expect("settlement receipt emitted");
+    assert!(!receipt.is_insurer_payment);
+    assert_eq!(receipt.payer, patient);
+    assert_eq!(receipt.amount, 2000);
+
+    assert_eq!(token.balance(&provider), 12_000);
+    assert_eq!(token.balance(&patient), 3_000);
+    assert_eq!(client.get_claim(&claim_id).status, ClaimStatus::Closed);
+    let payments = client.get_patient_payments(&claim_id);
+    assert_eq!(payments.get(0).unwrap().settled_amount, 2000);
+}
+
+#[test]
+fn test_payment_without_funds_is_rejected_and_not_recorded() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, token, provider, patient, insurer) = setup_with_settlement(&env);
+    let claim_id = submit_and_adjudicate(&env, &client, &provider, &patient, &insurer);
+    client.process_payment(&claim_id, &insurer, &10000, &1690100000, &reference_hash(&env, 8));
+
+    // Patient only holds 5_000; the approved responsibility is 2_000, so drain first.
+    token.transfer(&patient, &insurer, &4_500);
+    let result = client.try_apply_patient_payment(&claim_id, &patient, &2000, &1690200000);
+    assert!(result.is_err());
+    assert_eq!(client.get_patient_payments(&claim_id).len(), 0);
+    assert_eq!(client.get_claim(&claim_id).patient_paid_amount, 0);
+}
+
+#[test]
+fn test_reconcile_settled_payment() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, _token, provider, patient, insurer) = setup_with_settlement(&env);
+    let claim_id = submit_and_adjudicate(&env, &client, &provider, &patient, &insurer);
+    client.process_payment(&claim_id, &insurer, &10000, &1690100000, &reference_hash(&env, 8));
+
+    let fr_owner = Address::generate(&env);
+    client.reconcile_claim(&claim_id, &0, &true, &fr_owner, &0, &insurer);
+    assert!(client.get_insurer_payments(&claim_id).get(0).unwrap().reconciled);
+}
@@ -39,6 +39,9 @@ pub enum Error {
     DisputeNotFound = 17,
     /// #520: Dispute is not in the Open state and cannot be resolved again.
     DisputeAlreadyResolved = 18,
+    /// The provider's settlement-token balance did not increase by exactly the
+    /// payment amount.
+    SettlementMismatch = 19,
 }
 
 #[contracttype]
@@ -103,12 +106,30 @@ pub struct DenialInfo {
     pub is_appealable: bool,
 }
 
+/// Receipt emitted when a payment moves settlement tokens to the provider.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SettlementReceipt {
+    pub claim_id: u64,
+    /// Index into the claim's insurer or patient payment list.
+    pub payment_idx: u32,
+    pub is_insurer_payment: bool,
+    pub payer: Address,
+    pub payee: Address,
+    pub token: Address,
+    pub amount: i128,
+    pub settled_at: u64,
+}
+
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct InsurerPaymentRecord {
     pub payment_date: u64,
     pub payment_amount: i128,
     pub payment_reference_hash: BytesN<32>,
+    /// Amount actually received by the provider in the settlement token;
+    /// zero when no settlement token is configured.
+    pub settled_amount: i128,
     pub reconciled: bool,
     pub financial_record_owner: Option<Address>,
     pub financial_record_idx: Option<u32>,
@@ -119,6 +140,9 @@ pub struct InsurerPaymentRecord {
 pub struct PatientPaymentRecord {
     pub payment_date: u64,
     pub payment_amount: i128,
+    /// Amount actually received by the provider in the settlement token;
+    /// zero when no settlement token is configured.
+    pub settled_amount: i128,
     pub reconciled: bool,
     pub financial_record_owner: Option<Address>,
     pub financial_record_idx: Option<u32>,
@@ -176,4 +200,6 @@ pub enum DataKey {
     Dispute(u64),
     /// #520: claim_id -> Vec<u64> of dispute_ids opened against that claim.
     ClaimDisputes(u64),
+    /// Optional SAC token used to settle insurer and patient payments.
+    SettlementToken,
 }
@@ -23,10 +23,19 @@
 //! **Encryption/Integrity:** Signer addresses stored encrypted. Proposal data immutable once
 //! submitted. Signature threshold enforced mathematically. XDR serialization for canonical data
 //! representation. Signer set validated before execution.
+//!
+//! ## Executable actions
+//!
+//! `propose_multisig_action` records an opaque payload and is marked `Executed` as soon as the
+//! threshold is met (a signalling vote). `propose_executable_action` instead records an
+//! [`ActionCall`] — target contract, function and arguments. Reaching the threshold moves it to
+//! `Approved`, and `execute_multisig_action` then invokes the target exactly once with this
+//! contract as the invoker, so the multisig can hold admin roles on other contracts. The outcome
+//! is stored as an [`ExecutionRecord`] readable through `get_execution`.
 
 use soroban_sdk::{
     contract, contracterror, contractimpl, contracttype, symbol_short, xdr::ToXdr, Address, Bytes,
-    BytesN, Env, Symbol, Vec,
+    BytesN, Env, Symbol, Val, Vec,
 };
 
 mod test;
@@ -56,6 +65,10 @@ pub enum Error {
     ThresholdBreached  = 13,
     /// Removing this signer would make the quorum minimum unreachable.
     QuorumBreached     = 14,
+    /// The proposal carries no executable call.
+    NotExecutable      = 15,
+    /// The proposal has not reached its approval threshold yet.
+    NotApproved        = 16,
 }
 
 #[contracttype]
@@ -72,6 +85,8 @@ pub enum DataKey {
     SignerProposal,
     /// Catalog of all proposal IDs for enumeration / cleanup.
     ProposalIds,
+    /// Outcome of executing the current proposal under this action id.
+    Execution(Symbol),
 }
 
 // ── Types ─────────────────────────────────────────────────────────────────────
@@ -80,15 +95,51 @@ pub enum DataKey {
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub enum ProposalStatus {
     Pending,
+    /// Executable proposal that met threshold and quorum and awaits
+    /// `execute_multisig_action`.
+    Approved,
     Executed,
     /// Finalized as rejected: quorum reached but threshold not met, or voting
     /// window closed without enough approvals.
     Failed,
 }
 
+/// What a proposal does once approved.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[allow(clippy::large_enum_variant)]
+pub enum ProposalAction {
+    /// Signalling vote over an opaque payload; marked `Executed` at threshold.
+    Signal,
+    /// Cross-contract call performed by `execute_multisig_action`.
+    Call(ActionCall),
+}
+
+/// A cross-contract call carried out when an executable proposal is executed.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ActionCall {
+    pub contract: Address,
+    pub function: Symbol,
+    pub args: Vec<Val>,
+}
+
+/// Outcome of `execute_multisig_action`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ExecutionRecord {
+    pub executor: Address,
+    pub executed_at: u64,
+    /// `false` when the target call failed; its state changes were rolled back.
+    pub success: bool,
+    /// XDR encoding of the value returned by the target; empty on failure.
+    pub return_value: Bytes,
+}
+
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct Proposal {
+    /// Opaque payload, or the XDR encoding of `call` for executable proposals.
     pub payload: Bytes,
     pub approvals: Vec<Address>,
     /// Signers who explicitly abstained (counted toward quorum, not threshold).
@@ -99,6 +150,7 @@ pub struct Proposal {
     pub eligible_signers: Vec<Address>,
     /// Domain tag for replay protection.
     pub domain_tag: BytesN<32>,
+    pub action: ProposalAction,
 }
 
 #[contracttype]
@@ -171,80 +223,118 @@ impl MultisigGovernance {
         action_id: Symbol,
         payload: Bytes,
     ) -> Result<(), Error> {
-        signer.require_auth();
-        Self::assert_signer(&env, &signer)?;
+        let domain_tag = Self::compute_domain_tag(&env, &action_id);
+        Self::open_proposal(
+            &env,
+            signer,
+            action_id,
+            payload,
+            domain_tag,
+            ProposalAction::Signal,
+        )
+    }
 
-        let key = DataKey::Proposal(action_id.clone());
+    /// Any admin signer may open a proposal that, once approved, invokes
+    /// `call.function` on `call.contract` with `call.args`.
+    ///
+    /// The domain tag commits to the call and to this contract's address, so
+    /// approvals cannot be replayed against a different call or deployment.
+    pub fn propose_executable_action(
+        env: Env,
+        signer: Address,
+        action_id: Symbol,
+        call: ActionCall,
+    ) -> Result<(), Error> {
+        let payload: Bytes = call.clone().to_xdr(&env);
+        let mut tagged: Bytes = action_id.clone().to_xdr(&env);
+        tagged.append(&env.current_contract_address().to_xdr(&env));
+        tagged.append(&payload);
+        let domain_tag: BytesN<32> = env.crypto().sha256(&tagged).into();
+        Self::open_proposal(
+            &env,
+            signer,
+            action_id,
+            payload,
+            domain_tag,
+            ProposalAction::Call(call),
+        )
+    }
 
-        // #636: Atomic read-then-decide guard (mirrors the fix applied to
-        // propose_signer_change in #305).
-        //
-        // The previous code used a two-step has() → set() pattern which meant
-        // that an action_id whose proposal expired without being explicitly
-        // cleaned up via cleanup_expired_proposals could never be reused —
-        // callers were permanently blocked from re-proposing the same action.
-        //
-        // Fix: read the full proposal in one operation, then decide:
-        //   • Pending + still within TTL  → block; return ProposalExists
-        //   • Pending + TTL elapsed       → allow; overwrite the stale entry
-        //   • Executed / Failed           → allow; the slot is logically free
-        //   • No entry                   → allow
-        if let Some(existing) = env
+    /// Execute an approved executable proposal. Any current signer may trigger
+    /// it; each proposal executes at most once.
+    ///
+    /// A failing target call does not abort this invocation: its effects are
+    /// rolled back, the proposal is marked `Failed` and the failure is recorded.
+    pub fn execute_multisig_action(
+        env: Env,
+        executor: Address,
+        action_id: Symbol,
+    ) -> Result<ExecutionRecord, Error> {
+        executor.require_auth();
+        Self::assert_signer(&env, &executor)?;
+
+        let key = DataKey::Proposal(action_id.clone());
+        let mut proposal: Proposal = env
             .storage()
             .persistent()
-            .get::<_, Proposal>(&key)
-        {
-            if existing.status == ProposalStatus::Pending {
-                let ttl: u64 = env
-                    .storage()
-                    .persistent()
-                    .get(&DataKey::Ttl)
-                    .ok_or(Error::NotInitialized)?;
-                if env.ledger().timestamp() <= existing.proposed_at + ttl {
-                    // Active proposal still within its TTL — reject.
-                    return Err(Error::ProposalExists);
-                }
-                // Expired pending proposal — fall through and overwrite.
-            }
-            // Finalized (Executed / Failed) proposals do not block new ones.
-        }
+            .get(&key)
+            .ok_or(Error::ProposalNotFound)?;
+        let call = match proposal.action.clone() {
+            ProposalAction::Call(call) => call,
+            ProposalAction::Signal => return Err(Error::NotExecutable),
+        };
 
-        // Snapshot the eligible signer set at proposal time (#232).
-        let eligible_signers: Vec<Address> = env
+        let ttl: u64 = env
             .storage()
             .persistent()
-            .get(&DataKey::Signers)
+            .get(&DataKey::Ttl)
             .ok_or(Error::NotInitialized)?;
 
-        let domain_tag = Self::compute_domain_tag(&env, &action_id);
-
-        let mut approvals: Vec<Address> = Vec::new(&env);
-        approvals.push_back(signer.clone());
+        match proposal.status {
+            ProposalStatus::Executed => return Err(Error::AlreadyExecuted),
+            ProposalStatus::Failed => return Err(Error::AlreadyFinalized),
+            ProposalStatus::Pending => {
+                // A proposer's own vote can satisfy a threshold of one.
+                Self::try_finalize(&env, &mut proposal)?;
+                if proposal.status != ProposalStatus::Approved {
+                    return Err(Error::NotApproved);
+                }
+            }
+            ProposalStatus::Approved => {}
+        }
+        if env.ledger().timestamp() > proposal.proposed_at + ttl {
+            return Err(Error::Expired);
+        }
 
-        let proposal = Proposal {
-            payload,
-            approvals,
-            abstentions: Vec::new(&env),
-            proposed_at: env.ledger().timestamp(),
-            status: ProposalStatus::Pending,
-            eligible_signers,
-            domain_tag,
+        let outcome = env.try_invoke_contract::<Val, soroban_sdk::Error>(
+            &call.contract,
+            &call.function,
+            call.args.clone(),
+        );
+        let (success, return_value) = match outcome {
+            Ok(Ok(value)) => (true, value.to_xdr(&env)),
+            _ => (false, Bytes::new(&env)),
         };
 
+        let record = ExecutionRecord {
+            executor: executor.clone(),
+            executed_at: env.ledger().timestamp(),
+            success,
+            return_value,
+        };
+        proposal.status = if success {
+            ProposalStatus::Executed
+        } else {
+            ProposalStatus::Failed
+        };
         env.storage().persistent().set(&key, &proposal);
-
-        // Track proposal ID for cleanup enumeration.
-        let mut ids: Vec<Symbol> = env
-            .storage()
+        env.storage()
             .persistent()
-            .get(&DataKey::ProposalIds)
-            .unwrap_or(Vec::new(&env));
-        ids.push_back(action_id.clone());
-        env.storage().persistent().set(&DataKey::ProposalIds, &ids);
+            .set(&DataKey::Execution(action_id.clone()), &record);
 
         env.events()
-            .publish((symbol_short!("proposed"), action_id), signer);
-        Ok(())
+            .publish((symbol_short!("executed"), action_id), (executor, success));
+        Ok(record)
     }
 
     /// Delete all proposals whose TTL has elapsed. Callable by anyone.
@@ -293,8 +383,9 @@ impl MultisigGovernance {
     }
 
     /// An admin signer approves an existing proposal. Once the approval count
-    /// reaches the threshold the proposal is marked Executed and an event is
-    /// emitted. Expired or already-executed proposals are rejected.
+    /// reaches the threshold the proposal is marked Executed (or Approved, for
+    /// executable proposals) and an event is emitted. Expired or
+    /// already-finalized proposals are rejected.
     pub fn approve_multisig_action(
         env: Env,
         signer: Address,
@@ -313,6 +404,9 @@ impl MultisigGovernance {
         if proposal.status == ProposalStatus::Executed {
             return Err(Error::AlreadyExecuted);
         }
+        if proposal.status == ProposalStatus::Approved {
+            return Err(Error::AlreadyFinalized);
+        }
 
         let ttl: u64 = env
             .storage()
@@ -354,6 +448,14 @@ impl MultisigGovernance {
             .ok_or(Error::ProposalNotFound)
     }
 
+    /// Outcome of `execute_multisig_action` for the current proposal under
+    /// `action_id`, or `None` if it has not been executed.
+    pub fn get_execution(env: Env, action_id: Symbol) -> Option<ExecutionRecord> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Execution(action_id))
+    }
+
     /// Record an abstention for an in-flight proposal.
     ///
     /// Abstentions count toward quorum but not toward the approval threshold.
@@ -425,7 +527,8 @@ impl MultisigGovernance {
         Ok(())
     }
 
-    /// Mark an expired proposal as `Failed` without requiring a vote.
+    /// Mark an expired proposal (pending, or approved but never executed) as
+    /// `Failed` without requiring a vote.
     ///
     /// Returns `Expired` (re-used as "not yet expired") if the TTL has not
     /// elapsed — callers should check the error meaning.
@@ -437,7 +540,8 @@ impl MultisigGovernance {
             .get(&key)
             .ok_or(Error::ProposalNotFound)?;
 
-        if proposal.status != ProposalStatus::Pending {
+        if proposal.status != ProposalStatus::Pending && proposal.status != ProposalStatus::Approved
+        {
             return Err(Error::AlreadyFinalized);
         }
 
@@ -657,9 +761,98 @@ impl MultisigGovernance {
 
     // ── internal helpers ──────────────────────────────────────────────────────
 
+    /// Shared body of `propose_multisig_action` / `propose_executable_action`.
+    fn open_proposal(
+        env: &Env,
+        signer: Address,
+        action_id: Symbol,
+        payload: Bytes,
+        domain_tag: BytesN<32>,
+        action: ProposalAction,
+    ) -> Result<(), Error> {
+        signer.require_auth();
+        Self::assert_signer(env, &signer)?;
+
+        let key = DataKey::Proposal(action_id.clone());
+
+        // #636: Atomic read-then-decide guard (mirrors the fix applied to
+        // propose_signer_change in #305).
+        //
+        // The previous code used a two-step has() → set() pattern which meant
+        // that an action_id whose proposal expired without being explicitly
+        // cleaned up via cleanup_expired_proposals could never be reused —
+        // callers were permanently blocked from re-proposing the same action.
+        //
+        // Fix: read the full proposal in one operation, then decide:
+        //   • Pending/Approved + within TTL → block; return ProposalExists
+        //   • Pending/Approved + TTL elapsed → allow; overwrite the stale entry
+        //   • Executed / Failed           → allow; the slot is logically free
+        //   • No entry                   → allow
+        if let Some(existing) = env
+            .storage()
+            .persistent()
+            .get::<_, Proposal>(&key)
+        {
+            if existing.status == ProposalStatus::Pending
+                || existing.status == ProposalStatus::Approved
+            {
+                let ttl: u64 = env
+                    .storage()
+                    .persistent()
+                    .get(&DataKey::Ttl)
+                    .ok_or(Error::NotInitialized)?;
+                if env.ledger().timestamp() <= existing.proposed_at + ttl {
+                    // Active proposal still within its TTL — reject.
+                    return Err(Error::ProposalExists);
+                }
+                // Expired pending proposal — fall through and overwrite.
+            }
+            // Finalized (Executed / Failed) proposals do not block new ones.
+        }
+
+        // Snapshot the eligible signer set at proposal time (#232).
+        let eligible_signers: Vec<Address> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Signers)
+            .ok_or(Error::NotInitialized)?;
+
+        let mut approvals: Vec<Address> = Vec::new(env);
+        approvals.push_back(signer.clone());
+
+        let proposal = Proposal {
+            payload,
+            approvals,
+            abstentions: Vec::new(env),
+            proposed_at: env.ledger().timestamp(),
+            status: ProposalStatus::Pending,
+            eligible_signers,
+            domain_tag,
+            action,
+        };
+
+        env.storage().persistent().set(&key, &proposal);
+        env.storage()
+            .persistent()
+            .remove(&DataKey::Execution(action_id.clone()));
+
+        // Track proposal ID for cleanup enumeration.
+        let mut ids: Vec<Symbol> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::ProposalIds)
+            .unwrap_or(Vec::new(env));
+        ids.push_back(action_id.clone());
+        env.storage().persistent().set(&DataKey::ProposalIds, &ids);
+
+        env.events()
+            .publish((symbol_short!("proposed"), action_id), signer);
+        Ok(())
+    }
+
     /// Attempt to finalize the proposal after a vote is recorded.
-    /// Executes if threshold is met and quorum is satisfied; marks Failed if
-    /// all eligible signers have voted and threshold is still not met.
+    /// Executes (or, for executable proposals, approves) if threshold is met
+    /// and quorum is satisfied.
     fn try_finalize(env: &Env, proposal: &mut Proposal) -> Result<(), Error> {
         let threshold: u32 = env
             .storage()
@@ -680,7 +873,10 @@ impl MultisigGovernance {
             if participation < quorum_min {
                 return Err(Error::QuorumNotMet);
             }
-            proposal.status = ProposalStatus::Executed;
+            proposal.status = match proposal.action {
+                ProposalAction::Call(_) => ProposalStatus::Approved,
+                ProposalAction::Signal => ProposalStatus::Executed,
+            };
             return Ok(());
         }
         Ok(())
@@ -730,7 +926,9 @@ impl MultisigGovernance {
     }
 
     /// Remove `removed` signer's approvals and abstentions from every in-flight
-    /// (Pending) proposal tracked in `ProposalIds`.
+    /// (Pending or Approved) proposal tracked in `ProposalIds`. An Approved
+    /// proposal that no longer meets threshold and quorum goes back to
+    /// Pending, so `execute_multisig_action` rejects it until it is re-approved.
     fn invalidate_signer_votes(env: &Env, removed: &Address) {
         let ids: Vec<Symbol> = env
             .storage()
@@ -741,7 +939,9 @@ impl MultisigGovernance {
         for id in ids.iter() {
             let key = DataKey::Proposal(id.clone());
             if let Some(mut proposal) = env.storage().persistent().get::<_, Proposal>(&key) {
-                if proposal.status != ProposalStatus::Pending {
+                if proposal.status != ProposalStatus::Pending
+                    && proposal.status != ProposalStatus::Approved
+                {
                     continue;
                 }
 
@@ -763,6 +963,13 @@ impl MultisigGovernance {
                 }
                 proposal.abstentions = new_abstentions;
 
+                if proposal.status == ProposalStatus::Approved {
+                    proposal.status = ProposalStatus::Pending;
+                    if Self::try_finalize(env, &mut proposal).is_err() {
+                        proposal.status = ProposalStatus::Pending;
+                    }
+                }
+
                 env.storage().persistent().set(&key, &proposal);
             }
         }
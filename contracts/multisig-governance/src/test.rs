@@ -2,9 +2,9 @@
 
 use super::*;
 use soroban_sdk::{
-    symbol_short,
+    contract, contractimpl, contracttype, symbol_short,
     testutils::{Address as _, Ledger},
-    Address, Bytes, Env, Vec,
+    vec, Address, Bytes, Env, IntoVal, Symbol, Vec,
 };
 
 fn make_signers(env: &Env, n: u32) -> Vec<Address> {
@@ -669,3 +669,323 @@ fn test_abstain_counts_toward_quorum() {
     // All 3 voted (1 approval, 2 abstentions) → quorum met, threshold not met → Failed.
     assert_eq!(proposal.status, ProposalStatus::Failed);
 }
+
+// ── executable actions ────────────────────────────────────────────────────────
+
+/// Target contract whose admin is the multisig itself.
+#[contract]
+struct AdminOwned;
+
+#[contracttype]
+enum TargetKey {
+    Admin,
+    Value,
+}
+
+#[contractimpl]
+impl AdminOwned {
+    pub fn init(env: Env, admin: Address) {
+        env.storage().instance().set(&TargetKey::Admin, &admin);
+    }
+
+    pub fn set_value(env: Env, value: u32) -> u32 {
+        let admin: Address = env.storage().instance().get(&TargetKey::Admin).unwrap();
+        admin.require_auth();
+        env.storage().instance().set(&TargetKey::Value, &value);
+        value * 2
+    }
+
+    pub fn value(env: Env) -> u32 {
+        env.storage().instance().get(&TargetKey::Value).unwrap_or(0)
+    }
+
+    pub fn fail(env: Env) {
+        env.storage().instance().set(&TargetKey::Value, &999u32);
+        panic!("target failure");
+    }
+}
+
+fn setup_target(env: &Env, multisig: &Address) -> AdminOwnedClient<'static> {
+    let target_id = env.register(AdminOwned, ());
+    let target = AdminOwnedClient::new(env, &target_id);
+    target.init(multisig);
+    target
+}
+
+fn set_value_call(env: &Env, target: &Address, value: u32) -> ActionCall {
+    ActionCall {
+        contract: target.clone(),
+        function: Symbol::new(env, "set_value"),
+        args: vec![env, value.into_val(env)],
+    }
+}
+
+#[test]
+fn test_executable_proposal_moves_to_approved_at_threshold() {
+    let (env, signers, client) = setup_with_quorum(3, 2, 2);
+    let target = setup_target(&env, &client.address);
+    let s0 = signers.get(0).unwrap();
+    let s1 = signers.get(1).unwrap();
+    let call = set_value_call(&env, &target.address, 7);
+    client.propose_executable_action(&s0, &symbol_short!("setval"), &call);
+    client.approve_multisig_action(&s1, &symbol_short!("setval"));
+
+    let proposal = client.get_proposal(&symbol_short!("setval"));
+    assert_eq!(proposal.status, ProposalStatus::Approved);
+    assert_eq!(proposal.action, ProposalAction::Call(call));
+    // Approval alone does not touch the target.
+    assert_eq!(target.value(), 0);
+    assert_eq!(client.get_execution(&symbol_short!("setval")), None);
+}
+
+#[test]
+fn test_execute_invokes_target_as_admin() {
+    let (env, signers, client) = setup_with_quorum(3, 2, 2);
+    let target = setup_target(&env, &client.address);
+    let s0 = signers.get(0).unwrap();
+    let s1 = signers.get(1).unwrap();
+    let s2 = signers.get(2).unwrap();
+    client.propose_executable_action(
+        &s0,
+        &symbol_short!("setval"),
+        &set_value_call(&env, &target.address, 7),
+    );
+    client.approve_multisig_action(&s1, &symbol_short!("setval"));
+
+    let record = client.execute_multisig_action(&s2, &symbol_short!("setval"));
+    assert!(record.success);
+    assert_eq!(record.executor, s2);
+    assert_eq!(record.return_value, 14u32.to_xdr(&env));
+    assert_eq!(target.value(), 7);
+    assert_eq!(
+        client.get_proposal(&symbol_short!("setval")).status,
+        ProposalStatus::Executed
+    );
+    assert_eq!(client.get_execution(&symbol_short!("setval")), Some(record));
+}
+
+#[test]
+fn test_execute_twice_returns_error() {
+    let (env, signers, client) = setup_with_quorum(3, 2, 2);
+    let target = setup_target(&env, &client.address);
+    let s0 = signers.get(0).unwrap();
+    let s1 = signers.get(1).unwrap();
+    client.propose_executable_action(
+        &s0,
+        &symbol_short!("setval"),
+        &set_value_call(&env, &target.address, 7),
+    );
+    client.approve_multisig_action(&s1, &symbol_short!("setval"));
+    client.execute_multisig_action(&s0, &symbol_short!("setval"));
+
+    let err = client
+        .try_execute_multisig_action(&s1, &symbol_short!("setval"))
+        .unwrap_err()
+        .unwrap();
+    assert_eq!(err, Error::AlreadyExecuted);
+}
+
+#[test]
+fn test_execute_before_threshold_returns_error() {
+    let (env, signers, client) = setup_with_quorum(3, 2, 2);
+    let target = setup_target(&env, &client.address);
+    let s0 = signers.get(0).unwrap();
+    client.propose_executable_action(
+        &s0,
+        &symbol_short!("setval"),
+        &set_value_call(&env, &target.address, 7),
+    );
+
+    let err = client
+        .try_execute_multisig_action(&s0, &symbol_short!("setval"))
+        .unwrap_err()
+        .unwrap();
+    assert_eq!(err, Error::NotApproved);
+    assert_eq!(target.value(), 0);
+}
+
+#[test]
+fn test_execute_after_approver_removed_returns_error() {
+    let (env, signers, client) = setup_with_quorum(4, 2, 2);
+    let target = setup_target(&env, &client.address);
+    let s0 = signers.get(0).unwrap();
+    let s1 = signers.get(1).unwrap();
+    let s2 = signers.get(2).unwrap();
+    let s3 = signers.get(3).unwrap();
+    client.propose_executable_action(
+        &s0,
+        &symbol_short!("setval"),
+        &set_value_call(&env, &target.address, 7),
+    );
+    client.approve_multisig_action(&s1, &symbol_short!("setval"));
+    assert_eq!(
+        client.get_proposal(&symbol_short!("setval")).status,
+        ProposalStatus::Approved
+    );
+
+    // Removing s1 drops its approval below the threshold.
+    client.propose_signer_change(&s2, &SignerChangeKind::Remove, &s1);
+    client.approve_signer_change(&s3);
+    let proposal = client.get_proposal(&symbol_short!("setval"));
+    assert_eq!(proposal.status, ProposalStatus::Pending);
+    assert_eq!(proposal.approvals, vec![&env, s0.clone()]);
+
+    let err = client
+        .try_execute_multisig_action(&s0, &symbol_short!("setval"))
+        .unwrap_err()
+        .unwrap();
+    assert_eq!(err, Error::NotApproved);
+    assert_eq!(target.value(), 0);
+}
+
+#[test]
+fn test_execute_with_threshold_of_one_runs_on_proposer_vote() {
+    let (env, signers, client) = setup_with_quorum(3, 1, 1);
+    let target = setup_target(&env, &client.address);
+    let s0 = signers.get(0).unwrap();
+    client.propose_executable_action(
+        &s0,
+        &symbol_short!("setval"),
+        &set_value_call(&env, &target.address, 3),
+    );
+    let record = client.execute_multisig_action(&s0, &symbol_short!("setval"));
+    assert!(record.success);
+    assert_eq!(target.value(), 3);
+}
+
+#[test]
+fn test_execute_signal_proposal_returns_error() {
+    let (env, signers, client) = setup_with_quorum(3, 2, 2);
+    let s0 = signers.get(0).unwrap();
+    let s1 = signers.get(1).unwrap();
+    client.propose_multisig_action(&s0, &symbol_short!("upgrade"), &payload(&env));
+    client.approve_multisig_action(&s1, &symbol_short!("upgrade"));
+    let err = client
+        .try_execute_multisig_action(&s0, &symbol_short!("upgrade"))
+        .unwrap_err()
+        .unwrap();
+    assert_eq!(err, Error::NotExecutable);
+}
+
+#[test]
+fn test_execute_by_non_signer_returns_error() {
+    let (env, signers, client) = setup_with_quorum(3, 2, 2);
+    let target = setup_target(&env, &client.address);
+    let s0 = signers.get(0).unwrap();
+    let s1 = signers.get(1).unwrap();
+    client.propose_executable_action(
+        &s0,
+        &symbol_short!("setval"),
+        &set_value_call(&env, &target.address, 7),
+    );
+    client.approve_multisig_action(&s1, &symbol_short!("setval"));
+    let outsider = Address::generate(&env);
+    let err = client
+        .try_execute_multisig_action(&outsider, &symbol_short!("setval"))
+        .unwrap_err()
+        .unwrap();
+    assert_eq!(err, Error::NotASigner);
+}
+
+#[test]
+fn test_execute_after_ttl_returns_error() {
+    let (env, signers, client) = setup_with_quorum(3, 2, 2);
+    let target = setup_target(&env, &client.address);
+    let s0 = signers.get(0).unwrap();
+    let s1 = signers.get(1).unwrap();
+    client.propose_executable_action(
+        &s0,
+        &symbol_short!("setval"),
+        &set_value_call(&env, &target.address, 7),
+    );
+    client.approve_multisig_action(&s1, &symbol_short!("setval"));
+    env.ledger().with_mut(|l| l.timestamp += 3601);
+    let err = client
+        .try_execute_multisig_action(&s0, &symbol_short!("setval"))
+        .unwrap_err()
+        .unwrap();
+    assert_eq!(err, Error::Expired);
+
+    // The stale approval can be finalized as failed.
+    client.finalize_expired(&symbol_short!("setval"));
+    assert_eq!(
+        client.get_proposal(&symbol_short!("setval")).status,
+        ProposalStatus::Failed
+    );
+}
+
+#[test]
+fn test_failed_target_call_is_recorded_and_rolled_back() {
+    let (env, signers, client) = setup_with_quorum(3, 2, 2);
+    let target = setup_target(&env, &client.address);
+    let s0 = signers.get(0).unwrap();
+    let s1 = signers.get(1).unwrap();
+    let call = ActionCall {
+        contract: target.address.clone(),
+        function: Symbol::new(&env, "fail"),
+        args: Vec::new(&env),
+    };
+    client.propose_executable_action(&s0, &symbol_short!("boom"), &call);
+    client.approve_multisig_action(&s1, &symbol_short!("boom"));
+
+    let record = client.execute_multisig_action(&s0, &symbol_short!("boom"));
+    assert!(!record.success);
+    assert!(record.return_value.is_empty());
+    assert_eq!(target.value(), 0);
+    assert_eq!(
+        client.get_proposal(&symbol_short!("boom")).status,
+        ProposalStatus::Failed
+    );
+    let err = client
+        .try_execute_multisig_action(&s0, &symbol_short!("boom"))
+        .unwrap_err()
+        .unwrap();
+    assert_eq!(err, Error::AlreadyFinalized);
+}
+
+#[test]
+fn test_executable_domain_tag_binds_call() {
+    let (env, signers, client) = setup_with_quorum(3, 2, 2);
+    let target = setup_target(&env, &client.address);
+    let s0 = signers.get(0).unwrap();
+    client.propose_executable_action(
+        &s0,
+        &symbol_short!("a"),
+        &set_value_call(&env, &target.address, 1),
+    );
+    client.propose_executable_action(
+        &s0,
+        &symbol_short!("b"),
+        &set_value_call(&env, &target.address, 1),
+    );
+    client.propose_executable_action(
+        &s0,
+        &symbol_short!("c"),
+        &set_value_call(&env, &target.address, 2),
+    );
+    let a = client.get_proposal(&symbol_short!("a")).domain_tag;
+    let b = client.get_proposal(&symbol_short!("b")).domain_tag;
+    let c = client.get_proposal(&symbol_short!("c")).domain_tag;
+    assert_ne!(a, b);
+    assert_ne!(a, c);
+}
+
+#[test]
+fn test_approve_after_approved_returns_error() {
+    let (env, signers, client) = setup_with_quorum(3, 2, 2);
+    let target = setup_target(&env, &client.address);
+    let s0 = signers.get(0).unwrap();
+    let s1 = signers.get(1).unwrap();
+    let s2 = signers.get(2).unwrap();
+    client.propose_executable_action(
+        &s0,
+        &symbol_short!("setval"),
+        &set_value_call(&env, &target.address, 7),
+    );
+    client.approve_multisig_action(&s1, &symbol_short!("setval"));
+    let err = client
+        .try_approve_multisig_action(&s2, &symbol_short!("setval"))
+        .unwrap_err()
+        .unwrap();
+    assert_eq!(err, Error::AlreadyFinalized);
+}
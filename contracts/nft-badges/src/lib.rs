@@ -24,10 +24,15 @@
 //! **Encryption/Integrity:** Badge recipient address immutable (soulbound property).
 //! Achievement type enumeration prevents invalid badges. Issuer identity validated at mint time.
 //! Badge storage enforces non-transferability via smart contract logic.
+//!
+//! **Verifiable Credentials:** With a credential registry configured, each minted badge is
+//! anchored as a verifiable credential issued under this contract's DID, and revoking the
+//! badge revokes the credential.
 
+use shared::credentials::{self, AnchoredCredential, CredentialRegistryClient};
 use soroban_sdk::{
     contract, contracterror, contractimpl, contracttype, symbol_short,
-    Address, Env, String, Vec,
+    Address, Bytes, BytesN, Env, String, Vec,
 };
 
 #[contracterror]
@@ -42,6 +47,7 @@ pub enum Error {
     Soulbound         = 5,
     AlreadyMinted     = 6,
     AlreadyRevoked    = 7,
+    CredentialRegistryNotSet = 8,
 }
 
 #[contracttype]
@@ -50,6 +56,11 @@ pub enum DataKey {
     NextId,
     Badge(u64),
     OwnerBadges(Address),
+    /// access-control contract that anchors badge credentials.
+    CredentialRegistry,
+    BadgeSchema,
+    /// badge id -> AnchoredCredential issued for it.
+    BadgeCredential(u64),
 }
 
 #[contracttype]
@@ -133,6 +144,26 @@ impl NftBadgesContract {
 
         env.storage().instance().set(&DataKey::NextId, &(id + 1));
 
+        if let Some(registry) = env.storage().instance()
+            .get::<_, Address>(&DataKey::CredentialRegistry)
+        {
+            let schema_hash: BytesN<32> = env.storage().instance()
+                .get(&DataKey::BadgeSchema)
+                .unwrap();
+            let credential_id = credentials::derive_credential_id(&env, id);
+            CredentialRegistryClient::new(&env, &registry).issue_credential(
+                &env.current_contract_address(),
+                &recipient,
+                &credential_id,
+                &schema_hash,
+                &0,
+            );
+            env.storage().persistent().set(
+                &DataKey::BadgeCredential(id),
+                &AnchoredCredential { registry, credential_id },
+            );
+        }
+
         env.events().publish(
             (symbol_short!("MINT"), recipient),
             id,
@@ -147,6 +178,7 @@ impl NftBadgesContract {
 
     /// Revoke a previously minted badge. Restricted to the stored admin.
     /// The badge is flagged as revoked (with a reason) rather than deleted.
+    /// An anchored credential is revoked in the registry it was issued in.
     pub fn revoke(env: Env, admin: Address, badge_id: u64, reason: String) -> Result<(), Error> {
         admin.require_auth();
         let stored_admin: Address = env.storage().instance()
@@ -168,6 +200,13 @@ impl NftBadgesContract {
         badge.revoked_reason = Some(reason);
         env.storage().persistent().set(&DataKey::Badge(badge_id), &badge);
 
+        if let Some(credential) = env.storage().persistent()
+            .get::<_, AnchoredCredential>(&DataKey::BadgeCredential(badge_id))
+        {
+            CredentialRegistryClient::new(&env, &credential.registry)
+                .revoke_credential(&env.current_contract_address(), &credential.credential_id);
+        }
+
         env.events().publish(
             (symbol_short!("REVOKE"), badge.recipient),
             badge_id,
@@ -187,11 +226,72 @@ impl NftBadgesContract {
             .unwrap_or_else(|| Vec::new(&env))
     }
 
+    /// `true` if `owner` holds an unrevoked badge of `badge_type`.
+    /// Lets other contracts gate on achievements without enumerating badges.
+    pub fn has_badge(env: Env, owner: Address, badge_type: String) -> bool {
+        let badges: Vec<u64> = env.storage().persistent()
+            .get(&DataKey::OwnerBadges(owner))
+            .unwrap_or_else(|| Vec::new(&env));
+        badges.iter().any(|id| {
+            env.storage().persistent()
+                .get::<_, BadgeMetadata>(&DataKey::Badge(id))
+                .is_some_and(|b| b.badge_type == badge_type && !b.revoked)
+        })
+    }
+
+    /// Anchor badges minted from now on in the access-control credential
+    /// registry under `schema_hash`. Admin only. Badges anchored earlier stay
+    /// in the registry they were issued in.
+    pub fn set_credential_registry(
+        env:         Env,
+        admin:       Address,
+        registry:    Address,
+        schema_hash: BytesN<32>,
+    ) -> Result<(), Error> {
+        Self::require_admin(&env, &admin)?;
+        env.storage().instance().set(&DataKey::CredentialRegistry, &registry);
+        env.storage().instance().set(&DataKey::BadgeSchema, &schema_hash);
+        Ok(())
+    }
+
+    /// Register this contract's DID and signing key with the credential
+    /// registry so it can issue badge credentials. Admin only, once.
+    pub fn register_issuer_did(
+        env:        Env,
+        admin:      Address,
+        did:        Bytes,
+        key_id:     String,
+        public_key: BytesN<32>,
+    ) -> Result<(), Error> {
+        Self::require_admin(&env, &admin)?;
+        let registry: Address = env.storage().instance()
+            .get(&DataKey::CredentialRegistry)
+            .ok_or(Error::CredentialRegistryNotSet)?;
+        credentials::register_issuer(&env, &registry, &did, &key_id, &public_key);
+        Ok(())
+    }
+
+    /// Verifiable credential anchored for `badge_id`, if any.
+    pub fn get_badge_credential(env: Env, badge_id: u64) -> Option<AnchoredCredential> {
+        env.storage().persistent().get(&DataKey::BadgeCredential(badge_id))
+    }
+
     pub fn get_admin(env: Env) -> Result<Address, Error> {
         env.storage().instance()
             .get(&DataKey::Admin)
             .ok_or(Error::NotInitialized)
     }
+
+    fn require_admin(env: &Env, admin: &Address) -> Result<(), Error> {
+        admin.require_auth();
+        let stored_admin: Address = env.storage().instance()
+            .get(&DataKey::Admin)
+            .ok_or(Error::NotInitialized)?;
+        if *admin != stored_admin {
+            return Err(Error::Unauthorized);
+        }
+        Ok(())
+    }
 }
 
 #[cfg(test)]
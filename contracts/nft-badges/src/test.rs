@@ -1,7 +1,7 @@
 #![cfg(test)]
 
 use super::*;
-use soroban_sdk::{testutils::Address as _, Address, Env, String};
+use soroban_sdk::{testutils::Address as _, Address, Bytes, BytesN, Env, String};
 
 fn setup() -> (Env, NftBadgesContractClient<'static>, Address) {
     let env         = Env::default();
@@ -106,3 +106,73 @@ fn duplicate_mint_same_recipient_and_badge_type_is_rejected() {
         .unwrap();
     assert_eq!(err, Error::AlreadyMinted);
 }
+
+#[test]
+fn has_badge_ignores_revoked_badges() {
+    let (env, client, admin) = setup();
+    let student = Address::generate(&env);
+    assert!(!client.has_badge(&student, &s(&env, "completion")));
+    let id = client.mint(&admin, &student, &s(&env, "completion"), &s(&env, "Done"), &s(&env, "uri"));
+    assert!(client.has_badge(&student, &s(&env, "completion")));
+    assert!(!client.has_badge(&student, &s(&env, "honor_roll")));
+    client.revoke(&admin, &id, &s(&env, "error"));
+    assert!(!client.has_badge(&student, &s(&env, "completion")));
+}
+
+#[test]
+fn mint_and_revoke_anchor_badge_credential() {
+    let (env, client, admin) = setup();
+    let registry_id = env.register(access_control::AccessControl, ());
+    let registry    = access_control::AccessControlClient::new(&env, &registry_id);
+    let student     = Address::generate(&env);
+
+    let unanchored = client.mint(&admin, &student, &s(&env, "t0"), &s(&env, "A0"), &s(&env, "u0"));
+    assert_eq!(client.get_badge_credential(&unanchored), None);
+
+    client.set_credential_registry(&admin, &registry_id, &BytesN::from_array(&env, &[7; 32]));
+    client.register_issuer_did(
+        &admin,
+        &Bytes::from_slice(&env, b"did:stellar:badges"),
+        &s(&env, "key-1"),
+        &BytesN::from_array(&env, &[1; 32]),
+    );
+
+    let id = client.mint(&admin, &student, &s(&env, "completion"), &s(&env, "Done"), &s(&env, "uri"));
+    let credential = client.get_badge_credential(&id).unwrap();
+    assert_eq!(credential.registry, registry_id);
+    let credential_id = credential.credential_id;
+    let record = registry.get_credential(&client.address, &credential_id);
+    assert_eq!(record.issuer, client.address);
+    assert_eq!(record.subject, student);
+    assert!(!registry.is_credential_revoked(&client.address, &credential_id));
+
+    client.revoke(&admin, &id, &s(&env, "error"));
+    assert!(registry.is_credential_revoked(&client.address, &credential_id));
+
+    // Badges minted before the registry was set revoke without touching it.
+    client.revoke(&admin, &unanchored, &s(&env, "error"));
+}
+
+#[test]
+fn revoke_uses_registry_the_credential_was_issued_in() {
+    let (env, client, admin) = setup();
+    let first_id = env.register(access_control::AccessControl, ());
+    let first    = access_control::AccessControlClient::new(&env, &first_id);
+    let second_id = env.register(access_control::AccessControl, ());
+    let student  = Address::generate(&env);
+
+    client.set_credential_registry(&admin, &first_id, &BytesN::from_array(&env, &[7; 32]));
+    client.register_issuer_did(
+        &admin,
+        &Bytes::from_slice(&env, b"did:stellar:badges"),
+        &s(&env, "key-1"),
+        &BytesN::from_array(&env, &[1; 32]),
+    );
+    let id = client.mint(&admin, &student, &s(&env, "completion"), &s(&env, "Done"), &s(&env, "uri"));
+    let credential_id = client.get_badge_credential(&id).unwrap().credential_id;
+
+    // Moving to a new registry must not strand the existing credential.
+    client.set_credential_registry(&admin, &second_id, &BytesN::from_array(&env, &[8; 32]));
+    client.revoke(&admin, &id, &s(&env, "error"));
+    assert!(first.is_credential_revoked(&client.address, &credential_id));
+}
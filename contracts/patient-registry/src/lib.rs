@@ -14,17 +14,22 @@
 //!
 //! **Audit Controls:** Patient registration events logged with identity. Patient profile update
 //! events tracked. Deregistration events recorded. Incident reports linked to patient records.
-//! Correlation IDs enable multi-contract incident tracking. Access audit trail via pagination
-//! query logs.
+//! Correlation IDs enable multi-contract incident tracking. Every record read by a party other
+//! than the patient or guardian is written to the accounting-of-disclosures ledger together
+//! with the grant op_id that authorized it and the purpose the caller gave for the read.
 //!
 //! **Data Retention Policy:** Patient demographic data retained indefinitely. Contact information
 //! encrypted and persisted. Deregistration marks patient inactive without full deletion. Incident
-//! records retained for audit trail. Historical data preserved for continuity of care.
+//! records retained for audit trail. Historical data preserved for continuity of care. Medical
+//! records follow the admin-set `RetentionPolicy` of their `RetentionClass`: `sweep_retention`
+//! archives records whose retention has lapsed and tombstones them after the grace period,
+//! skipping patients under a `RegulatoryHold`. Each step emits an audit event.
 //!
 //! **Encryption/Integrity:** EncryptedEnvelopeRef for contact information with content hashing.
 //! PolicyMetadata enforces encryption requirements. Nonzero address validation. Hash validation
 //! ensures contact data integrity. Pagination queries validated.
 
+use shared::disclosure::{self, DisclosurePage, DisclosureSummary};
 use shared::pagination::PageResult;
 
 use shared::incident_tracking::{
@@ -71,6 +76,40 @@ pub enum RetentionClass {
     Financial,
 }
 
+/// Admin-configured retention for one `RetentionClass`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RetentionPolicy {
+    /// Seconds after a record's last update before the sweep archives it.
+    pub retention_secs: u64,
+    /// Seconds an archived record is kept before the sweep tombstones it.
+    pub grace_secs: u64,
+}
+
+/// A record the retention sweep archived and will tombstone once its grace
+/// period ends.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RetentionArchival {
+    pub record_id: u64,
+    pub record_type: Symbol,
+    pub class: RetentionClass,
+    pub archived_at: u64,
+}
+
+/// Outcome of one `sweep_retention` call.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RetentionSweepReport {
+    pub archived: u32,
+    pub purged: u32,
+    /// The patient is under a regulatory hold, so nothing was touched.
+    pub on_hold: bool,
+    /// Where the next sweep should resume the record scan, or `None` once it
+    /// has reached the end of the patient's records.
+    pub next_start: Option<u32>,
+}
+
 /// Emitted on every patient status transition (Active ↔ Deregistered).
 #[contractevent]
 pub struct PatientStatusChanged {
@@ -149,6 +188,14 @@ pub enum DataKey {
     ExportNonce(Address),
     /// Share link data keyed by token hash.
     ShareLink(BytesN<32>),
+    /// Tokens of a patient's share links that may still be redeemed (Vec<BytesN<32>>).
+    PatientShareLinks(Address),
+    /// Redemption history of a share link (Vec<ShareRedemption>).
+    ShareRedemptions(BytesN<32>),
+    /// Retention policy per class (RetentionPolicy).
+    RetentionPolicy(RetentionClass),
+    /// Records archived by the retention sweep awaiting purge (Vec<RetentionArchival>).
+    RetentionArchived(Address),
     /// Marks a patient as deregistered (value: timestamp of deregistration).
     Deregistered(Address),
     /// Contract-frozen flag (bool).
@@ -167,35 +214,129 @@ pub enum DataKey {
     DeletedRecord(u64),
     /// Archived record lookup keyed by global record ID.
     ArchivedRecord(u64),
-    /// Merkle root for a patient's records.
-    /// Merkle root over the patient's ordered record IDs (see `merkle` module).
+    /// Root of the patient's record accumulator (see `merkle` module).
     MerkleRoot(Address),
+    /// Number of leaves in the patient's record accumulator (u64).
+    MerkleLeafCount(Address),
+    /// Complete accumulator subtree keyed by (patient, level, index).
+    MerkleNode(Address, u32, u64),
+    /// Accumulator leaf index of a record's latest version (u64).
+    RecordLeafIndex(u64),
     /// Optional provider-registry contract address for cross-contract verification.
     ProviderRegistry,
     /// Per-caller nonce for replay attack protection: (caller) -> u64
     CallerNonce(Address),
+    /// Monotonic counter behind grant op ids (u64, instance storage).
+    GrantOpCounter,
+    /// op_id of the grant currently authorizing (patient, doctor).
+    AccessGrantOp(Address, Address),
+    /// Ed25519 public key a registered exporter signs export tickets with.
+    Exporter(Address),
+    /// ExportTicketRecord keyed by ticket nonce.
+    ExportTicket(BytesN<32>),
 }
 
 /// --------------------
 /// Share Link
 /// --------------------
+/// Records a share link covers.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum ShareScope {
+    /// Specific global record ids owned by the patient.
+    RecordIds(Vec<u64>),
+    /// Records of this type created between the timestamp and link creation.
+    RecordType(Symbol, u64),
+}
+
+/// Who may redeem a share link.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum ShareRecipient {
+    /// Anyone holding the token.
+    Anyone,
+    /// Only this address.
+    Address(Address),
+    /// Anyone presenting a passcode whose sha256 matches.
+    PasscodeHash(BytesN<32>),
+}
+
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct ShareLinkData {
+    pub token: BytesN<32>,
     pub patient: Address,
-    pub record_id: u64,
+    pub scope: ShareScope,
+    pub recipient: ShareRecipient,
+    /// Fields every redemption reveals.
+    pub fields: Vec<FieldPermission>,
     pub uses_remaining: u32,
+    pub created_at: u64,
     pub expires_at: u64,
+    /// When the patient revoked the link; 0 if never.
+    pub revoked_at: u64,
+    /// Grant op_id cited by the disclosures its redemptions record.
+    pub op_id: u64,
+}
+
+/// One record returned by `use_share_link`, limited to the link's fields.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SharedRecordView {
+    pub record_id: u64,
+    pub fields: PartialRecord,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ShareRedemption {
+    pub redeemer: Address,
+    pub redeemed_at: u64,
+    pub record_count: u32,
 }
 
+/// --------------------
+/// Data Export Tickets
+/// --------------------
+/// Records an export ticket covers.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum ExportScope {
+    /// Specific global record ids owned by the patient.
+    RecordIds(Vec<u64>),
+    /// Every live record of the patient with one of these types.
+    RecordTypes(Vec<Symbol>),
+}
+
+/// The payload an exporter signs. See `export_ticket_payload`.
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct ExportTicket {
     pub patient: Address,
+    pub exporter: Address,
+    pub scope: ExportScope,
     pub issued_at: u64,
     pub expires_at: u64,
     pub nonce: BytesN<32>,
-    pub signature: BytesN<32>,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum ExportTicketStatus {
+    Issued,
+    Consumed,
+    Revoked,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ExportTicketRecord {
+    pub ticket: ExportTicket,
+    pub status: ExportTicketStatus,
+    /// When the ticket was consumed or revoked; 0 while issued.
+    pub closed_at: u64,
+    /// Grant op_id cited by the disclosures the export records.
+    pub op_id: u64,
 }
 
 /// One entry in the platform-wide secondary index.
@@ -255,6 +396,27 @@ pub struct RecordData {
     pub policy: PolicyMetadata,
 }
 
+/// One record version committed to the patient's Merkle accumulator.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RecordLeaf {
+    pub record_id: u64,
+    pub content_hash: BytesN<32>,
+    pub version: u64,
+    pub leaf_index: u64,
+}
+
+/// Multi-proof that each leaf is in the accumulator with `root` and `leaf_count`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RecordMultiProof {
+    pub root: BytesN<32>,
+    pub leaf_count: u64,
+    /// Sorted by `leaf_index`.
+    pub leaves: Vec<RecordLeaf>,
+    pub siblings: Vec<BytesN<32>>,
+}
+
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct ArchivedRecordRef {
@@ -309,6 +471,16 @@ pub enum ContractError {
     /// A Vec parameter or accumulator exceeds its maximum allowed length.
     InputTooLarge = 29,
     StaleNonce = 30,
+    ExporterNotRegistered = 31,
+    ExportTicketNotFound = 32,
+    ExportTicketExpired = 33,
+    ExportTicketConsumed = 34,
+    ExportTicketRevoked = 35,
+    InvalidExportScope = 36,
+    ShareRecipientMismatch = 37,
+    InvalidShareScope = 38,
+    TooManyShareLinks = 39,
+    InvalidRetentionPolicy = 40,
 }
 
 pub fn validate_cid(cid: &Bytes) -> Result<(), ContractError> {
@@ -369,20 +541,150 @@ fn next_export_nonce(env: &Env, patient: &Address, issued_at: u64) -> BytesN<32>
     env.crypto().sha256(&preimage).into()
 }
 
-fn sign_export_ticket(
+/// Bytes an exporter signs for `ticket`: this contract's address followed by the
+/// ticket, both XDR-encoded, so a signature cannot be replayed on another deployment.
+fn export_payload(env: &Env, ticket: &ExportTicket) -> Bytes {
+    let mut payload = Bytes::new(env);
+    payload.append(&env.current_contract_address().to_xdr(env));
+    payload.append(&ticket.clone().to_xdr(env));
+    payload
+}
+
+/// Checks that `scope` is non-empty, bounded and, for record ids, only names
+/// records owned by `patient`.
+fn validate_export_scope(
     env: &Env,
     patient: &Address,
-    issued_at: u64,
-    expires_at: u64,
-    nonce: &BytesN<32>,
-) -> BytesN<32> {
-    let mut payload = Bytes::new(env);
-    payload.append(&patient.clone().to_xdr(env));
-    payload.extend_from_array(&issued_at.to_be_bytes());
-    payload.extend_from_array(&expires_at.to_be_bytes());
-    payload.append(&Bytes::from(nonce.clone()));
+    scope: &ExportScope,
+) -> Result<(), ContractError> {
+    let len = match scope {
+        ExportScope::RecordIds(ids) => ids.len(),
+        ExportScope::RecordTypes(types) => types.len(),
+    };
+    if len == 0 {
+        return Err(ContractError::InvalidExportScope);
+    }
+    if len > MAX_EXPORT_SCOPE {
+        return Err(ContractError::InputTooLarge);
+    }
+    if let ExportScope::RecordIds(ids) = scope {
+        for id in ids.iter() {
+            let record: Option<RecordData> =
+                env.storage().persistent().get(&DataKey::MedicalRecord(id));
+            match record {
+                Some(data) if data.patient == *patient => {}
+                _ => return Err(ContractError::InvalidExportScope),
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Global ids of the patient's records that `scope` currently covers.
+/// Soft-deleted records are left out.
+fn resolve_export_scope(env: &Env, patient: &Address, scope: &ExportScope) -> Vec<u64> {
+    let mut selected = Vec::new(env);
+    match scope {
+        ExportScope::RecordIds(ids) => {
+            for id in ids.iter() {
+                if !env.storage().persistent().has(&DataKey::DeletedRecord(id)) {
+                    selected.push_back(id);
+                }
+            }
+        }
+        ExportScope::RecordTypes(types) => {
+            let ids: Vec<u64> = env
+                .storage()
+                .persistent()
+                .get(&DataKey::PatientRecordIds(patient.clone()))
+                .unwrap_or(Vec::new(env));
+            for id in ids.iter() {
+                if env.storage().persistent().has(&DataKey::DeletedRecord(id)) {
+                    continue;
+                }
+                let record: Option<RecordData> =
+                    env.storage().persistent().get(&DataKey::MedicalRecord(id));
+                if let Some(data) = record {
+                    if types.contains(&data.record_type) {
+                        selected.push_back(id);
+                    }
+                }
+            }
+        }
+    }
+    selected
+}
+
+/// Global ids of the patient's live records that `scope` covers, with the
+/// record-type window closed at `until`.
+fn resolve_share_scope(env: &Env, patient: &Address, scope: &ShareScope, until: u64) -> Vec<u64> {
+    let mut selected = Vec::new(env);
+    match scope {
+        ShareScope::RecordIds(ids) => {
+            for id in ids.iter() {
+                if !env.storage().persistent().has(&DataKey::DeletedRecord(id)) {
+                    selected.push_back(id);
+                }
+            }
+        }
+        ShareScope::RecordType(record_type, since) => {
+            let ids: Vec<u64> = env
+                .storage()
+                .persistent()
+                .get(&DataKey::PatientRecordIds(patient.clone()))
+                .unwrap_or(Vec::new(env));
+            for id in ids.iter() {
+                if env.storage().persistent().has(&DataKey::DeletedRecord(id)) {
+                    continue;
+                }
+                let record: Option<RecordData> =
+                    env.storage().persistent().get(&DataKey::MedicalRecord(id));
+                let Some(data) = record else {
+                    continue;
+                };
+                let created_at = data.history.get(0).map(|v| v.updated_at).unwrap_or(0);
+                if data.record_type == *record_type && created_at >= *since && created_at <= until
+                {
+                    selected.push_back(id);
+                }
+            }
+        }
+    }
+    selected
+}
+
+/// Retention class of a record: its `PolicyMetadata.retention_class` when that
+/// names a class, otherwise the patient's class.
+fn record_retention_class(
+    env: &Env,
+    data: &RecordData,
+    patient_class: &RetentionClass,
+) -> RetentionClass {
+    let tag = &data.policy.retention_class;
+    if *tag == Symbol::new(env, "clinical") {
+        RetentionClass::Clinical
+    } else if *tag == Symbol::new(env, "administrative") {
+        RetentionClass::Administrative
+    } else if *tag == Symbol::new(env, "financial") {
+        RetentionClass::Financial
+    } else {
+        patient_class.clone()
+    }
+}
+
+/// Whether `link` can still be redeemed at `now`.
+fn share_link_live(link: &ShareLinkData, now: u64) -> bool {
+    link.revoked_at == 0 && link.uses_remaining > 0 && now < link.expires_at
+}
 
-    env.crypto().sha256(&payload).into()
+/// Drops `token` from the patient's list of redeemable links.
+fn drop_active_share_link(env: &Env, patient: &Address, token: &BytesN<32>) {
+    let key = DataKey::PatientShareLinks(patient.clone());
+    let mut tokens: Vec<BytesN<32>> = env.storage().persistent().get(&key).unwrap_or(Vec::new(env));
+    if let Some(i) = tokens.first_index_of(token) {
+        tokens.remove(i);
+        env.storage().persistent().set(&key, &tokens);
+    }
 }
 
 /// Enforces that `caller` is the patient, their guardian, or an authorized doctor.
@@ -411,6 +713,58 @@ fn require_record_access(
     Err(ContractError::NotAuthorized)
 }
 
+/// Allocates the op_id of a grant, share link or export ticket.
+fn next_grant_op_id(env: &Env) -> u64 {
+    let op_id: u64 = env
+        .storage()
+        .instance()
+        .get(&DataKey::GrantOpCounter)
+        .unwrap_or(0u64)
+        + 1;
+    env.storage().instance().set(&DataKey::GrantOpCounter, &op_id);
+    op_id
+}
+
+/// Whether `caller` is the patient or their guardian, whose own reads are
+/// not disclosures.
+fn is_patient_or_guardian(env: &Env, patient: &Address, caller: &Address) -> bool {
+    if caller == patient {
+        return true;
+    }
+    let guardian: Option<Address> = env
+        .storage()
+        .persistent()
+        .get(&DataKey::Guardian(patient.clone()));
+    guardian.as_ref() == Some(caller)
+}
+
+/// Writes a disclosure entry when `caller` read `patient`'s records for
+/// `purpose` and is neither the patient nor their guardian. Reads by an
+/// authorized doctor cite the doctor's grant op_id; admin reads of
+/// deregistered patients cite 0.
+fn log_disclosure(
+    env: &Env,
+    patient: &Address,
+    caller: &Address,
+    resource_id: String,
+    purpose: String,
+) {
+    if is_patient_or_guardian(env, patient, caller) {
+        return;
+    }
+    let basis_op_id: u64 = env
+        .storage()
+        .persistent()
+        .get(&DataKey::AccessGrantOp(patient.clone(), caller.clone()))
+        .unwrap_or(0u64);
+    disclosure::record_disclosure(env, patient, caller, resource_id, basis_op_id, purpose);
+}
+
+/// Resource id for a single record in the disclosure ledger: `record:<id>`.
+fn record_resource_id(env: &Env, record_id: u64) -> String {
+    disclosure::numbered_resource_id(env, "record:", record_id)
+}
+
 const FIELD_RECORD_TYPE: u32 = 1 << 0;
 const FIELD_ENCRYPTED_REF: u32 = 1 << 1;
 const FIELD_CREATED_AT: u32 = 1 << 2;
@@ -493,6 +847,21 @@ pub const MAX_BATCH_SIZE: u32 = 50;
 /// Maximum number of authorized doctors a single patient may have.
 pub const MAX_AUTHORIZED_DOCTORS: u32 = 50;
 
+/// Maximum number of record ids or record types in an `ExportScope`.
+pub const MAX_EXPORT_SCOPE: u32 = 50;
+
+/// Lifetime of an export ticket, in seconds.
+pub const EXPORT_TICKET_TTL_SECS: u64 = 3600;
+
+/// Maximum number of records a single share link may cover.
+pub const MAX_SHARE_RECORDS: u32 = 50;
+
+/// Maximum number of redemptions a share link may allow.
+pub const MAX_SHARE_USES: u32 = 100;
+
+/// Maximum number of redeemable share links per patient.
+pub const MAX_ACTIVE_SHARE_LINKS: u32 = 50;
+
 /// Input entry for `batch_register_patients`.
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
@@ -1349,6 +1718,11 @@ impl MedicalRegistry {
 
         map.set(doctor.clone(), true);
         env.storage().persistent().set(&key, &map);
+
+        let op_id = next_grant_op_id(&env);
+        env.storage()
+            .persistent()
+            .set(&DataKey::AccessGrantOp(patient, doctor), &op_id);
         Ok(())
     }
 
@@ -1418,8 +1792,11 @@ impl MedicalRegistry {
                 .set(&DataKey::TotalAccessGrants, &new_total);
         }
 
-        map.remove(doctor);
+        map.remove(doctor.clone());
         env.storage().persistent().set(&key, &map);
+        env.storage()
+            .persistent()
+            .remove(&DataKey::AccessGrantOp(patient, doctor));
         Ok(())
     }
 
@@ -1572,7 +1949,7 @@ impl MedicalRegistry {
             .unwrap_or(Vec::new(&env));
         ids.push_back(record_id);
         env.storage().persistent().set(&ids_key, &ids);
-        Self::update_merkle_root(&env, &patient, &ids);
+        Self::append_record_leaf(&env, &patient, record_id, &record.encrypted_ref.content_hash, 1);
 
         // ── Secondary index update ────────────────────────────────────────────
         // Atomically append (patient, record_id) to the global type index.
@@ -1619,6 +1996,7 @@ impl MedicalRegistry {
         env: Env,
         patient: Address,
         caller: Address,
+        purpose: String,
     ) -> Result<Vec<MedicalRecord>, ContractError> {
         caller.require_auth();
         let patient_key = DataKey::Patient(patient.clone());
@@ -1660,6 +2038,14 @@ impl MedicalRegistry {
             );
         }
 
+        log_disclosure(
+            &env,
+            &patient,
+            &caller,
+            String::from_str(&env, "records"),
+            purpose,
+        );
+
         Ok(env
             .storage()
             .persistent()
@@ -1687,6 +2073,7 @@ impl MedicalRegistry {
         caller: Address,
         offset: u32,
         limit: u32,
+        purpose: String,
     ) -> Result<PagedRecords, ContractError> {
         // ── Validate pagination params ────────────────────────────────────────
         if limit == 0 || limit > MAX_PAGE_SIZE {
@@ -1754,15 +2141,27 @@ impl MedicalRegistry {
             }
         }
 
+        if !page.is_empty() {
+            log_disclosure(
+                &env,
+                &patient,
+                &caller,
+                String::from_str(&env, "records"),
+                purpose,
+            );
+        }
+
         Ok(PagedRecords {
             records: page,
             total,
         })
     }
 
-    pub fn get_latest_record(        env: Env,
+    pub fn get_latest_record(
+        env: Env,
         patient: Address,
         caller: Address,
+        purpose: String,
     ) -> Result<MedicalRecord, ContractError> {
         caller.require_auth();
         let patient_key = DataKey::Patient(patient.clone());
@@ -1819,79 +2218,137 @@ impl MedicalRegistry {
             }
         }
 
+        log_disclosure(
+            &env,
+            &patient,
+            &caller,
+            record_resource_id(&env, latest.record_id),
+            purpose,
+        );
         Ok(latest)
     }
 
-    /// Merkle root over the patient's ordered record IDs (see `merkle` module).
-    /// If no root was persisted yet, recomputes from `PatientRecordIds` (or empty sentinel).
+    /// Root of the patient's record accumulator (see `merkle` module), or the
+    /// `sha256("")` sentinel before the first record.
     pub fn get_merkle_root(env: Env, patient: Address) -> BytesN<32> {
-        let key = DataKey::MerkleRoot(patient.clone());
-        if let Some(root) = env.storage().persistent().get::<DataKey, BytesN<32>>(&key) {
-            root
-        } else {
-            let ids_key = DataKey::PatientRecordIds(patient);
-            let ids: Vec<u64> = env
-                .storage()
-                .persistent()
-                .get(&ids_key)
-                .unwrap_or(Vec::new(&env));
-            merkle::compute_merkle_root(&env, &ids)
-        }
+        merkle::root(&env, &patient)
     }
 
-    /// Returns true iff `proof` is a valid Merkle membership proof for `record_id` under this patient's root.
-    pub fn verify_record_membership(
-        env: Env,
-        patient: Address,
-        record_id: u64,
-        proof: Vec<BytesN<32>>,
-    ) -> bool {
-        let root = Self::get_merkle_root(env.clone(), patient);
-        merkle::verify_membership(&env, record_id, &proof, &root)
+    /// Number of leaves in the patient's record accumulator.
+    pub fn get_merkle_leaf_count(env: Env, patient: Address) -> u64 {
+        merkle::leaf_count(&env, &patient)
     }
 
-    /// Generate a Merkle membership proof for the record at `record_index`
-    /// (0-based position within `PatientRecordIds`, insertion order -- not
-    /// a record ID) in `patient`'s record tree.
+    /// Prove the latest version of each of `record_ids` (at most
+    /// `MAX_PAGE_SIZE`, duplicates ignored) against the patient's current root.
     ///
-    /// The returned proof, together with `hash_leaf(env, record_id)`, can be
-    /// passed to `verify_record_membership`; or pass the leaf hash directly
-    /// to `verify_record_proof`. Returns `ContractError::RecordNotFound` if
-    /// `record_index` is out of bounds.
+    /// Each leaf commits to the record id, its content hash and version, so
+    /// the proof attests content as well as existence. Archived or deleted
+    /// records fail with `RecordNotFound`. `caller` must be the patient, their
+    /// guardian or an authorized doctor.
     pub fn get_record_proof(
         env: Env,
         patient: Address,
-        record_index: u32,
-    ) -> Result<Vec<BytesN<32>>, ContractError> {
-        let ids_key = DataKey::PatientRecordIds(patient);
-        let record_ids: Vec<u64> = env
-            .storage()
-            .persistent()
-            .get(&ids_key)
-            .unwrap_or(Vec::new(&env));
-
-        if record_index >= record_ids.len() {
+        caller: Address,
+        record_ids: Vec<u64>,
+    ) -> Result<RecordMultiProof, ContractError> {
+        caller.require_auth();
+        require_record_access(&env, &patient, &caller)?;
+        if record_ids.is_empty() {
             return Err(ContractError::RecordNotFound);
         }
+        if record_ids.len() > MAX_PAGE_SIZE {
+            return Err(ContractError::InputTooLarge);
+        }
 
-        Ok(merkle::generate_proof(&env, &record_ids, record_index))
+        // Insertion-sort the leaves by index, dropping duplicates.
+        let mut leaves: Vec<RecordLeaf> = Vec::new(&env);
+        for record_id in record_ids.iter() {
+            if env
+                .storage()
+                .persistent()
+                .has(&DataKey::DeletedRecord(record_id))
+            {
+                return Err(ContractError::RecordNotFound);
+            }
+            let data: RecordData = env
+                .storage()
+                .persistent()
+                .get(&DataKey::MedicalRecord(record_id))
+                .ok_or(ContractError::RecordNotFound)?;
+            if data.patient != patient {
+                return Err(ContractError::RecordNotFound);
+            }
+            let leaf_index: u64 = env
+                .storage()
+                .persistent()
+                .get(&DataKey::RecordLeafIndex(record_id))
+                .ok_or(ContractError::RecordNotFound)?;
+            let leaf = RecordLeaf {
+                record_id,
+                content_hash: data.current_ref.content_hash.clone(),
+                version: data.latest_version,
+                leaf_index,
+            };
+            let mut pos = leaves.len();
+            for (i, existing) in leaves.iter().enumerate() {
+                if existing.leaf_index >= leaf_index {
+                    pos = i as u32;
+                    break;
+                }
+            }
+            if leaves.get(pos).map(|l| l.leaf_index) != Some(leaf_index) {
+                leaves.insert(pos, leaf);
+            }
+        }
+
+        let mut indices = Vec::new(&env);
+        for leaf in leaves.iter() {
+            indices.push_back(leaf.leaf_index);
+        }
+        Ok(RecordMultiProof {
+            root: merkle::root(&env, &patient),
+            leaf_count: merkle::leaf_count(&env, &patient),
+            siblings: merkle::multiproof(&env, &patient, &indices),
+            leaves,
+        })
     }
 
-    /// Returns true iff `proof` is a valid Merkle membership proof for
-    /// `leaf_hash` under this patient's root.
+    /// Returns true iff `proof` is a valid multi-proof against the patient's
+    /// current root. Proofs issued before a later record write are stale.
     ///
-    /// Like `verify_record_membership` but takes the leaf hash directly
-    /// rather than a raw record ID -- pair with `get_record_proof` and
-    /// `merkle::hash_leaf` when the caller already has (or only needs) the
-    /// hash.
-    pub fn verify_record_proof(
-        env: Env,
-        patient: Address,
-        leaf_hash: BytesN<32>,
-        proof: Vec<BytesN<32>>,
-    ) -> bool {
-        let root = Self::get_merkle_root(env.clone(), patient);
-        merkle::verify_leaf_membership(&env, leaf_hash, &proof, &root)
+    /// The accumulator keeps every leaf ever appended, so each leaf must also
+    /// be the record's current one: a live record of `patient`, at its latest
+    /// version and leaf index. Superseded versions and archived or deleted
+    /// records never verify.
+    pub fn verify_record_proof(env: Env, patient: Address, proof: RecordMultiProof) -> bool {
+        if proof.root != merkle::root(&env, &patient)
+            || proof.leaf_count != merkle::leaf_count(&env, &patient)
+        {
+            return false;
+        }
+        let mut indices = Vec::new(&env);
+        let mut hashes = Vec::new(&env);
+        for leaf in proof.leaves.iter() {
+            if !Self::is_current_leaf(&env, &patient, &leaf) {
+                return false;
+            }
+            indices.push_back(leaf.leaf_index);
+            hashes.push_back(merkle::hash_leaf(
+                &env,
+                leaf.record_id,
+                &leaf.content_hash,
+                leaf.version,
+            ));
+        }
+        merkle::verify_multiproof(
+            &env,
+            &indices,
+            &hashes,
+            &proof.siblings,
+            proof.leaf_count,
+            &proof.root,
+        )
     }
 
     /// Returns all records for `patient` whose `record_type` matches the given symbol.
@@ -1938,6 +2395,13 @@ impl MedicalRegistry {
         record_data.latest_version += 1;
 
         env.storage().persistent().set(&record_key, &record_data);
+        Self::append_record_leaf(
+            &env,
+            &patient,
+            record_id,
+            &record_data.current_ref.content_hash,
+            record_data.latest_version,
+        );
 
         // TTL bump
         Self::bump_patient_keys(&env, &patient);
@@ -1958,6 +2422,7 @@ impl MedicalRegistry {
         record_id: u64,
         caller: Address,
         page: u32,
+        purpose: String,
     ) -> Result<PageResult, ContractError> {
         caller.require_auth();
         let record_key = DataKey::MedicalRecord(record_id);
@@ -1994,6 +2459,13 @@ impl MedicalRegistry {
             has_more = end < total;
         }
 
+        log_disclosure(
+            &env,
+            &record_data.patient,
+            &caller,
+            record_resource_id(&env, record_id),
+            purpose,
+        );
         Ok(PageResult {
             ids: page_items,
             has_more,
@@ -2005,6 +2477,7 @@ impl MedicalRegistry {
         patient: Address,
         caller: Address,
         record_id: u64,
+        purpose: String,
     ) -> PartialRecord {
         caller.require_auth();
 
@@ -2037,10 +2510,19 @@ impl MedicalRegistry {
 
             env.storage()
                 .persistent()
-                .get(&DataKey::FieldAccess(patient, caller, record_id))
+                .get(&DataKey::FieldAccess(patient.clone(), caller.clone(), record_id))
                 .unwrap_or(0u32)
         };
 
+        if mask != 0 {
+            log_disclosure(
+                &env,
+                &patient,
+                &caller,
+                record_resource_id(&env, record_id),
+                purpose,
+            );
+        }
         build_partial_record(&record_data, mask)
     }
 
@@ -2049,11 +2531,12 @@ impl MedicalRegistry {
         patient: Address,
         caller: Address,
         record_type: Symbol,
+        purpose: String,
     ) -> Result<Vec<MedicalRecord>, ContractError> {
         caller.require_auth();
         require_record_access(&env, &patient, &caller)?;
 
-        let ids_key = DataKey::PatientRecordIds(patient);
+        let ids_key = DataKey::PatientRecordIds(patient.clone());
         let record_ids: Vec<u64> = env
             .storage()
             .persistent()
@@ -2084,6 +2567,15 @@ impl MedicalRegistry {
                 }
             }
         }
+        if !filtered.is_empty() {
+            log_disclosure(
+                &env,
+                &patient,
+                &caller,
+                String::from_str(&env, "records"),
+                purpose,
+            );
+        }
         Ok(filtered)
     }
 
@@ -2097,6 +2589,7 @@ impl MedicalRegistry {
         caller: Address,
         ids: Vec<u32>,
         strict_not_found: bool,
+        purpose: String,
     ) -> Result<Vec<MedicalRecord>, ContractError> {
         if ids.len() > 10 {
             return Err(ContractError::TooManyIds);
@@ -2123,9 +2616,47 @@ impl MedicalRegistry {
             }
         }
 
+        if !selected.is_empty() {
+            log_disclosure(
+                &env,
+                &patient,
+                &caller,
+                String::from_str(&env, "records"),
+                purpose,
+            );
+        }
         Ok(selected)
     }
 
+    // =====================================================
+    //              ACCOUNTING OF DISCLOSURES
+    // =====================================================
+
+    /// Disclosures of `patient`'s records with `from <= disclosed_at <= to`.
+    /// `page` indexes the ledger in storage pages of 20 entries, oldest first.
+    /// Callable by the patient or their guardian.
+    pub fn get_disclosures(
+        env: Env,
+        patient: Address,
+        caller: Address,
+        from: u64,
+        to: u64,
+        page: u32,
+    ) -> Result<DisclosurePage, ContractError> {
+        require_patient_or_guardian(&env, &patient, &caller)?;
+        Ok(disclosure::get_disclosures(&env, &patient, from, to, page))
+    }
+
+    /// Per-accessor rollup of every disclosure of `patient`'s records.
+    pub fn get_disclosure_summary(
+        env: Env,
+        patient: Address,
+        caller: Address,
+    ) -> Result<DisclosureSummary, ContractError> {
+        require_patient_or_guardian(&env, &patient, &caller)?;
+        Ok(disclosure::get_summary(&env, &patient))
+    }
+
     /// Extend TTL for active patient record keys without changing record data.
     pub fn extend_record_ttl(env: Env, patient: Address) -> Result<(), ContractError> {
         Self::require_patient_exists(&env, &patient)?;
@@ -2176,79 +2707,29 @@ impl MedicalRegistry {
             return Err(ContractError::AlreadyDeleted);
         }
 
-        let archived = ArchivedRecordRef {
-            patient: patient.clone(),
+        Ok(Self::archive_record_inner(
+            &env,
+            &patient,
             record_id,
-            record_type: record_data.record_type.clone(),
+            &record_data,
             cid_hash,
-            archived_at_ledger: env.ledger().sequence(),
-        };
-        let archive_key = DataKey::ArchivedRecord(record_id);
-        env.storage().persistent().set(&archive_key, &archived);
-        env.storage().persistent().extend_ttl(
-            &archive_key,
-            ARCHIVE_LEDGER_THRESHOLD,
-            ARCHIVE_LEDGER_BUMP_AMOUNT,
-        );
+        ))
+    }
 
-        env.storage().persistent().remove(&record_key);
+    /// Retrieve the archival pointer used by off-chain storage/indexers.
+    pub fn get_archived_ref(
+        env: Env,
+        record_id: u64,
+    ) -> Result<ArchivedRecordRef, ContractError> {
         env.storage()
             .persistent()
-            .set(&DataKey::DeletedRecord(record_id), &env.ledger().timestamp());
+            .get(&DataKey::ArchivedRecord(record_id))
+            .ok_or(ContractError::NotFound)
+    }
 
-        let records_key = DataKey::MedicalRecords(patient.clone());
-        let records: Vec<MedicalRecord> = env
-            .storage()
-            .persistent()
-            .get(&records_key)
-            .unwrap_or(Vec::new(&env));
-        let mut active_records = Vec::new(&env);
-        for record in records.iter() {
-            if record.record_id != record_id {
-                active_records.push_back(record);
-            }
-        }
-        env.storage()
-            .persistent()
-            .set(&records_key, &active_records);
-
-        let ids_key = DataKey::PatientRecordIds(patient.clone());
-        let ids: Vec<u64> = env
-            .storage()
-            .persistent()
-            .get(&ids_key)
-            .unwrap_or(Vec::new(&env));
-        let mut active_ids = Vec::new(&env);
-        for id in ids.iter() {
-            if id != record_id {
-                active_ids.push_back(id);
-            }
-        }
-        env.storage().persistent().set(&ids_key, &active_ids);
-        Self::update_merkle_root(&env, &patient, &active_ids);
-        Self::bump_patient_keys(&env, &patient);
-
-        env.events().publish(
-            (symbol_short!("archived"), patient),
-            (record_id, archived.cid_hash.clone()),
-        );
-        Ok(archived)
-    }
-
-    /// Retrieve the archival pointer used by off-chain storage/indexers.
-    pub fn get_archived_ref(
-        env: Env,
-        record_id: u64,
-    ) -> Result<ArchivedRecordRef, ContractError> {
-        env.storage()
-            .persistent()
-            .get(&DataKey::ArchivedRecord(record_id))
-            .ok_or(ContractError::NotFound)
-    }
-
-    // =====================================================
-    //                  STATE SNAPSHOT
-    // =====================================================
+    // =====================================================
+    //                  STATE SNAPSHOT
+    // =====================================================
 
     /// Emit a full-state snapshot as events for off-chain reconstruction.
     ///
@@ -2327,7 +2808,8 @@ impl MedicalRegistry {
 
     /// Create a time-limited, use-counted sharing token for a single record.
     ///
-    /// Token = sha256(patient_bytes || record_id_be || nonce_be || expires_at_be)
+    /// Shorthand for `create_scoped_share_link` with one record, any
+    /// recipient and every field.
     ///
     /// # Arguments
     /// * `patient`    - The patient who owns the record (must auth).
@@ -2343,13 +2825,6 @@ impl MedicalRegistry {
     ) -> Result<BytesN<32>, ContractError> {
         patient.require_auth();
 
-        if uses_remaining == 0 {
-            return Err(ContractError::InvalidToken);
-        }
-        if expires_at <= env.ledger().timestamp() {
-            return Err(ContractError::InvalidToken);
-        }
-
         // Verify the record_id is in-bounds.
         let records_key = DataKey::MedicalRecords(patient.clone());
         let records: Vec<MedicalRecord> = env
@@ -2360,57 +2835,101 @@ impl MedicalRegistry {
         if record_id >= records.len() as u64 {
             return Err(ContractError::InvalidToken);
         }
+        let global_id = records.get(record_id as u32).unwrap().record_id;
 
-        // Increment per-patient nonce.
-        let nonce_key = DataKey::ShareNonce(patient.clone());
-        let nonce: u64 = env.storage().persistent().get(&nonce_key).unwrap_or(0u64);
-        let next_nonce = nonce + 1;
-        env.storage().persistent().set(&nonce_key, &next_nonce);
-
-        // Build preimage: patient address bytes (32) + record_id (8) + nonce (8) + expires_at (8)
-        let patient_bytes = patient.clone().to_xdr(&env);
-        let mut preimage = Bytes::new(&env);
-        preimage.append(&patient_bytes);
-        preimage.extend_from_array(&record_id.to_be_bytes());
-        preimage.extend_from_array(&next_nonce.to_be_bytes());
-        preimage.extend_from_array(&expires_at.to_be_bytes());
+        Self::store_share_link(
+            &env,
+            patient,
+            ShareScope::RecordIds(vec![&env, global_id]),
+            ShareRecipient::Anyone,
+            vec![
+                &env,
+                FieldPermission::RecordType,
+                FieldPermission::EncryptedRef,
+                FieldPermission::CreatedAt,
+                FieldPermission::CreatedBy,
+            ],
+            uses_remaining,
+            expires_at,
+        )
+    }
 
-        let token: BytesN<32> = env.crypto().sha256(&preimage).into();
+    /// Create a share link for a set of records.
+    ///
+    /// Token = sha256(patient_bytes || scope_xdr || nonce_be || expires_at_be)
+    ///
+    /// # Arguments
+    /// * `patient`    - The patient who owns the records (must auth).
+    /// * `scope`      - Record ids, or a record type plus the earliest creation time.
+    ///   A type scope never covers records created after the link.
+    /// * `recipient`  - Restricts redemption to an address or passcode holder.
+    /// * `fields`     - Fields each redemption reveals (at least one).
+    /// * `uses_remaining` - How many times the token may be used (1..=`MAX_SHARE_USES`).
+    /// * `expires_at` - Unix timestamp after which the token is invalid.
+    #[allow(clippy::too_many_arguments)]
+    pub fn create_scoped_share_link(
+        env: Env,
+        patient: Address,
+        scope: ShareScope,
+        recipient: ShareRecipient,
+        fields: Vec<FieldPermission>,
+        uses_remaining: u32,
+        expires_at: u64,
+    ) -> Result<BytesN<32>, ContractError> {
+        patient.require_auth();
 
-        // Reject duplicate tokens — prevents a nonce collision from silently
-        // overwriting an active link and granting unintended access.
-        if env
-            .storage()
-            .persistent()
-            .has(&DataKey::ShareLink(token.clone()))
-        {
-            return Err(ContractError::AlreadyExists);
+        match &scope {
+            ShareScope::RecordIds(ids) => {
+                if ids.is_empty() || ids.len() > MAX_SHARE_RECORDS {
+                    return Err(ContractError::InvalidShareScope);
+                }
+                for id in ids.iter() {
+                    let record: Option<RecordData> =
+                        env.storage().persistent().get(&DataKey::MedicalRecord(id));
+                    match record {
+                        Some(data) if data.patient == patient => {}
+                        _ => return Err(ContractError::InvalidShareScope),
+                    }
+                }
+            }
+            ShareScope::RecordType(..) => {
+                let now = env.ledger().timestamp();
+                let covered = resolve_share_scope(&env, &patient, &scope, now);
+                if covered.is_empty() || covered.len() > MAX_SHARE_RECORDS {
+                    return Err(ContractError::InvalidShareScope);
+                }
+            }
+        }
+        if fields.is_empty() {
+            return Err(ContractError::InvalidShareScope);
         }
 
-        let link = ShareLinkData {
-            patient: patient.clone(),
-            record_id,
+        Self::store_share_link(
+            &env,
+            patient,
+            scope,
+            recipient,
+            fields,
             uses_remaining,
             expires_at,
-        };
-        env.storage()
-            .persistent()
-            .set(&DataKey::ShareLink(token.clone()), &link);
-
-        env.events().publish(
-            (symbol_short!("sl_create"), patient),
-            (token.clone(), record_id, uses_remaining, expires_at),
-        );
-
-        Ok(token)
+        )
     }
 
-    /// Redeem a share link token to read a single medical record.
+    /// Redeem a share link token.
     ///
-    /// Any address may call this function. The token is validated for expiry
-    /// and remaining uses; uses_remaining is decremented on success and the
-    /// token is removed when it reaches zero.
-    pub fn use_share_link(env: Env, token: BytesN<32>) -> Result<MedicalRecord, ContractError> {
+    /// `redeemer` must auth and satisfy the link's `ShareRecipient`; passcode
+    /// links need `passcode`. Each covered record is returned limited to the
+    /// link's fields, and the redemption is appended to the link's history.
+    /// Each record released to anyone but the patient or guardian is logged
+    /// as a `share_link` disclosure citing the link's op_id.
+    /// Expired, exhausted, revoked and unknown tokens all fail with `InvalidToken`.
+    pub fn use_share_link(
+        env: Env,
+        token: BytesN<32>,
+        redeemer: Address,
+        passcode: Option<Bytes>,
+    ) -> Result<Vec<SharedRecordView>, ContractError> {
+        redeemer.require_auth();
         let link_key = DataKey::ShareLink(token.clone());
         let mut link: ShareLinkData = env
             .storage()
@@ -2418,78 +2937,334 @@ impl MedicalRegistry {
             .get(&link_key)
             .ok_or(ContractError::InvalidToken)?;
 
-        // Check expiry.
-        if env.ledger().timestamp() >= link.expires_at {
-            env.storage().persistent().remove(&link_key);
+        let now = env.ledger().timestamp();
+        if !share_link_live(&link, now) {
+            drop_active_share_link(&env, &link.patient, &token);
             return Err(ContractError::InvalidToken);
         }
 
-        // Check uses.
-        if link.uses_remaining == 0 {
-            env.storage().persistent().remove(&link_key);
-            return Err(ContractError::InvalidToken);
+        match &link.recipient {
+            ShareRecipient::Anyone => {}
+            ShareRecipient::Address(recipient) => {
+                if *recipient != redeemer {
+                    return Err(ContractError::ShareRecipientMismatch);
+                }
+            }
+            ShareRecipient::PasscodeHash(hash) => {
+                let digest: Option<BytesN<32>> =
+                    passcode.map(|code| env.crypto().sha256(&code).into());
+                if digest.as_ref() != Some(hash) {
+                    return Err(ContractError::ShareRecipientMismatch);
+                }
+            }
         }
 
-        // Fetch the record.
-        let records_key = DataKey::MedicalRecords(link.patient.clone());
-        let records: Vec<MedicalRecord> = env
-            .storage()
-            .persistent()
-            .get(&records_key)
-            .unwrap_or(Vec::new(&env));
-        let record = records
-            .get(link.record_id as u32)
-            .ok_or(ContractError::InvalidToken)?;
+        let mask = field_permission_mask(link.fields.clone());
+        let disclosed = !is_patient_or_guardian(&env, &link.patient, &redeemer);
+        let mut views = Vec::new(&env);
+        for id in resolve_share_scope(&env, &link.patient, &link.scope, link.created_at).iter() {
+            if let Some(data) = env
+                .storage()
+                .persistent()
+                .get::<DataKey, RecordData>(&DataKey::MedicalRecord(id))
+            {
+                views.push_back(SharedRecordView {
+                    record_id: id,
+                    fields: build_partial_record(&data, mask),
+                });
+                if disclosed {
+                    disclosure::record_disclosure(
+                        &env,
+                        &link.patient,
+                        &redeemer,
+                        record_resource_id(&env, id),
+                        link.op_id,
+                        String::from_str(&env, "share_link"),
+                    );
+                }
+            }
+        }
 
         // Decrement uses.
         link.uses_remaining -= 1;
+        env.storage().persistent().set(&link_key, &link);
         if link.uses_remaining == 0 {
-            env.storage().persistent().remove(&link_key);
-        } else {
-            env.storage().persistent().set(&link_key, &link);
+            drop_active_share_link(&env, &link.patient, &token);
         }
 
+        let history_key = DataKey::ShareRedemptions(token.clone());
+        let mut history: Vec<ShareRedemption> = env
+            .storage()
+            .persistent()
+            .get(&history_key)
+            .unwrap_or(Vec::new(&env));
+        history.push_back(ShareRedemption {
+            redeemer: redeemer.clone(),
+            redeemed_at: now,
+            record_count: views.len(),
+        });
+        env.storage().persistent().set(&history_key, &history);
+
         env.events().publish(
             (symbol_short!("sl_use"), token),
-            (link.patient, link.record_id, link.uses_remaining),
+            (link.patient, redeemer, views.len(), link.uses_remaining),
         );
 
-        Ok(record)
+        Ok(views)
+    }
+
+    /// Revoke a share link (patient or guardian). Redemption history is kept.
+    pub fn revoke_share_link(
+        env: Env,
+        caller: Address,
+        token: BytesN<32>,
+    ) -> Result<(), ContractError> {
+        let link_key = DataKey::ShareLink(token.clone());
+        let mut link: ShareLinkData = env
+            .storage()
+            .persistent()
+            .get(&link_key)
+            .ok_or(ContractError::InvalidToken)?;
+        require_patient_or_guardian(&env, &link.patient, &caller)?;
+        if link.revoked_at != 0 {
+            return Err(ContractError::InvalidToken);
+        }
+        link.revoked_at = env.ledger().timestamp();
+        env.storage().persistent().set(&link_key, &link);
+        drop_active_share_link(&env, &link.patient, &token);
+
+        env.events()
+            .publish((symbol_short!("sl_revoke"), link.patient), token);
+        Ok(())
+    }
+
+    /// The patient's share links that can still be redeemed.
+    pub fn get_share_links(
+        env: Env,
+        patient: Address,
+        caller: Address,
+    ) -> Result<Vec<ShareLinkData>, ContractError> {
+        require_patient_or_guardian(&env, &patient, &caller)?;
+        let tokens: Vec<BytesN<32>> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::PatientShareLinks(patient))
+            .unwrap_or(Vec::new(&env));
+        let now = env.ledger().timestamp();
+        let mut links = Vec::new(&env);
+        for token in tokens.iter() {
+            if let Some(link) = env
+                .storage()
+                .persistent()
+                .get::<DataKey, ShareLinkData>(&DataKey::ShareLink(token))
+            {
+                if share_link_live(&link, now) {
+                    links.push_back(link);
+                }
+            }
+        }
+        Ok(links)
     }
 
-    /// Create a one-hour export authorization ticket for a patient's data.
-    pub fn request_data_export(env: Env, patient: Address) -> ExportTicket {
+    /// Every redemption of `token`, oldest first (patient or guardian).
+    pub fn get_share_redemptions(
+        env: Env,
+        caller: Address,
+        token: BytesN<32>,
+    ) -> Result<Vec<ShareRedemption>, ContractError> {
+        let link: ShareLinkData = env
+            .storage()
+            .persistent()
+            .get(&DataKey::ShareLink(token.clone()))
+            .ok_or(ContractError::InvalidToken)?;
+        require_patient_or_guardian(&env, &link.patient, &caller)?;
+        Ok(env
+            .storage()
+            .persistent()
+            .get(&DataKey::ShareRedemptions(token))
+            .unwrap_or(Vec::new(&env)))
+    }
+
+    // =====================================================
+    //                 DATA EXPORT TICKETS
+    // =====================================================
+
+    /// Register (or re-key) an exporter and the Ed25519 key it signs export
+    /// tickets with (admin-only). Re-keying invalidates signatures made with
+    /// the old key.
+    pub fn register_exporter(
+        env: Env,
+        exporter: Address,
+        public_key: BytesN<32>,
+    ) -> Result<(), ContractError> {
+        Self::require_admin(&env);
+        env.storage()
+            .persistent()
+            .set(&DataKey::Exporter(exporter.clone()), &public_key);
+        env.events()
+            .publish((symbol_short!("exp_reg"), exporter), public_key);
+        Ok(())
+    }
+
+    /// Remove an exporter (admin-only). Its outstanding tickets can no longer
+    /// be validated.
+    pub fn remove_exporter(env: Env, exporter: Address) -> Result<(), ContractError> {
+        Self::require_admin(&env);
+        let key = DataKey::Exporter(exporter.clone());
+        if !env.storage().persistent().has(&key) {
+            return Err(ContractError::ExporterNotRegistered);
+        }
+        env.storage().persistent().remove(&key);
+        env.events().publish((symbol_short!("exp_rm"), exporter), ());
+        Ok(())
+    }
+
+    pub fn get_exporter_key(env: Env, exporter: Address) -> Option<BytesN<32>> {
+        env.storage().persistent().get(&DataKey::Exporter(exporter))
+    }
+
+    /// Authorize `exporter` to export the records in `scope` for the next
+    /// `EXPORT_TICKET_TTL_SECS`. The exporter signs `export_ticket_payload`
+    /// for the returned ticket with its registered key and presents the
+    /// signature to `validate_export_ticket`.
+    pub fn request_data_export(
+        env: Env,
+        patient: Address,
+        exporter: Address,
+        scope: ExportScope,
+    ) -> Result<ExportTicket, ContractError> {
         patient.require_auth();
+        Self::require_patient_exists(&env, &patient)?;
+        if !env
+            .storage()
+            .persistent()
+            .has(&DataKey::Exporter(exporter.clone()))
+        {
+            return Err(ContractError::ExporterNotRegistered);
+        }
+        validate_export_scope(&env, &patient, &scope)?;
 
         let issued_at = env.ledger().timestamp();
-        let expires_at = issued_at.saturating_add(3600);
-        let nonce = next_export_nonce(&env, &patient, issued_at);
-        let signature = sign_export_ticket(&env, &patient, issued_at, expires_at, &nonce);
-
-        ExportTicket {
-            patient,
+        let ticket = ExportTicket {
+            patient: patient.clone(),
+            exporter: exporter.clone(),
+            scope,
             issued_at,
-            expires_at,
-            nonce,
-            signature,
-        }
+            expires_at: issued_at.saturating_add(EXPORT_TICKET_TTL_SECS),
+            nonce: next_export_nonce(&env, &patient, issued_at),
+        };
+        env.storage().persistent().set(
+            &DataKey::ExportTicket(ticket.nonce.clone()),
+            &ExportTicketRecord {
+                ticket: ticket.clone(),
+                status: ExportTicketStatus::Issued,
+                closed_at: 0,
+                op_id: next_grant_op_id(&env),
+            },
+        );
+        env.events().publish(
+            (symbol_short!("exp_req"), patient),
+            (ticket.nonce.clone(), exporter, ticket.expires_at),
+        );
+        Ok(ticket)
     }
 
-    /// Validate a patient data export ticket for expiry and integrity.
-    pub fn validate_export_ticket(env: Env, ticket: ExportTicket) -> bool {
-        if env.ledger().timestamp() > ticket.expires_at {
-            return false;
+    /// The exact bytes the exporter must sign for ticket `nonce`.
+    pub fn export_ticket_payload(env: Env, nonce: BytesN<32>) -> Result<Bytes, ContractError> {
+        let record: ExportTicketRecord = env
+            .storage()
+            .persistent()
+            .get(&DataKey::ExportTicket(nonce))
+            .ok_or(ContractError::ExportTicketNotFound)?;
+        Ok(export_payload(&env, &record.ticket))
+    }
+
+    /// Validate and consume export ticket `nonce`.
+    ///
+    /// The ticket must still be issued and unexpired, and `signature` must be the
+    /// exporter's Ed25519 signature over `export_ticket_payload`; a bad signature
+    /// aborts the call. On success the ticket is consumed, so it cannot be used
+    /// again, and the global ids of the live records it covers are returned.
+    /// Each returned record is logged as an `export` disclosure to the exporter
+    /// in the patient's ledger, citing the ticket's op_id.
+    pub fn validate_export_ticket(
+        env: Env,
+        nonce: BytesN<32>,
+        signature: BytesN<64>,
+    ) -> Result<Vec<u64>, ContractError> {
+        let key = DataKey::ExportTicket(nonce.clone());
+        let mut record: ExportTicketRecord = env
+            .storage()
+            .persistent()
+            .get(&key)
+            .ok_or(ContractError::ExportTicketNotFound)?;
+        match record.status {
+            ExportTicketStatus::Issued => {}
+            ExportTicketStatus::Consumed => return Err(ContractError::ExportTicketConsumed),
+            ExportTicketStatus::Revoked => return Err(ContractError::ExportTicketRevoked),
+        }
+        let now = env.ledger().timestamp();
+        if now > record.ticket.expires_at {
+            return Err(ContractError::ExportTicketExpired);
         }
+        let public_key: BytesN<32> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Exporter(record.ticket.exporter.clone()))
+            .ok_or(ContractError::ExporterNotRegistered)?;
+        env.crypto()
+            .ed25519_verify(&public_key, &export_payload(&env, &record.ticket), &signature);
 
-        let expected_signature = sign_export_ticket(
-            &env,
-            &ticket.patient,
-            ticket.issued_at,
-            ticket.expires_at,
-            &ticket.nonce,
+        record.status = ExportTicketStatus::Consumed;
+        record.closed_at = now;
+        env.storage().persistent().set(&key, &record);
+
+        let ids = resolve_export_scope(&env, &record.ticket.patient, &record.ticket.scope);
+        for id in ids.iter() {
+            disclosure::record_disclosure(
+                &env,
+                &record.ticket.patient,
+                &record.ticket.exporter,
+                record_resource_id(&env, id),
+                record.op_id,
+                String::from_str(&env, "export"),
+            );
+        }
+        env.events().publish(
+            (symbol_short!("exp_use"), record.ticket.patient),
+            (nonce, record.ticket.exporter, ids.len()),
         );
+        Ok(ids)
+    }
+
+    /// Revoke an export ticket before it is used (patient or guardian).
+    pub fn revoke_export_ticket(
+        env: Env,
+        caller: Address,
+        nonce: BytesN<32>,
+    ) -> Result<(), ContractError> {
+        let key = DataKey::ExportTicket(nonce.clone());
+        let mut record: ExportTicketRecord = env
+            .storage()
+            .persistent()
+            .get(&key)
+            .ok_or(ContractError::ExportTicketNotFound)?;
+        require_patient_or_guardian(&env, &record.ticket.patient, &caller)?;
+        match record.status {
+            ExportTicketStatus::Issued => {}
+            ExportTicketStatus::Consumed => return Err(ContractError::ExportTicketConsumed),
+            ExportTicketStatus::Revoked => return Err(ContractError::ExportTicketRevoked),
+        }
+        record.status = ExportTicketStatus::Revoked;
+        record.closed_at = env.ledger().timestamp();
+        env.storage().persistent().set(&key, &record);
+        env.events()
+            .publish((symbol_short!("exp_rvk"), record.ticket.patient), nonce);
+        Ok(())
+    }
 
-        expected_signature == ticket.signature
+    pub fn get_export_ticket(env: Env, nonce: BytesN<32>) -> Option<ExportTicketRecord> {
+        env.storage().persistent().get(&DataKey::ExportTicket(nonce))
     }
 
     // =====================================================
@@ -2530,47 +3305,7 @@ impl MedicalRegistry {
             return Err(ContractError::AlreadyDeleted);
         }
 
-        // Stamp the tombstone.
-        env.storage().persistent().set(
-            &DataKey::DeletedRecord(record_id),
-            &env.ledger().timestamp(),
-        );
-
-        // ── Secondary index update ────────────────────────────────────────────
-        // Remove this entry from the global type index atomically.
-        let idx_key = DataKey::GlobalTypeIndex(record_data.record_type.clone());
-        let type_index: Vec<TypeIndexEntry> = env
-            .storage()
-            .persistent()
-            .get(&idx_key)
-            .unwrap_or(Vec::new(&env));
-
-        let mut updated = Vec::new(&env);
-        for entry in type_index.iter() {
-            if entry.record_id != record_id {
-                updated.push_back(entry);
-            }
-        }
-        env.storage().persistent().set(&idx_key, &updated);
-        env.storage()
-            .persistent()
-            .extend_ttl(&idx_key, LEDGER_THRESHOLD, LEDGER_BUMP_AMOUNT);
-        // ─────────────────────────────────────────────────────────────────────
-
-        // Recompute Merkle root so outstanding proofs against the old root are invalidated.
-        let ids_key = DataKey::PatientRecordIds(patient.clone());
-        let ids: Vec<u64> = env
-            .storage()
-            .persistent()
-            .get(&ids_key)
-            .unwrap_or(Vec::new(&env));
-        Self::update_merkle_root(&env, &patient, &ids);
-
-        env.events().publish(
-            (symbol_short!("rec_del"), patient),
-            (record_id, env.ledger().timestamp()),
-        );
-
+        Self::tombstone_record(&env, &patient, record_id, &record_data.record_type);
         Ok(())
     }
 
@@ -2696,6 +3431,194 @@ impl MedicalRegistry {
         Ok(data.retention_class)
     }
 
+    /// Set how long records of `class` are kept (admin-only). Records are
+    /// archived `retention_secs` after their last update and tombstoned
+    /// `grace_secs` after that.
+    pub fn set_retention_policy(
+        env: Env,
+        class: RetentionClass,
+        policy: RetentionPolicy,
+    ) -> Result<(), ContractError> {
+        Self::require_admin(&env);
+        if policy.retention_secs == 0 {
+            return Err(ContractError::InvalidRetentionPolicy);
+        }
+        env.storage()
+            .instance()
+            .set(&DataKey::RetentionPolicy(class.clone()), &policy);
+        env.events().publish(
+            (symbol_short!("ret_pol"), class),
+            (policy.retention_secs, policy.grace_secs),
+        );
+        Ok(())
+    }
+
+    /// Stop applying retention to `class` (admin-only). Records already
+    /// archived stay archived but are no longer purged.
+    pub fn remove_retention_policy(env: Env, class: RetentionClass) -> Result<(), ContractError> {
+        Self::require_admin(&env);
+        let key = DataKey::RetentionPolicy(class.clone());
+        if !env.storage().instance().has(&key) {
+            return Err(ContractError::NotFound);
+        }
+        env.storage().instance().remove(&key);
+        env.events()
+            .publish((symbol_short!("ret_pol"), class), symbol_short!("removed"));
+        Ok(())
+    }
+
+    pub fn get_retention_policy(env: Env, class: RetentionClass) -> Option<RetentionPolicy> {
+        env.storage().instance().get(&DataKey::RetentionPolicy(class))
+    }
+
+    /// Records of `patient` archived by the sweep and awaiting purge.
+    pub fn get_pending_purges(env: Env, patient: Address) -> Vec<RetentionArchival> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::RetentionArchived(patient))
+            .unwrap_or(Vec::new(&env))
+    }
+
+    /// Apply retention policies to `patient`'s records. Callable by anyone so
+    /// keepers can run it.
+    ///
+    /// Handles at most `limit` records (`1..=MAX_BATCH_SIZE`), counting every
+    /// purge and every live record scanned, whether or not it is archived. The
+    /// scan begins at index `start` of the patient's records; pass the
+    /// report's `next_start` to continue, which already accounts for records
+    /// this call archived out of the list. Records archived
+    /// by an earlier sweep whose grace period has ended are purged first: they
+    /// are tombstoned through the `soft_delete_record` path and their archive
+    /// pointer is dropped (`ret_purge`). Live records whose retention has
+    /// lapsed are then archived through the `archive_record` path, using the
+    /// record's content hash as the archive pointer (`ret_arch`). Classes with
+    /// no policy are skipped, as are soft-deleted records. A patient under an
+    /// active `RegulatoryHold` is left untouched (`ret_hold`).
+    pub fn sweep_retention(
+        env: Env,
+        patient: Address,
+        start: u32,
+        limit: u32,
+    ) -> Result<RetentionSweepReport, ContractError> {
+        Self::require_not_frozen(&env);
+        if limit == 0 {
+            return Err(ContractError::InvalidPagination);
+        }
+        if limit > MAX_BATCH_SIZE {
+            return Err(ContractError::BatchTooLarge);
+        }
+        let patient_data: PatientData = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Patient(patient.clone()))
+            .ok_or(ContractError::NotFound)?;
+
+        let mut report = RetentionSweepReport {
+            archived: 0,
+            purged: 0,
+            on_hold: false,
+            next_start: Some(start),
+        };
+        if let Some(hold) = Self::active_hold(&env, &patient) {
+            env.events()
+                .publish((symbol_short!("ret_hold"), patient), hold.expires_at);
+            report.on_hold = true;
+            return Ok(report);
+        }
+
+        let now = env.ledger().timestamp();
+        let mut budget = limit;
+
+        // Purge archived records whose grace period has ended.
+        let pending_key = DataKey::RetentionArchived(patient.clone());
+        let pending: Vec<RetentionArchival> = env
+            .storage()
+            .persistent()
+            .get(&pending_key)
+            .unwrap_or(Vec::new(&env));
+        let mut still_pending = Vec::new(&env);
+        for entry in pending.iter() {
+            let policy: Option<RetentionPolicy> = env
+                .storage()
+                .instance()
+                .get(&DataKey::RetentionPolicy(entry.class.clone()));
+            let due = policy
+                .is_some_and(|p| now >= entry.archived_at.saturating_add(p.grace_secs));
+            if budget == 0 || !due {
+                still_pending.push_back(entry);
+                continue;
+            }
+            Self::tombstone_record(&env, &patient, entry.record_id, &entry.record_type);
+            env.storage()
+                .persistent()
+                .remove(&DataKey::ArchivedRecord(entry.record_id));
+            env.events().publish(
+                (symbol_short!("ret_purge"), patient.clone()),
+                (entry.record_id, entry.class, entry.archived_at, now),
+            );
+            report.purged += 1;
+            budget -= 1;
+        }
+
+        // Archive live records whose retention has lapsed.
+        let ids: Vec<u64> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::PatientRecordIds(patient.clone()))
+            .unwrap_or(Vec::new(&env));
+        let mut index = start;
+        while index < ids.len() && budget > 0 {
+            let id = ids.get_unchecked(index);
+            index += 1;
+            budget -= 1;
+            if env.storage().persistent().has(&DataKey::DeletedRecord(id)) {
+                continue;
+            }
+            let Some(data) = env
+                .storage()
+                .persistent()
+                .get::<DataKey, RecordData>(&DataKey::MedicalRecord(id))
+            else {
+                continue;
+            };
+            let class = record_retention_class(&env, &data, &patient_data.retention_class);
+            let Some(policy) = env
+                .storage()
+                .instance()
+                .get::<DataKey, RetentionPolicy>(&DataKey::RetentionPolicy(class.clone()))
+            else {
+                continue;
+            };
+            let last_update = data.history.last().map(|v| v.updated_at).unwrap_or(0);
+            if now < last_update.saturating_add(policy.retention_secs) {
+                continue;
+            }
+            let cid_hash = data.current_ref.content_hash.clone();
+            Self::archive_record_inner(&env, &patient, id, &data, cid_hash.clone());
+            still_pending.push_back(RetentionArchival {
+                record_id: id,
+                record_type: data.record_type.clone(),
+                class: class.clone(),
+                archived_at: now,
+            });
+            env.events().publish(
+                (symbol_short!("ret_arch"), patient.clone()),
+                (id, class, cid_hash, now),
+            );
+            report.archived += 1;
+        }
+        // Archived ids have left `PatientRecordIds`, so the next page starts
+        // that many positions earlier in the shortened list.
+        report.next_start = if index < ids.len() {
+            Some(index - report.archived)
+        } else {
+            None
+        };
+
+        env.storage().persistent().set(&pending_key, &still_pending);
+        Ok(report)
+    }
+
     // =====================================================
     //                  PRIVATE HELPERS
     // =====================================================
@@ -2726,6 +3649,202 @@ impl MedicalRegistry {
         }
     }
 
+    /// Shared by `archive_record` and the retention sweep: swaps the hot record
+    /// for an `ArchivedRecordRef` and drops it from the patient's live lists.
+    fn archive_record_inner(
+        env: &Env,
+        patient: &Address,
+        record_id: u64,
+        record_data: &RecordData,
+        cid_hash: BytesN<32>,
+    ) -> ArchivedRecordRef {
+        let archived = ArchivedRecordRef {
+            patient: patient.clone(),
+            record_id,
+            record_type: record_data.record_type.clone(),
+            cid_hash,
+            archived_at_ledger: env.ledger().sequence(),
+        };
+        let archive_key = DataKey::ArchivedRecord(record_id);
+        env.storage().persistent().set(&archive_key, &archived);
+        env.storage().persistent().extend_ttl(
+            &archive_key,
+            ARCHIVE_LEDGER_THRESHOLD,
+            ARCHIVE_LEDGER_BUMP_AMOUNT,
+        );
+
+        env.storage()
+            .persistent()
+            .remove(&DataKey::MedicalRecord(record_id));
+        env.storage()
+            .persistent()
+            .set(&DataKey::DeletedRecord(record_id), &env.ledger().timestamp());
+
+        let records_key = DataKey::MedicalRecords(patient.clone());
+        let records: Vec<MedicalRecord> = env
+            .storage()
+            .persistent()
+            .get(&records_key)
+            .unwrap_or(Vec::new(env));
+        let mut active_records = Vec::new(env);
+        for record in records.iter() {
+            if record.record_id != record_id {
+                active_records.push_back(record);
+            }
+        }
+        env.storage()
+            .persistent()
+            .set(&records_key, &active_records);
+
+        let ids_key = DataKey::PatientRecordIds(patient.clone());
+        let ids: Vec<u64> = env
+            .storage()
+            .persistent()
+            .get(&ids_key)
+            .unwrap_or(Vec::new(env));
+        let mut active_ids = Vec::new(env);
+        for id in ids.iter() {
+            if id != record_id {
+                active_ids.push_back(id);
+            }
+        }
+        env.storage().persistent().set(&ids_key, &active_ids);
+        merkle::append(env, patient, merkle::hash_tombstone(env, record_id));
+        Self::bump_patient_keys(env, patient);
+
+        env.events().publish(
+            (symbol_short!("archived"), patient.clone()),
+            (record_id, archived.cid_hash.clone()),
+        );
+        archived
+    }
+
+    /// Shared by `soft_delete_record` and the retention sweep: stamps the
+    /// tombstone and removes the record from the global type index.
+    fn tombstone_record(env: &Env, patient: &Address, record_id: u64, record_type: &Symbol) {
+        // Stamp the tombstone.
+        env.storage().persistent().set(
+            &DataKey::DeletedRecord(record_id),
+            &env.ledger().timestamp(),
+        );
+
+        // ── Secondary index update ────────────────────────────────────────────
+        // Remove this entry from the global type index atomically.
+        let idx_key = DataKey::GlobalTypeIndex(record_type.clone());
+        let type_index: Vec<TypeIndexEntry> = env
+            .storage()
+            .persistent()
+            .get(&idx_key)
+            .unwrap_or(Vec::new(env));
+
+        let mut updated = Vec::new(env);
+        for entry in type_index.iter() {
+            if entry.record_id != record_id {
+                updated.push_back(entry);
+            }
+        }
+        env.storage().persistent().set(&idx_key, &updated);
+        env.storage()
+            .persistent()
+            .extend_ttl(&idx_key, LEDGER_THRESHOLD, LEDGER_BUMP_AMOUNT);
+        // ─────────────────────────────────────────────────────────────────────
+
+        // Log the deletion so outstanding proofs against the old root are invalidated.
+        merkle::append(env, patient, merkle::hash_tombstone(env, record_id));
+
+        env.events().publish(
+            (symbol_short!("rec_del"), patient.clone()),
+            (record_id, env.ledger().timestamp()),
+        );
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn store_share_link(
+        env: &Env,
+        patient: Address,
+        scope: ShareScope,
+        recipient: ShareRecipient,
+        fields: Vec<FieldPermission>,
+        uses_remaining: u32,
+        expires_at: u64,
+    ) -> Result<BytesN<32>, ContractError> {
+        if uses_remaining == 0 || uses_remaining > MAX_SHARE_USES {
+            return Err(ContractError::InvalidToken);
+        }
+        let now = env.ledger().timestamp();
+        if expires_at <= now {
+            return Err(ContractError::InvalidToken);
+        }
+
+        // Prune links that can no longer be redeemed before applying the cap.
+        let list_key = DataKey::PatientShareLinks(patient.clone());
+        let tokens: Vec<BytesN<32>> = env
+            .storage()
+            .persistent()
+            .get(&list_key)
+            .unwrap_or(Vec::new(env));
+        let mut active = Vec::new(env);
+        for token in tokens.iter() {
+            let link: Option<ShareLinkData> =
+                env.storage().persistent().get(&DataKey::ShareLink(token.clone()));
+            if link.is_some_and(|l| share_link_live(&l, now)) {
+                active.push_back(token);
+            }
+        }
+        if active.len() >= MAX_ACTIVE_SHARE_LINKS {
+            return Err(ContractError::TooManyShareLinks);
+        }
+
+        // Increment per-patient nonce.
+        let nonce_key = DataKey::ShareNonce(patient.clone());
+        let nonce: u64 = env.storage().persistent().get(&nonce_key).unwrap_or(0u64);
+        let next_nonce = nonce + 1;
+        env.storage().persistent().set(&nonce_key, &next_nonce);
+
+        let mut preimage = Bytes::new(env);
+        preimage.append(&patient.clone().to_xdr(env));
+        preimage.append(&scope.clone().to_xdr(env));
+        preimage.extend_from_array(&next_nonce.to_be_bytes());
+        preimage.extend_from_array(&expires_at.to_be_bytes());
+
+        let token: BytesN<32> = env.crypto().sha256(&preimage).into();
+
+        // Reject duplicate tokens — prevents a nonce collision from silently
+        // overwriting an active link and granting unintended access.
+        if env
+            .storage()
+            .persistent()
+            .has(&DataKey::ShareLink(token.clone()))
+        {
+            return Err(ContractError::AlreadyExists);
+        }
+
+        let link = ShareLinkData {
+            token: token.clone(),
+            patient: patient.clone(),
+            scope,
+            recipient,
+            fields,
+            uses_remaining,
+            created_at: now,
+            expires_at,
+            revoked_at: 0,
+            op_id: next_grant_op_id(env),
+        };
+        env.storage()
+            .persistent()
+            .set(&DataKey::ShareLink(token.clone()), &link);
+        active.push_back(token.clone());
+        env.storage().persistent().set(&list_key, &active);
+
+        env.events().publish(
+            (symbol_short!("sl_create"), patient),
+            (token.clone(), uses_remaining, expires_at),
+        );
+
+        Ok(token)
+    }
+
     fn require_admin(env: &Env) {
         let admin: Address = env
             .storage()
@@ -2767,15 +3886,39 @@ impl MedicalRegistry {
         }
     }
 
-    /// Recompute and persist the Merkle root for `patient` from their current
-    /// record-ID list.  Called by `add_medical_record` after every insertion.
-    fn update_merkle_root(env: &Env, patient: &Address, ids: &Vec<u64>) {
-        let root = merkle::compute_merkle_root(env, ids);
-        let root_key = DataKey::MerkleRoot(patient.clone());
-        env.storage().persistent().set(&root_key, &root);
+    /// Whether `leaf` is the accumulator entry for the latest version of a
+    /// live record belonging to `patient`.
+    fn is_current_leaf(env: &Env, patient: &Address, leaf: &RecordLeaf) -> bool {
+        let store = env.storage().persistent();
+        if store.has(&DataKey::DeletedRecord(leaf.record_id))
+            || store.has(&DataKey::ArchivedRecord(leaf.record_id))
+        {
+            return false;
+        }
+        let Some(data) = store.get::<DataKey, RecordData>(&DataKey::MedicalRecord(leaf.record_id))
+        else {
+            return false;
+        };
+        data.patient == *patient
+            && data.latest_version == leaf.version
+            && store.get::<DataKey, u64>(&DataKey::RecordLeafIndex(leaf.record_id))
+                == Some(leaf.leaf_index)
+    }
+
+    /// Commit a new version of `record_id` to the patient's accumulator and
+    /// remember its leaf index for `get_record_proof`.
+    fn append_record_leaf(
+        env: &Env,
+        patient: &Address,
+        record_id: u64,
+        content_hash: &BytesN<32>,
+        version: u64,
+    ) {
+        let leaf = merkle::hash_leaf(env, record_id, content_hash, version);
+        let index = merkle::append(env, patient, leaf);
         env.storage()
             .persistent()
-            .extend_ttl(&root_key, LEDGER_THRESHOLD, LEDGER_BUMP_AMOUNT);
+            .set(&DataKey::RecordLeafIndex(record_id), &index);
     }
 
     /// Bump TTL for all persistent keys belonging to a patient using the
@@ -2788,13 +3931,14 @@ impl MedicalRegistry {
             .map(|d| d.retention_class)
             .unwrap_or(RetentionClass::Clinical);
 
-        let keys: [DataKey; 6] = [
+        let keys: [DataKey; 7] = [
             DataKey::Patient(patient.clone()),
             DataKey::MedicalRecords(patient.clone()),
             DataKey::AuthorizedDoctors(patient.clone()),
             DataKey::PatientRecordIds(patient.clone()),
             DataKey::ConsentAck(patient.clone()),
             DataKey::MerkleRoot(patient.clone()),
+            DataKey::MerkleLeafCount(patient.clone()),
         ];
         for key in keys.iter() {
             extend_for_retention_class(env, key, &class);
@@ -1,176 +1,283 @@
-//! Merkle tree utilities for patient record membership proofs.
+//! Incremental Merkle accumulator for patient record proofs.
 //!
 //! ## Tree construction
-//! Leaves are the patient's record IDs in insertion order.
+//! Each patient has an append-only log of leaves, one per record version
+//! written, in insertion order.
 //! ```text
-//! leaf  = sha256(0x00 || record_id_be_8)
-//! node  = sha256(0x01 || min(left, right) || max(left, right))
+//! leaf      = sha256(0x00 || record_id_be_8 || content_hash_32 || version_be_8)
+//! tombstone = leaf(record_id, [0; 32], 0)
+//! node      = sha256(0x01 || left || right)
+//! zero[0]   = [0; 32]
+//! zero[l+1] = node(zero[l], zero[l])
 //! ```
-//! Sorting children before hashing means the verifier never needs position
-//! bits — the proof is just `Vec<BytesN<32>>` of sibling hashes.
+//! With `n` leaves the tree has depth `d = ceil(log2(n))`. Leaf positions
+//! `n..2^d` hold `zero[0]`, so the root is fully determined by `n` and the
+//! leaves. The empty tree's root is the sentinel `sha256("")`.
 //!
-//! Odd-length layers: the dangling node is paired with itself.
+//! ## Storage
+//! Every node whose subtree is complete is stored under
+//! `DataKey::MerkleNode(patient, level, index)` when its last leaf is
+//! appended. Appending touches one node per level it completes, and the root
+//! and any proof sibling are rebuilt from at most one stored node or zero
+//! hash per level, so appends and proofs both cost O(log n). Every stored
+//! node read has its TTL extended, so the frontier nodes that each append
+//! reads, and the siblings a proof uses, stay live after they are written.
+//!
+//! ## Multi-proofs
+//! A proof for a set of leaves lists, level by level and left to right, the
+//! sibling of every node on the leaves' paths that cannot be computed from
+//! the leaves themselves. Children are hashed in position order, so
+//! verification needs each leaf's index and the leaf count.
+
+use soroban_sdk::{Address, Bytes, BytesN, Env, Vec};
 
-use soroban_sdk::{panic_with_error, Bytes, BytesN, Env, Vec};
+use crate::DataKey;
+use ttl_config::critical::{LEDGER_BUMP_AMOUNT, LEDGER_THRESHOLD};
 
 const LEAF_TAG: u8 = 0x00;
 const NODE_TAG: u8 = 0x01;
 
 // ─── primitives ────────────────────────────────────────────────────────────
 
-/// Hash a record ID as a Merkle leaf.
+/// Hash one version of a record as a Merkle leaf.
 ///
-/// `leaf = sha256(0x00 || record_id_be_8)`
-pub fn hash_leaf(env: &Env, record_id: u64) -> BytesN<32> {
+/// `leaf = sha256(0x00 || record_id_be_8 || content_hash || version_be_8)`
+pub fn hash_leaf(env: &Env, record_id: u64, content_hash: &BytesN<32>, version: u64) -> BytesN<32> {
     let mut pre = Bytes::new(env);
     pre.extend_from_array(&[LEAF_TAG]);
     pre.extend_from_array(&record_id.to_be_bytes());
+    pre.extend_from_array(&content_hash.to_array());
+    pre.extend_from_array(&version.to_be_bytes());
     env.crypto().sha256(&pre).into()
 }
 
-/// Hash two child hashes into a parent node.
-///
-/// Children are sorted lexicographically before hashing so that no position
-/// information needs to be stored alongside proof siblings:
+/// Leaf appended when a record is archived or deleted. Version 0 never
+/// belongs to a real record version.
+pub fn hash_tombstone(env: &Env, record_id: u64) -> BytesN<32> {
+    hash_leaf(env, record_id, &BytesN::from_array(env, &[0u8; 32]), 0)
+}
+
+/// Hash two child hashes into a parent node, left child first.
 ///
-/// `node = sha256(0x01 || min(a,b) || max(a,b))`
-pub fn hash_pair(env: &Env, a: BytesN<32>, b: BytesN<32>) -> BytesN<32> {
-    let (lo, hi) = if a.to_array() <= b.to_array() {
-        (a, b)
-    } else {
-        (b, a)
-    };
+/// `node = sha256(0x01 || left || right)`
+pub fn hash_node(env: &Env, left: &BytesN<32>, right: &BytesN<32>) -> BytesN<32> {
     let mut pre = Bytes::new(env);
     pre.extend_from_array(&[NODE_TAG]);
-    pre.extend_from_array(&lo.to_array());
-    pre.extend_from_array(&hi.to_array());
+    pre.extend_from_array(&left.to_array());
+    pre.extend_from_array(&right.to_array());
     env.crypto().sha256(&pre).into()
 }
 
-// ─── root computation ──────────────────────────────────────────────────────
+/// Root of a tree with no leaves.
+pub fn empty_root(env: &Env) -> BytesN<32> {
+    env.crypto().sha256(&Bytes::new(env)).into()
+}
 
-/// Compute the Merkle root over an ordered slice of record IDs.
-///
-/// Empty set: returns `sha256("")` as a deterministic sentinel.
-pub fn compute_merkle_root(env: &Env, record_ids: &Vec<u64>) -> BytesN<32> {
-    let n = record_ids.len();
-    if n == 0 {
-        return env.crypto().sha256(&Bytes::new(env)).into();
+/// Depth of a tree with `leaf_count` leaves: `ceil(log2(leaf_count))`.
+pub fn depth(leaf_count: u64) -> u32 {
+    if leaf_count <= 1 {
+        0
+    } else {
+        64 - (leaf_count - 1).leading_zeros()
     }
+}
 
-    // Build leaf layer
-    let mut layer: Vec<BytesN<32>> = Vec::new(env);
-    for id in record_ids.iter() {
-        layer.push_back(hash_leaf(env, id));
+/// `zero[0..=depth]`, the roots of empty subtrees at each level.
+fn zero_hashes(env: &Env, depth: u32) -> Vec<BytesN<32>> {
+    let mut zeros = Vec::new(env);
+    let mut current = BytesN::from_array(env, &[0u8; 32]);
+    zeros.push_back(current.clone());
+    for _ in 0..depth {
+        current = hash_node(env, &current, &current);
+        zeros.push_back(current.clone());
     }
+    zeros
+}
 
-    // Reduce layer-by-layer until a single root remains
-    while layer.len() > 1 {
-        let len = layer.len();
-        let mut next: Vec<BytesN<32>> = Vec::new(env);
-        let mut i = 0u32;
-        while i + 1 < len {
-            next.push_back(hash_pair(
-                env,
-                layer.get(i).unwrap_or_else(|| panic_with_error!(env, crate::ContractError::NotFound)),
-                layer.get(i + 1).unwrap_or_else(|| panic_with_error!(env, crate::ContractError::NotFound)),
-            ));
-            i += 2;
-        }
-        // Odd node: pair with itself
-        if len % 2 == 1 {
-            let last = layer.get(len - 1).unwrap_or_else(|| panic_with_error!(env, crate::ContractError::NotFound));
-            next.push_back(hash_pair(env, last.clone(), last));
+// ─── accumulator ───────────────────────────────────────────────────────────
+
+/// Number of leaves appended for `patient`.
+pub fn leaf_count(env: &Env, patient: &Address) -> u64 {
+    env.storage()
+        .persistent()
+        .get(&DataKey::MerkleLeafCount(patient.clone()))
+        .unwrap_or(0u64)
+}
+
+/// Current root for `patient`, or `empty_root` before the first append.
+pub fn root(env: &Env, patient: &Address) -> BytesN<32> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::MerkleRoot(patient.clone()))
+        .unwrap_or_else(|| empty_root(env))
+}
+
+/// Append `leaf` to `patient`'s accumulator, persist the new root and return
+/// the leaf's index.
+pub fn append(env: &Env, patient: &Address, leaf: BytesN<32>) -> u64 {
+    let index = leaf_count(env, patient);
+    let count = index + 1;
+
+    // Store the leaf and every subtree it completes.
+    let mut level = 0u32;
+    let mut position = index;
+    let mut node = leaf;
+    loop {
+        store_node(env, patient, level, position, &node);
+        if position.is_multiple_of(2) {
+            break;
         }
-        layer = next;
+        let left = load_node(env, patient, level, position - 1);
+        node = hash_node(env, &left, &node);
+        level += 1;
+        position /= 2;
     }
 
-    layer.get(0).unwrap_or_else(|| panic_with_error!(env, crate::ContractError::NotFound))
+    let d = depth(count);
+    let zeros = zero_hashes(env, d);
+    let new_root = subtree(env, patient, d, 0, count, &zeros);
+
+    let count_key = DataKey::MerkleLeafCount(patient.clone());
+    env.storage().persistent().set(&count_key, &count);
+    env.storage()
+        .persistent()
+        .extend_ttl(&count_key, LEDGER_THRESHOLD, LEDGER_BUMP_AMOUNT);
+    let root_key = DataKey::MerkleRoot(patient.clone());
+    env.storage().persistent().set(&root_key, &new_root);
+    env.storage()
+        .persistent()
+        .extend_ttl(&root_key, LEDGER_THRESHOLD, LEDGER_BUMP_AMOUNT);
+    index
+}
+
+fn store_node(env: &Env, patient: &Address, level: u32, index: u64, node: &BytesN<32>) {
+    let key = DataKey::MerkleNode(patient.clone(), level, index);
+    env.storage().persistent().set(&key, node);
+    env.storage()
+        .persistent()
+        .extend_ttl(&key, LEDGER_THRESHOLD, LEDGER_BUMP_AMOUNT);
 }
 
-// ─── proof generation ───────────────────────────────────────────────────────
+fn load_node(env: &Env, patient: &Address, level: u32, index: u64) -> BytesN<32> {
+    let key = DataKey::MerkleNode(patient.clone(), level, index);
+    let node = env.storage().persistent().get(&key).unwrap();
+    env.storage()
+        .persistent()
+        .extend_ttl(&key, LEDGER_THRESHOLD, LEDGER_BUMP_AMOUNT);
+    node
+}
 
-/// Generate a Merkle membership proof for the leaf at `target_index` within
-/// `record_ids` (insertion order, matching `compute_merkle_root`).
-///
-/// Returns one sibling hash per tree level (leaf level → root), in the same
-/// shape `verify_membership` / `verify_leaf_membership` expect. Returns an
-/// empty `Vec` if `target_index` is out of bounds.
+/// Hash of the subtree at (`level`, `index`) in a tree of `count` leaves.
 ///
-/// Mirrors `compute_merkle_root`'s layer-by-layer reduction (same pairing
-/// and odd-node-self-pairing rules) while tracking which sibling pairs with
-/// the target leaf at each level. Because `hash_pair` sorts its two inputs
-/// before hashing, the proof never needs to record left/right position.
-pub fn generate_proof(env: &Env, record_ids: &Vec<u64>, target_index: u32) -> Vec<BytesN<32>> {
-    let mut proof: Vec<BytesN<32>> = Vec::new(env);
-    let n = record_ids.len();
-    if target_index >= n {
-        return proof;
+/// Complete subtrees are read from storage and subtrees past the last leaf
+/// are zero hashes. Only the subtree holding the last leaf is partial, and
+/// it recurses into at most one partial child per level.
+fn subtree(
+    env: &Env,
+    patient: &Address,
+    level: u32,
+    index: u64,
+    count: u64,
+    zeros: &Vec<BytesN<32>>,
+) -> BytesN<32> {
+    let first = index << level;
+    let end = (index + 1) << level;
+    if first >= count {
+        return zeros.get(level).unwrap();
     }
-
-    let mut layer: Vec<BytesN<32>> = Vec::new(env);
-    for id in record_ids.iter() {
-        layer.push_back(hash_leaf(env, id));
+    if end <= count {
+        return load_node(env, patient, level, index);
     }
+    let left = subtree(env, patient, level - 1, index * 2, count, zeros);
+    let right = subtree(env, patient, level - 1, index * 2 + 1, count, zeros);
+    hash_node(env, &left, &right)
+}
 
-    let mut idx = target_index;
-    while layer.len() > 1 {
-        let len = layer.len();
-        let mut next: Vec<BytesN<32>> = Vec::new(env);
+// ─── proofs ────────────────────────────────────────────────────────────────
+
+/// Siblings proving the leaves at `indices` (strictly ascending, each below
+/// the leaf count) against the current root.
+pub fn multiproof(env: &Env, patient: &Address, indices: &Vec<u64>) -> Vec<BytesN<32>> {
+    let count = leaf_count(env, patient);
+    let d = depth(count);
+    let zeros = zero_hashes(env, d);
+
+    let mut siblings = Vec::new(env);
+    let mut layer = indices.clone();
+    for level in 0..d {
+        let mut next: Vec<u64> = Vec::new(env);
         let mut i = 0u32;
-        while i + 1 < len {
-            let left = layer.get(i).unwrap_or_else(|| panic_with_error!(env, crate::ContractError::NotFound));
-            let right = layer.get(i + 1).unwrap_or_else(|| panic_with_error!(env, crate::ContractError::NotFound));
-            if idx == i {
-                proof.push_back(right.clone());
-            } else if idx == i + 1 {
-                proof.push_back(left.clone());
+        while i < layer.len() {
+            let k = layer.get(i).unwrap();
+            if k.is_multiple_of(2) && layer.get(i + 1) == Some(k + 1) {
+                i += 2;
+            } else {
+                siblings.push_back(subtree(env, patient, level, k ^ 1, count, &zeros));
+                i += 1;
             }
-            next.push_back(hash_pair(env, left, right));
-            i += 2;
-        }
-        // Odd node: pair with itself; if it's our target, the "sibling" is itself.
-        if len % 2 == 1 {
-            let last = layer.get(len - 1).unwrap_or_else(|| panic_with_error!(env, crate::ContractError::NotFound));
-            if idx == len - 1 {
-                proof.push_back(last.clone());
+            if next.last() != Some(k / 2) {
+                next.push_back(k / 2);
             }
-            next.push_back(hash_pair(env, last.clone(), last));
         }
-        idx /= 2;
         layer = next;
     }
-
-    proof
+    siblings
 }
 
-// ─── membership verification ───────────────────────────────────────────────
-
-/// Verify that `record_id` belongs to the tree with the given `root`.
-///
-/// `proof` contains one sibling hash per tree level (leaf level → root).
-/// Returns `true` iff the recomputed root matches `root`.
-pub fn verify_membership(
+/// Verify that `leaves[i]` sits at `indices[i]` in the tree of `leaf_count`
+/// leaves with the given `root`. `indices` must be strictly ascending and
+/// every sibling must be consumed.
+pub fn verify_multiproof(
     env: &Env,
-    record_id: u64,
-    proof: &Vec<BytesN<32>>,
+    indices: &Vec<u64>,
+    leaves: &Vec<BytesN<32>>,
+    siblings: &Vec<BytesN<32>>,
+    leaf_count: u64,
     root: &BytesN<32>,
 ) -> bool {
-    verify_leaf_membership(env, hash_leaf(env, record_id), proof, root)
-}
+    if indices.is_empty() || indices.len() != leaves.len() {
+        return false;
+    }
+    let mut previous: Option<u64> = None;
+    for k in indices.iter() {
+        if k >= leaf_count || previous.is_some_and(|p| p >= k) {
+            return false;
+        }
+        previous = Some(k);
+    }
 
-/// Verify that a precomputed leaf hash belongs to the tree with the given
-/// `root`, given a sibling proof. Same as `verify_membership` but for
-/// callers that already have the leaf hash rather than the raw record ID.
-pub fn verify_leaf_membership(
-    env: &Env,
-    leaf_hash: BytesN<32>,
-    proof: &Vec<BytesN<32>>,
-    root: &BytesN<32>,
-) -> bool {
-    let mut current = leaf_hash;
-    for sibling in proof.iter() {
-        current = hash_pair(env, current, sibling);
+    let mut layer_idx = indices.clone();
+    let mut layer_hash = leaves.clone();
+    let mut used = 0u32;
+    for _ in 0..depth(leaf_count) {
+        let mut next_idx: Vec<u64> = Vec::new(env);
+        let mut next_hash: Vec<BytesN<32>> = Vec::new(env);
+        let mut i = 0u32;
+        while i < layer_idx.len() {
+            let k = layer_idx.get(i).unwrap();
+            let h = layer_hash.get(i).unwrap();
+            let parent = if k.is_multiple_of(2) && layer_idx.get(i + 1) == Some(k + 1) {
+                let right = layer_hash.get(i + 1).unwrap();
+                i += 2;
+                hash_node(env, &h, &right)
+            } else {
+                let Some(sibling) = siblings.get(used) else {
+                    return false;
+                };
+                used += 1;
+                i += 1;
+                if k.is_multiple_of(2) {
+                    hash_node(env, &h, &sibling)
+                } else {
+                    hash_node(env, &sibling, &h)
+                }
+            };
+            next_idx.push_back(k / 2);
+            next_hash.push_back(parent);
+        }
+        layer_idx = next_idx;
+        layer_hash = next_hash;
     }
-    &current == root
+
+    used == siblings.len() && layer_hash.len() == 1 && layer_hash.get(0).as_ref() == Some(root)
 }
@@ -3,7 +3,9 @@
 use super::*;
 use shared::privacy::{EncryptedEnvelopeRef, PolicyMetadata};
 use soroban_sdk::{
-    testutils::{Address as _, Events, Ledger, MockAuth, MockAuthInvoke},
+    testutils::{
+        storage::Persistent as _, Address as _, Events, Ledger, MockAuth, MockAuthInvoke,
+    },
     Address, Bytes, BytesN, Env, IntoVal, String, Symbol, TryFromVal, TryIntoVal, Vec,
 };
 
@@ -24,6 +26,11 @@ fn policy(env: &Env) -> PolicyMetadata {
     }
 }
 
+/// Purpose given for record reads in the disclosure ledger.
+fn treatment(env: &Env) -> String {
+    String::from_str(env, "treatment")
+}
+
 fn make_cid_v1(env: &Env, seed: u8) -> Bytes {
     let mut raw = [seed; 36];
     raw[0] = b'b';
@@ -472,7 +479,7 @@ fn test_grant_access_and_add_medical_record() {
         &policy(&env),
     );
 
-    let records = client.get_medical_records(&patient, &patient);
+    let records = client.get_medical_records(&patient, &patient, &treatment(&env));
     assert_eq!(records.len(), 1);
 
     let record = records.get(0).unwrap();
@@ -670,7 +677,7 @@ fn test_add_medical_record_rejects_invalid_cid() {
         result,
         Err(Ok(ContractError::InvalidEncryptedEnvelope))
     ));
-    assert_eq!(client.get_medical_records(&patient, &patient).len(), 0);
+    assert_eq!(client.get_medical_records(&patient, &patient, &treatment(&env)).len(), 0);
 }
 
 // ------------------------------------------------
@@ -1291,7 +1298,7 @@ fn test_add_record_allowed_after_consent() {
         &policy(&env),
     );
 
-    assert_eq!(client.get_medical_records(&patient, &patient).len(), 1);
+    assert_eq!(client.get_medical_records(&patient, &patient, &treatment(&env)).len(), 1);
 }
 
 #[test]
@@ -1458,7 +1465,7 @@ fn test_guardian_enables_record_write() {
         &policy(&env),
     );
 
-    assert_eq!(client.get_medical_records(&patient, &patient).len(), 1);
+    assert_eq!(client.get_medical_records(&patient, &patient, &treatment(&env)).len(), 1);
 }
 
 #[test]
@@ -1911,7 +1918,7 @@ fn test_add_record_extends_patient_ttl() {
     );
 
     // Verify the records are still accessible after adding
-    let records = client.get_medical_records(&patient, &patient);
+    let records = client.get_medical_records(&patient, &patient, &treatment(&env));
     assert_eq!(records.len(), 1);
 }
 
@@ -1935,7 +1942,12 @@ fn test_get_records_by_type_returns_matching_records() {
         &policy(&env),
     );
 
-    let results = client.get_records_by_type(&patient, &patient, &Symbol::new(&env, "VISIT"));
+    let results = client.get_records_by_type(
+        &patient,
+        &patient,
+        &Symbol::new(&env, "VISIT"),
+        &treatment(&env),
+    );
     assert_eq!(results.len(), 1);
     assert_eq!(
         results.get(0).unwrap().record_type,
@@ -1968,7 +1980,12 @@ fn test_get_records_by_type_missing_doctor_history_returns_not_found() {
             .set(&DataKey::MedicalRecord(record_id), &record_data);
     });
 
-    let result = client.try_get_records_by_type(&patient, &patient, &Symbol::new(&env, "VISIT"));
+    let result = client.try_get_records_by_type(
+        &patient,
+        &patient,
+        &Symbol::new(&env, "VISIT"),
+        &treatment(&env),
+    );
     assert_eq!(result, Err(Ok(ContractError::NotFound)));
 }
 
@@ -1977,7 +1994,7 @@ fn test_get_record_history_missing_record_returns_not_found() {
     let env = Env::default();
     let (client, patient, _doctor) = setup_for_filter(&env);
 
-    let result = client.try_get_record_history(&999, &patient, &0);
+    let result = client.try_get_record_history(&999, &patient, &0, &treatment(&env));
 
     assert_eq!(result, Err(Ok(ContractError::NotFound)));
 }
@@ -1998,7 +2015,7 @@ fn test_get_records_by_type_ttl_refreshes_records() {
     );
 
     // Call get_medical_records — internally bumps TTL
-    let records = client.get_medical_records(&patient, &patient);
+    let records = client.get_medical_records(&patient, &patient, &treatment(&env));
     assert_eq!(records.len(), 1);
 
     // Advance the ledger significantly — data should still be accessible
@@ -2006,7 +2023,7 @@ fn test_get_records_by_type_ttl_refreshes_records() {
         100 + LEDGER_THRESHOLD - 1,
         1_000_000 + 1_000,
     ));
-    let records_after = client.get_medical_records(&patient, &patient);
+    let records_after = client.get_medical_records(&patient, &patient, &treatment(&env));
     assert_eq!(records_after.len(), 1);
 }
 
@@ -2024,7 +2041,12 @@ fn test_get_records_by_type_returns_empty_when_no_match() {
     );
 
     // No PRESCRIPTION records exist — should return empty vec, not error
-    let result = client.get_records_by_type(&patient, &patient, &Symbol::new(&env, "PRESCRIPTION"));
+    let result = client.get_records_by_type(
+        &patient,
+        &patient,
+        &Symbol::new(&env, "PRESCRIPTION"),
+        &treatment(&env),
+    );
     assert_eq!(result.len(), 0);
 }
 
@@ -2046,14 +2068,14 @@ fn test_get_records_extends_ttl() {
     );
 
     // Accessing records bumps TTL; data still present after threshold
-    let records = client.get_medical_records(&patient, &patient);
+    let records = client.get_medical_records(&patient, &patient, &treatment(&env));
     assert_eq!(records.len(), 1);
 
     env.ledger().set(make_ledger_info(
         100 + LEDGER_THRESHOLD - 1,
         1_000_000 + 1_000,
     ));
-    let records_after = client.get_medical_records(&patient, &patient);
+    let records_after = client.get_medical_records(&patient, &patient, &treatment(&env));
     assert_eq!(records_after.len(), 1);
 }
 
@@ -2071,7 +2093,12 @@ fn test_get_records_by_type_returns_empty_when_no_match_after_ttl() {
     );
 
     // No PRESCRIPTION records exist — should return empty vec, not error
-    let result = client.get_records_by_type(&patient, &patient, &Symbol::new(&env, "PRESCRIPTION"));
+    let result = client.get_records_by_type(
+        &patient,
+        &patient,
+        &Symbol::new(&env, "PRESCRIPTION"),
+        &treatment(&env),
+    );
     assert_eq!(result.len(), 0);
 }
 
@@ -2101,7 +2128,7 @@ fn test_get_latest_record_returns_most_recent() {
     );
 
     let latest = client
-        .try_get_latest_record(&patient, &patient)
+        .try_get_latest_record(&patient, &patient, &treatment(&env))
         .unwrap()
         .unwrap();
     assert_eq!(
@@ -2135,7 +2162,7 @@ fn test_get_latest_record_returns_error_if_no_records() {
     );
     client.acknowledge_consent(&patient, &patient, &v1);
 
-    let result = client.try_get_latest_record(&patient, &patient);
+    let result = client.try_get_latest_record(&patient, &patient, &treatment(&env));
     assert!(matches!(result, Err(Ok(ContractError::NoRecordsFound))));
 }
 
@@ -2154,7 +2181,7 @@ fn test_get_latest_record_access_control() {
     );
 
     let attacker = Address::generate(&env);
-    let result = client.try_get_latest_record(&patient, &attacker);
+    let result = client.try_get_latest_record(&patient, &attacker, &treatment(&env));
     assert!(result.is_err());
 }
 
@@ -2231,7 +2258,12 @@ fn test_get_records_by_type_returns_empty_when_no_records_at_all() {
     let (client, patient, _doctor) = setup_for_filter(&env);
 
     // Patient registered but no records added yet
-    let result = client.get_records_by_type(&patient, &patient, &Symbol::new(&env, "LAB"));
+    let result = client.get_records_by_type(
+        &patient,
+        &patient,
+        &Symbol::new(&env, "LAB"),
+        &treatment(&env),
+    );
     assert_eq!(result.len(), 0);
 }
 
@@ -2244,7 +2276,7 @@ fn test_get_records_by_ids_strict_missing_errors() {
     ids.push_back(1);
     ids.push_back(999);
 
-    let result = client.try_get_records_by_ids(&patient, &patient, &ids, &true);
+    let result = client.try_get_records_by_ids(&patient, &patient, &ids, &true, &treatment(&env));
     assert!(result.is_err());
 }
 
@@ -2258,7 +2290,7 @@ fn test_get_records_by_ids_rejects_more_than_ten_ids() {
         ids.push_back(i);
     }
 
-    let result = client.try_get_records_by_ids(&patient, &patient, &ids, &false);
+    let result = client.try_get_records_by_ids(&patient, &patient, &ids, &false, &treatment(&env));
     assert!(result.is_err());
 }
 
@@ -2270,7 +2302,7 @@ fn test_get_records_by_ids_unauthorized_caller_rejected() {
 
     let mut ids = Vec::new(&env);
     ids.push_back(0);
-    let result = client.try_get_records_by_ids(&patient, &stranger, &ids, &false);
+    let result = client.try_get_records_by_ids(&patient, &stranger, &ids, &false, &treatment(&env));
     assert!(result.is_err());
 }
 
@@ -2282,7 +2314,7 @@ fn test_get_record_fields_full_access_for_patient() {
 
     let (_admin, patient, doctor, client) = setup_with_record(&env);
 
-    let partial = client.get_record_fields(&patient, &patient, &1u64);
+    let partial = client.get_record_fields(&patient, &patient, &1u64, &treatment(&env));
 
     assert_eq!(partial.record_type, Some(Symbol::new(&env, "LAB")));
     assert_eq!(
@@ -2306,7 +2338,7 @@ fn test_get_record_fields_partial_access_for_grantee() {
 
     client.grant_field_access(&patient, &doctor, &1u64, &fields);
 
-    let partial = client.get_record_fields(&patient, &doctor, &1u64);
+    let partial = client.get_record_fields(&patient, &doctor, &1u64, &treatment(&env));
 
     assert_eq!(partial.record_type, Some(Symbol::new(&env, "LAB")));
     assert_eq!(partial.created_at, Some(2_000));
@@ -2322,7 +2354,7 @@ fn test_get_record_fields_returns_none_when_no_access() {
     let (_admin, patient, _doctor, client) = setup_with_record(&env);
     let stranger = Address::generate(&env);
 
-    let partial = client.get_record_fields(&patient, &stranger, &1u64);
+    let partial = client.get_record_fields(&patient, &stranger, &1u64, &treatment(&env));
 
     assert_eq!(partial.record_type, None);
     assert_eq!(partial.encrypted_ref_hash, None);
@@ -2848,15 +2880,16 @@ fn test_single_use_link_works_once() {
     env.ledger().set_timestamp(1000);
 
     let (_admin, patient, _doctor, client) = setup_with_record(&env);
+    let reader = Address::generate(&env);
 
     let token = client.create_share_link(&patient, &0u64, &1u32, &2000u64);
 
     // First use succeeds
-    let record = client.use_share_link(&token);
-    assert_eq!(record.record_type, Symbol::new(&env, "LAB"));
+    let views = client.use_share_link(&token, &reader, &None);
+    assert_eq!(views.get(0).unwrap().fields.record_type, Some(Symbol::new(&env, "LAB")));
 
     // Second use fails — token exhausted
-    let result = client.try_use_share_link(&token);
+    let result = client.try_use_share_link(&token, &reader, &None);
     assert!(matches!(result, Err(Ok(ContractError::InvalidToken))));
 }
 
@@ -2867,17 +2900,18 @@ fn test_multi_use_link_decrements_and_exhausts() {
     env.ledger().set_timestamp(1000);
 
     let (_admin, patient, _doctor, client) = setup_with_record(&env);
+    let reader = Address::generate(&env);
 
     let token = client.create_share_link(&patient, &0u64, &3u32, &9000u64);
 
     // Three successful uses
     for _ in 0..3 {
-        let record = client.use_share_link(&token);
-        assert_eq!(record.record_type, Symbol::new(&env, "LAB"));
+        let views = client.use_share_link(&token, &reader, &None);
+        assert_eq!(views.get(0).unwrap().fields.record_type, Some(Symbol::new(&env, "LAB")));
     }
 
     // Fourth use fails
-    let result = client.try_use_share_link(&token);
+    let result = client.try_use_share_link(&token, &reader, &None);
     assert!(matches!(result, Err(Ok(ContractError::InvalidToken))));
 }
 
@@ -2888,6 +2922,7 @@ fn test_expired_token_returns_invalid_token() {
     env.ledger().set_timestamp(1000);
 
     let (_admin, patient, _doctor, client) = setup_with_record(&env);
+    let reader = Address::generate(&env);
 
     // expires_at = 1500
     let token = client.create_share_link(&patient, &0u64, &5u32, &1500u64);
@@ -2895,7 +2930,7 @@ fn test_expired_token_returns_invalid_token() {
     // Advance time past expiry
     env.ledger().set_timestamp(1501);
 
-    let result = client.try_use_share_link(&token);
+    let result = client.try_use_share_link(&token, &reader, &None);
     assert!(matches!(result, Err(Ok(ContractError::InvalidToken))));
 }
 
@@ -2944,9 +2979,10 @@ fn test_unknown_token_returns_invalid_token() {
 
     let contract_id = env.register(MedicalRegistry, ());
     let client = MedicalRegistryClient::new(&env, &contract_id);
+    let reader = Address::generate(&env);
 
     let fake_token = BytesN::from_array(&env, &[0xdeu8; 32]);
-    let result = client.try_use_share_link(&fake_token);
+    let result = client.try_use_share_link(&fake_token, &reader, &None);
     assert!(matches!(result, Err(Ok(ContractError::InvalidToken))));
 }
 
@@ -2957,6 +2993,7 @@ fn test_two_links_for_same_record_are_independent() {
     env.ledger().set_timestamp(1000);
 
     let (_admin, patient, _doctor, client) = setup_with_record(&env);
+    let reader = Address::generate(&env);
 
     let token_a = client.create_share_link(&patient, &0u64, &1u32, &2000u64);
     let token_b = client.create_share_link(&patient, &0u64, &2u32, &2000u64);
@@ -2965,13 +3002,13 @@ fn test_two_links_for_same_record_are_independent() {
     assert_ne!(token_a, token_b);
 
     // Exhaust token_a
-    client.use_share_link(&token_a);
-    assert!(client.try_use_share_link(&token_a).is_err());
+    client.use_share_link(&token_a, &reader, &None);
+    assert!(client.try_use_share_link(&token_a, &reader, &None).is_err());
 
     // token_b still has 2 uses
-    client.use_share_link(&token_b);
-    client.use_share_link(&token_b);
-    assert!(client.try_use_share_link(&token_b).is_err());
+    client.use_share_link(&token_b, &reader, &None);
+    client.use_share_link(&token_b, &reader, &None);
+    assert!(client.try_use_share_link(&token_b, &reader, &None).is_err());
 }
 
 #[test]
@@ -3047,17 +3084,18 @@ fn test_concurrent_single_use_link_only_one_succeeds() {
     env.ledger().set_timestamp(1000);
 
     let (_admin, patient, _doctor, client) = setup_with_record(&env);
+    let reader = Address::generate(&env);
 
     // Create a single-use link (uses_remaining = 1).
     let token = client.create_share_link(&patient, &0u64, &1u32, &2000u64);
 
     // First redemption — represents the winning transaction in the race.
-    let record = client.use_share_link(&token);
-    assert_eq!(record.record_type, Symbol::new(&env, "LAB"));
+    let views = client.use_share_link(&token, &reader, &None);
+    assert_eq!(views.get(0).unwrap().fields.record_type, Some(Symbol::new(&env, "LAB")));
 
     // Second redemption — represents the losing transaction; the token was
     // removed atomically after the first use so this must fail.
-    let result = client.try_use_share_link(&token);
+    let result = client.try_use_share_link(&token, &reader, &None);
     assert!(
         matches!(result, Err(Ok(ContractError::InvalidToken))),
         "second redemption must fail with InvalidToken after single-use link is exhausted"
@@ -3073,38 +3111,303 @@ fn test_concurrent_two_use_link_third_call_fails() {
     env.ledger().set_timestamp(1000);
 
     let (_admin, patient, _doctor, client) = setup_with_record(&env);
+    let reader = Address::generate(&env);
 
     let token = client.create_share_link(&patient, &0u64, &2u32, &5000u64);
 
     // First and second callers both succeed.
-    let record1 = client.use_share_link(&token);
-    assert_eq!(record1.record_type, Symbol::new(&env, "LAB"));
+    let views1 = client.use_share_link(&token, &reader, &None);
+    assert_eq!(views1.get(0).unwrap().fields.record_type, Some(Symbol::new(&env, "LAB")));
 
-    let record2 = client.use_share_link(&token);
-    assert_eq!(record2.record_type, Symbol::new(&env, "LAB"));
+    let views2 = client.use_share_link(&token, &reader, &None);
+    assert_eq!(views2.get(0).unwrap().fields.record_type, Some(Symbol::new(&env, "LAB")));
 
     // Third caller loses — link is exhausted.
-    let result = client.try_use_share_link(&token);
+    let result = client.try_use_share_link(&token, &reader, &None);
     assert!(
         matches!(result, Err(Ok(ContractError::InvalidToken))),
         "third redemption must fail after two-use link is exhausted"
     );
 }
 
+/// Patient with a LAB record at t=1000 and IMAGING records at t=1000 and t=50_000.
+fn setup_share_bundle(env: &Env) -> (Address, Address, MedicalRegistryClient<'_>) {
+    env.mock_all_auths();
+    env.ledger().set_timestamp(1000);
+    let (_admin, patient, doctor, client) = setup_with_record(env);
+    let imaging = Symbol::new(env, "IMAGING");
+    client.add_medical_record(
+        &patient,
+        &doctor,
+        &encrypted_ref(env, 2),
+        &imaging,
+        &policy(env),
+    );
+    env.ledger().set_timestamp(50_000);
+    client.add_medical_record(
+        &patient,
+        &doctor,
+        &encrypted_ref(env, 3),
+        &imaging,
+        &policy(env),
+    );
+    (patient, doctor, client)
+}
+
+#[test]
+fn test_scoped_share_link_returns_field_limited_bundle() {
+    let env = Env::default();
+    let (patient, doctor, client) = setup_share_bundle(&env);
+    let reader = Address::generate(&env);
+
+    let token = client.create_scoped_share_link(
+        &patient,
+        &ShareScope::RecordType(Symbol::new(&env, "IMAGING"), 10_000),
+        &ShareRecipient::Anyone,
+        &vec![
+            &env,
+            FieldPermission::RecordType,
+            FieldPermission::CreatedAt,
+        ],
+        &1,
+        &60_000,
+    );
+    // An imaging record added after the link was created stays out of it.
+    env.ledger().set_timestamp(50_001);
+    client.add_medical_record(
+        &patient,
+        &doctor,
+        &encrypted_ref(&env, 4),
+        &Symbol::new(&env, "IMAGING"),
+        &policy(&env),
+    );
+
+    let views = client.use_share_link(&token, &reader, &None);
+    assert_eq!(views.len(), 1);
+    let view = views.get(0).unwrap();
+    assert_eq!(view.fields.record_type, Some(Symbol::new(&env, "IMAGING")));
+    assert_eq!(view.fields.created_at, Some(50_000));
+    assert_eq!(view.fields.encrypted_ref_hash, None);
+    assert_eq!(view.fields.created_by, None);
+}
+
+#[test]
+fn test_scoped_share_link_rejects_foreign_or_empty_scope() {
+    let env = Env::default();
+    let (patient, _doctor, client) = setup_share_bundle(&env);
+    let fields = vec![&env, FieldPermission::RecordType];
+
+    assert_eq!(
+        client.try_create_scoped_share_link(
+            &patient,
+            &ShareScope::RecordIds(vec![&env, 99u64]),
+            &ShareRecipient::Anyone,
+            &fields,
+            &1,
+            &60_000,
+        ),
+        Err(Ok(ContractError::InvalidShareScope))
+    );
+    assert_eq!(
+        client.try_create_scoped_share_link(
+            &patient,
+            &ShareScope::RecordType(Symbol::new(&env, "RX"), 0),
+            &ShareRecipient::Anyone,
+            &fields,
+            &1,
+            &60_000,
+        ),
+        Err(Ok(ContractError::InvalidShareScope))
+    );
+    assert_eq!(
+        client.try_create_scoped_share_link(
+            &patient,
+            &ShareScope::RecordIds(vec![&env, 1u64]),
+            &ShareRecipient::Anyone,
+            &Vec::new(&env),
+            &1,
+            &60_000,
+        ),
+        Err(Ok(ContractError::InvalidShareScope))
+    );
+}
+
+#[test]
+fn test_share_link_bound_to_recipient_address() {
+    let env = Env::default();
+    let (patient, _doctor, client) = setup_share_bundle(&env);
+    let recipient = Address::generate(&env);
+    let other = Address::generate(&env);
+
+    let token = client.create_scoped_share_link(
+        &patient,
+        &ShareScope::RecordIds(vec![&env, 1u64]),
+        &ShareRecipient::Address(recipient.clone()),
+        &vec![&env, FieldPermission::RecordType],
+        &2,
+        &60_000,
+    );
+    assert_eq!(
+        client.try_use_share_link(&token, &other, &None),
+        Err(Ok(ContractError::ShareRecipientMismatch))
+    );
+    assert_eq!(client.use_share_link(&token, &recipient, &None).len(), 1);
+}
+
+#[test]
+fn test_share_link_bound_to_passcode_hash() {
+    let env = Env::default();
+    let (patient, _doctor, client) = setup_share_bundle(&env);
+    let reader = Address::generate(&env);
+    let passcode = Bytes::from_slice(&env, b"correct horse");
+    let hash: BytesN<32> = env.crypto().sha256(&passcode).into();
+
+    let token = client.create_scoped_share_link(
+        &patient,
+        &ShareScope::RecordIds(vec![&env, 1u64]),
+        &ShareRecipient::PasscodeHash(hash),
+        &vec![&env, FieldPermission::RecordType],
+        &1,
+        &60_000,
+    );
+    assert_eq!(
+        client.try_use_share_link(&token, &reader, &None),
+        Err(Ok(ContractError::ShareRecipientMismatch))
+    );
+    assert_eq!(
+        client.try_use_share_link(&token, &reader, &Some(Bytes::from_slice(&env, b"wrong"))),
+        Err(Ok(ContractError::ShareRecipientMismatch))
+    );
+    assert_eq!(
+        client
+            .use_share_link(&token, &reader, &Some(passcode))
+            .len(),
+        1
+    );
+}
+
+#[test]
+fn test_list_revoke_and_redemption_history() {
+    let env = Env::default();
+    let (patient, _doctor, client) = setup_share_bundle(&env);
+    let reader = Address::generate(&env);
+    let stranger = Address::generate(&env);
+
+    let kept = client.create_share_link(&patient, &0u64, &3u32, &60_000u64);
+    let revoked = client.create_share_link(&patient, &0u64, &3u32, &60_000u64);
+    let links = client.get_share_links(&patient, &patient);
+    assert_eq!(links.len(), 2);
+    assert_eq!(links.get(0).unwrap().token, kept);
+
+    client.use_share_link(&revoked, &reader, &None);
+    assert_eq!(
+        client.try_revoke_share_link(&stranger, &revoked),
+        Err(Ok(ContractError::NotAuthorized))
+    );
+    client.revoke_share_link(&patient, &revoked);
+    assert!(matches!(
+        client.try_use_share_link(&revoked, &reader, &None),
+        Err(Ok(ContractError::InvalidToken))
+    ));
+
+    let links = client.get_share_links(&patient, &patient);
+    assert_eq!(links.len(), 1);
+    assert_eq!(links.get(0).unwrap().token, kept);
+
+    let history = client.get_share_redemptions(&patient, &revoked);
+    assert_eq!(history.len(), 1);
+    let redemption = history.get(0).unwrap();
+    assert_eq!(redemption.redeemer, reader);
+    assert_eq!(redemption.redeemed_at, 50_000);
+    assert_eq!(redemption.record_count, 1);
+    assert_eq!(
+        client.try_get_share_redemptions(&stranger, &revoked),
+        Err(Ok(ContractError::NotAuthorized))
+    );
+}
+
+fn setup_export(
+    env: &Env,
+) -> (
+    Address,
+    MedicalRegistryClient<'_>,
+    Address,
+    ed25519_dalek::SigningKey,
+) {
+    let (_admin, patient, _doctor, client) = setup_with_record(env);
+    let exporter = Address::generate(env);
+    let key = ed25519_dalek::SigningKey::from_bytes(&[9u8; 32]);
+    client.register_exporter(
+        &exporter,
+        &BytesN::from_array(env, &key.verifying_key().to_bytes()),
+    );
+    (patient, client, exporter, key)
+}
+
+fn sign_ticket(
+    env: &Env,
+    client: &MedicalRegistryClient,
+    key: &ed25519_dalek::SigningKey,
+    nonce: &BytesN<32>,
+) -> BytesN<64> {
+    use ed25519_dalek::Signer;
+    let payload = client.export_ticket_payload(nonce);
+    let mut buf = [0u8; 1024];
+    let len = payload.len() as usize;
+    payload.copy_into_slice(&mut buf[..len]);
+    BytesN::from_array(env, &key.sign(&buf[..len]).to_bytes())
+}
+
 #[test]
 fn test_request_data_export_returns_valid_ticket() {
     let env = Env::default();
     env.mock_all_auths();
     env.ledger().set_timestamp(10_000);
 
-    let (_admin, patient, _doctor, client) = setup_with_record(&env);
+    let (patient, client, exporter, key) = setup_export(&env);
 
-    let ticket = client.request_data_export(&patient);
+    let scope = ExportScope::RecordTypes(Vec::from_array(&env, [Symbol::new(&env, "LAB")]));
+    let ticket = client.request_data_export(&patient, &exporter, &scope);
 
     assert_eq!(ticket.patient, patient);
+    assert_eq!(ticket.exporter, exporter);
     assert_eq!(ticket.issued_at, 10_000);
     assert_eq!(ticket.expires_at, 13_600);
-    assert!(client.validate_export_ticket(&ticket));
+
+    let signature = sign_ticket(&env, &client, &key, &ticket.nonce);
+    let ids = client.validate_export_ticket(&ticket.nonce, &signature);
+    assert_eq!(ids, Vec::from_array(&env, [1u64]));
+    let record = client.get_export_ticket(&ticket.nonce).unwrap();
+    assert_eq!(record.status, ExportTicketStatus::Consumed);
+    assert_eq!(record.closed_at, 10_000);
+}
+
+#[test]
+fn test_validate_export_ticket_is_single_use() {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.ledger().set_timestamp(10_000);
+
+    let (patient, client, exporter, key) = setup_export(&env);
+    let scope = ExportScope::RecordIds(Vec::from_array(&env, [1u64]));
+    let ticket = client.request_data_export(&patient, &exporter, &scope);
+    let signature = sign_ticket(&env, &client, &key, &ticket.nonce);
+
+    client.validate_export_ticket(&ticket.nonce, &signature);
+    assert_eq!(
+        client.try_validate_export_ticket(&ticket.nonce, &signature),
+        Err(Ok(ContractError::ExportTicketConsumed))
+    );
+
+    // The one release is logged for the exporter, citing the ticket.
+    let op_id = client.get_export_ticket(&ticket.nonce).unwrap().op_id;
+    let page = client.get_disclosures(&patient, &patient, &0, &u64::MAX, &0);
+    assert_eq!(page.records.len(), 1);
+    let record = page.records.get(0).unwrap();
+    assert_eq!(record.accessor, exporter);
+    assert_eq!(record.resource_id, String::from_str(&env, "record:1"));
+    assert_eq!(record.basis_op_id, op_id);
+    assert_eq!(record.purpose, String::from_str(&env, "export"));
 }
 
 #[test]
@@ -3113,29 +3416,257 @@ fn test_validate_export_ticket_rejects_expired_ticket() {
     env.mock_all_auths();
     env.ledger().set_timestamp(20_000);
 
-    let (_admin, patient, _doctor, client) = setup_with_record(&env);
-
-    let ticket = client.request_data_export(&patient);
+    let (patient, client, exporter, key) = setup_export(&env);
+    let scope = ExportScope::RecordIds(Vec::from_array(&env, [1u64]));
+    let ticket = client.request_data_export(&patient, &exporter, &scope);
+    let signature = sign_ticket(&env, &client, &key, &ticket.nonce);
     env.ledger().set_timestamp(ticket.expires_at + 1);
 
-    assert!(!client.validate_export_ticket(&ticket));
+    assert_eq!(
+        client.try_validate_export_ticket(&ticket.nonce, &signature),
+        Err(Ok(ContractError::ExportTicketExpired))
+    );
 }
 
 #[test]
-fn test_validate_export_ticket_rejects_tampered_ticket() {
+fn test_validate_export_ticket_rejects_forged_signature() {
     let env = Env::default();
     env.mock_all_auths();
     env.ledger().set_timestamp(30_000);
 
+    let (patient, client, exporter, _key) = setup_export(&env);
+    let scope = ExportScope::RecordIds(Vec::from_array(&env, [1u64]));
+    let ticket = client.request_data_export(&patient, &exporter, &scope);
+
+    let forger = ed25519_dalek::SigningKey::from_bytes(&[7u8; 32]);
+    let forged = sign_ticket(&env, &client, &forger, &ticket.nonce);
+    assert!(client
+        .try_validate_export_ticket(&ticket.nonce, &forged)
+        .is_err());
+    assert_eq!(
+        client.get_export_ticket(&ticket.nonce).unwrap().status,
+        ExportTicketStatus::Issued
+    );
+}
+
+#[test]
+fn test_revoked_export_ticket_cannot_be_used() {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.ledger().set_timestamp(40_000);
+
+    let (patient, client, exporter, key) = setup_export(&env);
+    let scope = ExportScope::RecordIds(Vec::from_array(&env, [1u64]));
+    let ticket = client.request_data_export(&patient, &exporter, &scope);
+    let signature = sign_ticket(&env, &client, &key, &ticket.nonce);
+
+    let stranger = Address::generate(&env);
+    assert_eq!(
+        client.try_revoke_export_ticket(&stranger, &ticket.nonce),
+        Err(Ok(ContractError::NotAuthorized))
+    );
+    client.revoke_export_ticket(&patient, &ticket.nonce);
+    assert_eq!(
+        client.try_validate_export_ticket(&ticket.nonce, &signature),
+        Err(Ok(ContractError::ExportTicketRevoked))
+    );
+}
+
+#[test]
+fn test_request_data_export_validates_exporter_and_scope() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (patient, client, exporter, _key) = setup_export(&env);
+    let unknown = Address::generate(&env);
+    let scope = ExportScope::RecordIds(Vec::from_array(&env, [1u64]));
+    assert_eq!(
+        client.try_request_data_export(&patient, &unknown, &scope),
+        Err(Ok(ContractError::ExporterNotRegistered))
+    );
+    assert_eq!(
+        client.try_request_data_export(
+            &patient,
+            &exporter,
+            &ExportScope::RecordIds(Vec::new(&env))
+        ),
+        Err(Ok(ContractError::InvalidExportScope))
+    );
+    // Record 99 does not exist, so it cannot belong to the patient.
+    assert_eq!(
+        client.try_request_data_export(
+            &patient,
+            &exporter,
+            &ExportScope::RecordIds(Vec::from_array(&env, [1u64, 99]))
+        ),
+        Err(Ok(ContractError::InvalidExportScope))
+    );
+
+    client.remove_exporter(&exporter);
+    assert_eq!(
+        client.try_request_data_export(&patient, &exporter, &scope),
+        Err(Ok(ContractError::ExporterNotRegistered))
+    );
+}
+
+// ------------------------------------------------
+// RETENTION SWEEP TESTS
+// ------------------------------------------------
+
+fn retention_policy(retention_secs: u64, grace_secs: u64) -> RetentionPolicy {
+    RetentionPolicy {
+        retention_secs,
+        grace_secs,
+    }
+}
+
+#[test]
+fn test_sweep_archives_lapsed_records_then_purges_after_grace() {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.ledger().set_timestamp(1000);
+    let (_admin, patient, _doctor, client) = setup_with_record(&env);
+    client.set_retention_policy(&RetentionClass::Clinical, &retention_policy(100, 50));
+
+    env.ledger().set_timestamp(1099);
+    let report = client.sweep_retention(&patient, &0, &10);
+    assert_eq!((report.archived, report.purged), (0, 0));
+
+    env.ledger().set_timestamp(1100);
+    let report = client.sweep_retention(&patient, &0, &10);
+    assert_eq!((report.archived, report.purged), (1, 0));
+    let archived_topics: soroban_sdk::Vec<soroban_sdk::Val> =
+        (symbol_short!("ret_arch"), patient.clone()).into_val(&env);
+    assert!(env
+        .events()
+        .all()
+        .iter()
+        .any(|(_, topics, _)| topics == archived_topics));
+    assert_eq!(
+        client.get_archived_ref(&1).cid_hash,
+        encrypted_ref(&env, 1).content_hash
+    );
+    let pending = client.get_pending_purges(&patient);
+    assert_eq!(pending.len(), 1);
+    assert_eq!(pending.get(0).unwrap().class, RetentionClass::Clinical);
+
+    env.ledger().set_timestamp(1149);
+    assert_eq!(client.sweep_retention(&patient, &0, &10).purged, 0);
+
+    env.ledger().set_timestamp(1150);
+    let report = client.sweep_retention(&patient, &0, &10);
+    assert_eq!((report.archived, report.purged), (0, 1));
+    let purge_topics: soroban_sdk::Vec<soroban_sdk::Val> =
+        (symbol_short!("ret_purge"), patient.clone()).into_val(&env);
+    assert!(env
+        .events()
+        .all()
+        .iter()
+        .any(|(_, topics, _)| topics == purge_topics));
+    assert_eq!(
+        client.try_get_archived_ref(&1),
+        Err(Ok(ContractError::NotFound))
+    );
+    assert_eq!(client.get_pending_purges(&patient).len(), 0);
+    assert_eq!(client.get_global_type_count(&Symbol::new(&env, "LAB")), 0);
+}
+
+#[test]
+fn test_sweep_respects_regulatory_hold() {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.ledger().set_timestamp(1000);
     let (_admin, patient, _doctor, client) = setup_with_record(&env);
+    client.set_retention_policy(&RetentionClass::Clinical, &retention_policy(100, 0));
+    client.place_hold(&patient, &BytesN::from_array(&env, &[5u8; 32]), &5000);
 
-    let ticket = client.request_data_export(&patient);
-    let tampered = ExportTicket {
-        signature: BytesN::from_array(&env, &[0xabu8; 32]),
-        ..ticket
-    };
+    env.ledger().set_timestamp(2000);
+    let report = client.sweep_retention(&patient, &0, &10);
+    assert!(report.on_hold);
+    assert_eq!(report.archived, 0);
+    assert!(client.try_get_archived_ref(&1).is_err());
+
+    // Once the hold lapses the sweep proceeds.
+    env.ledger().set_timestamp(5000);
+    let report = client.sweep_retention(&patient, &0, &10);
+    assert!(!report.on_hold);
+    assert_eq!(report.archived, 1);
+}
+
+#[test]
+fn test_sweep_skips_classes_without_policy_and_validates_input() {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.ledger().set_timestamp(1000);
+    let (_admin, patient, _doctor, client) = setup_with_record(&env);
+    client.set_retention_policy(&RetentionClass::Financial, &retention_policy(1, 0));
+
+    env.ledger().set_timestamp(1_000_000);
+    assert_eq!(client.sweep_retention(&patient, &0, &10).archived, 0);
+
+    assert_eq!(
+        client.try_sweep_retention(&patient, &0, &0),
+        Err(Ok(ContractError::InvalidPagination))
+    );
+    assert_eq!(
+        client.try_sweep_retention(&patient, &0, &(MAX_BATCH_SIZE + 1)),
+        Err(Ok(ContractError::BatchTooLarge))
+    );
+    assert_eq!(
+        client.try_set_retention_policy(&RetentionClass::Clinical, &retention_policy(0, 10)),
+        Err(Ok(ContractError::InvalidRetentionPolicy))
+    );
+    client.remove_retention_policy(&RetentionClass::Financial);
+    assert_eq!(
+        client.get_retention_policy(&RetentionClass::Financial),
+        None
+    );
+}
 
-    assert!(!client.validate_export_ticket(&tampered));
+#[test]
+fn test_sweep_limit_counts_scanned_records_and_pages() {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.ledger().set_timestamp(1000);
+    let (client, patient, _ids) = setup_with_records(&env, 5);
+    client.set_retention_policy(&RetentionClass::Financial, &retention_policy(1, 0));
+
+    // No record matches the policy, yet each scanned record uses the budget.
+    env.ledger().set_timestamp(1_000_000);
+    let report = client.sweep_retention(&patient, &0, &2);
+    assert_eq!((report.archived, report.next_start), (0, Some(2)));
+    let report = client.sweep_retention(&patient, &2, &2);
+    assert_eq!(report.next_start, Some(4));
+    let report = client.sweep_retention(&patient, &4, &2);
+    assert_eq!(report.next_start, None);
+
+    client.set_retention_policy(&RetentionClass::Clinical, &retention_policy(1, 0));
+    let report = client.sweep_retention(&patient, &3, &10);
+    assert_eq!((report.archived, report.next_start), (2, None));
+}
+
+#[test]
+fn test_sweep_paging_reaches_every_record_after_archiving() {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.ledger().set_timestamp(1000);
+    let (client, patient, ids) = setup_with_records(&env, 5);
+    client.set_retention_policy(&RetentionClass::Clinical, &retention_policy(1, 1_000_000));
+
+    // Each page archives what it scans, shrinking the list under the cursor.
+    env.ledger().set_timestamp(10_000);
+    let report = client.sweep_retention(&patient, &0, &2);
+    assert_eq!((report.archived, report.next_start), (2, Some(0)));
+    let report = client.sweep_retention(&patient, &report.next_start.unwrap(), &2);
+    assert_eq!((report.archived, report.next_start), (2, Some(0)));
+    let report = client.sweep_retention(&patient, &report.next_start.unwrap(), &2);
+    assert_eq!((report.archived, report.next_start), (1, None));
+
+    let pending = client.get_pending_purges(&patient);
+    assert_eq!(pending.len(), 5);
+    for (entry, id) in pending.iter().zip(ids.iter()) {
+        assert_eq!(entry.record_id, id);
+    }
 }
 
 // ------------------------------------------------
@@ -3208,7 +3739,7 @@ fn test_deregister_records_retained_admin_can_read() {
     client.deregister_patient(&patient);
 
     // Admin can still read records
-    let records = client.get_medical_records(&patient, &admin);
+    let records = client.get_medical_records(&patient, &admin, &treatment(&env));
     assert_eq!(records.len(), 1);
 }
 
@@ -3229,7 +3760,7 @@ fn test_deregister_blocks_grantee_read() {
     client.deregister_patient(&patient);
 
     // Former grantee (doctor) can no longer read
-    client.get_medical_records(&patient, &doctor);
+    client.get_medical_records(&patient, &doctor, &treatment(&env));
 }
 
 #[test]
@@ -3326,62 +3857,10 @@ fn setup_with_records(env: &Env, n: u32) -> (MedicalRegistryClient, Address, Vec
     (client, patient, ids)
 }
 
-/// Compute a Merkle membership proof for `target_id` given the ordered `ids`
-/// list.  Mirrors `compute_merkle_root` exactly so the proof is consistent
-/// with what the contract stores.
-fn build_proof(env: &Env, ids: &Vec<u64>, target_id: u64) -> Vec<BytesN<32>> {
-    let n = ids.len();
-    assert!(n > 0, "no records");
-
-    let mut layer: Vec<BytesN<32>> = Vec::new(env);
-    for id in ids.iter() {
-        layer.push_back(merkle::hash_leaf(env, id));
-    }
-
-    let mut pos: u32 = 0;
-    for (i, id) in ids.iter().enumerate() {
-        if id == target_id {
-            pos = i as u32;
-        }
-    }
-
-    let mut proof: Vec<BytesN<32>> = Vec::new(env);
-    let mut cur_len = layer.len();
-    let mut cur_pos = pos;
-
-    while cur_len > 1 {
-        let mut next: Vec<BytesN<32>> = Vec::new(env);
-        let mut i = 0u32;
-        while i + 1 < cur_len {
-            next.push_back(merkle::hash_pair(
-                env,
-                layer.get(i).unwrap(),
-                layer.get(i + 1).unwrap(),
-            ));
-            i += 2;
-        }
-        if cur_len % 2 == 1 {
-            let last = layer.get(cur_len - 1).unwrap();
-            next.push_back(merkle::hash_pair(env, last.clone(), last));
-        }
-
-        let sibling_pos = if cur_pos % 2 == 0 {
-            if cur_pos + 1 < cur_len {
-                cur_pos + 1
-            } else {
-                cur_pos
-            }
-        } else {
-            cur_pos - 1
-        };
-        proof.push_back(layer.get(sibling_pos).unwrap());
-
-        cur_pos /= 2;
-        cur_len = next.len();
-        layer = next;
-    }
-
-    proof
+/// Leaf the contract commits for the first version of a record built by
+/// `setup_with_records` (content hash `[seed; 32]`).
+fn first_leaf(env: &Env, record_id: u64, seed: u8) -> BytesN<32> {
+    merkle::hash_leaf(env, record_id, &BytesN::from_array(env, &[seed; 32]), 1)
 }
 
 #[test]
@@ -3393,8 +3872,8 @@ fn test_merkle_root_empty_before_any_record() {
 
     env.mock_all_auths();
 
-    let expected = merkle::compute_merkle_root(&env, &Vec::new(&env));
-    assert_eq!(client.get_merkle_root(&patient), expected);
+    assert_eq!(client.get_merkle_root(&patient), merkle::empty_root(&env));
+    assert_eq!(client.get_merkle_leaf_count(&patient), 0);
 }
 
 #[test]
@@ -3404,8 +3883,8 @@ fn test_merkle_root_single_record() {
     let (client, patient, ids) = setup_with_records(&env, 1);
 
     let id = ids.get(0).unwrap();
-    let expected = merkle::hash_leaf(&env, id);
-    assert_eq!(client.get_merkle_root(&patient), expected);
+    assert_eq!(client.get_merkle_root(&patient), first_leaf(&env, id, 1));
+    assert_eq!(client.get_merkle_leaf_count(&patient), 1);
 }
 
 #[test]
@@ -3414,13 +3893,31 @@ fn test_merkle_root_two_records() {
     env.mock_all_auths();
     let (client, patient, ids) = setup_with_records(&env, 2);
 
-    let id0 = ids.get(0).unwrap();
-    let id1 = ids.get(1).unwrap();
-    let expected = merkle::hash_pair(
+    let expected = merkle::hash_node(
+        &env,
+        &first_leaf(&env, ids.get(0).unwrap(), 1),
+        &first_leaf(&env, ids.get(1).unwrap(), 2),
+    );
+    assert_eq!(client.get_merkle_root(&patient), expected);
+}
+
+#[test]
+fn test_merkle_root_three_records_pads_with_zero_leaf() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, patient, ids) = setup_with_records(&env, 3);
+
+    let left = merkle::hash_node(
         &env,
-        merkle::hash_leaf(&env, id0),
-        merkle::hash_leaf(&env, id1),
+        &first_leaf(&env, ids.get(0).unwrap(), 1),
+        &first_leaf(&env, ids.get(1).unwrap(), 2),
     );
+    let right = merkle::hash_node(
+        &env,
+        &first_leaf(&env, ids.get(2).unwrap(), 3),
+        &BytesN::from_array(&env, &[0u8; 32]),
+    );
+    let expected = merkle::hash_node(&env, &left, &right);
     assert_eq!(client.get_merkle_root(&patient), expected);
 }
 
@@ -3474,102 +3971,258 @@ fn test_merkle_root_updates_on_each_addition() {
 }
 
 #[test]
-fn test_verify_membership_single_leaf() {
+fn test_merkle_root_changes_on_update_and_archive() {
     let env = Env::default();
     env.mock_all_auths();
-    let (client, patient, ids) = setup_with_records(&env, 1);
-
+    let (client, patient, ids) = setup_with_records(&env, 2);
     let id = ids.get(0).unwrap();
-    let proof: Vec<BytesN<32>> = Vec::new(&env);
-    assert!(client.verify_record_membership(&patient, &id, &proof));
+
+    let root_0 = client.get_merkle_root(&patient);
+    client.update_record(&patient, &id, &encrypted_ref(&env, 9), &policy(&env));
+    let root_1 = client.get_merkle_root(&patient);
+    assert_ne!(root_0, root_1);
+    assert_eq!(client.get_merkle_leaf_count(&patient), 3);
+
+    client.archive_record(&patient, &id, &BytesN::from_array(&env, &[5u8; 32]));
+    let root_2 = client.get_merkle_root(&patient);
+    assert_ne!(root_1, root_2);
+    assert_eq!(client.get_merkle_leaf_count(&patient), 4);
 }
 
 #[test]
-fn test_verify_membership_two_leaves_each() {
+fn test_record_multiproof_verifies_for_any_subset() {
     let env = Env::default();
     env.mock_all_auths();
-    let (client, patient, ids) = setup_with_records(&env, 2);
+    let (client, patient, ids) = setup_with_records(&env, 5);
+
+    let subsets = [
+        vec![&env, ids.get(0).unwrap()],
+        vec![&env, ids.get(4).unwrap()],
+        vec![&env, ids.get(1).unwrap(), ids.get(2).unwrap()],
+        vec![
+            &env,
+            ids.get(4).unwrap(),
+            ids.get(0).unwrap(),
+            ids.get(2).unwrap(),
+        ],
+        ids.clone(),
+    ];
+    for subset in subsets.iter() {
+        let proof = client.get_record_proof(&patient, &patient, subset);
+        assert_eq!(proof.leaves.len(), subset.len());
+        assert!(client.verify_record_proof(&patient, &proof));
+    }
 
-    let id0 = ids.get(0).unwrap();
-    let id1 = ids.get(1).unwrap();
+    // Proving every leaf only needs the zero padding beside leaf 4 (level 0)
+    // and beside its parent (level 1); duplicates are collapsed.
+    let all = client.get_record_proof(&patient, &patient, &ids);
+    assert_eq!(all.siblings.len(), 2);
+    let dup = client.get_record_proof(
+        &patient,
+        &patient,
+        &vec![&env, ids.get(3).unwrap(), ids.get(3).unwrap()],
+    );
+    assert_eq!(dup.leaves.len(), 1);
+}
+
+#[test]
+fn test_proof_reads_extend_merkle_node_ttl() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, patient, ids) = setup_with_records(&env, 3);
+    let node_ttl = || {
+        env.as_contract(&client.address, || {
+            env.storage()
+                .persistent()
+                .get_ttl(&DataKey::MerkleNode(patient.clone(), 1, 0))
+        })
+    };
+    let fresh = node_ttl();
 
-    let mut p0: Vec<BytesN<32>> = Vec::new(&env);
-    p0.push_back(merkle::hash_leaf(&env, id1));
-    assert!(client.verify_record_membership(&patient, &id0, &p0));
+    // Let the completed left subtree age past the extension threshold.
+    env.ledger().with_mut(|li| li.sequence_number += 20_000);
+    assert_eq!(node_ttl(), fresh - 20_000);
 
-    let mut p1: Vec<BytesN<32>> = Vec::new(&env);
-    p1.push_back(merkle::hash_leaf(&env, id0));
-    assert!(client.verify_record_membership(&patient, &id1, &p1));
+    // Proving the last leaf reads that subtree as a sibling.
+    client.get_record_proof(&patient, &patient, &vec![&env, ids.get(2).unwrap()]);
+    assert_eq!(node_ttl(), fresh);
 }
 
 #[test]
-fn test_verify_membership_three_leaves() {
+fn test_record_multiproof_commits_latest_version() {
     let env = Env::default();
     env.mock_all_auths();
     let (client, patient, ids) = setup_with_records(&env, 3);
+    let id = ids.get(1).unwrap();
 
-    for i in 0..3u32 {
-        let id = ids.get(i).unwrap();
-        let proof = build_proof(&env, &ids, id);
-        assert!(
-            client.verify_record_membership(&patient, &id, &proof),
-            "membership check failed for record at index {i}"
-        );
-    }
+    client.update_record(&patient, &id, &encrypted_ref(&env, 42), &policy(&env));
+    let proof =
+        client.get_record_proof(&patient, &patient, &vec![&env, ids.get(0).unwrap(), id]);
+
+    let leaf = proof.leaves.get(1).unwrap();
+    assert_eq!(leaf.record_id, id);
+    assert_eq!(leaf.version, 2);
+    assert_eq!(leaf.content_hash, BytesN::from_array(&env, &[42u8; 32]));
+    assert_eq!(leaf.leaf_index, 3);
+    assert!(client.verify_record_proof(&patient, &proof));
 }
 
 #[test]
-fn test_verify_membership_four_leaves() {
+fn test_record_multiproof_rejects_tampering() {
     let env = Env::default();
     env.mock_all_auths();
     let (client, patient, ids) = setup_with_records(&env, 4);
+    let proof = client.get_record_proof(&patient, &patient, &vec![&env, ids.get(1).unwrap()]);
 
-    for i in 0..4u32 {
-        let id = ids.get(i).unwrap();
-        let proof = build_proof(&env, &ids, id);
-        assert!(
-            client.verify_record_membership(&patient, &id, &proof),
-            "membership check failed for record at index {i}"
-        );
-    }
+    // Claiming different content for the record.
+    let mut forged = proof.clone();
+    let mut leaf = forged.leaves.get(0).unwrap();
+    leaf.content_hash = BytesN::from_array(&env, &[0xeeu8; 32]);
+    forged.leaves.set(0, leaf);
+    assert!(!client.verify_record_proof(&patient, &forged));
+
+    // Claiming an older version of the record.
+    let mut forged = proof.clone();
+    let mut leaf = forged.leaves.get(0).unwrap();
+    leaf.version = 0;
+    forged.leaves.set(0, leaf);
+    assert!(!client.verify_record_proof(&patient, &forged));
+
+    // Corrupted sibling.
+    let mut forged = proof.clone();
+    forged.siblings.set(0, BytesN::from_array(&env, &[0u8; 32]));
+    assert!(!client.verify_record_proof(&patient, &forged));
+
+    // Truncated sibling list.
+    let mut forged = proof;
+    forged.siblings.pop_back();
+    assert!(!client.verify_record_proof(&patient, &forged));
 }
 
 #[test]
-fn test_verify_non_membership_wrong_id() {
+fn test_record_multiproof_stale_after_append() {
     let env = Env::default();
     env.mock_all_auths();
     let (client, patient, ids) = setup_with_records(&env, 2);
+    let id = ids.get(0).unwrap();
+    let proof = client.get_record_proof(&patient, &patient, &vec![&env, id]);
 
-    let non_existent: u64 = 9999;
-    let mut bogus: Vec<BytesN<32>> = Vec::new(&env);
-    bogus.push_back(merkle::hash_leaf(&env, ids.get(0).unwrap()));
-    assert!(!client.verify_record_membership(&patient, &non_existent, &bogus));
+    client.update_record(
+        &patient,
+        &ids.get(1).unwrap(),
+        &encrypted_ref(&env, 7),
+        &policy(&env),
+    );
+    assert!(!client.verify_record_proof(&patient, &proof));
+
+    // A fresh proof for the untouched record verifies against the new root.
+    let fresh = client.get_record_proof(&patient, &patient, &vec![&env, id]);
+    assert!(client.verify_record_proof(&patient, &fresh));
 }
 
 #[test]
-fn test_verify_non_membership_wrong_proof() {
+fn test_record_multiproof_rejects_unprovable_ids() {
     let env = Env::default();
     env.mock_all_auths();
-    let (client, patient, ids) = setup_with_records(&env, 2);
+    let (client, patient, ids) = setup_with_records(&env, 3);
+    let (_other_client, other_patient, _) = setup_with_records(&env, 1);
+
+    let deleted = ids.get(0).unwrap();
+    client.soft_delete_record(&deleted, &patient);
+    assert_eq!(
+        client.try_get_record_proof(&patient, &patient, &vec![&env, deleted]),
+        Err(Ok(ContractError::RecordNotFound))
+    );
+    assert_eq!(
+        client.try_get_record_proof(
+            &other_patient,
+            &other_patient,
+            &vec![&env, ids.get(1).unwrap()]
+        ),
+        Err(Ok(ContractError::RecordNotFound))
+    );
+    assert_eq!(
+        client.try_get_record_proof(&patient, &patient, &Vec::new(&env)),
+        Err(Ok(ContractError::RecordNotFound))
+    );
 
-    let id0 = ids.get(0).unwrap();
-    let corrupt = BytesN::from_array(&env, &[0u8; 32]);
-    let mut bad_proof: Vec<BytesN<32>> = Vec::new(&env);
-    bad_proof.push_back(corrupt);
-    assert!(!client.verify_record_membership(&patient, &id0, &bad_proof));
+    let mut too_many = Vec::new(&env);
+    for _ in 0..=MAX_PAGE_SIZE {
+        too_many.push_back(ids.get(1).unwrap());
+    }
+    assert_eq!(
+        client.try_get_record_proof(&patient, &patient, &too_many),
+        Err(Ok(ContractError::InputTooLarge))
+    );
+}
+
+/// Builds a proof for `leaf` against the patient's current root, as anyone
+/// could from the public accumulator nodes.
+fn forge_leaf_proof(
+    env: &Env,
+    client: &MedicalRegistryClient,
+    patient: &Address,
+    leaf: RecordLeaf,
+) -> RecordMultiProof {
+    let siblings = env.as_contract(&client.address, || {
+        merkle::multiproof(env, patient, &vec![env, leaf.leaf_index])
+    });
+    RecordMultiProof {
+        root: client.get_merkle_root(patient),
+        leaf_count: client.get_merkle_leaf_count(patient),
+        leaves: vec![env, leaf],
+        siblings,
+    }
 }
 
 #[test]
-fn test_verify_membership_patient_with_no_records_returns_false() {
+fn test_record_proof_rejects_superseded_version() {
     let env = Env::default();
-    let contract_id = env.register(MedicalRegistry, ());
-    let client = MedicalRegistryClient::new(&env, &contract_id);
-    let patient = Address::generate(&env);
+    env.mock_all_auths();
+    let (client, patient, ids) = setup_with_records(&env, 3);
+    let id = ids.get(1).unwrap();
+    let original = client
+        .get_record_proof(&patient, &patient, &vec![&env, id])
+        .leaves
+        .get(0)
+        .unwrap();
+
+    client.update_record(&patient, &id, &encrypted_ref(&env, 42), &policy(&env));
+
+    // The version-1 leaf is still in the accumulator but no longer current.
+    let proof = forge_leaf_proof(&env, &client, &patient, original);
+    assert!(!client.verify_record_proof(&patient, &proof));
+}
+
+#[test]
+fn test_record_proof_rejects_deleted_record() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, patient, ids) = setup_with_records(&env, 3);
+    let id = ids.get(0).unwrap();
+    let leaf = client
+        .get_record_proof(&patient, &patient, &vec![&env, id])
+        .leaves
+        .get(0)
+        .unwrap();
 
+    client.soft_delete_record(&id, &patient);
+
+    let proof = forge_leaf_proof(&env, &client, &patient, leaf);
+    assert!(!client.verify_record_proof(&patient, &proof));
+}
+
+#[test]
+fn test_record_proof_requires_record_access() {
+    let env = Env::default();
     env.mock_all_auths();
+    let (client, patient, ids) = setup_with_records(&env, 2);
+    let stranger = Address::generate(&env);
 
-    let proof: Vec<BytesN<32>> = Vec::new(&env);
-    assert!(!client.verify_record_membership(&patient, &1, &proof));
+    assert_eq!(
+        client.try_get_record_proof(&patient, &stranger, &ids),
+        Err(Ok(ContractError::NotAuthorized))
+    );
 }
 
 // ─── Issue #326 ── Type-index consistency after multiple soft deletes ──────
@@ -3678,19 +4331,27 @@ fn test_merkle_empty_tree_proof_returns_false() {
 
     env.mock_all_auths();
 
-    // Empty tree: root is the sha256("") sentinel.
-    // An empty proof for any record_id must return false.
-    let empty_proof: Vec<BytesN<32>> = Vec::new(&env);
+    // Empty tree: root is the sha256("") sentinel and no leaf can be proven.
+    let mut proof = RecordMultiProof {
+        root: merkle::empty_root(&env),
+        leaf_count: 0,
+        leaves: Vec::new(&env),
+        siblings: Vec::new(&env),
+    };
     assert!(
-        !client.verify_record_membership(&patient, &1, &empty_proof),
+        !client.verify_record_proof(&patient, &proof),
         "empty-tree empty-proof must return false"
     );
 
-    // A non-empty proof against an empty tree must also return false.
-    let mut non_empty_proof: Vec<BytesN<32>> = Vec::new(&env);
-    non_empty_proof.push_back(BytesN::from_array(&env, &[0xabu8; 32]));
+    // A claimed leaf against an empty tree must also return false.
+    proof.leaves.push_back(RecordLeaf {
+        record_id: 1,
+        content_hash: BytesN::from_array(&env, &[0xabu8; 32]),
+        version: 1,
+        leaf_index: 0,
+    });
     assert!(
-        !client.verify_record_membership(&patient, &1, &non_empty_proof),
+        !client.verify_record_proof(&patient, &proof),
         "empty-tree non-empty-proof must return false"
     );
 }
@@ -3701,11 +4362,11 @@ fn test_merkle_single_record_valid_proof_verified() {
     env.mock_all_auths();
     let (client, patient, ids) = setup_with_records(&env, 1);
 
-    let id = ids.get(0).unwrap();
-    // Single-leaf tree: root = hash_leaf(id). The correct proof is empty.
-    let proof: Vec<BytesN<32>> = Vec::new(&env);
+    // Single-leaf tree: root = leaf. The correct proof has no siblings.
+    let proof = client.get_record_proof(&patient, &patient, &ids);
+    assert!(proof.siblings.is_empty());
     assert!(
-        client.verify_record_membership(&patient, &id, &proof),
+        client.verify_record_proof(&patient, &proof),
         "single-record valid proof must be accepted"
     );
 }
@@ -3716,13 +4377,14 @@ fn test_merkle_wrong_depth_proof_rejected() {
     env.mock_all_auths();
     let (client, patient, ids) = setup_with_records(&env, 1);
 
-    let id = ids.get(0).unwrap();
     // Correct proof for a single-leaf tree is empty (depth 0).
-    // Supplying an extra sibling (depth 1) computes a different root → rejected.
-    let mut wrong_depth_proof: Vec<BytesN<32>> = Vec::new(&env);
-    wrong_depth_proof.push_back(BytesN::from_array(&env, &[0xffu8; 32]));
+    // Supplying an extra sibling (depth 1) leaves it unconsumed → rejected.
+    let mut proof = client.get_record_proof(&patient, &patient, &ids);
+    proof
+        .siblings
+        .push_back(BytesN::from_array(&env, &[0xffu8; 32]));
     assert!(
-        !client.verify_record_membership(&patient, &id, &wrong_depth_proof),
+        !client.verify_record_proof(&patient, &proof),
         "proof with wrong depth must be rejected"
     );
 }
@@ -3751,7 +4413,7 @@ fn test_record_history_four_entries_after_three_updates() {
     client.update_record(&doctor, &record_id, &encrypted_ref(&env, 3), &policy(&env));
     client.update_record(&doctor, &record_id, &encrypted_ref(&env, 4), &policy(&env));
 
-    let history = client.get_record_history(&record_id, &patient, &0);
+    let history = client.get_record_history(&record_id, &patient, &0, &treatment(&env));
     assert_eq!(history.ids.len(), 4, "expected 4 history entries (1 initial + 3 updates)");
 }
 
@@ -3825,7 +4487,7 @@ fn test_history_entries_contain_correct_refs() {
     client.update_record(&doctor, &record_id, &encrypted_ref(&env, 2), &policy(&env));
     client.update_record(&doctor, &record_id, &encrypted_ref(&env, 3), &policy(&env));
 
-    let history = client.get_record_history(&record_id, &patient, &0);
+    let history = client.get_record_history(&record_id, &patient, &0, &treatment(&env));
     assert_eq!(history.ids.len(), 3);
 
     assert_eq!(RecordVersion::try_from_val(&env, &history.ids.get(0).unwrap()).unwrap().encrypted_ref, encrypted_ref(&env, 1));
@@ -3897,7 +4559,7 @@ fn test_concurrent_updates_from_multiple_providers() {
     env.ledger().set_timestamp(3000);
     client.update_record(&doctor1, &record_id, &encrypted_ref(&env, 4), &policy(&env));
 
-    let history = client.get_record_history(&record_id, &patient, &0);
+    let history = client.get_record_history(&record_id, &patient, &0, &treatment(&env));
     assert_eq!(history.ids.len(), 4, "all 4 versions must be in history");
 
     // Verify updated_by attribution.
@@ -3956,7 +4618,7 @@ fn test_get_record_history_readable_by_authorized_doctor() {
     client.update_record(&doctor, &record_id, &encrypted_ref(&env, 2), &policy(&env));
 
     // Doctor (authorized) can read history.
-    let history = client.get_record_history(&record_id, &doctor, &0);
+    let history = client.get_record_history(&record_id, &doctor, &0, &treatment(&env));
     assert_eq!(history.ids.len(), 2);
 }
 
@@ -3981,28 +4643,28 @@ fn test_get_record_history_pagination() {
     }
 
     // Page 0: should return 20 items, has_more = true
-    let page0 = client.get_record_history(&record_id, &patient, &0);
+    let page0 = client.get_record_history(&record_id, &patient, &0, &treatment(&env));
     assert_eq!(page0.ids.len(), 20);
     assert_eq!(page0.has_more, true);
     assert_eq!(RecordVersion::try_from_val(&env, &page0.ids.get(0).unwrap()).unwrap().encrypted_ref, encrypted_ref(&env, 0));
     assert_eq!(RecordVersion::try_from_val(&env, &page0.ids.get(19).unwrap()).unwrap().encrypted_ref, encrypted_ref(&env, 19));
 
     // Page 1: should return 20 items, has_more = true
-    let page1 = client.get_record_history(&record_id, &patient, &1);
+    let page1 = client.get_record_history(&record_id, &patient, &1, &treatment(&env));
     assert_eq!(page1.ids.len(), 20);
     assert_eq!(page1.has_more, true);
     assert_eq!(RecordVersion::try_from_val(&env, &page1.ids.get(0).unwrap()).unwrap().encrypted_ref, encrypted_ref(&env, 20));
     assert_eq!(RecordVersion::try_from_val(&env, &page1.ids.get(19).unwrap()).unwrap().encrypted_ref, encrypted_ref(&env, 39));
 
     // Page 2: should return 6 items, has_more = false (last page behavior)
-    let page2 = client.get_record_history(&record_id, &patient, &2);
+    let page2 = client.get_record_history(&record_id, &patient, &2, &treatment(&env));
     assert_eq!(page2.ids.len(), 6);
     assert_eq!(page2.has_more, false);
     assert_eq!(RecordVersion::try_from_val(&env, &page2.ids.get(0).unwrap()).unwrap().encrypted_ref, encrypted_ref(&env, 40));
     assert_eq!(RecordVersion::try_from_val(&env, &page2.ids.get(5).unwrap()).unwrap().encrypted_ref, encrypted_ref(&env, 45));
 
     // Page 3: should return 0 items, has_more = false (empty page)
-    let page3 = client.get_record_history(&record_id, &patient, &3);
+    let page3 = client.get_record_history(&record_id, &patient, &3, &treatment(&env));
     assert_eq!(page3.ids.len(), 0);
     assert_eq!(page3.has_more, false);
 }
@@ -4457,3 +5119,163 @@ fn test_guardian_can_revoke_access() {
     client.revoke_access(&patient, &guardian, &doctor);
     assert_eq!(client.get_authorized_doctors(&patient).len(), 0);
 }
+// ── Accounting of disclosures ────────────────────────────────────────────────
+
+/// Registered, consented patient with one LAB record added by `doctor`.
+fn setup_disclosures(env: &Env) -> (MedicalRegistryClient<'_>, Address, Address, Address) {
+    env.mock_all_auths();
+    let contract_id = env.register(MedicalRegistry, ());
+    let client = MedicalRegistryClient::new(env, &contract_id);
+
+    let admin = Address::generate(env);
+    client.initialize(&admin, &Address::generate(env), &Address::generate(env));
+    let patient = Address::generate(env);
+    let doctor = Address::generate(env);
+    client.register_patient(
+        &patient,
+        &String::from_str(env, "Test Patient"),
+        &631152000,
+        &encrypted_ref(env, 1),
+        &policy(env),
+    );
+    let v1 = BytesN::from_array(env, &[1u8; 32]);
+    client.publish_consent_version(&v1);
+    client.acknowledge_consent(&patient, &patient, &v1);
+    client.grant_access(&patient, &patient, &doctor);
+    client.add_medical_record(
+        &patient,
+        &doctor,
+        &encrypted_ref(env, 11),
+        &Symbol::new(env, "LAB"),
+        &policy(env),
+    );
+    (client, admin, patient, doctor)
+}
+
+#[test]
+fn test_doctor_read_records_disclosure_with_grant_op() {
+    let env = Env::default();
+    let (client, _, patient, doctor) = setup_disclosures(&env);
+
+    // The patient's own reads are not disclosures.
+    client.get_medical_records(&patient, &patient, &treatment(&env));
+    assert_eq!(client.get_disclosure_summary(&patient, &patient).total, 0);
+
+    env.ledger().set_timestamp(1_000);
+    client.get_medical_records(&patient, &doctor, &treatment(&env));
+    let payment = String::from_str(&env, "payment");
+    let latest = client.get_latest_record(&patient, &doctor, &payment);
+
+    let page = client.get_disclosures(&patient, &patient, &0, &u64::MAX, &0);
+    assert_eq!(page.records.len(), 2);
+    let first = page.records.get(0).unwrap();
+    assert_eq!(first.accessor, doctor);
+    assert_eq!(first.resource_id, String::from_str(&env, "records"));
+    assert_eq!(first.purpose, treatment(&env));
+    assert_eq!(first.disclosed_at, 1_000);
+    assert_ne!(first.basis_op_id, 0);
+
+    let second = page.records.get(1).unwrap();
+    assert_eq!(second.basis_op_id, first.basis_op_id);
+    assert_eq!(second.resource_id, String::from_str(&env, "record:1"));
+    assert_eq!(second.purpose, payment);
+    assert_eq!(latest.record_id, 1);
+}
+
+#[test]
+fn test_regrant_cites_new_grant_op() {
+    let env = Env::default();
+    let (client, _, patient, doctor) = setup_disclosures(&env);
+
+    client.get_medical_records(&patient, &doctor, &treatment(&env));
+    client.revoke_access(&patient, &patient, &doctor);
+    client.grant_access(&patient, &patient, &doctor);
+    client.get_records_by_type(&patient, &doctor, &Symbol::new(&env, "LAB"), &treatment(&env));
+
+    let page = client.get_disclosures(&patient, &patient, &0, &u64::MAX, &0);
+    let before = page.records.get(0).unwrap().basis_op_id;
+    let after = page.records.get(1).unwrap().basis_op_id;
+    assert!(after > before);
+}
+
+#[test]
+fn test_share_link_redemption_is_a_disclosure() {
+    let env = Env::default();
+    let (client, _, patient, _doctor) = setup_disclosures(&env);
+    let reader = Address::generate(&env);
+    let token = client.create_scoped_share_link(
+        &patient,
+        &ShareScope::RecordIds(vec![&env, 1u64]),
+        &ShareRecipient::Anyone,
+        &vec![&env, FieldPermission::RecordType],
+        &2,
+        &60_000,
+    );
+    let link_op = client.get_share_links(&patient, &patient).get(0).unwrap().op_id;
+
+    // The patient redeeming their own link is not a disclosure.
+    client.use_share_link(&token, &patient, &None);
+    assert_eq!(client.get_disclosure_summary(&patient, &patient).total, 0);
+
+    client.use_share_link(&token, &reader, &None);
+    let page = client.get_disclosures(&patient, &patient, &0, &u64::MAX, &0);
+    assert_eq!(page.records.len(), 1);
+    let record = page.records.get(0).unwrap();
+    assert_eq!(record.accessor, reader);
+    assert_eq!(record.resource_id, String::from_str(&env, "record:1"));
+    assert_eq!(record.basis_op_id, link_op);
+    assert_eq!(record.purpose, String::from_str(&env, "share_link"));
+}
+
+#[test]
+fn test_field_read_without_mask_is_not_a_disclosure() {
+    let env = Env::default();
+    let (client, _, patient, doctor) = setup_disclosures(&env);
+
+    client.get_record_fields(&patient, &doctor, &1, &treatment(&env));
+    assert_eq!(client.get_disclosure_summary(&patient, &patient).total, 0);
+
+    let mut fields = Vec::new(&env);
+    fields.push_back(FieldPermission::RecordType);
+    client.grant_field_access(&patient, &doctor, &1, &fields);
+    client.get_record_fields(&patient, &doctor, &1, &treatment(&env));
+    assert_eq!(client.get_disclosure_summary(&patient, &patient).total, 1);
+}
+
+#[test]
+fn test_disclosure_queries_restricted_to_patient_or_guardian() {
+    let env = Env::default();
+    let (client, _, patient, doctor) = setup_disclosures(&env);
+
+    assert_eq!(
+        client.try_get_disclosures(&patient, &doctor, &0, &u64::MAX, &0),
+        Err(Ok(ContractError::NotAuthorized))
+    );
+    assert_eq!(
+        client.try_get_disclosure_summary(&patient, &doctor),
+        Err(Ok(ContractError::NotAuthorized))
+    );
+}
+
+#[test]
+fn test_disclosure_window_and_summary() {
+    let env = Env::default();
+    let (client, _, patient, doctor) = setup_disclosures(&env);
+    let other = Address::generate(&env);
+    client.grant_access(&patient, &patient, &other);
+
+    for (t, who) in [(100u64, &doctor), (200, &other), (300, &doctor)] {
+        env.ledger().set_timestamp(t);
+        client.get_medical_records(&patient, who, &treatment(&env));
+    }
+
+    let window = client.get_disclosures(&patient, &patient, &150, &250, &0);
+    assert_eq!(window.records.len(), 1);
+    assert_eq!(window.records.get(0).unwrap().accessor, other);
+
+    let summary = client.get_disclosure_summary(&patient, &patient);
+    assert_eq!(summary.total, 3);
+    assert_eq!((summary.first_at, summary.last_at), (100, 300));
+    assert_eq!(summary.accessors.len(), 2);
+    assert_eq!(summary.accessors.get(0).unwrap().count, 2);
+}
@@ -1,7 +1,11 @@
-use crate::types::{DataKey, Error, Referral, ReferralStatus};
+use crate::types::{
+    DataKey, Error, Referral, ReferralAttachment, ReferralPage, ReferralRoute, ReferralStatus,
+    MAX_ATTACHMENTS, SLA_ROUTINE_SECS, SLA_STAT_SECS, SLA_URGENT_SECS,
+};
+use shared::pagination::{self, PageResult, MAX_PAGE_SIZE};
 use shared::privacy::validate_nonzero_address;
 use shared_contracts::safe_increment;
-use soroban_sdk::{contract, contractimpl, Address, BytesN, Env, IntoVal, String, Symbol, Vec, vec};
+use soroban_sdk::{contract, contractimpl, Address, BytesN, Env, String, Symbol, TryFromVal, Vec};
 
 #[contract]
 pub struct ReferralContract;
@@ -49,6 +53,27 @@ impl ReferralContract {
         }
 
         let referral_id = safe_increment(&env, &DataKey::ReferralCount);
+        let now = env.ledger().timestamp();
+        let due_at = now + Self::sla_window(&env, &priority);
+
+        pagination::push_paged(
+            &env,
+            |page| DataKey::InboxPage(referred_to.clone(), page),
+            || DataKey::InboxHead(referred_to.clone()),
+            referral_id,
+        );
+        pagination::push_paged(
+            &env,
+            |page| DataKey::OutboxPage(referring_provider.clone(), page),
+            || DataKey::OutboxHead(referring_provider.clone()),
+            referral_id,
+        );
+        pagination::push_paged(
+            &env,
+            |page| DataKey::PatientPage(patient_id.clone(), page),
+            || DataKey::PatientHead(patient_id.clone()),
+            referral_id,
+        );
 
         let referral = Referral {
             referral_id,
@@ -59,9 +84,13 @@ impl ReferralContract {
             reason,
             priority,
             status: ReferralStatus::Pending,
-            created_at: env.ledger().timestamp(),
+            created_at: now,
             accepted_at: None,
             completed_at: None,
+            routed_at: now,
+            due_at,
+            declined_at: None,
+            decline_reason: None,
         };
 
         env.storage()
@@ -99,6 +128,9 @@ impl ReferralContract {
         if referral.status != ReferralStatus::Pending {
             return Err(Error::InvalidStatusTransition);
         }
+        if env.ledger().timestamp() > referral.due_at {
+            return Err(Error::ReferralExpired);
+        }
 
         referral.status = ReferralStatus::Accepted;
         referral.accepted_at = Some(env.ledger().timestamp());
@@ -140,8 +172,14 @@ impl ReferralContract {
         if referral.status != ReferralStatus::Pending {
             return Err(Error::InvalidStatusTransition);
         }
+        let now = env.ledger().timestamp();
+        if now > referral.due_at {
+            return Err(Error::ReferralExpired);
+        }
 
         referral.status = ReferralStatus::Declined;
+        referral.declined_at = Some(now);
+        referral.decline_reason = Some(decline_reason.clone());
 
         env.storage()
             .persistent()
@@ -175,24 +213,40 @@ impl ReferralContract {
             return Err(Error::NotAuthorized);
         }
 
-        let new_status = if status == Symbol::new(&env, "Pending") {
-            ReferralStatus::Pending
-        } else if status == Symbol::new(&env, "Accepted") {
-            ReferralStatus::Accepted
-        } else if status == Symbol::new(&env, "Declined") {
-            ReferralStatus::Declined
-        } else if status == Symbol::new(&env, "Scheduled") {
+        // Pending, Accepted and Declined are reached only through
+        // forward_referral, accept_referral and decline_referral, which
+        // enforce the due date and record routing details. Completed goes
+        // through complete_referral, which records completed_at.
+        let new_status = if status == Symbol::new(&env, "Scheduled") {
             ReferralStatus::Scheduled
         } else if status == Symbol::new(&env, "InProgress") {
             ReferralStatus::InProgress
-        } else if status == Symbol::new(&env, "Completed") {
-            ReferralStatus::Completed
         } else if status == Symbol::new(&env, "Cancelled") {
             ReferralStatus::Cancelled
         } else {
             return Err(Error::InvalidStatusTransition);
         };
 
+        // Care can only progress once the receiver has accepted, so an
+        // overdue Pending referral cannot skip accept_referral and a
+        // Declined one stays available to forward_referral.
+        let allowed = match new_status {
+            ReferralStatus::Cancelled => matches!(
+                referral.status,
+                ReferralStatus::Pending
+                    | ReferralStatus::Accepted
+                    | ReferralStatus::Scheduled
+                    | ReferralStatus::InProgress
+            ),
+            _ => matches!(
+                referral.status,
+                ReferralStatus::Accepted | ReferralStatus::Scheduled | ReferralStatus::InProgress
+            ),
+        };
+        if !allowed {
+            return Err(Error::InvalidStatusTransition);
+        }
+
         referral.status = new_status;
         env.storage()
             .persistent()
@@ -228,7 +282,10 @@ impl ReferralContract {
 
         // Cannot complete unless it has at least been accepted
         match referral.status {
-            ReferralStatus::Pending | ReferralStatus::Declined | ReferralStatus::Cancelled => {
+            ReferralStatus::Pending
+            | ReferralStatus::Declined
+            | ReferralStatus::Cancelled
+            | ReferralStatus::Expired => {
                 return Err(Error::InvalidStatusTransition)
             }
             _ => {}
@@ -310,4 +367,322 @@ impl ReferralContract {
 
         Ok(())
     }
+
+    /// Forward a declined referral to another registered provider. The
+    /// declined routing is appended to the referral's history, the referral
+    /// returns to `Pending` and a fresh due date is computed from its
+    /// priority. Attachments travel with the referral and become readable
+    /// by the new receiving provider only.
+    pub fn forward_referral(
+        env: Env,
+        referral_id: u64,
+        referring_provider: Address,
+        new_receiving_provider: Address,
+    ) -> Result<(), Error> {
+        validate_nonzero_address(&referring_provider).map_err(|_| Error::InvalidAddress)?;
+        validate_nonzero_address(&new_receiving_provider).map_err(|_| Error::InvalidAddress)?;
+        referring_provider.require_auth();
+
+        let mut referral = Self::load_referral(&env, referral_id)?;
+        if referral.referring_provider != referring_provider {
+            return Err(Error::NotAuthorized);
+        }
+        if referral.status != ReferralStatus::Declined {
+            return Err(Error::InvalidStatusTransition);
+        }
+        if new_receiving_provider == referral.receiving_provider {
+            return Err(Error::InvalidAddress);
+        }
+        if !Self::is_provider_registered(&env, &new_receiving_provider) {
+            return Err(Error::ProviderNotRegistered);
+        }
+
+        let history_key = DataKey::RoutingHistory(referral_id);
+        let mut history: Vec<ReferralRoute> = env
+            .storage()
+            .persistent()
+            .get(&history_key)
+            .unwrap_or(Vec::new(&env));
+        history.push_back(ReferralRoute {
+            receiving_provider: referral.receiving_provider.clone(),
+            routed_at: referral.routed_at,
+            declined_at: referral.declined_at.unwrap_or(referral.routed_at),
+            decline_reason: referral
+                .decline_reason
+                .clone()
+                .unwrap_or(String::from_str(&env, "")),
+        });
+        env.storage().persistent().set(&history_key, &history);
+
+        // Every earlier receiver is in the history, and their inbox already
+        // lists the referral.
+        let already_listed = history
+            .iter()
+            .any(|route| route.receiving_provider == new_receiving_provider);
+        if !already_listed {
+            pagination::push_paged(
+                &env,
+                |page| DataKey::InboxPage(new_receiving_provider.clone(), page),
+                || DataKey::InboxHead(new_receiving_provider.clone()),
+                referral_id,
+            );
+        }
+
+        let now = env.ledger().timestamp();
+        let previous = referral.receiving_provider.clone();
+        referral.receiving_provider = new_receiving_provider.clone();
+        referral.status = ReferralStatus::Pending;
+        referral.routed_at = now;
+        referral.due_at = now + Self::sla_window(&env, &referral.priority);
+        referral.declined_at = None;
+        referral.decline_reason = None;
+        env.storage()
+            .persistent()
+            .set(&DataKey::Referral(referral_id), &referral);
+
+        env.events().publish(
+            (Symbol::new(&env, "referral_forwarded"), referral_id),
+            (previous, new_receiving_provider),
+        );
+        Ok(())
+    }
+
+    /// Move a pending referral whose due date has passed to `Expired`.
+    /// Callable by anyone so keepers can sweep overdue referrals.
+    pub fn expire_referral(env: Env, referral_id: u64) -> Result<(), Error> {
+        let mut referral = Self::load_referral(&env, referral_id)?;
+        if referral.status != ReferralStatus::Pending {
+            return Err(Error::InvalidStatusTransition);
+        }
+        if env.ledger().timestamp() <= referral.due_at {
+            return Err(Error::NotYetDue);
+        }
+
+        referral.status = ReferralStatus::Expired;
+        env.storage()
+            .persistent()
+            .set(&DataKey::Referral(referral_id), &referral);
+        env.events().publish(
+            (Symbol::new(&env, "referral_expired"), referral_id),
+            (referral.receiving_provider, referral.due_at),
+        );
+        Ok(())
+    }
+
+    /// Attach the hash of a clinical document to an open referral. Only the
+    /// referring provider may attach documents.
+    pub fn add_attachment(
+        env: Env,
+        referral_id: u64,
+        referring_provider: Address,
+        document_hash: BytesN<32>,
+        document_type: Symbol,
+    ) -> Result<u32, Error> {
+        validate_nonzero_address(&referring_provider).map_err(|_| Error::InvalidAddress)?;
+        referring_provider.require_auth();
+
+        let referral = Self::load_referral(&env, referral_id)?;
+        if referral.referring_provider != referring_provider {
+            return Err(Error::NotAuthorized);
+        }
+        match referral.status {
+            ReferralStatus::Completed | ReferralStatus::Cancelled | ReferralStatus::Expired => {
+                return Err(Error::InvalidStatusTransition)
+            }
+            _ => {}
+        }
+
+        let key = DataKey::Attachments(referral_id);
+        let mut attachments: Vec<ReferralAttachment> = env
+            .storage()
+            .persistent()
+            .get(&key)
+            .unwrap_or(Vec::new(&env));
+        if attachments.len() >= MAX_ATTACHMENTS {
+            return Err(Error::TooManyAttachments);
+        }
+        attachments.push_back(ReferralAttachment {
+            document_hash: document_hash.clone(),
+            document_type,
+            added_by: referring_provider,
+            added_at: env.ledger().timestamp(),
+        });
+        let index = attachments.len() - 1;
+        env.storage().persistent().set(&key, &attachments);
+
+        // Only the hash is announced; it reveals nothing about the document.
+        env.events().publish(
+            (Symbol::new(&env, "attachment_added"), referral_id),
+            (index, document_hash),
+        );
+        Ok(index)
+    }
+
+    /// Attachments on a referral. Readable only by the current receiving
+    /// provider.
+    pub fn get_attachments(
+        env: Env,
+        referral_id: u64,
+        receiving_provider: Address,
+    ) -> Result<Vec<ReferralAttachment>, Error> {
+        receiving_provider.require_auth();
+        let referral = Self::load_referral(&env, referral_id)?;
+        if referral.receiving_provider != receiving_provider {
+            return Err(Error::NotAuthorized);
+        }
+        Ok(env
+            .storage()
+            .persistent()
+            .get(&DataKey::Attachments(referral_id))
+            .unwrap_or(Vec::new(&env)))
+    }
+
+    /// Read a referral. Restricted to the referring provider, the current
+    /// receiving provider and the patient.
+    pub fn get_referral(env: Env, referral_id: u64, requester: Address) -> Result<Referral, Error> {
+        Self::load_referral_for_party(&env, referral_id, &requester)
+    }
+
+    /// Earlier routings of a forwarded referral, oldest first.
+    pub fn get_routing_history(
+        env: Env,
+        referral_id: u64,
+        requester: Address,
+    ) -> Result<Vec<ReferralRoute>, Error> {
+        Self::load_referral_for_party(&env, referral_id, &requester)?;
+        Ok(env
+            .storage()
+            .persistent()
+            .get(&DataKey::RoutingHistory(referral_id))
+            .unwrap_or(Vec::new(&env)))
+    }
+
+    /// Referrals addressed to `provider`, one index page at a time, optionally
+    /// filtered by status and specialty. Referrals since forwarded to another
+    /// provider are left out.
+    pub fn get_inbox(
+        env: Env,
+        provider: Address,
+        page: u32,
+        status: Option<ReferralStatus>,
+        specialty: Option<Symbol>,
+    ) -> Result<ReferralPage, Error> {
+        provider.require_auth();
+        let ids = pagination::get_paged(
+            &env,
+            |pg| DataKey::InboxPage(provider.clone(), pg),
+            || DataKey::InboxHead(provider.clone()),
+            page,
+        );
+        Ok(Self::filter_page(&env, ids, &status, &specialty, |r| {
+            r.receiving_provider == provider
+        }))
+    }
+
+    /// Referrals sent by `provider`, one index page at a time, optionally
+    /// filtered by status and specialty.
+    pub fn get_outbox(
+        env: Env,
+        provider: Address,
+        page: u32,
+        status: Option<ReferralStatus>,
+        specialty: Option<Symbol>,
+    ) -> Result<ReferralPage, Error> {
+        provider.require_auth();
+        let ids = pagination::get_paged(
+            &env,
+            |pg| DataKey::OutboxPage(provider.clone(), pg),
+            || DataKey::OutboxHead(provider.clone()),
+            page,
+        );
+        Ok(Self::filter_page(&env, ids, &status, &specialty, |_| true))
+    }
+
+    /// A page of referral IDs for a patient. Only the patient may list them.
+    pub fn get_patient_referrals(
+        env: Env,
+        patient_id: Address,
+        page: u32,
+    ) -> Result<PageResult, Error> {
+        patient_id.require_auth();
+        Ok(pagination::get_paged(
+            &env,
+            |pg| DataKey::PatientPage(patient_id.clone(), pg),
+            || DataKey::PatientHead(patient_id.clone()),
+            page,
+        ))
+    }
+
+    /// Maximum index entries per page (re-exported for callers).
+    pub fn max_page_size(_env: Env) -> u32 {
+        MAX_PAGE_SIZE
+    }
+
+    fn filter_page(
+        env: &Env,
+        ids: PageResult,
+        status: &Option<ReferralStatus>,
+        specialty: &Option<Symbol>,
+        keep: impl Fn(&Referral) -> bool,
+    ) -> ReferralPage {
+        let mut referrals = Vec::new(env);
+        for raw in ids.ids.iter() {
+            let Ok(id) = u64::try_from_val(env, &raw) else {
+                continue;
+            };
+            let Ok(referral) = Self::load_referral(env, id) else {
+                continue;
+            };
+            if !keep(&referral) {
+                continue;
+            }
+            if status.as_ref().is_some_and(|s| *s != referral.status) {
+                continue;
+            }
+            if specialty.as_ref().is_some_and(|s| *s != referral.specialty) {
+                continue;
+            }
+            referrals.push_back(referral);
+        }
+        ReferralPage {
+            referrals,
+            has_more: ids.has_more,
+        }
+    }
+
+    fn load_referral(env: &Env, referral_id: u64) -> Result<Referral, Error> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Referral(referral_id))
+            .ok_or(Error::ReferralNotFound)
+    }
+
+    fn load_referral_for_party(
+        env: &Env,
+        referral_id: u64,
+        requester: &Address,
+    ) -> Result<Referral, Error> {
+        requester.require_auth();
+        let referral = Self::load_referral(env, referral_id)?;
+        if *requester != referral.referring_provider
+            && *requester != referral.receiving_provider
+            && *requester != referral.patient_id
+        {
+            return Err(Error::NotAuthorized);
+        }
+        Ok(referral)
+    }
+
+    /// Response window for a priority. Unrecognised priorities get the
+    /// routine window.
+    fn sla_window(env: &Env, priority: &Symbol) -> u64 {
+        if *priority == Symbol::new(env, "STAT") || *priority == Symbol::new(env, "Stat") {
+            SLA_STAT_SECS
+        } else if *priority == Symbol::new(env, "Urgent") || *priority == Symbol::new(env, "URGENT")
+        {
+            SLA_URGENT_SECS
+        } else {
+            SLA_ROUTINE_SECS
+        }
+    }
 }
@@ -25,6 +25,14 @@
 //! **Encryption/Integrity:** Clinical summary data encrypted via secure storage. Referring
 //! provider identity validated. Referred provider credentials verified. Patient linkage encrypted.
 //! Referral status enumeration prevents invalid states.
+//!
+//! ## Response windows
+//!
+//! Each referral carries a `due_at` computed from its `priority` (`STAT`, `Urgent`, otherwise
+//! routine). A pending referral not accepted or declined by then can be moved to `Expired` by
+//! anyone via `expire_referral`. A declined referral can be forwarded by its referring provider
+//! to another registered provider; the declined routing is kept in `get_routing_history` and a
+//! new window starts.
 
 pub mod contract;
 pub mod test;
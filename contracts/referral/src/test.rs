@@ -2,15 +2,40 @@
 #![allow(deprecated)]
 
 use crate::contract::{ReferralContract, ReferralContractClient};
-use soroban_sdk::{testutils::Address as _, Address, BytesN, Env, String, Symbol, Vec, vec};
+use crate::types::*;
+use soroban_sdk::{
+    contract, contractimpl,
+    testutils::{Address as _, Ledger},
+    Address, BytesN, Env, String, Symbol, Vec,
+};
 use provider_registry::{ProviderRegistry, ProviderRegistryClient};
 
+/// Provider registry stand-in that accepts every provider.
+#[contract]
+struct OpenProviderRegistry;
+
+#[contractimpl]
+impl OpenProviderRegistry {
+    pub fn is_provider(_env: Env, _provider: Address) -> bool {
+        true
+    }
+}
+
+/// Register the referral contract wired to a registry that accepts every
+/// provider.
+fn register_referral(env: &Env) -> Address {
+    let registry_id = env.register(OpenProviderRegistry, ());
+    let contract_id = env.register(ReferralContract, ());
+    ReferralContractClient::new(env, &contract_id).initialize(&registry_id);
+    contract_id
+}
+
 #[test]
 fn test_referral_lifecycle() {
     let env = Env::default();
     env.mock_all_auths();
 
-    let contract_id = env.register(ReferralContract, ());
+    let contract_id = register_referral(&env);
     let client = ReferralContractClient::new(&env, &contract_id);
 
     let referring_provider = Address::generate(&env);
@@ -72,7 +97,7 @@ fn test_decline_and_update_status() {
     let env = Env::default();
     env.mock_all_auths();
 
-    let contract_id = env.register(ReferralContract, ());
+    let contract_id = register_referral(&env);
     let client = ReferralContractClient::new(&env, &contract_id);
 
     let referring_provider = Address::generate(&env);
@@ -106,12 +131,105 @@ fn test_decline_and_update_status() {
         &Vec::new(&env),
     );
 
+    for status in ["Pending", "Accepted", "Declined"] {
+        assert_eq!(
+            client.try_update_referral_status(
+                &referral_id2,
+                &referred_to,
+                &Symbol::new(&env, status),
+                &None,
+            ),
+            Err(Ok(Error::InvalidStatusTransition))
+        );
+    }
+
+    client.accept_referral(&referral_id2, &referred_to, &None);
     client.update_referral_status(
         &referral_id2,
         &referred_to,
         &Symbol::new(&env, "Scheduled"),
         &None,
     );
+
+    // Completion goes through complete_referral, which stamps completed_at.
+    assert_eq!(
+        client.try_update_referral_status(
+            &referral_id2,
+            &referred_to,
+            &Symbol::new(&env, "Completed"),
+            &None,
+        ),
+        Err(Ok(Error::InvalidStatusTransition))
+    );
+    let referral = client.get_referral(&referral_id2, &referred_to);
+    assert_eq!(referral.status, ReferralStatus::Scheduled);
+    assert_eq!(referral.completed_at, None);
+}
+
+#[test]
+fn test_pending_referral_cannot_progress_without_acceptance() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let client = ReferralContractClient::new(&env, &register_referral(&env));
+    let referring = Address::generate(&env);
+    let patient = Address::generate(&env);
+    let to = Address::generate(&env);
+    let id = create(&env, &client, &referring, &patient, &to, "Urgent");
+
+    // Overdue but not yet expired: neither party can skip accept_referral.
+    env.ledger()
+        .with_mut(|l| l.timestamp += SLA_URGENT_SECS + 1);
+    for status in ["Scheduled", "InProgress", "Completed"] {
+        for provider in [&to, &referring] {
+            assert_eq!(
+                client.try_update_referral_status(&id, provider, &Symbol::new(&env, status), &None),
+                Err(Ok(Error::InvalidStatusTransition))
+            );
+        }
+    }
+    assert_eq!(
+        client.get_referral(&id, &patient).status,
+        ReferralStatus::Pending
+    );
+    client.expire_referral(&id);
+}
+
+#[test]
+fn test_declined_referral_cannot_be_scheduled() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let client = ReferralContractClient::new(&env, &register_referral(&env));
+    let referring = Address::generate(&env);
+    let patient = Address::generate(&env);
+    let first = Address::generate(&env);
+    let second = Address::generate(&env);
+    let id = create(&env, &client, &referring, &patient, &first, "Routine");
+    client.decline_referral(&id, &first, &String::from_str(&env, "Full"), &None);
+
+    for status in ["Scheduled", "InProgress", "Completed", "Cancelled"] {
+        assert_eq!(
+            client.try_update_referral_status(&id, &referring, &Symbol::new(&env, status), &None),
+            Err(Ok(Error::InvalidStatusTransition))
+        );
+    }
+
+    // The referral is still declined, so it can be forwarded.
+    client.forward_referral(&id, &referring, &second);
+    client.accept_referral(&id, &second, &None);
+    for status in ["Scheduled", "InProgress"] {
+        client.update_referral_status(&id, &second, &Symbol::new(&env, status), &None);
+    }
+    env.ledger().with_mut(|l| l.timestamp += 60);
+    client.complete_referral(
+        &id,
+        &second,
+        &BytesN::from_array(&env, &[2; 32]),
+        &String::from_str(&env, "Physiotherapy"),
+        &false,
+    );
+    let referral = client.get_referral(&id, &patient);
+    assert_eq!(referral.status, ReferralStatus::Completed);
+    assert_eq!(referral.completed_at, Some(env.ledger().timestamp()));
 }
 
 #[test]
@@ -119,7 +237,7 @@ fn test_auth_failures() {
     let env = Env::default();
     env.mock_all_auths();
 
-    let contract_id = env.register(ReferralContract, ());
+    let contract_id = register_referral(&env);
     let client = ReferralContractClient::new(&env, &contract_id);
 
     let referring_provider = Address::generate(&env);
@@ -209,6 +327,454 @@ fn test_provider_registration_verification() {
         &requested_services,
     );
     assert!(result.is_ok());
-    let referral_id_created = result.unwrap();
+    let referral_id_created = result.unwrap().unwrap();
     assert_eq!(referral_id_created, 1);
 }
+
+// ── SLA timers, forwarding and attachments ───────────────────────────────────
+
+fn create(
+    env: &Env,
+    client: &ReferralContractClient,
+    referring: &Address,
+    patient: &Address,
+    to: &Address,
+    priority: &str,
+) -> u64 {
+    client.create_referral(
+        referring,
+        patient,
+        to,
+        &Symbol::new(env, "Cardio"),
+        &String::from_str(env, "Chest pain"),
+        &Symbol::new(env, priority),
+        &BytesN::from_array(env, &[1; 32]),
+        &Vec::new(env),
+    )
+}
+
+#[test]
+fn test_due_date_follows_priority() {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.ledger().set_timestamp(1_000);
+    let client = ReferralContractClient::new(&env, &register_referral(&env));
+    let referring = Address::generate(&env);
+    let patient = Address::generate(&env);
+    let to = Address::generate(&env);
+
+    let stat = create(&env, &client, &referring, &patient, &to, "STAT");
+    let urgent = create(&env, &client, &referring, &patient, &to, "Urgent");
+    let routine = create(&env, &client, &referring, &patient, &to, "Routine");
+    let other = create(&env, &client, &referring, &patient, &to, "Elective");
+
+    assert_eq!(
+        client.get_referral(&stat, &to).due_at,
+        1_000 + SLA_STAT_SECS
+    );
+    assert_eq!(
+        client.get_referral(&urgent, &to).due_at,
+        1_000 + SLA_URGENT_SECS
+    );
+    assert_eq!(
+        client.get_referral(&routine, &to).due_at,
+        1_000 + SLA_ROUTINE_SECS
+    );
+    assert_eq!(
+        client.get_referral(&other, &to).due_at,
+        1_000 + SLA_ROUTINE_SECS
+    );
+}
+
+#[test]
+fn test_overdue_referral_expires() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let client = ReferralContractClient::new(&env, &register_referral(&env));
+    let referring = Address::generate(&env);
+    let patient = Address::generate(&env);
+    let to = Address::generate(&env);
+    let id = create(&env, &client, &referring, &patient, &to, "Urgent");
+
+    assert_eq!(client.try_expire_referral(&id), Err(Ok(Error::NotYetDue)));
+
+    env.ledger()
+        .with_mut(|l| l.timestamp += SLA_URGENT_SECS + 1);
+    assert_eq!(
+        client.try_accept_referral(&id, &to, &None),
+        Err(Ok(Error::ReferralExpired))
+    );
+    assert_eq!(
+        client.try_decline_referral(&id, &to, &String::from_str(&env, "late"), &None),
+        Err(Ok(Error::ReferralExpired))
+    );
+
+    client.expire_referral(&id);
+    assert_eq!(
+        client.get_referral(&id, &patient).status,
+        ReferralStatus::Expired
+    );
+    assert_eq!(
+        client.try_expire_referral(&id),
+        Err(Ok(Error::InvalidStatusTransition))
+    );
+    assert_eq!(
+        client.try_update_referral_status(&id, &to, &Symbol::new(&env, "Scheduled"), &None),
+        Err(Ok(Error::InvalidStatusTransition))
+    );
+}
+
+#[test]
+fn test_accepted_referral_does_not_expire() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let client = ReferralContractClient::new(&env, &register_referral(&env));
+    let referring = Address::generate(&env);
+    let patient = Address::generate(&env);
+    let to = Address::generate(&env);
+    let id = create(&env, &client, &referring, &patient, &to, "STAT");
+    client.accept_referral(&id, &to, &None);
+
+    env.ledger().with_mut(|l| l.timestamp += SLA_STAT_SECS + 1);
+    assert_eq!(
+        client.try_expire_referral(&id),
+        Err(Ok(Error::InvalidStatusTransition))
+    );
+}
+
+#[test]
+fn test_forward_declined_referral_keeps_history() {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.ledger().set_timestamp(500);
+    let client = ReferralContractClient::new(&env, &register_referral(&env));
+    let referring = Address::generate(&env);
+    let patient = Address::generate(&env);
+    let first = Address::generate(&env);
+    let second = Address::generate(&env);
+    let id = create(&env, &client, &referring, &patient, &first, "Urgent");
+
+    // Only declined referrals can be forwarded.
+    assert_eq!(
+        client.try_forward_referral(&id, &referring, &second),
+        Err(Ok(Error::InvalidStatusTransition))
+    );
+
+    env.ledger().set_timestamp(800);
+    let reason = String::from_str(&env, "Not taking new patients");
+    client.decline_referral(&id, &first, &reason, &Some(second.clone()));
+
+    env.ledger().set_timestamp(900);
+    assert_eq!(
+        client.try_forward_referral(&id, &first, &second),
+        Err(Ok(Error::NotAuthorized))
+    );
+    assert_eq!(
+        client.try_forward_referral(&id, &referring, &first),
+        Err(Ok(Error::InvalidAddress))
+    );
+    client.forward_referral(&id, &referring, &second);
+
+    let referral = client.get_referral(&id, &second);
+    assert_eq!(referral.receiving_provider, second);
+    assert_eq!(referral.status, ReferralStatus::Pending);
+    assert_eq!(referral.routed_at, 900);
+    assert_eq!(referral.due_at, 900 + SLA_URGENT_SECS);
+    assert_eq!(referral.created_at, 500);
+    assert_eq!(referral.decline_reason, None);
+
+    let history = client.get_routing_history(&id, &referring);
+    assert_eq!(history.len(), 1);
+    let route = history.get(0).unwrap();
+    assert_eq!(route.receiving_provider, first);
+    assert_eq!(route.routed_at, 500);
+    assert_eq!(route.declined_at, 800);
+    assert_eq!(route.decline_reason, reason);
+
+    // The previous receiver is no longer a party to the referral.
+    assert_eq!(
+        client.try_get_referral(&id, &first),
+        Err(Ok(Error::NotAuthorized))
+    );
+    client.accept_referral(&id, &second, &None);
+}
+
+#[test]
+fn test_forward_back_to_earlier_receiver_lists_once() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let client = ReferralContractClient::new(&env, &register_referral(&env));
+    let referring = Address::generate(&env);
+    let patient = Address::generate(&env);
+    let first = Address::generate(&env);
+    let second = Address::generate(&env);
+    let id = create(&env, &client, &referring, &patient, &first, "Routine");
+    let reason = String::from_str(&env, "full");
+
+    client.decline_referral(&id, &first, &reason, &None);
+    client.forward_referral(&id, &referring, &second);
+    client.decline_referral(&id, &second, &reason, &None);
+    client.forward_referral(&id, &referring, &first);
+
+    let inbox = client.get_inbox(&first, &0, &None, &None);
+    assert_eq!(inbox.referrals.len(), 1);
+    assert_eq!(inbox.referrals.get(0).unwrap().referral_id, id);
+    assert_eq!(client.get_routing_history(&id, &referring).len(), 2);
+}
+
+#[test]
+fn test_forward_requires_registered_provider() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let provider_registry_id = env.register(ProviderRegistry, ());
+    let pr_client = ProviderRegistryClient::new(&env, &provider_registry_id);
+    let admin = Address::generate(&env);
+    pr_client.initialize(&admin);
+    let client = ReferralContractClient::new(&env, &env.register(ReferralContract, ()));
+    client.initialize(&provider_registry_id);
+
+    let referring = Address::generate(&env);
+    let patient = Address::generate(&env);
+    let registered = Address::generate(&env);
+    pr_client.register_provider(
+        &admin,
+        &registered,
+        &String::from_str(&env, "Dr. Smith"),
+        &String::from_str(&env, "Cardiology"),
+        &String::from_str(&env, "LIC123"),
+        &BytesN::from_array(&env, &[1; 32]),
+        &admin,
+        &BytesN::from_array(&env, &[2; 32]),
+        &(env.ledger().timestamp() + 86400),
+        &BytesN::from_array(&env, &[3; 32]),
+    );
+    let id = create(&env, &client, &referring, &patient, &registered, "Routine");
+    client.decline_referral(&id, &registered, &String::from_str(&env, "full"), &None);
+
+    let unregistered = Address::generate(&env);
+    assert_eq!(
+        client.try_forward_referral(&id, &referring, &unregistered),
+        Err(Ok(Error::ProviderNotRegistered))
+    );
+}
+
+#[test]
+fn test_attachments_readable_only_by_receiver() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let client = ReferralContractClient::new(&env, &register_referral(&env));
+    let referring = Address::generate(&env);
+    let patient = Address::generate(&env);
+    let first = Address::generate(&env);
+    let second = Address::generate(&env);
+    let id = create(&env, &client, &referring, &patient, &first, "Routine");
+
+    let ecg = BytesN::from_array(&env, &[7; 32]);
+    let idx = client.add_attachment(&id, &referring, &ecg, &Symbol::new(&env, "ECG"));
+    assert_eq!(idx, 0);
+    assert_eq!(
+        client.try_add_attachment(&id, &first, &ecg, &Symbol::new(&env, "ECG")),
+        Err(Ok(Error::NotAuthorized))
+    );
+
+    let attachments = client.get_attachments(&id, &first);
+    assert_eq!(attachments.len(), 1);
+    assert_eq!(attachments.get(0).unwrap().document_hash, ecg);
+    assert_eq!(attachments.get(0).unwrap().added_by, referring);
+
+    for who in [&referring, &patient, &second] {
+        assert_eq!(
+            client.try_get_attachments(&id, who),
+            Err(Ok(Error::NotAuthorized))
+        );
+    }
+
+    // Attachments follow the referral to the new receiver.
+    client.decline_referral(&id, &first, &String::from_str(&env, "full"), &None);
+    client.forward_referral(&id, &referring, &second);
+    assert_eq!(client.get_attachments(&id, &second).len(), 1);
+    assert_eq!(
+        client.try_get_attachments(&id, &first),
+        Err(Ok(Error::NotAuthorized))
+    );
+}
+
+#[test]
+fn test_attachment_limits() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let client = ReferralContractClient::new(&env, &register_referral(&env));
+    let referring = Address::generate(&env);
+    let patient = Address::generate(&env);
+    let to = Address::generate(&env);
+    let id = create(&env, &client, &referring, &patient, &to, "Routine");
+
+    for i in 0..MAX_ATTACHMENTS {
+        client.add_attachment(
+            &id,
+            &referring,
+            &BytesN::from_array(&env, &[i as u8; 32]),
+            &Symbol::new(&env, "Note"),
+        );
+    }
+    assert_eq!(
+        client.try_add_attachment(
+            &id,
+            &referring,
+            &BytesN::from_array(&env, &[99; 32]),
+            &Symbol::new(&env, "Note"),
+        ),
+        Err(Ok(Error::TooManyAttachments))
+    );
+
+    // Closed referrals take no new attachments.
+    let id2 = create(&env, &client, &referring, &patient, &to, "Routine");
+    env.ledger()
+        .with_mut(|l| l.timestamp += SLA_ROUTINE_SECS + 1);
+    client.expire_referral(&id2);
+    assert_eq!(
+        client.try_add_attachment(
+            &id2,
+            &referring,
+            &BytesN::from_array(&env, &[1; 32]),
+            &Symbol::new(&env, "Note"),
+        ),
+        Err(Ok(Error::InvalidStatusTransition))
+    );
+}
+
+// ── inbox / outbox indices ───────────────────────────────────────────────────
+
+fn create_with_specialty(
+    env: &Env,
+    client: &ReferralContractClient,
+    referring: &Address,
+    patient: &Address,
+    to: &Address,
+    specialty: &str,
+) -> u64 {
+    client.create_referral(
+        referring,
+        patient,
+        to,
+        &Symbol::new(env, specialty),
+        &String::from_str(env, "Consult"),
+        &Symbol::new(env, "Routine"),
+        &BytesN::from_array(env, &[1; 32]),
+        &Vec::new(env),
+    )
+}
+
+#[test]
+fn test_inbox_and_outbox_list_referrals() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let client = ReferralContractClient::new(&env, &register_referral(&env));
+    let gp = Address::generate(&env);
+    let other_gp = Address::generate(&env);
+    let cardio = Address::generate(&env);
+    let patient = Address::generate(&env);
+
+    let a = create_with_specialty(&env, &client, &gp, &patient, &cardio, "Cardio");
+    let b = create_with_specialty(&env, &client, &other_gp, &patient, &cardio, "Cardio");
+    create_with_specialty(&env, &client, &gp, &patient, &other_gp, "Ortho");
+
+    let inbox = client.get_inbox(&cardio, &0, &None, &None);
+    assert_eq!(inbox.referrals.len(), 2);
+    assert_eq!(inbox.referrals.get(0).unwrap().referral_id, a);
+    assert_eq!(inbox.referrals.get(1).unwrap().referral_id, b);
+    assert!(!inbox.has_more);
+
+    let outbox = client.get_outbox(&gp, &0, &None, &None);
+    assert_eq!(outbox.referrals.len(), 2);
+    assert_eq!(client.get_patient_referrals(&patient, &0).ids.len(), 3);
+}
+
+#[test]
+fn test_inbox_filters_by_status_and_specialty() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let client = ReferralContractClient::new(&env, &register_referral(&env));
+    let gp = Address::generate(&env);
+    let specialist = Address::generate(&env);
+    let patient = Address::generate(&env);
+
+    let cardio = create_with_specialty(&env, &client, &gp, &patient, &specialist, "Cardio");
+    let neuro = create_with_specialty(&env, &client, &gp, &patient, &specialist, "Neuro");
+    create_with_specialty(&env, &client, &gp, &patient, &specialist, "Cardio");
+    client.accept_referral(&cardio, &specialist, &None);
+    client.decline_referral(&neuro, &specialist, &String::from_str(&env, "full"), &None);
+
+    let pending = client.get_inbox(&specialist, &0, &Some(ReferralStatus::Pending), &None);
+    assert_eq!(pending.referrals.len(), 1);
+
+    let cardio_only = client.get_inbox(&specialist, &0, &None, &Some(Symbol::new(&env, "Cardio")));
+    assert_eq!(cardio_only.referrals.len(), 2);
+
+    let accepted_cardio = client.get_inbox(
+        &specialist,
+        &0,
+        &Some(ReferralStatus::Accepted),
+        &Some(Symbol::new(&env, "Cardio")),
+    );
+    assert_eq!(accepted_cardio.referrals.len(), 1);
+    assert_eq!(
+        accepted_cardio.referrals.get(0).unwrap().referral_id,
+        cardio
+    );
+
+    let declined = client.get_outbox(&gp, &0, &Some(ReferralStatus::Declined), &None);
+    assert_eq!(declined.referrals.len(), 1);
+    assert_eq!(declined.referrals.get(0).unwrap().referral_id, neuro);
+}
+
+#[test]
+fn test_inbox_paginates() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let client = ReferralContractClient::new(&env, &register_referral(&env));
+    let gp = Address::generate(&env);
+    let specialist = Address::generate(&env);
+    let patient = Address::generate(&env);
+
+    let page_size = client.max_page_size();
+    for _ in 0..page_size + 2 {
+        create_with_specialty(&env, &client, &gp, &patient, &specialist, "Cardio");
+    }
+
+    let first = client.get_inbox(&specialist, &0, &None, &None);
+    assert_eq!(first.referrals.len(), page_size);
+    assert!(first.has_more);
+    let second = client.get_inbox(&specialist, &1, &None, &None);
+    assert_eq!(second.referrals.len(), 2);
+    assert!(!second.has_more);
+    assert_eq!(
+        second.referrals.get(0).unwrap().referral_id,
+        (page_size + 1) as u64
+    );
+}
+
+#[test]
+fn test_forwarded_referral_moves_between_inboxes() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let client = ReferralContractClient::new(&env, &register_referral(&env));
+    let gp = Address::generate(&env);
+    let first = Address::generate(&env);
+    let second = Address::generate(&env);
+    let patient = Address::generate(&env);
+
+    let id = create_with_specialty(&env, &client, &gp, &patient, &first, "Cardio");
+    client.decline_referral(&id, &first, &String::from_str(&env, "full"), &None);
+    client.forward_referral(&id, &gp, &second);
+
+    assert!(client
+        .get_inbox(&first, &0, &None, &None)
+        .referrals
+        .is_empty());
+    let inbox = client.get_inbox(&second, &0, &Some(ReferralStatus::Pending), &None);
+    assert_eq!(inbox.referrals.len(), 1);
+    assert_eq!(inbox.referrals.get(0).unwrap().referral_id, id);
+    assert_eq!(client.get_outbox(&gp, &0, &None, &None).referrals.len(), 1);
+}
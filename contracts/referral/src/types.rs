@@ -1,4 +1,13 @@
-use soroban_sdk::{contracterror, contracttype, Address, String, Symbol};
+use soroban_sdk::{contracterror, contracttype, Address, BytesN, String, Symbol, Vec};
+
+/// Response window for `STAT` referrals.
+pub const SLA_STAT_SECS: u64 = 24 * 60 * 60;
+/// Response window for `Urgent` referrals.
+pub const SLA_URGENT_SECS: u64 = 3 * 24 * 60 * 60;
+/// Response window for `Routine` and any unrecognised priority.
+pub const SLA_ROUTINE_SECS: u64 = 14 * 24 * 60 * 60;
+/// Maximum number of attachments on a single referral.
+pub const MAX_ATTACHMENTS: u32 = 20;
 
 #[contracterror]
 #[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
@@ -9,6 +18,11 @@ pub enum Error {
     InvalidStatusTransition = 3,
     InvalidAddress = 4,
     ProviderNotRegistered = 5,
+    /// The referral's response window has elapsed.
+    ReferralExpired = 6,
+    /// `expire_referral` was called before the referral fell due.
+    NotYetDue = 7,
+    TooManyAttachments = 8,
 }
 
 #[contracttype]
@@ -21,6 +35,8 @@ pub enum ReferralStatus {
     InProgress,
     Completed,
     Cancelled,
+    /// Not accepted or declined before `due_at`.
+    Expired,
 }
 
 #[contracttype]
@@ -37,6 +53,35 @@ pub struct Referral {
     pub created_at: u64,
     pub accepted_at: Option<u64>,
     pub completed_at: Option<u64>,
+    /// When the current receiving provider was routed the referral.
+    pub routed_at: u64,
+    /// Deadline for the current receiving provider to accept or decline,
+    /// derived from `priority`.
+    pub due_at: u64,
+    pub declined_at: Option<u64>,
+    pub decline_reason: Option<String>,
+}
+
+/// A previous routing of a referral, kept when a declined referral is
+/// forwarded to another provider.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ReferralRoute {
+    pub receiving_provider: Address,
+    pub routed_at: u64,
+    pub declined_at: u64,
+    pub decline_reason: String,
+}
+
+/// Hash of a clinical document attached to a referral. The document itself
+/// lives off-chain.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ReferralAttachment {
+    pub document_hash: BytesN<32>,
+    pub document_type: Symbol,
+    pub added_by: Address,
+    pub added_at: u64,
 }
 
 #[contracttype]
@@ -45,4 +90,30 @@ pub enum DataKey {
     Referral(u64),
     ReferralCount,
     ProviderRegistry,
+    /// referral_id -> Vec<ReferralRoute>, oldest first.
+    RoutingHistory(u64),
+    /// referral_id -> Vec<ReferralAttachment>
+    Attachments(u64),
+    /// Paged inbox per receiving provider: (provider, page_num) → Vec<u64>
+    InboxPage(Address, u32),
+    /// Current (highest-written) page index for a provider's inbox
+    InboxHead(Address),
+    /// Paged outbox per referring provider
+    OutboxPage(Address, u32),
+    /// Current page index for a provider's outbox
+    OutboxHead(Address),
+    /// Paged referral index per patient
+    PatientPage(Address, u32),
+    /// Current page index for a patient's referral list
+    PatientHead(Address),
+}
+
+/// One page of an inbox or outbox after filtering. `referrals` may hold
+/// fewer than `MAX_PAGE_SIZE` entries when filters drop some; keep reading
+/// `page + 1` while `has_more` is true.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ReferralPage {
+    pub referrals: Vec<Referral>,
+    pub has_more: bool,
 }
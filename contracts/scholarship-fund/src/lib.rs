@@ -22,30 +22,78 @@
 //! **Encryption/Integrity:** Fund amount validation prevents overflow. Address zero checks prevent
 //! invalid recipients. Deposit/withdrawal amounts immutable once recorded. Fund balance enforced
 //! mathematically. Authorization required before disbursement.
+//!
+//! ## Custody
+//!
+//! The fund holds a single Stellar asset fixed at `initialize`. Deposits transfer tokens into the
+//! contract; withdrawals and disbursements transfer them out. `check_solvency` compares the
+//! recorded `PoolBalance` with the contract's token balance, and payouts refuse to run while the
+//! contract holds less than it has recorded.
+//!
+//! ## Award schedules
+//!
+//! `create_award` earmarks an award's total from the pool and splits it into tranches. Each
+//! tranche vests linearly between `vest_start` and `vest_end` (equal values give a cliff) and may
+//! additionally require the recipient to hold an unrevoked `nft-badges` badge, e.g. course
+//! completion. Recipients `claim_award` whatever has vested. `revoke_award` freezes the schedule:
+//! the vested portion stays claimable and the unvested remainder is clawed back into the pool.
 
-use soroban_sdk::{contract,contracterror,contractimpl,contracttype,symbol_short,Address,Env,String};
+use soroban_sdk::{contract,contractclient,contracterror,contractimpl,contracttype,symbol_short,token,Address,Env,String,Vec};
 #[contracterror]
 #[derive(Copy,Clone,Debug,Eq,PartialEq)]
 #[repr(u32)]
-pub enum Error{NotInitialized=1,AlreadyInitialized=2,Unauthorized=3,ZeroAmount=4,InsufficientFunds=5,FundsCommitted=6}
+pub enum Error{NotInitialized=1,AlreadyInitialized=2,Unauthorized=3,ZeroAmount=4,InsufficientFunds=5,FundsCommitted=6,RecipientNotEligible=7,RecipientCapExceeded=8,
+    /// The contract's token balance is below the recorded pool balance.
+    Insolvent=9,InvalidSchedule=10,AwardNotFound=11,AwardRevoked=12,NothingToClaim=13,BadgeContractNotSet=14,
+    /// A vesting or balance computation left the i128 range.
+    ArithmeticOverflow=15}
+#[contracttype]
+pub enum DataKey{Admin,PoolBalance,CommittedFunds,Deposit(Address),Eligible(Address),RecipientAwards(Address),RecipientCap(Address),
+    /// Token contract of the custodied asset.
+    Asset,
+    /// Portion of `CommittedFunds` earmarked by award schedules.
+    ScheduledFunds,NextAwardId,Award(u64),RecipientAwardIds(Address),BadgeContract}
+#[contracttype]
+#[derive(Clone,Debug,Eq,PartialEq)]
+pub struct FundStats{pub pool_balance:i128,pub committed_balance:i128,pub scheduled_balance:i128}
+/// Recorded pool balance against the tokens the contract actually holds.
+#[contracttype]
+#[derive(Clone,Debug,Eq,PartialEq)]
+pub struct SolvencyReport{pub recorded_balance:i128,pub token_balance:i128,pub solvent:bool}
+/// One release step of an award. Vests linearly from `vest_start` to `vest_end`; when
+/// `badge_type` is set nothing vests until the recipient holds that badge.
+#[contracttype]
+#[derive(Clone,Debug,Eq,PartialEq)]
+pub struct Tranche{pub amount:i128,pub vest_start:u64,pub vest_end:u64,pub badge_type:Option<String>}
 #[contracttype]
-pub enum DataKey{Admin,PoolBalance,CommittedFunds,Deposit(Address)}
+#[derive(Clone,Debug,Eq,PartialEq)]
+pub struct Award{
+    pub id:u64,pub recipient:Address,pub total:i128,pub tranches:Vec<Tranche>,
+    /// Per-tranche milestone flags; latched once the badge has been observed.
+    pub milestones_met:Vec<bool>,
+    pub released:i128,pub clawed_back:i128,pub created_at:u64,pub revoked:bool,pub reason:String}
+/// Vested/unvested breakdown of an award at the current ledger time.
 #[contracttype]
 #[derive(Clone,Debug,Eq,PartialEq)]
-pub struct FundStats{pub pool_balance:i128,pub committed_balance:i128}
+pub struct AwardBalance{pub award_id:u64,pub total:i128,pub vested:i128,pub unvested:i128,pub released:i128,pub claimable:i128,pub clawed_back:i128,pub revoked:bool}
+#[contractclient(name="BadgesClient")]
+pub trait Badges{fn has_badge(env:Env,owner:Address,badge_type:String)->bool;}
 #[contract]
 pub struct ScholarshipFundContract;
 #[contractimpl]
 impl ScholarshipFundContract{
-    pub fn initialize(env:Env,admin:Address)->Result<(),Error>{
+    /// `asset` is the token contract (e.g. a Stellar Asset Contract) the fund holds.
+    pub fn initialize(env:Env,admin:Address,asset:Address)->Result<(),Error>{
         if env.storage().instance().has(&DataKey::Admin){return Err(Error::AlreadyInitialized);}
         env.storage().instance().set(&DataKey::Admin,&admin);
+        env.storage().instance().set(&DataKey::Asset,&asset);
         env.storage().instance().set(&DataKey::PoolBalance,&0i128);
         Ok(())
     }
     pub fn deposit(env:Env,depositor:Address,amount:i128)->Result<(),Error>{
         depositor.require_auth();
         if amount<=0{return Err(Error::ZeroAmount);}
+        token_client(&env)?.transfer(&depositor,env.current_contract_address(),&amount);
         let prev:i128=env.storage().persistent().get(&DataKey::Deposit(depositor.clone())).unwrap_or(0);
         env.storage().persistent().set(&DataKey::Deposit(depositor.clone()),&(prev+amount));
         let pool:i128=env.storage().instance().get(&DataKey::PoolBalance).unwrap_or(0);
@@ -62,17 +110,18 @@ impl ScholarshipFundContract{
         if pool<amount{return Err(Error::InsufficientFunds);}
         let committed:i128=env.storage().instance().get(&DataKey::CommittedFunds).unwrap_or(0);
         if pool-committed<amount{return Err(Error::FundsCommitted);}
+        let token=token_client(&env)?;
+        ensure_solvent(&env,&token,pool)?;
         env.storage().persistent().set(&DataKey::Deposit(depositor.clone()),&(held-amount));
         env.storage().instance().set(&DataKey::PoolBalance,&(pool-amount));
+        token.transfer(&env.current_contract_address(),&depositor,&amount);
         env.events().publish((symbol_short!("WITHDRAW"),depositor),amount);
         Ok(())
     }
     /// Earmark pool funds for a pending award, protecting them from donor withdrawal.
     /// Admin-only. Can only commit funds currently uncommitted in the pool.
     pub fn commit_funds(env:Env,admin:Address,amount:i128)->Result<(),Error>{
-        admin.require_auth();
-        let stored:Address=env.storage().instance().get(&DataKey::Admin).ok_or(Error::NotInitialized)?;
-        if admin!=stored{return Err(Error::Unauthorized);}
+        require_admin(&env,&admin)?;
         if amount<=0{return Err(Error::ZeroAmount);}
         let pool:i128=env.storage().instance().get(&DataKey::PoolBalance).unwrap_or(0);
         let committed:i128=env.storage().instance().get(&DataKey::CommittedFunds).unwrap_or(0);
@@ -82,30 +131,205 @@ impl ScholarshipFundContract{
         Ok(())
     }
     pub fn disburse(env:Env,admin:Address,recipient:Address,amount:i128,reason:String)->Result<(),Error>{
-        admin.require_auth();
-        let stored:Address=env.storage().instance().get(&DataKey::Admin).ok_or(Error::NotInitialized)?;
-        if admin!=stored{return Err(Error::Unauthorized);}
+        require_admin(&env,&admin)?;
         if amount<=0{return Err(Error::ZeroAmount);}
         let eligible:bool=env.storage().persistent().get(&DataKey::Eligible(recipient.clone())).unwrap_or(false);
         if !eligible{return Err(Error::RecipientNotEligible);}
         let prior_awards:i128=env.storage().persistent().get(&DataKey::RecipientAwards(recipient.clone())).unwrap_or(0);
         let cap:i128=env.storage().persistent().get(&DataKey::RecipientCap(recipient.clone())).unwrap_or(0);
-        if cap>0 && prior_awards+amount>cap{return Err(Error::RecipientCapExceeded);}
+        let awarded=prior_awards.checked_add(amount).ok_or(Error::ArithmeticOverflow)?;
+        if cap>0 && awarded>cap{return Err(Error::RecipientCapExceeded);}
         let pool:i128=env.storage().instance().get(&DataKey::PoolBalance).unwrap_or(0);
-        if pool<amount{return Err(Error::InsufficientFunds);}
+        let scheduled:i128=env.storage().instance().get(&DataKey::ScheduledFunds).unwrap_or(0);
+        if pool-scheduled<amount{return Err(Error::InsufficientFunds);}
+        let token=token_client(&env)?;
+        ensure_solvent(&env,&token,pool)?;
         env.storage().instance().set(&DataKey::PoolBalance,&(pool-amount));
+        env.storage().persistent().set(&DataKey::RecipientAwards(recipient.clone()),&awarded);
         let committed:i128=env.storage().instance().get(&DataKey::CommittedFunds).unwrap_or(0);
-        if committed>0{
-            let released=if amount<committed{amount}else{committed};
+        if committed>scheduled{
+            let released=if amount<committed-scheduled{amount}else{committed-scheduled};
             env.storage().instance().set(&DataKey::CommittedFunds,&(committed-released));
         }
+        token.transfer(&env.current_contract_address(),&recipient,&amount);
         env.events().publish((symbol_short!("DISBURSE"),recipient),(amount,reason));
         Ok(())
     }
-    pub fn get_stats(env:Env)->FundStats{FundStats{pool_balance:env.storage().instance().get(&DataKey::PoolBalance).unwrap_or(0),committed_balance:env.storage().instance().get(&DataKey::CommittedFunds).unwrap_or(0)}}
+    /// Schedule an award paid out in `tranches`. The total is earmarked from uncommitted pool
+    /// funds immediately and counts against the recipient's cap. Admin-only.
+    pub fn create_award(env:Env,admin:Address,recipient:Address,tranches:Vec<Tranche>,reason:String)->Result<u64,Error>{
+        require_admin(&env,&admin)?;
+        if tranches.is_empty(){return Err(Error::InvalidSchedule);}
+        let mut total:i128=0;
+        let mut milestones_met=Vec::new(&env);
+        for t in tranches.iter(){
+            if t.amount<=0||t.vest_end<t.vest_start{return Err(Error::InvalidSchedule);}
+            if t.badge_type.is_some()&&!env.storage().instance().has(&DataKey::BadgeContract){return Err(Error::BadgeContractNotSet);}
+            total=total.checked_add(t.amount).ok_or(Error::InvalidSchedule)?;
+            milestones_met.push_back(t.badge_type.is_none());
+        }
+        let eligible:bool=env.storage().persistent().get(&DataKey::Eligible(recipient.clone())).unwrap_or(false);
+        if !eligible{return Err(Error::RecipientNotEligible);}
+        let prior_awards:i128=env.storage().persistent().get(&DataKey::RecipientAwards(recipient.clone())).unwrap_or(0);
+        let cap:i128=env.storage().persistent().get(&DataKey::RecipientCap(recipient.clone())).unwrap_or(0);
+        let awarded=prior_awards.checked_add(total).ok_or(Error::ArithmeticOverflow)?;
+        if cap>0 && awarded>cap{return Err(Error::RecipientCapExceeded);}
+        let pool:i128=env.storage().instance().get(&DataKey::PoolBalance).unwrap_or(0);
+        let committed:i128=env.storage().instance().get(&DataKey::CommittedFunds).unwrap_or(0);
+        if pool-committed<total{return Err(Error::InsufficientFunds);}
+        let scheduled:i128=env.storage().instance().get(&DataKey::ScheduledFunds).unwrap_or(0);
+        env.storage().instance().set(&DataKey::CommittedFunds,&(committed+total));
+        env.storage().instance().set(&DataKey::ScheduledFunds,&(scheduled+total));
+        env.storage().persistent().set(&DataKey::RecipientAwards(recipient.clone()),&awarded);
+        let id:u64=env.storage().instance().get(&DataKey::NextAwardId).unwrap_or(1);
+        env.storage().instance().set(&DataKey::NextAwardId,&(id+1));
+        let award=Award{id,recipient:recipient.clone(),total,tranches,milestones_met,released:0,clawed_back:0,created_at:env.ledger().timestamp(),revoked:false,reason};
+        env.storage().persistent().set(&DataKey::Award(id),&award);
+        let mut ids:Vec<u64>=env.storage().persistent().get(&DataKey::RecipientAwardIds(recipient.clone())).unwrap_or_else(||Vec::new(&env));
+        ids.push_back(id);
+        env.storage().persistent().set(&DataKey::RecipientAwardIds(recipient.clone()),&ids);
+        env.events().publish((symbol_short!("AWARD"),recipient),(id,total));
+        Ok(id)
+    }
+    /// Pay out everything vested and not yet released on an award. Recipient-only.
+    pub fn claim_award(env:Env,recipient:Address,award_id:u64)->Result<i128,Error>{
+        recipient.require_auth();
+        let mut award=load_award(&env,award_id)?;
+        if award.recipient!=recipient{return Err(Error::Unauthorized);}
+        latch_milestones(&env,&mut award);
+        let amount=vested_amount(&env,&award)?-award.released;
+        if amount<=0{return Err(Error::NothingToClaim);}
+        let pool:i128=env.storage().instance().get(&DataKey::PoolBalance).unwrap_or(0);
+        let token=token_client(&env)?;
+        ensure_solvent(&env,&token,pool)?;
+        award.released+=amount;
+        env.storage().persistent().set(&DataKey::Award(award_id),&award);
+        release_scheduled(&env,amount)?;
+        env.storage().instance().set(&DataKey::PoolBalance,&(pool-amount));
+        token.transfer(&env.current_contract_address(),&recipient,&amount);
+        env.events().publish((symbol_short!("CLAIM"),recipient),(award_id,amount));
+        Ok(amount)
+    }
+    /// Revoke an award. Whatever has vested stays claimable; the unvested remainder returns to
+    /// the uncommitted pool and no longer counts against the recipient's cap. Admin-only.
+    pub fn revoke_award(env:Env,admin:Address,award_id:u64,reason:String)->Result<i128,Error>{
+        require_admin(&env,&admin)?;
+        let mut award=load_award(&env,award_id)?;
+        if award.revoked{return Err(Error::AwardRevoked);}
+        latch_milestones(&env,&mut award);
+        let unvested=award.total-vested_amount(&env,&award)?;
+        award.clawed_back=unvested;
+        award.revoked=true;
+        env.storage().persistent().set(&DataKey::Award(award_id),&award);
+        release_scheduled(&env,unvested)?;
+        let prior_awards:i128=env.storage().persistent().get(&DataKey::RecipientAwards(award.recipient.clone())).unwrap_or(0);
+        env.storage().persistent().set(&DataKey::RecipientAwards(award.recipient.clone()),&(prior_awards-unvested));
+        env.events().publish((symbol_short!("CLAWBACK"),award.recipient),(award_id,unvested,reason));
+        Ok(unvested)
+    }
+    /// Set the `nft-badges` contract consulted for milestone tranches. Admin-only.
+    pub fn set_badge_contract(env:Env,admin:Address,badges:Address)->Result<(),Error>{
+        require_admin(&env,&admin)?;
+        env.storage().instance().set(&DataKey::BadgeContract,&badges);
+        Ok(())
+    }
+    pub fn get_award(env:Env,award_id:u64)->Result<Award,Error>{load_award(&env,award_id)}
+    /// Mark a recipient as eligible (or not) for disbursements. Admin-only.
+    pub fn set_recipient_eligibility(env:Env,admin:Address,recipient:Address,eligible:bool)->Result<(),Error>{
+        require_admin(&env,&admin)?;
+        env.storage().persistent().set(&DataKey::Eligible(recipient.clone()),&eligible);
+        env.events().publish((symbol_short!("ELIGIBLE"),recipient),eligible);
+        Ok(())
+    }
+    /// Cap the cumulative amount a recipient may receive; 0 removes the cap. Admin-only.
+    pub fn set_recipient_cap(env:Env,admin:Address,recipient:Address,cap:i128)->Result<(),Error>{
+        require_admin(&env,&admin)?;
+        if cap<0{return Err(Error::ZeroAmount);}
+        env.storage().persistent().set(&DataKey::RecipientCap(recipient),&cap);
+        Ok(())
+    }
+    /// Compare the recorded pool balance with the contract's token balance.
+    /// Tokens sent directly to the contract show up as a surplus and keep it solvent.
+    pub fn check_solvency(env:Env)->Result<SolvencyReport,Error>{
+        let recorded:i128=env.storage().instance().get(&DataKey::PoolBalance).unwrap_or(0);
+        let held=token_client(&env)?.balance(&env.current_contract_address());
+        Ok(SolvencyReport{recorded_balance:recorded,token_balance:held,solvent:held>=recorded})
+    }
+    pub fn get_asset(env:Env)->Result<Address,Error>{env.storage().instance().get(&DataKey::Asset).ok_or(Error::NotInitialized)}
+    pub fn get_stats(env:Env)->FundStats{FundStats{pool_balance:env.storage().instance().get(&DataKey::PoolBalance).unwrap_or(0),committed_balance:env.storage().instance().get(&DataKey::CommittedFunds).unwrap_or(0),scheduled_balance:env.storage().instance().get(&DataKey::ScheduledFunds).unwrap_or(0)}}
     pub fn get_deposit(env:Env,depositor:Address)->i128{env.storage().persistent().get(&DataKey::Deposit(depositor)).unwrap_or(0)}
-    /// Cumulative amount this recipient has received across all disbursements.
-    pub fn get_recipient_awards(env:Env,recipient:Address)->i128{env.storage().persistent().get(&DataKey::RecipientAwards(recipient)).unwrap_or(0)}
+    /// Cumulative amount granted to this recipient: one-step disbursements plus scheduled award
+    /// totals, less anything clawed back. This is the figure checked against the recipient cap.
+    pub fn get_total_awarded(env:Env,recipient:Address)->i128{env.storage().persistent().get(&DataKey::RecipientAwards(recipient)).unwrap_or(0)}
+    /// Vested and unvested balances of each scheduled award held by `recipient`.
+    pub fn get_recipient_awards(env:Env,recipient:Address)->Result<Vec<AwardBalance>,Error>{
+        let ids:Vec<u64>=env.storage().persistent().get(&DataKey::RecipientAwardIds(recipient)).unwrap_or_else(||Vec::new(&env));
+        let mut out=Vec::new(&env);
+        for id in ids.iter(){
+            if let Ok(award)=load_award(&env,id){
+                let vested=vested_amount(&env,&award)?;
+                out.push_back(AwardBalance{award_id:id,total:award.total,vested,unvested:award.total-award.clawed_back-vested,released:award.released,claimable:vested-award.released,clawed_back:award.clawed_back,revoked:award.revoked});
+            }
+        }
+        Ok(out)
+    }
+}
+fn require_admin(env:&Env,admin:&Address)->Result<(),Error>{
+    admin.require_auth();
+    let stored:Address=env.storage().instance().get(&DataKey::Admin).ok_or(Error::NotInitialized)?;
+    if *admin!=stored{return Err(Error::Unauthorized);}
+    Ok(())
+}
+fn token_client(env:&Env)->Result<token::Client<'_>,Error>{
+    let asset:Address=env.storage().instance().get(&DataKey::Asset).ok_or(Error::NotInitialized)?;
+    Ok(token::Client::new(env,&asset))
+}
+fn load_award(env:&Env,award_id:u64)->Result<Award,Error>{env.storage().persistent().get(&DataKey::Award(award_id)).ok_or(Error::AwardNotFound)}
+/// Record milestones whose badge the recipient now holds. Skipped for revoked awards, whose
+/// schedule is frozen.
+fn latch_milestones(env:&Env,award:&mut Award){
+    if award.revoked{return;}
+    let Some(badges)=env.storage().instance().get::<_,Address>(&DataKey::BadgeContract) else{return;};
+    let client=BadgesClient::new(env,&badges);
+    for (i,t) in award.tranches.iter().enumerate(){
+        let i=i as u32;
+        if award.milestones_met.get(i).unwrap_or(false){continue;}
+        if let Some(badge_type)=t.badge_type{
+            if client.has_badge(&award.recipient,&badge_type){award.milestones_met.set(i,true);}
+        }
+    }
+}
+/// Amount vested at the current ledger time. Milestones are evaluated live for views; a revoked
+/// award is frozen at whatever was vested when it was revoked.
+fn vested_amount(env:&Env,award:&Award)->Result<i128,Error>{
+    if award.revoked{return Ok(award.total-award.clawed_back);}
+    let mut probe=award.clone();
+    latch_milestones(env,&mut probe);
+    let now=env.ledger().timestamp();
+    let mut vested:i128=0;
+    for (i,t) in probe.tranches.iter().enumerate(){
+        if !probe.milestones_met.get(i as u32).unwrap_or(false)||now<t.vest_start{continue;}
+        let part=if now>=t.vest_end{t.amount}else{
+            t.amount.checked_mul((now-t.vest_start) as i128).ok_or(Error::ArithmeticOverflow)?/(t.vest_end-t.vest_start) as i128
+        };
+        vested=vested.checked_add(part).ok_or(Error::ArithmeticOverflow)?;
+    }
+    Ok(vested)
+}
+/// Drop `amount` from both the committed and scheduled totals.
+fn release_scheduled(env:&Env,amount:i128)->Result<(),Error>{
+    let c:i128=env.storage().instance().get(&DataKey::CommittedFunds).unwrap_or(0);
+    let s:i128=env.storage().instance().get(&DataKey::ScheduledFunds).unwrap_or(0);
+    let c=c.checked_sub(amount).ok_or(Error::ArithmeticOverflow)?;
+    let s=s.checked_sub(amount).ok_or(Error::ArithmeticOverflow)?;
+    env.storage().instance().set(&DataKey::CommittedFunds,&c);
+    env.storage().instance().set(&DataKey::ScheduledFunds,&s);
+    Ok(())
+}
+/// Refuse payouts while the contract holds fewer tokens than `recorded`.
+fn ensure_solvent(env:&Env,token:&token::Client,recorded:i128)->Result<(),Error>{
+    if token.balance(&env.current_contract_address())<recorded{return Err(Error::Insolvent);}
+    Ok(())
 }
 #[cfg(test)]
 mod test;
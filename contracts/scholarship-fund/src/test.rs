@@ -1,23 +1,32 @@
 #![cfg(test)]
 use super::*;
-use soroban_sdk::{testutils::Address as _,Address,Env,String};
+use soroban_sdk::{testutils::{Address as _,Ledger},token::{StellarAssetClient,TokenClient},vec,Address,Env,String};
 fn setup()->(Env,ScholarshipFundContractClient<'static>,Address){
     let env=Env::default();env.mock_all_auths();
+    let issuer=Address::generate(&env);
+    let asset=env.register_stellar_asset_contract_v2(issuer).address();
     let id=env.register(ScholarshipFundContract,());
     let c=ScholarshipFundContractClient::new(&env,&id);
-    let admin=Address::generate(&env);c.initialize(&admin);(env,c,admin)
+    let admin=Address::generate(&env);c.initialize(&admin,&asset);(env,c,admin)
 }
-#[test]fn deposit_increases_pool(){let(_,c,_)=setup();let d=Address::generate(&c.env);c.deposit(&d,&500_000);assert_eq!(c.get_stats().pool_balance,500_000);}
-#[test]fn withdraw_reduces_pool(){let(_,c,_)=setup();let d=Address::generate(&c.env);c.deposit(&d,&1_000_000);c.withdraw(&d,&400_000);assert_eq!(c.get_stats().pool_balance,600_000);}
-#[test]fn disburse_reduces_pool(){let(env,c,admin)=setup();let donor=Address::generate(&env);let student=Address::generate(&env);c.deposit(&donor,&2_000_000);c.set_recipient_eligibility(&admin,&student,&true);c.disburse(&admin,&student,&1_000_000,&String::from_str(&env,"award"));assert_eq!(c.get_stats().pool_balance,1_000_000);}
+/// Generate a donor holding `amount` of the fund's asset.
+fn funded(c:&ScholarshipFundContractClient,amount:i128)->Address{
+    let d=Address::generate(&c.env);
+    StellarAssetClient::new(&c.env,&c.get_asset()).mint(&d,&amount);d
+}
+fn token<'a>(c:&ScholarshipFundContractClient<'a>)->TokenClient<'a>{TokenClient::new(&c.env,&c.get_asset())}
+#[test]fn deposit_increases_pool(){let(_,c,_)=setup();let d=funded(&c,500_000);c.deposit(&d,&500_000);assert_eq!(c.get_stats().pool_balance,500_000);}
+#[test]fn withdraw_reduces_pool(){let(_,c,_)=setup();let d=funded(&c,1_000_000);c.deposit(&d,&1_000_000);c.withdraw(&d,&400_000);assert_eq!(c.get_stats().pool_balance,600_000);}
+#[test]fn disburse_reduces_pool(){let(env,c,admin)=setup();let donor=funded(&c,2_000_000);let student=Address::generate(&env);c.deposit(&donor,&2_000_000);c.set_recipient_eligibility(&admin,&student,&true);c.disburse(&admin,&student,&1_000_000,&String::from_str(&env,"award"));assert_eq!(c.get_stats().pool_balance,1_000_000);}
 #[test]#[should_panic]fn disburse_empty_pool_panics(){let(env,c,admin)=setup();let s=Address::generate(&env);c.set_recipient_eligibility(&admin,&s,&true);c.disburse(&admin,&s,&1,&String::from_str(&env,"x"));}
-#[test]#[should_panic]fn non_admin_disburse_panics(){let(env,c,admin)=setup();let a=Address::generate(&env);let s=Address::generate(&env);let d=Address::generate(&env);c.deposit(&d,&1_000_000);c.set_recipient_eligibility(&admin,&s,&true);c.disburse(&a,&s,&1,&String::from_str(&env,"x"));}
-#[test]#[should_panic]fn over_withdraw_panics(){let(_,c,_)=setup();let d=Address::generate(&c.env);c.deposit(&d,&100_000);c.withdraw(&d,&200_000);}
+#[test]#[should_panic]fn non_admin_disburse_panics(){let(env,c,admin)=setup();let a=Address::generate(&env);let s=Address::generate(&env);let d=funded(&c,1_000_000);c.deposit(&d,&1_000_000);c.set_recipient_eligibility(&admin,&s,&true);c.disburse(&a,&s,&1,&String::from_str(&env,"x"));}
+#[test]#[should_panic]fn over_withdraw_panics(){let(_,c,_)=setup();let d=funded(&c,200_000);c.deposit(&d,&100_000);c.withdraw(&d,&200_000);}
 #[test]
 fn committed_funds_survive_a_pending_award() {
     let (env, c, admin) = setup();
-    let donor = Address::generate(&env);
+    let donor = funded(&c, 1_000_000);
     let student = Address::generate(&env);
+    c.set_recipient_eligibility(&admin, &student, &true);
 
     // Donor deposits, admin plans an award and earmarks the funds for it.
     c.deposit(&donor, &1_000_000);
@@ -34,8 +43,8 @@ fn committed_funds_survive_a_pending_award() {
 }
 #[test]
 fn uncommitted_funds_remain_withdrawable() {
-    let (env, c, admin) = setup();
-    let donor = Address::generate(&env);
+    let (_, c, admin) = setup();
+    let donor = funded(&c, 1_000_000);
 
     c.deposit(&donor, &1_000_000);
     c.commit_funds(&admin, &400_000);
@@ -44,3 +53,356 @@ fn uncommitted_funds_remain_withdrawable() {
     c.withdraw(&donor, &600_000);
     assert_eq!(c.get_stats().pool_balance, 400_000);
 }
+
+// ── token custody ─────────────────────────────────────────────────────────────
+
+#[test]
+fn deposit_and_withdraw_move_tokens() {
+    let (_, c, _) = setup();
+    let donor = funded(&c, 1_000_000);
+    let t = token(&c);
+
+    c.deposit(&donor, &1_000_000);
+    assert_eq!(t.balance(&donor), 0);
+    assert_eq!(t.balance(&c.address), 1_000_000);
+
+    c.withdraw(&donor, &250_000);
+    assert_eq!(t.balance(&donor), 250_000);
+    assert_eq!(t.balance(&c.address), 750_000);
+}
+
+#[test]
+fn disburse_pays_recipient_in_tokens() {
+    let (env, c, admin) = setup();
+    let donor = funded(&c, 1_000_000);
+    let student = Address::generate(&env);
+    c.deposit(&donor, &1_000_000);
+    c.set_recipient_eligibility(&admin, &student, &true);
+
+    c.disburse(&admin, &student, &300_000, &String::from_str(&env, "tuition"));
+    assert_eq!(token(&c).balance(&student), 300_000);
+    assert_eq!(token(&c).balance(&c.address), 700_000);
+    assert_eq!(c.get_total_awarded(&student), 300_000);
+}
+
+#[test]
+fn deposit_without_tokens_fails() {
+    let (env, c, _) = setup();
+    let donor = Address::generate(&env);
+    assert!(c.try_deposit(&donor, &1).is_err());
+    assert_eq!(c.get_stats().pool_balance, 0);
+}
+
+#[test]
+fn check_solvency_reports_surplus_as_solvent() {
+    let (_, c, _) = setup();
+    let donor = funded(&c, 1_500_000);
+    c.deposit(&donor, &1_000_000);
+    // Tokens sent straight to the contract are not recorded as deposits.
+    token(&c).transfer(&donor, &c.address, &500_000);
+
+    let report = c.check_solvency();
+    assert_eq!(report.recorded_balance, 1_000_000);
+    assert_eq!(report.token_balance, 1_500_000);
+    assert!(report.solvent);
+}
+
+#[test]
+fn payouts_blocked_when_insolvent() {
+    let (env, c, admin) = setup();
+    let donor = funded(&c, 1_000_000);
+    let student = Address::generate(&env);
+    c.deposit(&donor, &1_000_000);
+    c.set_recipient_eligibility(&admin, &student, &true);
+
+    // Simulate tokens leaving the contract without the ledger being updated.
+    let sink = Address::generate(&env);
+    let t = token(&c);
+    env.as_contract(&c.address, || t.transfer(&c.address, &sink, &400_000));
+
+    let report = c.check_solvency();
+    assert_eq!(report.token_balance, 600_000);
+    assert!(!report.solvent);
+    assert_eq!(c.try_withdraw(&donor, &100_000), Err(Ok(Error::Insolvent)));
+    assert_eq!(
+        c.try_disburse(&admin, &student, &100_000, &String::from_str(&env, "x")),
+        Err(Ok(Error::Insolvent))
+    );
+}
+
+#[test]
+fn disburse_respects_recipient_cap() {
+    let (env, c, admin) = setup();
+    let donor = funded(&c, 1_000_000);
+    let student = Address::generate(&env);
+    c.deposit(&donor, &1_000_000);
+    c.set_recipient_eligibility(&admin, &student, &true);
+    c.set_recipient_cap(&admin, &student, &500_000);
+
+    c.disburse(&admin, &student, &400_000, &String::from_str(&env, "a"));
+    assert_eq!(
+        c.try_disburse(&admin, &student, &200_000, &String::from_str(&env, "b")),
+        Err(Ok(Error::RecipientCapExceeded))
+    );
+}
+
+// ── award schedules ───────────────────────────────────────────────────────────
+
+fn timed(amount: i128, start: u64, end: u64) -> Tranche {
+    Tranche {
+        amount,
+        vest_start: start,
+        vest_end: end,
+        badge_type: None,
+    }
+}
+
+fn milestone(env: &Env, amount: i128, badge: &str) -> Tranche {
+    Tranche {
+        amount,
+        vest_start: 0,
+        vest_end: 0,
+        badge_type: Some(String::from_str(env, badge)),
+    }
+}
+
+/// Fund with 1_000_000, make `student` eligible and wire up an nft-badges contract.
+fn award_setup() -> (
+    Env,
+    ScholarshipFundContractClient<'static>,
+    Address,
+    Address,
+    nft_badges::NftBadgesContractClient<'static>,
+) {
+    let (env, c, admin) = setup();
+    let donor = funded(&c, 1_000_000);
+    c.deposit(&donor, &1_000_000);
+    let student = Address::generate(&env);
+    c.set_recipient_eligibility(&admin, &student, &true);
+    let badges_id = env.register(nft_badges::NftBadgesContract, ());
+    let badges = nft_badges::NftBadgesContractClient::new(&env, &badges_id);
+    badges.initialize(&admin);
+    c.set_badge_contract(&admin, &badges_id);
+    (env, c, admin, student, badges)
+}
+
+#[test]
+fn award_vests_linearly_and_is_claimable() {
+    let (env, c, admin, student, _) = award_setup();
+    env.ledger().set_timestamp(1_000);
+    let id = c.create_award(
+        &admin,
+        &student,
+        &vec![&env, timed(600_000, 1_000, 2_000)],
+        &String::from_str(&env, "stipend"),
+    );
+
+    let stats = c.get_stats();
+    assert_eq!(stats.committed_balance, 600_000);
+    assert_eq!(stats.scheduled_balance, 600_000);
+    assert_eq!(
+        c.try_claim_award(&student, &id),
+        Err(Ok(Error::NothingToClaim))
+    );
+
+    env.ledger().set_timestamp(1_500);
+    let bal = c.get_recipient_awards(&student).get(0).unwrap();
+    assert_eq!(
+        (bal.vested, bal.unvested, bal.claimable),
+        (300_000, 300_000, 300_000)
+    );
+    assert_eq!(c.claim_award(&student, &id), 300_000);
+    assert_eq!(token(&c).balance(&student), 300_000);
+
+    env.ledger().set_timestamp(2_500);
+    assert_eq!(c.claim_award(&student, &id), 300_000);
+    let stats = c.get_stats();
+    assert_eq!(
+        (
+            stats.pool_balance,
+            stats.committed_balance,
+            stats.scheduled_balance
+        ),
+        (400_000, 0, 0)
+    );
+}
+
+#[test]
+fn vesting_overflow_is_an_error() {
+    let (env, c, admin) = setup();
+    let amount = i128::MAX / 2;
+    let donor = funded(&c, amount);
+    c.deposit(&donor, &amount);
+    let student = Address::generate(&env);
+    c.set_recipient_eligibility(&admin, &student, &true);
+    let id = c.create_award(
+        &admin,
+        &student,
+        &vec![&env, timed(amount, 1_000, 3_000)],
+        &String::from_str(&env, "endowment"),
+    );
+
+    env.ledger().set_timestamp(2_000);
+    assert_eq!(
+        c.try_claim_award(&student, &id),
+        Err(Ok(Error::ArithmeticOverflow))
+    );
+    assert_eq!(
+        c.try_get_recipient_awards(&student),
+        Err(Ok(Error::ArithmeticOverflow))
+    );
+
+    // Fully vested tranches need no scaling.
+    env.ledger().set_timestamp(3_000);
+    assert_eq!(c.claim_award(&student, &id), amount);
+}
+
+#[test]
+fn cumulative_award_overflow_is_an_error() {
+    let (env, c, admin) = setup();
+    let amount = i128::MAX / 2 + 1;
+    let student = Address::generate(&env);
+    c.set_recipient_eligibility(&admin, &student, &true);
+    c.deposit(&funded(&c, amount), &amount);
+    c.disburse(&admin, &student, &amount, &String::from_str(&env, "grant"));
+
+    // The pool is refilled, but the student's cumulative total cannot grow
+    // by the same amount again.
+    c.deposit(&funded(&c, amount), &amount);
+    assert_eq!(
+        c.try_create_award(
+            &admin,
+            &student,
+            &vec![&env, timed(amount, 1_000, 3_000)],
+            &String::from_str(&env, "endowment"),
+        ),
+        Err(Ok(Error::ArithmeticOverflow))
+    );
+    assert_eq!(
+        c.try_disburse(&admin, &student, &amount, &String::from_str(&env, "grant")),
+        Err(Ok(Error::ArithmeticOverflow))
+    );
+    assert_eq!(c.get_total_awarded(&student), amount);
+}
+
+#[test]
+fn milestone_tranche_waits_for_badge() {
+    let (env, c, admin, student, badges) = award_setup();
+    let id = c.create_award(
+        &admin,
+        &student,
+        &vec![
+            &env,
+            timed(100_000, 0, 0),
+            milestone(&env, 400_000, "completion"),
+        ],
+        &String::from_str(&env, "course"),
+    );
+    assert_eq!(c.claim_award(&student, &id), 100_000);
+    assert_eq!(
+        c.try_claim_award(&student, &id),
+        Err(Ok(Error::NothingToClaim))
+    );
+
+    let s = |v: &str| String::from_str(&env, v);
+    badges.mint(
+        &admin,
+        &student,
+        &s("completion"),
+        &s("Course complete"),
+        &s("ipfs://x"),
+    );
+    assert_eq!(
+        c.get_recipient_awards(&student).get(0).unwrap().claimable,
+        400_000
+    );
+    assert_eq!(c.claim_award(&student, &id), 400_000);
+    assert_eq!(c.get_award(&id).milestones_met, vec![&env, true, true]);
+}
+
+#[test]
+fn revoke_award_claws_back_unvested() {
+    let (env, c, admin, student, _) = award_setup();
+    env.ledger().set_timestamp(0);
+    let id = c.create_award(
+        &admin,
+        &student,
+        &vec![
+            &env,
+            timed(200_000, 0, 100),
+            milestone(&env, 300_000, "completion"),
+        ],
+        &String::from_str(&env, "award"),
+    );
+    env.ledger().set_timestamp(50);
+    assert_eq!(
+        c.revoke_award(&admin, &id, &String::from_str(&env, "withdrew")),
+        400_000
+    );
+
+    // Vested portion stays claimable; the schedule is frozen afterwards.
+    env.ledger().set_timestamp(1_000);
+    let bal = c.get_recipient_awards(&student).get(0).unwrap();
+    assert_eq!(
+        (bal.vested, bal.unvested, bal.clawed_back, bal.revoked),
+        (100_000, 0, 400_000, true)
+    );
+    assert_eq!(c.claim_award(&student, &id), 100_000);
+    assert_eq!(c.get_total_awarded(&student), 100_000);
+
+    let stats = c.get_stats();
+    assert_eq!(
+        (
+            stats.pool_balance,
+            stats.committed_balance,
+            stats.scheduled_balance
+        ),
+        (900_000, 0, 0)
+    );
+    assert_eq!(
+        c.try_revoke_award(&admin, &id, &String::from_str(&env, "again")),
+        Err(Ok(Error::AwardRevoked))
+    );
+}
+
+#[test]
+fn scheduled_funds_are_not_disbursable_or_withdrawable() {
+    let (env, c, admin, student, _) = award_setup();
+    c.create_award(
+        &admin,
+        &student,
+        &vec![&env, timed(800_000, 100, 200)],
+        &String::from_str(&env, "a"),
+    );
+    let other = Address::generate(&env);
+    c.set_recipient_eligibility(&admin, &other, &true);
+    assert_eq!(
+        c.try_disburse(&admin, &other, &300_000, &String::from_str(&env, "b")),
+        Err(Ok(Error::InsufficientFunds))
+    );
+    c.disburse(&admin, &other, &200_000, &String::from_str(&env, "b"));
+    assert_eq!(c.get_stats().scheduled_balance, 800_000);
+}
+
+#[test]
+fn create_award_validates_schedule() {
+    let (env, c, admin, student, _) = award_setup();
+    let r = String::from_str(&env, "x");
+    assert_eq!(
+        c.try_create_award(&admin, &student, &vec![&env], &r),
+        Err(Ok(Error::InvalidSchedule))
+    );
+    assert_eq!(
+        c.try_create_award(&admin, &student, &vec![&env, timed(10, 5, 1)], &r),
+        Err(Ok(Error::InvalidSchedule))
+    );
+    assert_eq!(
+        c.try_create_award(&admin, &student, &vec![&env, timed(2_000_000, 0, 0)], &r),
+        Err(Ok(Error::InsufficientFunds))
+    );
+    c.set_recipient_cap(&admin, &student, &100);
+    assert_eq!(
+        c.try_create_award(&admin, &student, &vec![&env, timed(101, 0, 0)], &r),
+        Err(Ok(Error::RecipientCapExceeded))
+    );
+}
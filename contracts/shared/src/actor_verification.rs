@@ -10,6 +10,8 @@ pub enum ActorType {
     Provider,
     Hospital,
     Insurer,
+    /// Verified practitioner in doctor-registry.
+    Doctor,
 }
 
 /// Cached verification result with expiration
@@ -29,6 +31,7 @@ pub enum VerificationKey {
     ProviderRegistry,
     HospitalRegistry,
     InsurerRegistry,
+    DoctorRegistry,
 }
 
 /// Cache duration in ledger seconds (e.g., 24 hours)
@@ -37,7 +40,7 @@ pub const CACHE_DURATION: u64 = 86400;
 /// Verify if an address is a registered actor of the given type.
 /// Uses caching to reduce cross-contract calls.
 /// Registry addresses must be stored in instance storage under the
-/// `VerificationKey::{Patient,Provider,Hospital,Insurer}Registry` keys
+/// `VerificationKey::{Patient,Provider,Hospital,Insurer,Doctor}Registry` keys
 /// before this function is called.
 pub fn verify_actor(env: &Env, actor_type: ActorType, address: &Address) -> bool {
     // Check cache first
@@ -58,6 +61,7 @@ pub fn verify_actor(env: &Env, actor_type: ActorType, address: &Address) -> bool
         ActorType::Provider => verify_provider(env, address),
         ActorType::Hospital => verify_hospital(env, address),
         ActorType::Insurer => verify_insurer(env, address),
+        ActorType::Doctor => verify_doctor(env, address),
     };
 
     // Cache the result
@@ -122,6 +126,19 @@ fn verify_insurer(env: &Env, address: &Address) -> bool {
     env.invoke_contract(&registry, &Symbol::new(env, "is_insurer_active"), args)
 }
 
+fn verify_doctor(env: &Env, address: &Address) -> bool {
+    let registry: Address = match env
+        .storage()
+        .instance()
+        .get::<VerificationKey, Address>(&VerificationKey::DoctorRegistry)
+    {
+        Some(r) => r,
+        None => return false,
+    };
+    let args = vec![env, address.clone().into_val(env)];
+    env.invoke_contract(&registry, &Symbol::new(env, "is_verified_doctor"), args)
+}
+
 /// Helper to check if a provider is registered with a provider registry.
 pub fn is_provider_registered(env: &Env, provider_registry: &Address, provider: &Address) -> bool {
     let args = vec![env, provider.clone().into_val(env)];
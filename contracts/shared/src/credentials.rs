@@ -0,0 +1,79 @@
+//! Verifiable credential anchors.
+//!
+//! The credential registry lives in access-control: issuers with a DID
+//! document anchor each off-chain credential by its hash, scoped to the
+//! issuer, and revocation is a bit in the issuer's status list. This module
+//! holds the record type and a client so that issuing contracts
+//! (healthcare-credentialing, nft-badges) can publish credentials for their
+//! own records.
+
+use soroban_sdk::{
+    contractclient, contracttype, xdr::ToXdr, Address, Bytes, BytesN, Env, String,
+};
+
+/// On-chain anchor of a W3C-style verifiable credential. The credential body
+/// stays off-chain; `id` is its hash. Revocation is tracked in the issuer's
+/// status list: bit `status_index` of list `status_list`, most significant
+/// bit first, is set once the credential is revoked.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CredentialRecord {
+    pub id: BytesN<32>,
+    pub issuer: Address,
+    pub subject: Address,
+    pub schema_hash: BytesN<32>,
+    pub issued_at: u64,
+    pub expires_at: u64, // 0 means no expiration
+    pub status_list: u32,
+    pub status_index: u32,
+}
+
+/// A credential an issuing contract anchored, with the registry it was issued
+/// in. Issuers store this per record and revoke through `registry`, so
+/// switching to a new registry does not strand credentials issued earlier.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AnchoredCredential {
+    pub registry: Address,
+    pub credential_id: BytesN<32>,
+}
+
+/// The subset of the access-control interface used by issuing contracts.
+#[contractclient(name = "CredentialRegistryClient")]
+pub trait CredentialRegistry {
+    fn register_did(env: Env, address: Address, did: Bytes);
+    fn create_did_document(env: Env, subject: Address, key_id: String, public_key: BytesN<32>);
+    fn issue_credential(
+        env: Env,
+        issuer: Address,
+        subject: Address,
+        credential_id: BytesN<32>,
+        schema_hash: BytesN<32>,
+        expires_at: u64,
+    ) -> CredentialRecord;
+    fn revoke_credential(env: Env, issuer: Address, credential_id: BytesN<32>);
+}
+
+/// Register the calling contract as a credential issuer: its DID plus a DID
+/// document holding the Ed25519 key that signs its off-chain credentials.
+pub fn register_issuer(
+    env: &Env,
+    registry: &Address,
+    did: &Bytes,
+    key_id: &String,
+    public_key: &BytesN<32>,
+) {
+    let client = CredentialRegistryClient::new(env, registry);
+    let issuer = env.current_contract_address();
+    client.register_did(&issuer, did);
+    client.create_did_document(&issuer, key_id, public_key);
+}
+
+/// Credential id for the issuing contract's own record `local_id`:
+/// `sha256(xdr(current contract address) || local_id as big-endian u64)`.
+pub fn derive_credential_id(env: &Env, local_id: u64) -> BytesN<32> {
+    let mut preimage = Bytes::new(env);
+    preimage.append(&env.current_contract_address().to_xdr(env));
+    preimage.extend_from_array(&local_id.to_be_bytes());
+    env.crypto().sha256(&preimage).into()
+}
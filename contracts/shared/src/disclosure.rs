@@ -0,0 +1,244 @@
+//! HIPAA accounting-of-disclosures ledger.
+//!
+//! Contracts that serve protected health information call [`record_disclosure`]
+//! whenever a party other than the patient reads it. Entries are append-only:
+//! nothing in this module updates or deletes a [`DisclosureRecord`].
+//!
+//! Storage lives under the calling contract's own [`DisclosureKey`] namespace,
+//! so each contract keeps its own ledger with an identical layout.
+//!
+//! ## Storage layout
+//! * `DisclosureCounter`          → `u64` last disclosure id
+//! * `Disclosure(id)`             → [`DisclosureRecord`]
+//! * `DisclosurePage(patient, n)` / `DisclosureHead(patient)` → ids via [`crate::pagination`]
+//! * `DisclosureSummary(patient)` → [`DisclosureSummary`]
+
+use soroban_sdk::{contracttype, symbol_short, Address, Env, FromVal, String, Vec};
+
+use crate::pagination::{get_paged, push_paged};
+
+/// Distinct accessors tracked individually in a [`DisclosureSummary`].
+/// Disclosures to further accessors still count towards `total`.
+pub const MAX_SUMMARY_ACCESSORS: u32 = 50;
+
+/// One read of a patient's protected data.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DisclosureRecord {
+    pub id: u64,
+    pub patient: Address,
+    /// Who received the data.
+    pub accessor: Address,
+    /// What was read (record id, resource id, …) in the contract's own terms.
+    pub resource_id: String,
+    /// op_id of the grant or consent that authorized the read; 0 if none applies.
+    pub basis_op_id: u64,
+    pub purpose: String,
+    pub disclosed_at: u64,
+}
+
+/// Per-accessor line of a [`DisclosureSummary`].
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AccessorDisclosures {
+    pub accessor: Address,
+    pub count: u32,
+    pub first_at: u64,
+    pub last_at: u64,
+}
+
+/// Patient-facing rollup of every disclosure recorded for a patient.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DisclosureSummary {
+    pub total: u64,
+    pub first_at: u64,
+    pub last_at: u64,
+    /// At most `MAX_SUMMARY_ACCESSORS` entries, in order of first disclosure.
+    pub accessors: Vec<AccessorDisclosures>,
+    /// Disclosures to accessors beyond `MAX_SUMMARY_ACCESSORS`.
+    pub untracked: u64,
+}
+
+/// One page of [`get_disclosures`].
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DisclosurePage {
+    pub records: Vec<DisclosureRecord>,
+    /// Whether a later page may hold more records inside the window.
+    pub has_more: bool,
+}
+
+/// Storage keys for the disclosure ledger. Variant names are prefixed so they
+/// cannot collide with a host contract's own `DataKey` variants.
+#[contracttype]
+pub enum DisclosureKey {
+    DisclosureCounter,
+    Disclosure(u64),
+    DisclosurePage(Address, u32),
+    DisclosureHead(Address),
+    DisclosureSummary(Address),
+}
+
+/// Resource id `<prefix><id>`, e.g. `record:42`, for ledgers that name
+/// resources by a numeric id.
+pub fn numbered_resource_id(env: &Env, prefix: &str, id: u64) -> String {
+    let mut buf = [0u8; 64];
+    let start = prefix.len();
+    buf[..start].copy_from_slice(prefix.as_bytes());
+    let mut digits = [0u8; 20];
+    let mut n = id;
+    let mut len = 0;
+    loop {
+        digits[len] = b'0' + (n % 10) as u8;
+        len += 1;
+        n /= 10;
+        if n == 0 {
+            break;
+        }
+    }
+    for i in 0..len {
+        buf[start + i] = digits[len - 1 - i];
+    }
+    String::from_bytes(env, &buf[..start + len])
+}
+
+/// Append a disclosure and return its id.
+pub fn record_disclosure(
+    env: &Env,
+    patient: &Address,
+    accessor: &Address,
+    resource_id: String,
+    basis_op_id: u64,
+    purpose: String,
+) -> u64 {
+    let id: u64 = env
+        .storage()
+        .persistent()
+        .get(&DisclosureKey::DisclosureCounter)
+        .unwrap_or(0u64)
+        + 1;
+    env.storage().persistent().set(&DisclosureKey::DisclosureCounter, &id);
+
+    let now = env.ledger().timestamp();
+    let record = DisclosureRecord {
+        id,
+        patient: patient.clone(),
+        accessor: accessor.clone(),
+        resource_id,
+        basis_op_id,
+        purpose,
+        disclosed_at: now,
+    };
+    env.storage()
+        .persistent()
+        .set(&DisclosureKey::Disclosure(id), &record);
+
+    push_paged(
+        env,
+        |n| DisclosureKey::DisclosurePage(patient.clone(), n),
+        || DisclosureKey::DisclosureHead(patient.clone()),
+        id,
+    );
+
+    let summary_key = DisclosureKey::DisclosureSummary(patient.clone());
+    let mut summary: DisclosureSummary = env
+        .storage()
+        .persistent()
+        .get(&summary_key)
+        .unwrap_or(DisclosureSummary {
+            total: 0,
+            first_at: now,
+            last_at: now,
+            accessors: Vec::new(env),
+            untracked: 0,
+        });
+    summary.total += 1;
+    summary.last_at = now;
+    match summary
+        .accessors
+        .iter()
+        .position(|line| line.accessor == *accessor)
+    {
+        Some(i) => {
+            let mut line = summary.accessors.get(i as u32).unwrap();
+            line.count += 1;
+            line.last_at = now;
+            summary.accessors.set(i as u32, line);
+        }
+        None if summary.accessors.len() < MAX_SUMMARY_ACCESSORS => {
+            summary.accessors.push_back(AccessorDisclosures {
+                accessor: accessor.clone(),
+                count: 1,
+                first_at: now,
+                last_at: now,
+            });
+        }
+        None => summary.untracked += 1,
+    }
+    env.storage().persistent().set(&summary_key, &summary);
+
+    env.events().publish(
+        (symbol_short!("disclose"), patient.clone()),
+        (id, accessor.clone(), basis_op_id),
+    );
+    id
+}
+
+/// Disclosures for `patient` with `from <= disclosed_at <= to`.
+///
+/// `page` indexes the patient's ledger in storage pages of
+/// `pagination::MAX_PAGE_SIZE` entries, oldest first, so a page may hold
+/// fewer matches than that (or none) while `has_more` is still `true`.
+/// Paging stops early once a record newer than `to` is seen.
+pub fn get_disclosures(
+    env: &Env,
+    patient: &Address,
+    from: u64,
+    to: u64,
+    page: u32,
+) -> DisclosurePage {
+    let result = get_paged(
+        env,
+        |n| DisclosureKey::DisclosurePage(patient.clone(), n),
+        || DisclosureKey::DisclosureHead(patient.clone()),
+        page,
+    );
+    let mut records = Vec::new(env);
+    let mut past_window = false;
+    for val in result.ids.iter() {
+        let id = u64::from_val(env, &val);
+        let Some(record) = env
+            .storage()
+            .persistent()
+            .get::<DisclosureKey, DisclosureRecord>(&DisclosureKey::Disclosure(id))
+        else {
+            continue;
+        };
+        if record.disclosed_at > to {
+            past_window = true;
+            break;
+        }
+        if record.disclosed_at >= from {
+            records.push_back(record);
+        }
+    }
+    DisclosurePage {
+        records,
+        has_more: result.has_more && !past_window,
+    }
+}
+
+/// Rollup of every disclosure recorded for `patient`.
+pub fn get_summary(env: &Env, patient: &Address) -> DisclosureSummary {
+    env.storage()
+        .persistent()
+        .get(&DisclosureKey::DisclosureSummary(patient.clone()))
+        .unwrap_or(DisclosureSummary {
+            total: 0,
+            first_at: 0,
+            last_at: 0,
+            accessors: Vec::new(env),
+            untracked: 0,
+        })
+}
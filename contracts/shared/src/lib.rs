@@ -25,6 +25,8 @@
 //! for integrity verification.
 
 pub mod actor_verification;
+pub mod credentials;
+pub mod disclosure;
 #[cfg(test)]
 pub mod test_utils;
 pub mod error_hints;
@@ -1,13 +1,14 @@
-//! Core trait and placeholder for ZK medical-eligibility verification.
+//! Core trait, Groth16 implementation and placeholder for ZK medical-eligibility verification.
 //!
 //! See the [crate-level documentation](crate) and `INTERFACE.md` for the full
 //! expected proof-system boundary, security notes, and Soroban integration guidance.
 
-use soroban_sdk::{Address, Bytes, Vec};
+use soroban_sdk::{Address, Bytes, BytesN, Vec};
+use zk_eligibility::{groth16, MAX_PUBLIC_INPUTS};
 
 /// Semantic version label for this **Rust API** (not the future circuit version).
 /// Bump when `ZKProofVerifier` method signatures or `public_inputs` encoding contract changes.
-pub const RUST_INTERFACE_VERSION: &str = "1.0.0-stub";
+pub const RUST_INTERFACE_VERSION: &str = "1.1.0";
 
 /// Public inputs to the eligibility relation, as opaque byte blobs agreed with the circuit.
 ///
@@ -32,7 +33,8 @@ pub type PublicInputs = Vec<Bytes>;
 /// statement matches `public_inputs`. Implementations **must** return `false` for any
 /// parse error, wrong VK, or failed equation — never “soft-accept.”
 ///
-/// The [`PlaceholderZkProofVerifier`] always returns `false`.
+/// [`Groth16ZkProofVerifier`] is the production implementation; the
+/// [`PlaceholderZkProofVerifier`] always returns `false`.
 pub trait ZKProofVerifier {
     /// Stub implementations should return `false` until real verification is implemented.
     fn verify_eligibility_proof(
@@ -74,3 +76,40 @@ impl ZKProofVerifier for PlaceholderZkProofVerifier {
         false
     }
 }
+
+/// Groth16 (BLS12-381) verifier bound to one encoded verifying key.
+///
+/// Encodings follow [`zk_eligibility::groth16`]: every `public_inputs` element must
+/// be a 32-byte big-endian scalar below the group order. `patient` is not
+/// inspected here; bind it inside the circuit (e.g. as a public-input
+/// commitment) when proofs must not replay across accounts.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Groth16ZkProofVerifier {
+    pub vk: Bytes,
+}
+
+impl Groth16ZkProofVerifier {
+    pub fn new(vk: Bytes) -> Self {
+        Self { vk }
+    }
+}
+
+impl ZKProofVerifier for Groth16ZkProofVerifier {
+    fn verify_eligibility_proof(
+        &self,
+        patient: Address,
+        proof: Bytes,
+        public_inputs: PublicInputs,
+    ) -> bool {
+        let _ = patient;
+        let env = proof.env().clone();
+        let mut scalars: Vec<BytesN<32>> = Vec::new(&env);
+        for input in public_inputs.iter() {
+            match BytesN::<32>::try_from(input) {
+                Ok(s) => scalars.push_back(s),
+                Err(_) => return false,
+            }
+        }
+        groth16::verify_encoded(&env, &self.vk, &proof, &scalars, MAX_PUBLIC_INPUTS)
+    }
+}
@@ -29,8 +29,8 @@ pub mod interface;
 use soroban_sdk::{contract, contractimpl, contracttype, Address, Env};
 
 pub use interface::{
-    verify_eligibility_proof, PlaceholderZkProofVerifier, PublicInputs, RUST_INTERFACE_VERSION,
-    ZKProofVerifier,
+    verify_eligibility_proof, Groth16ZkProofVerifier, PlaceholderZkProofVerifier, PublicInputs,
+    RUST_INTERFACE_VERSION, ZKProofVerifier,
 };
 
 #[contracttype]
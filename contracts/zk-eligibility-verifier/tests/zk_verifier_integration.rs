@@ -1,21 +1,54 @@
-//! Integration-style tests for the ZK eligibility verifier scaffolding.
+//! Integration-style tests for the ZK eligibility verifier.
 //!
-//! Expand this module when a circuit, VK layout, and proof byte format exist.
-//! See `INTERFACE.md` in this crate for the expected end-to-end pipeline.
+//! Fixtures are shared with the `zk-eligibility` contract tests; see
+//! `INTERFACE.md` in this crate for the end-to-end pipeline.
 
 use soroban_sdk::{testutils::Address as _, Address, Bytes, Env, Vec};
 use zk_eligibility_verifier::{
-    verify_eligibility_proof, PlaceholderZkProofVerifier, RUST_INTERFACE_VERSION, ZKProofVerifier,
+    verify_eligibility_proof, Groth16ZkProofVerifier, PlaceholderZkProofVerifier, ZKProofVerifier,
+    RUST_INTERFACE_VERSION,
 };
 
+const VK_V1_HEX: &str = include_str!("../../zk-eligibility/test_vectors/vk_v1.hex");
+const VK_V2_HEX: &str = include_str!("../../zk-eligibility/test_vectors/vk_v2.hex");
+const PROOF_V1_A_HEX: &str = include_str!("../../zk-eligibility/test_vectors/proof_v1_a.hex");
+const INPUTS_V1_A_HEX: &str = include_str!("../../zk-eligibility/test_vectors/inputs_v1_a.hex");
+const PROOF_V2_HEX: &str = include_str!("../../zk-eligibility/test_vectors/proof_v2.hex");
+const INPUTS_V2_HEX: &str = include_str!("../../zk-eligibility/test_vectors/inputs_v2.hex");
+
+fn from_hex(env: &Env, hex: &str) -> Bytes {
+    fn nibble(c: u8) -> u8 {
+        match c {
+            b'0'..=b'9' => c - b'0',
+            b'a'..=b'f' => c - b'a' + 10,
+            _ => panic!("invalid hex fixture"),
+        }
+    }
+    let mut out = Bytes::new(env);
+    for pair in hex.trim().as_bytes().chunks(2) {
+        out.push_back((nibble(pair[0]) << 4) | nibble(pair[1]));
+    }
+    out
+}
+
+/// Public inputs a fixture proof commits to: expiry (2100-01-01), policy id 42
+/// and the nullifier, as 32-byte scalars.
+fn fixture_inputs(env: &Env, inputs_hex: &str) -> Vec<Bytes> {
+    let raw = from_hex(env, inputs_hex);
+    let mut inputs = Vec::new(env);
+    for i in 0..raw.len() / 32 {
+        inputs.push_back(raw.slice(i * 32..(i + 1) * 32));
+    }
+    inputs
+}
+
 #[test]
-fn rust_interface_version_is_documented_stub() {
+fn rust_interface_version_is_not_stub() {
     assert!(
-        RUST_INTERFACE_VERSION.contains("stub"),
+        !RUST_INTERFACE_VERSION.contains("stub"),
         "bump RUST_INTERFACE_VERSION when the trait API or encoding contract changes"
     );
 }
-
 #[test]
 fn placeholder_verifier_returns_false_for_empty_proof() {
     let env = Env::default();
@@ -46,41 +79,71 @@ fn placeholder_verifier_returns_false_with_nonempty_inputs() {
     assert!(!verifier.verify_eligibility_proof(patient, proof, public_inputs));
 }
 
-// ============================================================================
-// Skeleton — enable when ZK artifacts and test vectors are available in CI
-// ============================================================================
-//
-// #[test]
-// fn valid_proof_accepts_eligibility() {
-//     let env = Env::default();
-//     let patient = Address::generate(&env);
-//     let (proof, public_inputs) = load_fixture(&env, "fixtures/eligibility_ok_v1.bin");
-//     let verifier = ProductionVerifier::from_env(&env, VK_ID_V1);
-//     assert!(verify_eligibility_proof(&verifier, patient, proof, public_inputs));
-// }
-//
-// #[test]
-// fn tampered_public_input_rejects() {
-//     let env = Env::default();
-//     let patient = Address::generate(&env);
-//     let (mut proof, mut public_inputs) = load_fixture(&env, "fixtures/eligibility_ok_v1.bin");
-//     flip_last_byte_of_first_public_input(&mut public_inputs);
-//     let verifier = ProductionVerifier::from_env(&env, VK_ID_V1);
-//     assert!(!verify_eligibility_proof(&verifier, patient, proof, public_inputs));
-// }
-//
-// #[test]
-// fn wrong_patient_binding_rejects() {
-//     let env = Env::default();
-//     let patient_a = Address::generate(&env);
-//     let patient_b = Address::generate(&env);
-//     let (proof, public_inputs) = load_fixture_for_patient(&env, patient_a, "...");
-//     let verifier = ProductionVerifier::from_env(&env, VK_ID_V1);
-//     assert!(!verify_eligibility_proof(&verifier, patient_b, proof, public_inputs));
-// }
-//
-// #[test]
-// #[ignore = "requires prover daemon + circuit release artifact"]
-// fn soroban_contract_end_to_end() {
-//     // deploy wrapper contract that stores VK hash and calls ZKProofVerifier impl
-// }
+#[test]
+fn groth16_valid_proof_accepts_eligibility() {
+    let env = Env::default();
+    let patient = Address::generate(&env);
+    let verifier = Groth16ZkProofVerifier::new(from_hex(&env, VK_V1_HEX));
+    let proof = from_hex(&env, PROOF_V1_A_HEX);
+    assert!(verify_eligibility_proof(
+        &verifier,
+        patient,
+        proof,
+        fixture_inputs(&env, INPUTS_V1_A_HEX)
+    ));
+}
+
+#[test]
+fn groth16_tampered_public_input_rejects() {
+    let env = Env::default();
+    let patient = Address::generate(&env);
+    let verifier = Groth16ZkProofVerifier::new(from_hex(&env, VK_V1_HEX));
+    let proof = from_hex(&env, PROOF_V1_A_HEX);
+    let mut inputs = fixture_inputs(&env, INPUTS_V1_A_HEX);
+    assert!(verifier.verify_eligibility_proof(patient.clone(), proof.clone(), inputs.clone()));
+
+    // Only the policy id changes.
+    let mut policy = [0u8; 32];
+    policy[31] = 43;
+    inputs.set(1, Bytes::from_slice(&env, &policy));
+    assert!(!verifier.verify_eligibility_proof(patient, proof, inputs));
+}
+
+#[test]
+fn groth16_proof_for_other_key_rejects() {
+    let env = Env::default();
+    let patient = Address::generate(&env);
+    let proof = from_hex(&env, PROOF_V2_HEX);
+    let inputs = fixture_inputs(&env, INPUTS_V2_HEX);
+    let own_key = Groth16ZkProofVerifier::new(from_hex(&env, VK_V2_HEX));
+    assert!(own_key.verify_eligibility_proof(patient.clone(), proof.clone(), inputs.clone()));
+
+    // Only the verifying key changes.
+    let verifier = Groth16ZkProofVerifier::new(from_hex(&env, VK_V1_HEX));
+    assert!(!verifier.verify_eligibility_proof(patient, proof, inputs));
+}
+
+#[test]
+fn groth16_rejects_malformed_encodings() {
+    let env = Env::default();
+    let patient = Address::generate(&env);
+    let verifier = Groth16ZkProofVerifier::new(from_hex(&env, VK_V1_HEX));
+
+    // Public input that is not exactly 32 bytes.
+    let mut inputs = fixture_inputs(&env, INPUTS_V1_A_HEX);
+    inputs.set(1, Bytes::from_slice(&env, &[42]));
+    let proof = from_hex(&env, PROOF_V1_A_HEX);
+    assert!(!verifier.verify_eligibility_proof(patient.clone(), proof.clone(), inputs));
+
+    // Truncated proof.
+    let short = proof.slice(0..proof.len() - 1);
+    assert!(!verifier.verify_eligibility_proof(
+        patient.clone(),
+        short,
+        fixture_inputs(&env, INPUTS_V1_A_HEX)
+    ));
+
+    // Empty verifying key.
+    let empty = Groth16ZkProofVerifier::new(Bytes::new(&env));
+    assert!(!empty.verify_eligibility_proof(patient, proof, fixture_inputs(&env, INPUTS_V1_A_HEX)));
+}
@@ -0,0 +1,212 @@
+//! Groth16 proof verification over BLS12-381.
+//!
+//! All curve arithmetic runs through the Soroban host (`env.crypto().bls12_381()`),
+//! so the contract carries no pairing code of its own. Points use the
+//! uncompressed encodings accepted by the host; the host rejects off-curve and
+//! out-of-subgroup points when they are used in `g1_msm` / `pairing_check`.
+//!
+//! ## Byte layouts
+//!
+//! | Artifact | Layout | Size |
+//! |----------|--------|------|
+//! | Verifying key | `alpha_g1 ‖ beta_g2 ‖ gamma_g2 ‖ delta_g2 ‖ ic_0 ‖ … ‖ ic_n` | `672 + 96·(n+1)` |
+//! | Proof | `a_g1 ‖ b_g2 ‖ c_g1` | `384` |
+//! | Public input | big-endian scalar, strictly below the group order `r` | `32` |
+//!
+//! `n` is the number of public inputs the circuit exposes; a proof is only
+//! checked when exactly `n` inputs are supplied.
+//!
+//! ## Verification equation
+//!
+//! With `L = ic_0 + Σ xᵢ·icᵢ`, a proof is accepted iff
+//! `e(A, B) = e(α, β) · e(L, γ) · e(C, δ)`, evaluated as the single
+//! multi-pairing check `e(−A, B) · e(α, β) · e(L, γ) · e(C, δ) = 1`.
+
+use soroban_sdk::{
+    crypto::bls12_381::{Fr, G1Affine, G2Affine, G1_SERIALIZED_SIZE, G2_SERIALIZED_SIZE},
+    vec, Bytes, BytesN, Env, Vec,
+};
+
+/// Serialized size of an uncompressed G1 point.
+pub const G1_BYTES: u32 = G1_SERIALIZED_SIZE as u32;
+/// Serialized size of an uncompressed G2 point.
+pub const G2_BYTES: u32 = G2_SERIALIZED_SIZE as u32;
+/// Serialized size of a Groth16 proof (`A`, `B`, `C`).
+pub const PROOF_BYTES: u32 = 2 * G1_BYTES + G2_BYTES;
+/// Size of the fixed part of a verifying key (`alpha`, `beta`, `gamma`, `delta`).
+pub const VK_FIXED_BYTES: u32 = G1_BYTES + 3 * G2_BYTES;
+
+/// BLS12-381 scalar field order `r`, big-endian.
+const FR_MODULUS_BE: [u8; 32] = [
+    0x73, 0xed, 0xa7, 0x53, 0x29, 0x9d, 0x7d, 0x48, 0x33, 0x39, 0xd8, 0x08, 0x09, 0xa1, 0xd8, 0x05,
+    0x53, 0xbd, 0xa4, 0x02, 0xff, 0xfe, 0x5b, 0xfe, 0xff, 0xff, 0xff, 0xff, 0x00, 0x00, 0x00, 0x01,
+];
+
+/// Parsed Groth16 verifying key.
+#[derive(Clone)]
+pub struct VerifyingKey {
+    pub alpha_g1: G1Affine,
+    pub beta_g2: G2Affine,
+    pub gamma_g2: G2Affine,
+    pub delta_g2: G2Affine,
+    /// `ic[0]` is the constant term; `ic[i]` is paired with public input `i - 1`.
+    pub ic: Vec<G1Affine>,
+}
+
+/// Parsed Groth16 proof.
+#[derive(Clone)]
+pub struct Proof {
+    pub a: G1Affine,
+    pub b: G2Affine,
+    pub c: G1Affine,
+}
+
+impl VerifyingKey {
+    /// Parse a verifying key. Returns `None` when the length does not match the
+    /// layout above or when the key carries more than `max_inputs` input terms.
+    pub fn from_bytes(env: &Env, bytes: &Bytes, max_inputs: u32) -> Option<Self> {
+        let len = bytes.len();
+        if len < VK_FIXED_BYTES + G1_BYTES || !(len - VK_FIXED_BYTES).is_multiple_of(G1_BYTES) {
+            return None;
+        }
+        let ic_len = (len - VK_FIXED_BYTES) / G1_BYTES;
+        if ic_len > max_inputs.saturating_add(1) {
+            return None;
+        }
+
+        let mut off = 0u32;
+        let alpha_g1 = read_g1(bytes, &mut off)?;
+        let beta_g2 = read_g2(bytes, &mut off)?;
+        let gamma_g2 = read_g2(bytes, &mut off)?;
+        let delta_g2 = read_g2(bytes, &mut off)?;
+        let mut ic = Vec::new(env);
+        for _ in 0..ic_len {
+            ic.push_back(read_g1(bytes, &mut off)?);
+        }
+
+        Some(Self {
+            alpha_g1,
+            beta_g2,
+            gamma_g2,
+            delta_g2,
+            ic,
+        })
+    }
+
+    /// Number of public inputs this key expects.
+    pub fn num_inputs(&self) -> u32 {
+        self.ic.len().saturating_sub(1)
+    }
+}
+
+impl Proof {
+    /// Parse a proof. Returns `None` unless `bytes` is exactly [`PROOF_BYTES`] long.
+    pub fn from_bytes(bytes: &Bytes) -> Option<Self> {
+        if bytes.len() != PROOF_BYTES {
+            return None;
+        }
+        let mut off = 0u32;
+        Some(Self {
+            a: read_g1(bytes, &mut off)?,
+            b: read_g2(bytes, &mut off)?,
+            c: read_g1(bytes, &mut off)?,
+        })
+    }
+}
+
+/// Returns `true` when `input` encodes a scalar strictly below `r`.
+///
+/// The host reduces scalars modulo `r`, so without this check `x` and `x + r`
+/// would verify as the same public input.
+pub fn is_canonical_scalar(input: &BytesN<32>) -> bool {
+    let bytes = input.to_array();
+    for (b, m) in bytes.iter().zip(FR_MODULUS_BE.iter()) {
+        if b != m {
+            return b < m;
+        }
+    }
+    false
+}
+
+/// Verify `proof` against `vk` for the given public inputs.
+pub fn verify(
+    env: &Env,
+    vk: &VerifyingKey,
+    proof: &Proof,
+    public_inputs: &Vec<BytesN<32>>,
+) -> bool {
+    if public_inputs.len() != vk.num_inputs() {
+        return false;
+    }
+
+    let bls = env.crypto().bls12_381();
+
+    // L = ic_0 + Σ xᵢ·icᵢ
+    let mut acc = match vk.ic.get(0) {
+        Some(p) => p,
+        None => return false,
+    };
+    if !public_inputs.is_empty() {
+        let mut points: Vec<G1Affine> = Vec::new(env);
+        let mut scalars: Vec<Fr> = Vec::new(env);
+        for (i, input) in public_inputs.iter().enumerate() {
+            if !is_canonical_scalar(&input) {
+                return false;
+            }
+            points.push_back(vk.ic.get(i as u32 + 1).unwrap());
+            scalars.push_back(Fr::from_bytes(input));
+        }
+        acc = bls.g1_add(&acc, &bls.g1_msm(points, scalars));
+    }
+
+    let g1 = vec![
+        env,
+        -proof.a.clone(),
+        vk.alpha_g1.clone(),
+        acc,
+        proof.c.clone(),
+    ];
+    let g2 = vec![
+        env,
+        proof.b.clone(),
+        vk.beta_g2.clone(),
+        vk.gamma_g2.clone(),
+        vk.delta_g2.clone(),
+    ];
+    bls.pairing_check(g1, g2)
+}
+
+/// Parse and verify in one step. Any parse failure yields `false`.
+pub fn verify_encoded(
+    env: &Env,
+    vk: &Bytes,
+    proof: &Bytes,
+    public_inputs: &Vec<BytesN<32>>,
+    max_inputs: u32,
+) -> bool {
+    let vk = match VerifyingKey::from_bytes(env, vk, max_inputs) {
+        Some(vk) => vk,
+        None => return false,
+    };
+    let proof = match Proof::from_bytes(proof) {
+        Some(p) => p,
+        None => return false,
+    };
+    verify(env, &vk, &proof, public_inputs)
+}
+
+// ── internal helpers ──────────────────────────────────────────────────────────
+
+fn read_g1(bytes: &Bytes, off: &mut u32) -> Option<G1Affine> {
+    let end = off.checked_add(G1_BYTES)?;
+    let raw: BytesN<G1_SERIALIZED_SIZE> = bytes.slice(*off..end).try_into().ok()?;
+    *off = end;
+    Some(G1Affine::from_bytes(raw))
+}
+
+fn read_g2(bytes: &Bytes, off: &mut u32) -> Option<G2Affine> {
+    let end = off.checked_add(G2_BYTES)?;
+    let raw: BytesN<G2_SERIALIZED_SIZE> = bytes.slice(*off..end).try_into().ok()?;
+    *off = end;
+    Some(G2Affine::from_bytes(raw))
+}
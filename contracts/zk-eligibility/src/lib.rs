@@ -15,24 +15,31 @@
 //! - Verification cost is bounded: public_inputs length is capped at
 //!   MAX_PUBLIC_INPUTS and proof length at MAX_PROOF_BYTES.
 //! - A successful verification is recorded on-chain (nullifier pattern) so
-//!   the same proof cannot be replayed within the TTL window.
+//!   the same statement cannot be replayed within the TTL window. The
+//!   nullifier is the public input at `NULLIFIER_INPUT_IDX`, which the
+//!   circuit binds to `H(secret, scope)`; it is not derived from the proof
+//!   bytes, because a Groth16 proof can be re-randomized into a different
+//!   valid proof for the same inputs.
 //! - Nullifiers expire after `nullifier_ttl_ledgers` ledgers; expired
-//!   nullifiers allow re-verification with the same proof.
+//!   nullifiers allow re-verification of the same statement.
 //! - Integration point: other contracts call `verify_eligibility` and receive
 //!   a typed `Ok(())` / `Err(Error)` they can gate their own logic on.
+//! - Proofs are Groth16 over BLS12-381, checked with the host pairing
+//!   operations (see [`groth16`] for the VK, proof and public-input encodings).
 
 use soroban_sdk::{
     contract, contractimpl, contracttype, contracterror, symbol_short, Address, Bytes, BytesN,
     Env, Vec,
 };
 
+pub mod groth16;
 mod test;
 
 // ── Bounds ────────────────────────────────────────────────────────────────────
 
 /// Maximum number of 32-byte public input scalars accepted per proof.
 pub const MAX_PUBLIC_INPUTS: u32 = 16;
-/// Maximum proof byte length accepted (Groth16 ~192 bytes; give headroom).
+/// Maximum proof byte length accepted (uncompressed Groth16 is 384 bytes; give headroom).
 pub const MAX_PROOF_BYTES: u32 = 512;
 /// Maximum subjects/bundles accepted in a single batch call.
 pub const MAX_BATCH_SIZE: u32 = 10;
@@ -42,6 +49,8 @@ pub const DEFAULT_NULLIFIER_TTL_LEDGERS: u32 = 17_280;
 pub const ADMIN_ROTATION_WINDOW: u64 = 86_400;
 /// Index of the expiry timestamp in the public inputs array.
 pub const EXPIRY_INPUT_IDX: u32 = 0;
+/// Index of the circuit-bound nullifier `H(secret, scope)` in the public inputs array.
+pub const NULLIFIER_INPUT_IDX: u32 = 2;
 
 // ── Errors ────────────────────────────────────────────────────────────────────
 
@@ -64,6 +73,8 @@ pub enum Error {
     NotPendingAdmin      = 13,
     RotationExpired      = 14,
     ProofExpired         = 15,
+    InvalidVerifierKey   = 16,
+    MissingNullifier     = 17,
 }
 
 // ── Storage keys ──────────────────────────────────────────────────────────────
@@ -74,7 +85,7 @@ pub enum DataKey {
     Admin,
     /// Verifier key for a given schema version.
     VerifierKey(u32),
-    /// Nullifier: proof hash → NullifierRecord (schema version + expiry ledger).
+    /// Nullifier public input → NullifierRecord (schema version + expiry ledger).
     Nullifier(BytesN<32>),
     /// Cached subject eligibility after a successful proof.
     Eligibility(Address),
@@ -90,7 +101,7 @@ pub enum DataKey {
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct VerifierKeyEntry {
-    /// Raw verifier key bytes (circuit-specific, opaque to the contract).
+    /// Encoded Groth16 verifying key (layout documented in [`groth16`]).
     pub vk: Bytes,
     /// Schema version this key is valid for.
     pub schema_version: u32,
@@ -117,9 +128,9 @@ pub struct NullifierRecord {
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct ProofBundle {
-    /// Raw proof bytes.
+    /// Encoded Groth16 proof `A ‖ B ‖ C`.
     pub proof: Bytes,
-    /// Public inputs as a vector of 32-byte scalars.
+    /// Public inputs as big-endian BLS12-381 scalars, in circuit order.
     pub public_inputs: Vec<BytesN<32>>,
     /// Schema version the proof was generated against.
     pub schema_version: u32,
@@ -154,6 +165,10 @@ impl ZkEligibility {
     /// Register a verifier key for a new schema version. Admin only.
     /// Each schema_version may only be registered once; rotate by deprecating
     /// the old version and registering a new one.
+    ///
+    /// Keys that do not parse as a Groth16 verifying key, or that expose more
+    /// than `MAX_PUBLIC_INPUTS` public inputs, are rejected with
+    /// `Error::InvalidVerifierKey`.
     pub fn register_verifier_key(
         env: Env,
         admin: Address,
@@ -167,6 +182,9 @@ impl ZkEligibility {
         if env.storage().persistent().has(&key) {
             return Err(Error::SchemaAlreadyExists);
         }
+        if groth16::VerifyingKey::from_bytes(&env, &vk, MAX_PUBLIC_INPUTS).is_none() {
+            return Err(Error::InvalidVerifierKey);
+        }
 
         let entry = VerifierKeyEntry {
             vk,
@@ -256,9 +274,11 @@ impl ZkEligibility {
 
     /// Verify a ZK proof of eligibility.
     ///
-    /// On success the proof nullifier is stored so the proof cannot be
-    /// replayed within the TTL window. Returns `Ok(())` which callers use
-    /// to gate their own logic.
+    /// On success the nullifier public input is stored so the statement
+    /// cannot be replayed within the TTL window, whatever proof bytes carry
+    /// it. Returns `Ok(())` which callers use to gate their own logic.
+    ///
+    /// Bundles without a nullifier input return `Error::MissingNullifier`.
     ///
     /// `subject` is the address whose eligibility is being proven; it must
     /// sign the call so the proof cannot be submitted on behalf of another
@@ -301,8 +321,11 @@ impl ZkEligibility {
         }
 
         // ── Nullifier check ───────────────────────────────────────────────────
-        let proof_hash: BytesN<32> = env.crypto().sha256(&bundle.proof).into();
-        if Self::nullifier_active(&env, &proof_hash) {
+        let nullifier = bundle
+            .public_inputs
+            .get(NULLIFIER_INPUT_IDX)
+            .ok_or(Error::MissingNullifier)?;
+        if Self::nullifier_active(&env, &nullifier) {
             return Err(Error::ProofAlreadyUsed);
         }
 
@@ -312,14 +335,14 @@ impl ZkEligibility {
         }
 
         // ── Record nullifier ──────────────────────────────────────────────────
-        Self::store_nullifier(&env, &proof_hash, bundle.schema_version);
+        Self::store_nullifier(&env, &nullifier, bundle.schema_version);
         env.storage()
             .persistent()
             .set(&DataKey::Eligibility(subject.clone()), &true);
 
         env.events().publish(
             (symbol_short!("zk_ok"), subject, bundle.schema_version),
-            proof_hash,
+            nullifier,
         );
         Ok(())
     }
@@ -361,9 +384,9 @@ impl ZkEligibility {
             .ok_or(Error::SchemaNotFound)
     }
 
-    /// Check whether a proof (identified by its hash) has an active, unexpired nullifier.
-    pub fn is_nullified(env: Env, proof_hash: BytesN<32>) -> bool {
-        Self::nullifier_active(&env, &proof_hash)
+    /// Check whether a nullifier public input is active and unexpired.
+    pub fn is_nullified(env: Env, nullifier: BytesN<32>) -> bool {
+        Self::nullifier_active(&env, &nullifier)
     }
 
     /// Check whether a subject has a cached successful eligibility proof.
@@ -376,17 +399,17 @@ impl ZkEligibility {
 
     // ── internal helpers ──────────────────────────────────────────────────────
 
-    /// Returns `true` when the nullifier for `proof_hash` is active:
+    /// Returns `true` when `nullifier` is active:
     /// - The record exists and has not yet reached its `expires_at_ledger`.
     /// - The schema it was recorded against is either still active or was
     ///   migrated to a new version (deprecated-migrated). Non-migrated
     ///   deprecated schemas have their nullifiers invalidated so subjects
     ///   can re-verify under the new key.
-    fn nullifier_active(env: &Env, proof_hash: &BytesN<32>) -> bool {
+    fn nullifier_active(env: &Env, nullifier: &BytesN<32>) -> bool {
         let record: NullifierRecord = match env
             .storage()
             .persistent()
-            .get(&DataKey::Nullifier(proof_hash.clone()))
+            .get(&DataKey::Nullifier(nullifier.clone()))
         {
             Some(r) => r,
             None => return false,
@@ -406,7 +429,7 @@ impl ZkEligibility {
         }
     }
 
-    fn store_nullifier(env: &Env, proof_hash: &BytesN<32>, schema_version: u32) {
+    fn store_nullifier(env: &Env, nullifier: &BytesN<32>, schema_version: u32) {
         let ttl: u32 = env
             .storage()
             .persistent()
@@ -414,7 +437,7 @@ impl ZkEligibility {
             .unwrap_or(DEFAULT_NULLIFIER_TTL_LEDGERS);
         let expires_at = env.ledger().sequence().saturating_add(ttl);
         env.storage().persistent().set(
-            &DataKey::Nullifier(proof_hash.clone()),
+            &DataKey::Nullifier(nullifier.clone()),
             &NullifierRecord { schema_version, expires_at_ledger: expires_at },
         );
     }
@@ -439,8 +462,11 @@ impl ZkEligibility {
             return false;
         }
 
-        let proof_hash: BytesN<32> = env.crypto().sha256(&bundle.proof).into();
-        if Self::nullifier_active(env, &proof_hash) {
+        let nullifier = match bundle.public_inputs.get(NULLIFIER_INPUT_IDX) {
+            Some(n) => n,
+            None => return false,
+        };
+        if Self::nullifier_active(env, &nullifier) {
             return false;
         }
 
@@ -448,14 +474,14 @@ impl ZkEligibility {
             return false;
         }
 
-        Self::store_nullifier(env, &proof_hash, bundle.schema_version);
+        Self::store_nullifier(env, &nullifier, bundle.schema_version);
         env.storage()
             .persistent()
             .set(&DataKey::Eligibility(subject.clone()), &true);
 
         env.events().publish(
             (symbol_short!("zk_ok"), subject.clone(), bundle.schema_version),
-            proof_hash,
+            nullifier,
         );
         true
     }
@@ -539,24 +565,16 @@ impl ZkEligibility {
         ts
     }
 
-    /// Cryptographic verification stub.
-    ///
-    /// Production: replace the body with a call to the host's pairing
-    /// verifier or a cross-contract call to a deployed verifier contract.
-    /// The function signature is stable so all callers are unaffected.
+    /// Groth16 verification of `proof` against `vk` for `public_inputs`.
     ///
-    /// The stub accepts any proof whose first byte equals the first byte of
-    /// the verifier key — a deterministic, testable rule that exercises the
-    /// full call path without requiring real ZK machinery.
+    /// Returns `false` for any encoding error, input-count mismatch,
+    /// non-canonical scalar or failed pairing equation.
     fn run_verification(
-        _env: &Env,
+        env: &Env,
         vk: &Bytes,
         proof: &Bytes,
-        _public_inputs: &Vec<BytesN<32>>,
+        public_inputs: &Vec<BytesN<32>>,
     ) -> bool {
-        if vk.is_empty() || proof.is_empty() {
-            return false;
-        }
-        vk.get(0) == proof.get(0)
+        groth16::verify_encoded(env, vk, proof, public_inputs, MAX_PUBLIC_INPUTS)
     }
 }
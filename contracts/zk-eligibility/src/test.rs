@@ -15,24 +15,62 @@ fn setup() -> (Env, Address, ZkEligibilityClient<'static>) {
     (env, admin, client)
 }
 
-/// VK whose first byte is `tag`.
-fn vk(env: &Env, tag: u8) -> Bytes {
-    Bytes::from_slice(env, &[tag, 0xAB, 0xCD])
+// Fixtures under `test_vectors/` come from the eligibility circuit described
+// in `test_vectors/README.md` (expiry, policy id = 42, nullifier), set up with
+// fixed, publicly known trapdoors. They are valid Groth16 artifacts for
+// testing only.
+const VK_V1_HEX: &str = include_str!("../test_vectors/vk_v1.hex");
+const VK_V2_HEX: &str = include_str!("../test_vectors/vk_v2.hex");
+const PROOF_V1_A_HEX: &str = include_str!("../test_vectors/proof_v1_a.hex");
+const INPUTS_V1_A_HEX: &str = include_str!("../test_vectors/inputs_v1_a.hex");
+/// Public re-randomization of `proof_v1_a`, over the same inputs.
+const PROOF_V1_B_HEX: &str = include_str!("../test_vectors/proof_v1_b.hex");
+const PROOF_V1_C_HEX: &str = include_str!("../test_vectors/proof_v1_c.hex");
+const INPUTS_V1_C_HEX: &str = include_str!("../test_vectors/inputs_v1_c.hex");
+const PROOF_V2_HEX: &str = include_str!("../test_vectors/proof_v2.hex");
+const INPUTS_V2_HEX: &str = include_str!("../test_vectors/inputs_v2.hex");
+
+/// Policy id committed to by the fixture proofs.
+const FIXTURE_POLICY_ID: u8 = 42;
+
+fn from_hex(env: &Env, hex: &str) -> Bytes {
+    fn nibble(c: u8) -> u8 {
+        match c {
+            b'0'..=b'9' => c - b'0',
+            b'a'..=b'f' => c - b'a' + 10,
+            _ => panic!("invalid hex fixture"),
+        }
+    }
+    let raw = hex.trim().as_bytes();
+    let mut out = Bytes::new(env);
+    for pair in raw.chunks(2) {
+        out.push_back((nibble(pair[0]) << 4) | nibble(pair[1]));
+    }
+    out
 }
 
-/// Proof whose first byte is `tag` (matches VK with same tag → valid).
-fn proof(env: &Env, tag: u8) -> Bytes {
-    Bytes::from_slice(env, &[tag, 0x01, 0x02, 0x03])
+fn vk_v1(env: &Env) -> Bytes {
+    from_hex(env, VK_V1_HEX)
 }
 
-fn empty_inputs(env: &Env) -> Vec<BytesN<32>> {
-    Vec::new(env)
+fn vk_v2(env: &Env) -> Bytes {
+    from_hex(env, VK_V2_HEX)
 }
 
-fn bundle(env: &Env, tag: u8, schema_version: u32) -> ProofBundle {
+fn fixture_inputs(env: &Env, inputs_hex: &str) -> Vec<BytesN<32>> {
+    let raw = from_hex(env, inputs_hex);
+    let mut inputs = Vec::new(env);
+    for i in 0..raw.len() / 32 {
+        let scalar: BytesN<32> = raw.slice(i * 32..(i + 1) * 32).try_into().unwrap();
+        inputs.push_back(scalar);
+    }
+    inputs
+}
+
+fn bundle(env: &Env, proof_hex: &str, inputs_hex: &str, schema_version: u32) -> ProofBundle {
     ProofBundle {
-        proof: proof(env, tag),
-        public_inputs: empty_inputs(env),
+        proof: from_hex(env, proof_hex),
+        public_inputs: fixture_inputs(env, inputs_hex),
         schema_version,
     }
 }
@@ -54,7 +92,7 @@ fn test_call_before_init_returns_error() {
     let client = ZkEligibilityClient::new(&env, &contract_id);
     let admin = Address::generate(&env);
     let err = client
-        .try_register_verifier_key(&admin, &1u32, &vk(&env, 0xAA))
+        .try_register_verifier_key(&admin, &1u32, &vk_v1(&env))
         .unwrap_err()
         .unwrap();
     assert_eq!(err, Error::NotInitialized);
@@ -65,7 +103,7 @@ fn test_call_before_init_returns_error() {
 #[test]
 fn test_register_and_get_verifier_key() {
     let (env, admin, client) = setup();
-    client.register_verifier_key(&admin, &1u32, &vk(&env, 0xAA));
+    client.register_verifier_key(&admin, &1u32, &vk_v1(&env));
     let entry = client.get_verifier_key(&1u32);
     assert_eq!(entry.schema_version, 1);
     assert!(entry.active);
@@ -74,9 +112,9 @@ fn test_register_and_get_verifier_key() {
 #[test]
 fn test_duplicate_schema_returns_error() {
     let (env, admin, client) = setup();
-    client.register_verifier_key(&admin, &1u32, &vk(&env, 0xAA));
+    client.register_verifier_key(&admin, &1u32, &vk_v1(&env));
     let err = client
-        .try_register_verifier_key(&admin, &1u32, &vk(&env, 0xBB))
+        .try_register_verifier_key(&admin, &1u32, &vk_v2(&env))
         .unwrap_err()
         .unwrap();
     assert_eq!(err, Error::SchemaAlreadyExists);
@@ -87,7 +125,7 @@ fn test_non_admin_register_returns_error() {
     let (env, _, client) = setup();
     let stranger = Address::generate(&env);
     let err = client
-        .try_register_verifier_key(&stranger, &1u32, &vk(&env, 0xAA))
+        .try_register_verifier_key(&stranger, &1u32, &vk_v1(&env))
         .unwrap_err()
         .unwrap();
     assert_eq!(err, Error::Unauthorized);
@@ -96,7 +134,7 @@ fn test_non_admin_register_returns_error() {
 #[test]
 fn test_deprecate_verifier_key() {
     let (env, admin, client) = setup();
-    client.register_verifier_key(&admin, &1u32, &vk(&env, 0xAA));
+    client.register_verifier_key(&admin, &1u32, &vk_v1(&env));
     client.deprecate_verifier_key(&admin, &1u32);
     let entry = client.get_verifier_key(&1u32);
     assert!(!entry.active);
@@ -117,9 +155,9 @@ fn test_deprecate_unknown_schema_returns_error() {
 #[test]
 fn test_valid_proof_accepted() {
     let (env, admin, client) = setup();
-    client.register_verifier_key(&admin, &1u32, &vk(&env, 0xAA));
+    client.register_verifier_key(&admin, &1u32, &vk_v1(&env));
     let subject = Address::generate(&env);
-    client.verify_eligibility(&subject, &bundle(&env, 0xAA, 1));
+    client.verify_eligibility(&subject, &bundle(&env, PROOF_V1_A_HEX, INPUTS_V1_A_HEX, 1));
 }
 
 // ── verify_eligibility: failure paths ────────────────────────────────────────
@@ -127,14 +165,10 @@ fn test_valid_proof_accepted() {
 #[test]
 fn test_invalid_proof_returns_error() {
     let (env, admin, client) = setup();
-    client.register_verifier_key(&admin, &1u32, &vk(&env, 0xAA));
+    client.register_verifier_key(&admin, &1u32, &vk_v1(&env));
     let subject = Address::generate(&env);
-    // proof tag 0xBB ≠ vk tag 0xAA → verification fails
-    let bad_bundle = ProofBundle {
-        proof: proof(&env, 0xBB),
-        public_inputs: empty_inputs(&env),
-        schema_version: 1,
-    };
+    // proof generated against the v2 key does not verify under v1
+    let bad_bundle = bundle(&env, PROOF_V2_HEX, INPUTS_V2_HEX, 1);
     let err = client
         .try_verify_eligibility(&subject, &bad_bundle)
         .unwrap_err()
@@ -147,7 +181,7 @@ fn test_unknown_schema_returns_error() {
     let (env, _, client) = setup();
     let subject = Address::generate(&env);
     let err = client
-        .try_verify_eligibility(&subject, &bundle(&env, 0xAA, 99))
+        .try_verify_eligibility(&subject, &bundle(&env, PROOF_V1_A_HEX, INPUTS_V1_A_HEX, 99))
         .unwrap_err()
         .unwrap();
     assert_eq!(err, Error::SchemaNotFound);
@@ -156,11 +190,11 @@ fn test_unknown_schema_returns_error() {
 #[test]
 fn test_deprecated_schema_returns_error() {
     let (env, admin, client) = setup();
-    client.register_verifier_key(&admin, &1u32, &vk(&env, 0xAA));
+    client.register_verifier_key(&admin, &1u32, &vk_v1(&env));
     client.deprecate_verifier_key(&admin, &1u32);
     let subject = Address::generate(&env);
     let err = client
-        .try_verify_eligibility(&subject, &bundle(&env, 0xAA, 1))
+        .try_verify_eligibility(&subject, &bundle(&env, PROOF_V1_A_HEX, INPUTS_V1_A_HEX, 1))
         .unwrap_err()
         .unwrap();
     assert_eq!(err, Error::SchemaNotFound);
@@ -169,12 +203,12 @@ fn test_deprecated_schema_returns_error() {
 #[test]
 fn test_proof_replay_returns_error() {
     let (env, admin, client) = setup();
-    client.register_verifier_key(&admin, &1u32, &vk(&env, 0xAA));
+    client.register_verifier_key(&admin, &1u32, &vk_v1(&env));
     let subject = Address::generate(&env);
-    client.verify_eligibility(&subject, &bundle(&env, 0xAA, 1));
+    client.verify_eligibility(&subject, &bundle(&env, PROOF_V1_A_HEX, INPUTS_V1_A_HEX, 1));
     // Same proof submitted again
     let err = client
-        .try_verify_eligibility(&subject, &bundle(&env, 0xAA, 1))
+        .try_verify_eligibility(&subject, &bundle(&env, PROOF_V1_A_HEX, INPUTS_V1_A_HEX, 1))
         .unwrap_err()
         .unwrap();
     assert_eq!(err, Error::ProofAlreadyUsed);
@@ -183,12 +217,12 @@ fn test_proof_replay_returns_error() {
 #[test]
 fn test_proof_too_large_returns_error() {
     let (env, admin, client) = setup();
-    client.register_verifier_key(&admin, &1u32, &vk(&env, 0xAA));
+    client.register_verifier_key(&admin, &1u32, &vk_v1(&env));
     let subject = Address::generate(&env);
     let big_proof = Bytes::from_slice(&env, &[0xAA; 513]);
     let bad_bundle = ProofBundle {
         proof: big_proof,
-        public_inputs: empty_inputs(&env),
+        public_inputs: fixture_inputs(&env, INPUTS_V1_A_HEX),
         schema_version: 1,
     };
     let err = client
@@ -201,14 +235,14 @@ fn test_proof_too_large_returns_error() {
 #[test]
 fn test_too_many_public_inputs_returns_error() {
     let (env, admin, client) = setup();
-    client.register_verifier_key(&admin, &1u32, &vk(&env, 0xAA));
+    client.register_verifier_key(&admin, &1u32, &vk_v1(&env));
     let subject = Address::generate(&env);
     let mut inputs: Vec<BytesN<32>> = Vec::new(&env);
     for _ in 0..=MAX_PUBLIC_INPUTS {
         inputs.push_back(BytesN::from_array(&env, &[0u8; 32]));
     }
     let bad_bundle = ProofBundle {
-        proof: proof(&env, 0xAA),
+        proof: from_hex(&env, PROOF_V1_A_HEX),
         public_inputs: inputs,
         schema_version: 1,
     };
@@ -224,13 +258,25 @@ fn test_too_many_public_inputs_returns_error() {
 #[test]
 fn test_nullifier_recorded_after_valid_proof() {
     let (env, admin, client) = setup();
-    client.register_verifier_key(&admin, &1u32, &vk(&env, 0xAA));
+    client.register_verifier_key(&admin, &1u32, &vk_v1(&env));
+    let subject = Address::generate(&env);
+    let b = bundle(&env, PROOF_V1_A_HEX, INPUTS_V1_A_HEX, 1);
+    let nullifier = b.public_inputs.get(NULLIFIER_INPUT_IDX).unwrap();
+    assert!(!client.is_nullified(&nullifier));
+    client.verify_eligibility(&subject, &b);
+    assert!(client.is_nullified(&nullifier));
+}
+
+#[test]
+fn test_missing_nullifier_returns_error() {
+    let (env, admin, client) = setup();
+    client.register_verifier_key(&admin, &1u32, &vk_v1(&env));
     let subject = Address::generate(&env);
-    let p = proof(&env, 0xAA);
-    let proof_hash: BytesN<32> = env.crypto().sha256(&p).into();
-    assert!(!client.is_nullified(&proof_hash));
-    client.verify_eligibility(&subject, &bundle(&env, 0xAA, 1));
-    assert!(client.is_nullified(&proof_hash));
+    let mut b = bundle(&env, PROOF_V1_A_HEX, INPUTS_V1_A_HEX, 1);
+    b.public_inputs.pop_back();
+    let err = client.try_verify_eligibility(&subject, &b).unwrap_err().unwrap();
+    assert_eq!(err, Error::MissingNullifier);
+    assert!(!client.is_eligible(&subject));
 }
 
 // ── schema versioning ─────────────────────────────────────────────────────────
@@ -238,12 +284,130 @@ fn test_nullifier_recorded_after_valid_proof() {
 #[test]
 fn test_multiple_schema_versions_coexist() {
     let (env, admin, client) = setup();
-    client.register_verifier_key(&admin, &1u32, &vk(&env, 0xAA));
-    client.register_verifier_key(&admin, &2u32, &vk(&env, 0xBB));
+    client.register_verifier_key(&admin, &1u32, &vk_v1(&env));
+    client.register_verifier_key(&admin, &2u32, &vk_v2(&env));
 
     let subject = Address::generate(&env);
     // v1 proof
-    client.verify_eligibility(&subject, &bundle(&env, 0xAA, 1));
-    // v2 proof (different proof bytes → different nullifier)
-    client.verify_eligibility(&subject, &bundle(&env, 0xBB, 2));
+    client.verify_eligibility(&subject, &bundle(&env, PROOF_V1_A_HEX, INPUTS_V1_A_HEX, 1));
+    // v2 proof (different secret → different nullifier)
+    client.verify_eligibility(&subject, &bundle(&env, PROOF_V2_HEX, INPUTS_V2_HEX, 2));
+}
+
+#[test]
+fn test_rerandomized_proof_for_same_statement_rejected() {
+    let (env, admin, client) = setup();
+    client.register_verifier_key(&admin, &1u32, &vk_v1(&env));
+    let subject = Address::generate(&env);
+    client.verify_eligibility(&subject, &bundle(&env, PROOF_V1_A_HEX, INPUTS_V1_A_HEX, 1));
+    // Different proof bytes, same statement: the circuit-bound nullifier matches.
+    let err = client
+        .try_verify_eligibility(&subject, &bundle(&env, PROOF_V1_B_HEX, INPUTS_V1_A_HEX, 1))
+        .unwrap_err()
+        .unwrap();
+    assert_eq!(err, Error::ProofAlreadyUsed);
+}
+
+#[test]
+fn test_distinct_secret_for_same_policy_accepted() {
+    let (env, admin, client) = setup();
+    client.register_verifier_key(&admin, &1u32, &vk_v1(&env));
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    client.verify_eligibility(&alice, &bundle(&env, PROOF_V1_A_HEX, INPUTS_V1_A_HEX, 1));
+    client.verify_eligibility(&bob, &bundle(&env, PROOF_V1_C_HEX, INPUTS_V1_C_HEX, 1));
+    assert!(client.is_eligible(&bob));
+}
+
+// ── groth16 ───────────────────────────────────────────────────────────────────
+
+#[test]
+fn test_malformed_verifier_key_rejected() {
+    let (env, admin, client) = setup();
+    let truncated = vk_v1(&env).slice(0..groth16::VK_FIXED_BYTES);
+    let err = client
+        .try_register_verifier_key(&admin, &1u32, &truncated)
+        .unwrap_err()
+        .unwrap();
+    assert_eq!(err, Error::InvalidVerifierKey);
+
+    let mut ragged = vk_v1(&env);
+    ragged.push_back(0);
+    let err = client
+        .try_register_verifier_key(&admin, &1u32, &ragged)
+        .unwrap_err()
+        .unwrap();
+    assert_eq!(err, Error::InvalidVerifierKey);
+}
+
+#[test]
+fn test_tampered_public_input_returns_error() {
+    let (env, admin, client) = setup();
+    client.register_verifier_key(&admin, &1u32, &vk_v1(&env));
+    let subject = Address::generate(&env);
+    let mut b = bundle(&env, PROOF_V1_A_HEX, INPUTS_V1_A_HEX, 1);
+    let mut policy = [0u8; 32];
+    policy[31] = FIXTURE_POLICY_ID + 1;
+    b.public_inputs.set(1, BytesN::from_array(&env, &policy));
+    let err = client.try_verify_eligibility(&subject, &b).unwrap_err().unwrap();
+    assert_eq!(err, Error::VerificationFailed);
+    assert!(!client.is_eligible(&subject));
+}
+
+#[test]
+fn test_public_input_count_mismatch_returns_error() {
+    let (env, admin, client) = setup();
+    client.register_verifier_key(&admin, &1u32, &vk_v1(&env));
+    let subject = Address::generate(&env);
+    let mut b = bundle(&env, PROOF_V1_A_HEX, INPUTS_V1_A_HEX, 1);
+    b.public_inputs.push_back(BytesN::from_array(&env, &[0u8; 32]));
+    let err = client.try_verify_eligibility(&subject, &b).unwrap_err().unwrap();
+    assert_eq!(err, Error::VerificationFailed);
+}
+
+#[test]
+fn test_non_canonical_public_input_returns_error() {
+    let (env, admin, client) = setup();
+    client.register_verifier_key(&admin, &1u32, &vk_v1(&env));
+    let subject = Address::generate(&env);
+    // policy id + r reduces to the same field element; it must not verify.
+    let policy_plus_r: [u8; 32] = [
+        0x73, 0xed, 0xa7, 0x53, 0x29, 0x9d, 0x7d, 0x48, 0x33, 0x39, 0xd8, 0x08, 0x09, 0xa1, 0xd8,
+        0x05, 0x53, 0xbd, 0xa4, 0x02, 0xff, 0xfe, 0x5b, 0xfe, 0xff, 0xff, 0xff, 0xff, 0x00, 0x00,
+        0x00, 0x01 + FIXTURE_POLICY_ID,
+    ];
+    let mut b = bundle(&env, PROOF_V1_A_HEX, INPUTS_V1_A_HEX, 1);
+    b.public_inputs.set(1, BytesN::from_array(&env, &policy_plus_r));
+    let err = client.try_verify_eligibility(&subject, &b).unwrap_err().unwrap();
+    assert_eq!(err, Error::VerificationFailed);
+}
+
+#[test]
+fn test_wrong_length_proof_returns_error() {
+    let (env, admin, client) = setup();
+    client.register_verifier_key(&admin, &1u32, &vk_v1(&env));
+    let subject = Address::generate(&env);
+    let mut b = bundle(&env, PROOF_V1_A_HEX, INPUTS_V1_A_HEX, 1);
+    b.proof = b.proof.slice(0..groth16::PROOF_BYTES - 1);
+    let err = client.try_verify_eligibility(&subject, &b).unwrap_err().unwrap();
+    assert_eq!(err, Error::VerificationFailed);
+}
+
+#[test]
+fn test_batch_verification_uses_groth16() {
+    let (env, admin, client) = setup();
+    client.register_verifier_key(&admin, &1u32, &vk_v1(&env));
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    let mut subjects = Vec::new(&env);
+    subjects.push_back(alice.clone());
+    subjects.push_back(bob.clone());
+    let mut bundles = Vec::new(&env);
+    bundles.push_back(bundle(&env, PROOF_V1_A_HEX, INPUTS_V1_A_HEX, 1));
+    bundles.push_back(bundle(&env, PROOF_V2_HEX, INPUTS_V2_HEX, 1));
+    let results = client.verify_eligibility_batch(&subjects, &bundles);
+    assert_eq!(results.get(0), Some(true));
+    assert_eq!(results.get(1), Some(false));
+    assert!(client.is_eligible(&alice));
+    assert!(!client.is_eligible(&bob));
 }
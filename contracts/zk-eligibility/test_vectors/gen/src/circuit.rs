@@ -0,0 +1,149 @@
+//! The eligibility relation behind the fixtures, as a rank-1 constraint system.
+//!
+//! Public inputs, in circuit order:
+//!
+//! | Index | Input | Encoding |
+//! |-------|-------|----------|
+//! | 0 | `expiry` | big-endian u64 in the first 8 bytes of the scalar |
+//! | 1 | `scope` (policy id) | integer scalar |
+//! | 2 | `nullifier` | `H(secret, scope)` |
+//!
+//! The private witness is `secret`. `H` is MiMC-5 keyed by `secret` over
+//! `scope` in Miyaguchi–Preneel mode:
+//!
+//! ```text
+//! x_0 = scope
+//! x_{i+1} = (x_i + secret + c_i)^5        for i in 0..MIMC_ROUNDS
+//! H(secret, scope) = (x_R + secret) + scope + secret
+//! ```
+//!
+//! `x^5` is a permutation of the BLS12-381 scalar field because
+//! `gcd(5, r - 1) = 1`. `c_i` is `sha256("zk-eligibility/mimc5/" ‖ i_be32)`
+//! reduced modulo `r`.
+//!
+//! `expiry` takes part in no computation, so it is bound with `expiry · 1 =
+//! expiry`; without a constraint its verifying-key term would be the identity
+//! and any value would verify.
+
+use ark_bls12_381::Fr;
+use ark_ff::{Field, One, PrimeField, Zero};
+use sha2::{Digest, Sha256};
+
+/// `ceil(255 / log2(5))` rounds, enough for the permutation to reach full
+/// degree over the scalar field.
+pub const MIMC_ROUNDS: usize = 110;
+/// Public inputs, not counting the constant `one`.
+pub const NUM_PUBLIC: usize = 3;
+
+pub const ONE: usize = 0;
+pub const EXPIRY: usize = 1;
+pub const SCOPE: usize = 2;
+pub const NULLIFIER: usize = 3;
+pub const SECRET: usize = 4;
+/// Three private variables per round: `t^2`, `t^4` and `x_{i+1} = t^5`.
+pub const NUM_VARS: usize = 5 + 3 * MIMC_ROUNDS;
+
+/// Sparse linear combination of variables.
+pub type Lc = Vec<(usize, Fr)>;
+
+/// One constraint `<a, z> · <b, z> = <c, z>`.
+pub struct Constraint {
+    pub a: Lc,
+    pub b: Lc,
+    pub c: Lc,
+}
+
+fn t2(round: usize) -> usize {
+    5 + 3 * round
+}
+
+fn t4(round: usize) -> usize {
+    6 + 3 * round
+}
+
+/// Variable holding `x_i`.
+fn x(i: usize) -> usize {
+    if i == 0 {
+        SCOPE
+    } else {
+        7 + 3 * (i - 1)
+    }
+}
+
+pub fn round_constant(round: usize) -> Fr {
+    let mut hasher = Sha256::new();
+    hasher.update(b"zk-eligibility/mimc5/");
+    hasher.update((round as u32).to_be_bytes());
+    Fr::from_be_bytes_mod_order(&hasher.finalize())
+}
+
+/// `H(secret, scope)` computed natively.
+pub fn nullifier(secret: Fr, scope: Fr) -> Fr {
+    let mut x = scope;
+    for round in 0..MIMC_ROUNDS {
+        x = (x + secret + round_constant(round)).pow([5u64]);
+    }
+    x + secret + scope + secret
+}
+
+pub fn constraints() -> Vec<Constraint> {
+    let one = Fr::one();
+    let mut cs = Vec::with_capacity(3 * MIMC_ROUNDS + 2);
+    for round in 0..MIMC_ROUNDS {
+        let t: Lc = vec![(x(round), one), (SECRET, one), (ONE, round_constant(round))];
+        cs.push(Constraint {
+            a: t.clone(),
+            b: t.clone(),
+            c: vec![(t2(round), one)],
+        });
+        cs.push(Constraint {
+            a: vec![(t2(round), one)],
+            b: vec![(t2(round), one)],
+            c: vec![(t4(round), one)],
+        });
+        cs.push(Constraint {
+            a: vec![(t4(round), one)],
+            b: t,
+            c: vec![(x(round + 1), one)],
+        });
+    }
+    cs.push(Constraint {
+        a: vec![(x(MIMC_ROUNDS), one), (SECRET, one + one), (SCOPE, one)],
+        b: vec![(ONE, one)],
+        c: vec![(NULLIFIER, one)],
+    });
+    cs.push(Constraint {
+        a: vec![(EXPIRY, one)],
+        b: vec![(ONE, one)],
+        c: vec![(EXPIRY, one)],
+    });
+    cs
+}
+
+/// Full assignment `z` for the given statement and secret.
+pub fn witness(expiry: Fr, scope: Fr, secret: Fr) -> Vec<Fr> {
+    let mut z = vec![Fr::zero(); NUM_VARS];
+    z[ONE] = Fr::one();
+    z[EXPIRY] = expiry;
+    z[SCOPE] = scope;
+    z[SECRET] = secret;
+    let mut xi = scope;
+    for round in 0..MIMC_ROUNDS {
+        let t = xi + secret + round_constant(round);
+        z[t2(round)] = t.square();
+        z[t4(round)] = z[t2(round)].square();
+        xi = z[t4(round)] * t;
+        z[x(round + 1)] = xi;
+    }
+    z[NULLIFIER] = nullifier(secret, scope);
+    z
+}
+
+pub fn eval(lc: &Lc, z: &[Fr]) -> Fr {
+    lc.iter().map(|(var, coeff)| z[*var] * coeff).sum()
+}
+
+pub fn is_satisfied(cs: &[Constraint], z: &[Fr]) -> bool {
+    cs.iter()
+        .all(|k| eval(&k.a, z) * eval(&k.b, z) == eval(&k.c, z))
+}
@@ -0,0 +1,258 @@
+//! Regenerates the Groth16 fixtures in `contracts/zk-eligibility/test_vectors`.
+//!
+//! Run from this directory with `cargo run --release --offline`; the files are
+//! written to the parent directory. Output is deterministic: every trapdoor,
+//! blinding factor and secret is derived from a fixed label with SHA-256.
+//!
+//! The setup knows its trapdoor (`tau`, `alpha`, `beta`, `gamma`, `delta`), so
+//! the QAP polynomials are evaluated at `tau` directly and proofs are built
+//! from those evaluations. The proofs have the same distribution as ones from
+//! an ordinary prover, and each witness is checked against the constraint
+//! system before proving. Anyone holding these labels can forge proofs, which
+//! is why the keys are for tests only.
+
+mod circuit;
+
+use ark_bls12_381::{Bls12_381, Fr, G1Affine, G1Projective, G2Affine, G2Projective};
+use ark_ec::{pairing::Pairing, CurveGroup, Group};
+use ark_ff::{BigInteger, Field, One, PrimeField, Zero};
+use ark_serialize::CanonicalSerialize;
+use sha2::{Digest, Sha256};
+use std::{fs, path::Path};
+
+/// Expiry the fixtures commit to: 2100-01-01T00:00:00Z.
+const EXPIRY: u64 = 4_102_444_800;
+/// Policy id the fixtures commit to; it is also the nullifier scope.
+const POLICY_ID: u64 = 42;
+
+fn label_scalar(label: &str) -> Fr {
+    Fr::from_be_bytes_mod_order(&Sha256::digest(label.as_bytes()))
+}
+
+struct Trapdoor {
+    tau: Fr,
+    alpha: Fr,
+    beta: Fr,
+    gamma: Fr,
+    delta: Fr,
+}
+
+impl Trapdoor {
+    fn from_label(label: &str) -> Self {
+        let part = |name: &str| label_scalar(&format!("{label}/{name}"));
+        Self {
+            tau: part("tau"),
+            alpha: part("alpha"),
+            beta: part("beta"),
+            gamma: part("gamma"),
+            delta: part("delta"),
+        }
+    }
+}
+
+/// QAP polynomials of every variable evaluated at `tau`.
+struct QapAtTau {
+    u: Vec<Fr>,
+    v: Vec<Fr>,
+    w: Vec<Fr>,
+}
+
+/// Interpolates constraint `j` at the point `j + 1`.
+fn qap_at(cs: &[circuit::Constraint], tau: Fr) -> QapAtTau {
+    let points: Vec<Fr> = (1..=cs.len() as u64).map(Fr::from).collect();
+    let mut u = vec![Fr::zero(); circuit::NUM_VARS];
+    let mut v = u.clone();
+    let mut w = u.clone();
+    for (j, constraint) in cs.iter().enumerate() {
+        let mut lagrange = Fr::one();
+        for (m, point) in points.iter().enumerate() {
+            if m != j {
+                lagrange *= (tau - point) * (points[j] - point).inverse().unwrap();
+            }
+        }
+        for (var, coeff) in &constraint.a {
+            u[*var] += lagrange * coeff;
+        }
+        for (var, coeff) in &constraint.b {
+            v[*var] += lagrange * coeff;
+        }
+        for (var, coeff) in &constraint.c {
+            w[*var] += lagrange * coeff;
+        }
+    }
+    QapAtTau { u, v, w }
+}
+
+struct VerifyingKey {
+    alpha_g1: G1Affine,
+    beta_g2: G2Affine,
+    gamma_g2: G2Affine,
+    delta_g2: G2Affine,
+    ic: Vec<G1Affine>,
+}
+
+struct Proof {
+    a: G1Affine,
+    b: G2Affine,
+    c: G1Affine,
+}
+
+struct Setup {
+    trapdoor: Trapdoor,
+    qap: QapAtTau,
+    vk: VerifyingKey,
+}
+
+fn g1(x: Fr) -> G1Affine {
+    (G1Projective::generator() * x).into_affine()
+}
+
+fn g2(x: Fr) -> G2Affine {
+    (G2Projective::generator() * x).into_affine()
+}
+
+fn setup(label: &str, cs: &[circuit::Constraint]) -> Setup {
+    let td = Trapdoor::from_label(label);
+    let qap = qap_at(cs, td.tau);
+    let gamma_inv = td.gamma.inverse().unwrap();
+    let ic = (0..=circuit::NUM_PUBLIC)
+        .map(|i| g1((td.beta * qap.u[i] + td.alpha * qap.v[i] + qap.w[i]) * gamma_inv))
+        .collect();
+    let vk = VerifyingKey {
+        alpha_g1: g1(td.alpha),
+        beta_g2: g2(td.beta),
+        gamma_g2: g2(td.gamma),
+        delta_g2: g2(td.delta),
+        ic,
+    };
+    Setup {
+        trapdoor: td,
+        qap,
+        vk,
+    }
+}
+
+fn prove(setup: &Setup, z: &[Fr], label: &str) -> Proof {
+    let (td, qap) = (&setup.trapdoor, &setup.qap);
+    let r = label_scalar(&format!("{label}/r"));
+    let s = label_scalar(&format!("{label}/s"));
+    let dot = |poly: &[Fr]| -> Fr { poly.iter().zip(z).map(|(p, a)| *p * a).sum() };
+
+    let a = td.alpha + dot(&qap.u) + r * td.delta;
+    let b = td.beta + dot(&qap.v) + s * td.delta;
+    // h(tau)·t(tau) = U(tau)·V(tau) − W(tau) for a satisfying witness.
+    let ht = dot(&qap.u) * dot(&qap.v) - dot(&qap.w);
+    let private: Fr = (circuit::NUM_PUBLIC + 1..circuit::NUM_VARS)
+        .map(|i| z[i] * (td.beta * qap.u[i] + td.alpha * qap.v[i] + qap.w[i]))
+        .sum();
+    let c = (private + ht) * td.delta.inverse().unwrap() + s * a + r * b - r * s * td.delta;
+    Proof {
+        a: g1(a),
+        b: g2(b),
+        c: g1(c),
+    }
+}
+
+/// Re-randomizes `proof` using only public data: the result verifies for the
+/// same public inputs but has different bytes.
+fn rerandomize(vk: &VerifyingKey, proof: &Proof, label: &str) -> Proof {
+    let rho = label_scalar(&format!("{label}/rho"));
+    let xi = label_scalar(&format!("{label}/xi"));
+    Proof {
+        a: (proof.a * rho.inverse().unwrap()).into_affine(),
+        b: (proof.b * rho + vk.delta_g2 * (rho * xi)).into_affine(),
+        c: (proof.c + proof.a * xi).into_affine(),
+    }
+}
+
+fn verify(vk: &VerifyingKey, proof: &Proof, inputs: &[Fr]) -> bool {
+    let mut acc: G1Projective = vk.ic[0].into();
+    for (x, ic) in inputs.iter().zip(&vk.ic[1..]) {
+        acc += *ic * x;
+    }
+    let lhs = Bls12_381::pairing(proof.a, proof.b);
+    let rhs = Bls12_381::multi_pairing(
+        [vk.alpha_g1, acc.into_affine(), proof.c],
+        [vk.beta_g2, vk.gamma_g2, vk.delta_g2],
+    );
+    lhs == rhs
+}
+
+fn uncompressed<T: CanonicalSerialize>(point: &T, out: &mut Vec<u8>) {
+    point.serialize_uncompressed(out).unwrap();
+}
+
+fn encode_vk(vk: &VerifyingKey) -> Vec<u8> {
+    let mut out = Vec::new();
+    uncompressed(&vk.alpha_g1, &mut out);
+    uncompressed(&vk.beta_g2, &mut out);
+    uncompressed(&vk.gamma_g2, &mut out);
+    uncompressed(&vk.delta_g2, &mut out);
+    for ic in &vk.ic {
+        uncompressed(ic, &mut out);
+    }
+    out
+}
+
+fn encode_proof(proof: &Proof) -> Vec<u8> {
+    let mut out = Vec::new();
+    uncompressed(&proof.a, &mut out);
+    uncompressed(&proof.b, &mut out);
+    uncompressed(&proof.c, &mut out);
+    out
+}
+
+fn encode_inputs(inputs: &[Fr]) -> Vec<u8> {
+    inputs
+        .iter()
+        .flat_map(|x| x.into_bigint().to_bytes_be())
+        .collect()
+}
+
+fn write_hex(dir: &Path, name: &str, bytes: &[u8]) {
+    let hex: String = bytes.iter().map(|b| format!("{b:02x}")).collect();
+    fs::write(dir.join(name), hex + "\n").unwrap();
+    println!("wrote {name} ({} bytes)", bytes.len());
+}
+
+/// Proves the statement for `secret_label` under `setup`, checking the witness
+/// and the resulting proof before returning them.
+fn prove_statement(setup: &Setup, secret_label: &str) -> (Proof, Vec<Fr>) {
+    let mut expiry = [0u8; 32];
+    expiry[..8].copy_from_slice(&EXPIRY.to_be_bytes());
+    let expiry = Fr::from_be_bytes_mod_order(&expiry);
+    let scope = Fr::from(POLICY_ID);
+    let secret = label_scalar(secret_label);
+
+    let z = circuit::witness(expiry, scope, secret);
+    assert!(circuit::is_satisfied(&circuit::constraints(), &z));
+    let inputs = z[1..=circuit::NUM_PUBLIC].to_vec();
+    let proof = prove(setup, &z, secret_label);
+    assert!(verify(&setup.vk, &proof, &inputs));
+    (proof, inputs)
+}
+
+fn main() {
+    let out = Path::new(env!("CARGO_MANIFEST_DIR")).join("..");
+    let cs = circuit::constraints();
+
+    let v1 = setup("zk-eligibility/test-vectors/vk_v1", &cs);
+    let v2 = setup("zk-eligibility/test-vectors/vk_v2", &cs);
+
+    let (proof_a, inputs_a) = prove_statement(&v1, "zk-eligibility/test-vectors/secret_a");
+    let proof_b = rerandomize(&v1.vk, &proof_a, "zk-eligibility/test-vectors/proof_v1_b");
+    assert!(verify(&v1.vk, &proof_b, &inputs_a));
+    let (proof_c, inputs_c) = prove_statement(&v1, "zk-eligibility/test-vectors/secret_c");
+    let (proof_v2, inputs_v2) = prove_statement(&v2, "zk-eligibility/test-vectors/secret_d");
+    assert!(!verify(&v1.vk, &proof_v2, &inputs_v2));
+
+    write_hex(&out, "vk_v1.hex", &encode_vk(&v1.vk));
+    write_hex(&out, "vk_v2.hex", &encode_vk(&v2.vk));
+    write_hex(&out, "proof_v1_a.hex", &encode_proof(&proof_a));
+    write_hex(&out, "inputs_v1_a.hex", &encode_inputs(&inputs_a));
+    write_hex(&out, "proof_v1_b.hex", &encode_proof(&proof_b));
+    write_hex(&out, "proof_v1_c.hex", &encode_proof(&proof_c));
+    write_hex(&out, "inputs_v1_c.hex", &encode_inputs(&inputs_c));
+    write_hex(&out, "proof_v2.hex", &encode_proof(&proof_v2));
+    write_hex(&out, "inputs_v2.hex", &encode_inputs(&inputs_v2));
+}